@@ -77,6 +77,8 @@ pub enum BidStatusSvm {
     /// The bid lost the auction.
     /// This bid status will have a result field containing the signature of the transaction corresponding to the winning bid,
     /// unless the auction had no winner (because all bids were found to be invalid).
+    /// Bids with equal amounts, or amounts within the same minimum bid increment of the chain, are ranked by their priority fee
+    /// per compute unit (highest first) and then by their arrival time (earliest first).
     #[schema(title = "Lost")]
     Lost {
         #[schema(example = "Jb2urXPyEh4xiBgzYvwEFe4q1iMxG1DNxWGGQg94AmKgqFTwLAiTiHrYiYxwHUB4DV8u5ahNEVtMMDm3sNSRdTg", value_type = Option<String>)]
@@ -156,6 +158,25 @@ pub struct BidResult {
     pub id:     BidId,
 }
 
+/// The result of a dry run of the bid verification.
+#[derive(Serialize, Deserialize, ToResponse, ToSchema, Clone, Debug)]
+pub struct BidVerificationResult {
+    /// The permission key for bid in base64 format.
+    /// This is the concatenation of the opportunity type, the router, and the permission account.
+    #[schema(example = "DUcTi3rDyS5QEmZ4BNRBejtArmDCWaPYGfN44vBJXKL5", value_type = String)]
+    pub permission_key:         PermissionKeySvm,
+    /// Amount of bid in lamports.
+    #[schema(example = "1000", value_type = u64)]
+    pub bid_amount:             BidAmountSvm,
+    /// Whether the bid transaction was simulated. Bids on indicative quotes are not simulated.
+    pub simulated:              bool,
+    /// Compute units consumed by the simulation of the bid transaction.
+    #[schema(example = 25000)]
+    pub compute_units_consumed: Option<u64>,
+    /// Logs of the simulation of the bid transaction.
+    pub logs:                   Vec<String>,
+}
+
 #[derive(Clone, Debug, ToSchema, Serialize, Deserialize)]
 pub struct BidCoreFields {
     /// The unique id for bid.
@@ -294,6 +315,48 @@ pub struct GetBidsByTimeQueryParams {
     pub from_time: Option<OffsetDateTime>,
 }
 
+#[derive(Serialize, Deserialize, IntoParams)]
+pub struct GetSearcherReputationsQueryParams {
+    /// The profile to get the reputation of. The reputations of all the profiles are returned if not provided.
+    #[param(example="obo3ee3e-58cc-4372-a567-0e02b2c3d479", value_type = Option<String>)]
+    #[serde(default)]
+    pub profile_id: Option<ProfileId>,
+}
+
+/// Reputation of a searcher on a chain, based on the outcomes of its winning bids.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct SearcherReputation {
+    /// The id of the searcher profile.
+    #[schema(example = "obo3ee3e-58cc-4372-a567-0e02b2c3d479", value_type = String)]
+    pub profile_id:   ProfileId,
+    /// Number of auctions won by the searcher.
+    #[schema(example = 100)]
+    pub auctions_won: u64,
+    /// Number of winning bids that were included in a block successfully.
+    #[schema(example = 90)]
+    pub bids_landed:  u64,
+    /// Number of winning bids that failed on-chain.
+    #[schema(example = 5)]
+    pub bids_failed:  u64,
+    /// Number of winning bids of quotes that were never signed and expired.
+    #[schema(example = 5)]
+    pub bids_expired: u64,
+    /// Share of the won auctions whose bids failed on-chain or expired without being signed.
+    #[schema(example = 0.1)]
+    pub failure_rate: f64,
+    /// Whether the configured penalties apply to the searcher.
+    pub penalized:    bool,
+    /// The time until which the bids of the searcher are rejected, formatted in rfc3339.
+    #[schema(example = "2024-05-23T21:26:57.329954Z", value_type = Option<String>)]
+    #[serde(default, with = "crate::serde::nullable_datetime")]
+    pub banned_until: Option<OffsetDateTime>,
+}
+
+#[derive(Serialize, Deserialize, ToResponse, ToSchema, Clone)]
+pub struct SearcherReputations {
+    pub items: Vec<SearcherReputation>,
+}
+
 impl BidCreate {
     pub fn get_chain_id(&self) -> ChainId {
         match self {
@@ -320,6 +383,10 @@ pub enum Route {
     PostSubmitQuote,
     #[strum(serialize = ":bid_id/cancel")]
     PostCancelBid,
+    #[strum(serialize = "verify")]
+    PostVerifyBid,
+    #[strum(serialize = "reputations")]
+    GetSearcherReputations,
 }
 
 #[derive(Clone)]
@@ -374,6 +441,16 @@ impl Routable for Route {
                 access_level: AccessLevel::LoggedIn,
                 full_path:    full_path_with_chain,
             },
+            Route::PostVerifyBid => crate::RouteProperties {
+                method:       http::Method::POST,
+                access_level: AccessLevel::LoggedIn,
+                full_path:    full_path_with_chain,
+            },
+            Route::GetSearcherReputations => crate::RouteProperties {
+                method:       http::Method::GET,
+                access_level: AccessLevel::Admin,
+                full_path:    full_path_with_chain,
+            },
         }
     }
 }
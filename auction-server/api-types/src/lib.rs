@@ -16,6 +16,7 @@ use {
     },
     solana_sdk::hash::Hash,
     strum::AsRefStr,
+    time::OffsetDateTime,
     utoipa::{
         ToResponse,
         ToSchema,
@@ -66,10 +67,138 @@ pub struct SvmChainUpdate {
     pub latest_prioritization_fee: MicroLamports,
 }
 
-#[derive(ToResponse, ToSchema, Serialize, Deserialize)]
+#[derive(ToResponse, ToSchema, Serialize, Deserialize, Clone, Debug)]
 #[response(description = "An error occurred processing the request")]
 pub struct ErrorBodyResponse {
-    pub error: String,
+    pub error:   String,
+    #[serde(flatten)]
+    pub details: Option<ErrorDetails>,
+}
+
+/// Machine readable code of an error.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    BadParameters,
+    InvalidOpportunity,
+    InvalidChainId,
+    SimulationFailed,
+    OpportunityNotFound,
+    BidNotFound,
+    TemporarilyUnavailable,
+    InvalidToken,
+    Forbidden,
+    Unauthorized,
+    ProfileNotFound,
+    QuoteNotFound,
+    DuplicateOpportunity,
+    SwapOpportunityNotFound,
+    TransactionSizeTooLarge,
+    MultipleSetComputeUnitPriceInstructions,
+    SetComputeUnitPriceInstructionNotFound,
+    LowComputeUnitPrice,
+    InvalidInstruction,
+    InvalidExpressRelayInstructionCount,
+    InvalidSwapInstruction,
+    InvalidDeadline,
+    DeadlineTooLate,
+    InvalidSignature,
+    RelayerNotSigner,
+    InvalidFirstSigner,
+    DuplicateBid,
+    TooManyOpenWebsocketConnections,
+    QuoteIsExpired,
+    QuoteIsCancelled,
+    QuoteIsFinalized,
+    TokenMintNotAllowed,
+    CancelQuoteAccessRevoked,
+    UnsupportedTokenExtension,
+    RateLimited,
+    SearcherBanned,
+    SimulationRequired,
+    RelayerSpendTooHigh,
+    RelayerDailySpendLimitReached,
+    /// An error code that is not known to this version of the client.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Structured details of an error, returned next to the error message.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug, PartialEq)]
+pub struct ErrorDetails {
+    pub code:              ErrorCode,
+    /// The more specific reason of the error, e.g. the check of the instruction that failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "invalid_to_account_transfer_instruction")]
+    pub reason:            Option<String>,
+    /// Index of the transaction instruction that caused the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 1)]
+    pub instruction_index: Option<usize>,
+    /// The value that was expected by the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected:          Option<String>,
+    /// The value that was found in the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub found:             Option<String>,
+    /// Result of the failed simulation if the error is a simulation failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simulation:        Option<SimulationErrorDetails>,
+    /// Time to wait in milliseconds before retrying if the request was rate limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 250)]
+    pub retry_after_ms:    Option<u64>,
+    /// The time until which the bids of the searcher are rejected if the searcher is banned, formatted in rfc3339.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::serde::nullable_datetime"
+    )]
+    #[schema(example = "2024-05-23T21:26:57.329954Z", value_type = Option<String>)]
+    pub banned_until:      Option<OffsetDateTime>,
+}
+
+/// Details of a failed simulation of a transaction.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug, PartialEq)]
+pub struct SimulationErrorDetails {
+    /// The error of the transaction.
+    #[schema(example = "InstructionError(0, Custom(6001))")]
+    pub error:                  String,
+    /// Logs of the transaction. The earliest logs are dropped when the logs are too long.
+    pub logs:                   Vec<String>,
+    /// Compute units consumed by the transaction until it failed.
+    #[schema(example = 25000)]
+    pub compute_units_consumed: Option<u64>,
+    /// Instructions invoked through cross program invocations.
+    pub inner_instructions:     Vec<SimulationInnerInstruction>,
+    /// Whether some of the logs or inner instructions were dropped to cap the size of the details.
+    pub truncated:              bool,
+}
+
+/// An instruction invoked through a cross program invocation.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug, PartialEq)]
+pub struct SimulationInnerInstruction {
+    /// Index of the transaction instruction that invoked this instruction.
+    pub index:            u8,
+    /// Index of the program id in the account keys of the transaction.
+    pub program_id_index: u8,
+    /// Depth of the invocation, the transaction instructions have a stack height of 1.
+    pub stack_height:     u8,
+}
+
+impl ErrorDetails {
+    pub fn new(code: ErrorCode) -> Self {
+        Self {
+            code,
+            reason: None,
+            instruction_index: None,
+            expected: None,
+            found: None,
+            simulation: None,
+            retry_after_ms: None,
+            banned_until: None,
+        }
+    }
 }
 
 #[derive(AsRefStr)]
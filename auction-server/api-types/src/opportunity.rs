@@ -264,6 +264,14 @@ pub enum OpportunityParamsV1ProgramSvm {
         #[schema(example = true)]
         cancellable: bool,
 
+        /// If provided, the swap can be partially filled and bids should use the `swap_v3` instruction.
+        /// The minimum searcher amount of the swap instruction must be at least the searcher amount
+        /// reduced by this tolerance, in parts per million. Partial fills keep the price of the bid.
+        /// Bids are ranked by the minimum searcher amount they commit to, not by the full searcher amount.
+        #[schema(example = 5000, value_type = Option<u64>)]
+        #[serde(default)]
+        partial_fill_tolerance_ppm: Option<u64>,
+
         /// The minimum acceptable deadline for the quote, in seconds since the Unix epoch.
         /// The transaction must have a deadline greater than this value.
         #[schema(example = 17_000_000_000i64, value_type = i64)]
@@ -445,36 +453,47 @@ pub struct QuoteCreateV1SvmParams {
     /// The user wallet address which requested the quote from the wallet. If not provided, an indicative price without a transaction will be returned.
     #[schema(example = "DUcTi3rDyS5QEmZ4BNRBejtArmDCWaPYGfN44vBJXKL5", value_type = Option<String>)]
     #[serde_as(as = "Option<DisplayFromStr>")]
-    pub user_wallet_address:    Option<Pubkey>,
+    pub user_wallet_address:        Option<Pubkey>,
     /// The mint address of the token the user will provide in the swap.
     #[schema(example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", value_type = String)]
     #[serde_as(as = "DisplayFromStr")]
-    pub input_token_mint:       Pubkey,
+    pub input_token_mint:           Pubkey,
     /// The mint address of the token the user will receive in the swap.
     #[schema(example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", value_type = String)]
     #[serde_as(as = "DisplayFromStr")]
-    pub output_token_mint:      Pubkey,
+    pub output_token_mint:          Pubkey,
     /// The token amount that the user wants to swap out of/into.
     #[schema(inline)]
-    pub specified_token_amount: SpecifiedTokenAmount,
+    pub specified_token_amount:     SpecifiedTokenAmount,
     /// Information about the referral fee and the router to send the fee to. If not provided, referral fee will be set to 0.
     #[schema(inline)]
-    pub referral_fee_info:      Option<ReferralFeeInfo>,
+    pub referral_fee_info:          Option<ReferralFeeInfo>,
     /// The chain id for creating the quote.
     #[schema(example = "solana", value_type = String)]
-    pub chain_id:               ChainId,
+    pub chain_id:                   ChainId,
     /// Optional memo to be included in the transaction.
     #[schema(example = "memo")]
-    pub memo:                   Option<String>,
+    pub memo:                       Option<String>,
     /// Whether the quote is cancellable by the searcher between the time the quote is requested and the time the quote is signed and submitted back.
     /// For cancellable quotes, the quote needs to be signed and submitted back to the API. If the quote is not cancellable, the user may broadcast the transaction to the blockchain on their own instead of submitting it back to the API.
     /// Therefore, non-cancellable quotes allow the integrator to reduce the number of API calls to one, but at the cost of potentially worse prices. Price-optimizing integrators should use the default value of true.
     #[schema(example = "true")]
     #[serde(default = "default_cancellable")]
-    pub cancellable:            bool,
+    pub cancellable:                bool,
     /// Optional minimum transaction lifetime in seconds.
     #[schema(example = 10, value_type = Option<u32>)]
-    pub minimum_lifetime:       Option<u32>,
+    pub minimum_lifetime:           Option<u32>,
+    /// Optional maximum number of quotes to return, one for each of the best searcher bids. Defaults to 1.
+    /// The best quote is returned with the next best quotes as fallback quotes. Only supported for cancellable quotes.
+    #[schema(example = 3, value_type = Option<u32>)]
+    #[serde(default)]
+    pub number_of_quotes:           Option<u32>,
+    /// Optional partial fill tolerance in parts per million. If provided, the quote can be partially filled
+    /// as long as the user receives at least the quoted searcher amount reduced by this tolerance.
+    /// The user amount is reduced proportionally, so a partial fill is executed at the quoted price.
+    #[schema(example = 5000, value_type = Option<u64>)]
+    #[serde(default)]
+    pub partial_fill_tolerance_ppm: Option<u64>,
 }
 
 fn default_cancellable() -> bool {
@@ -546,24 +565,70 @@ pub struct QuoteV1Svm {
     /// The transaction for the quote to be executed on chain which is valid until the expiration time. Not provided if the quote to return is only an indicative price.
     #[schema(example = "SGVsbG8sIFdvcmxkIQ==", value_type = Option<String>)]
     #[serde(with = "crate::serde::nullable_transaction_svm")]
-    pub transaction:     Option<VersionedTransaction>,
+    pub transaction:           Option<VersionedTransaction>,
     /// The expiration time of the quote (in seconds since the Unix epoch). Not provided if indicative price.
     #[schema(example = 1_700_000_000_000_000i64, value_type = Option<i64>)]
-    pub expiration_time: Option<i64>,
+    pub expiration_time:       Option<i64>,
     /// The token and amount that the user needs to send to fulfill the swap transaction.
-    pub input_token:     TokenAmountSvm,
+    pub input_token:           TokenAmountSvm,
     /// The token and amount that the user will receive when the swap is complete.
-    pub output_token:    TokenAmountSvm,
+    pub output_token:          TokenAmountSvm,
     /// The token and amount of the referral fee paid to the party that routed the swap request to Express Relay.
-    pub referrer_fee:    TokenAmountSvm,
+    pub referrer_fee:          TokenAmountSvm,
     /// The token and amount of the platform fee paid to the Express Relay program and relayer.
-    pub platform_fee:    TokenAmountSvm,
+    pub platform_fee:          TokenAmountSvm,
     /// The chain id for the quote.
     #[schema(example = "solana", value_type = String)]
-    pub chain_id:        ChainId,
+    pub chain_id:              ChainId,
     /// The reference id for the quote.
     #[schema(example = "beedbeed-58cc-4372-a567-0e02b2c3d479", value_type = String)]
-    pub reference_id:    Uuid,
+    pub reference_id:          Uuid,
+    /// The quotes of the next best searchers in order, each with its own transaction and reference id.
+    /// Once one of the quotes is submitted, the other quotes can no longer be submitted.
+    #[schema(no_recursion)]
+    #[serde(default)]
+    pub fallback_quotes:       Vec<QuoteV1Svm>,
+    /// The minimum amount of the output token the user will receive if the quote is partially filled.
+    /// Only provided if the quote can be partially filled within the requested partial fill tolerance.
+    /// The input token amount is reduced proportionally when the quote is partially filled.
+    #[schema(example = 95, value_type = Option<u64>)]
+    #[serde(default)]
+    pub minimum_output_amount: Option<u64>,
+}
+
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+pub struct GetIndicativeQuoteQueryParams {
+    /// The mint address of the token the user will provide in the swap.
+    #[param(example = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", value_type = String)]
+    #[serde_as(as = "DisplayFromStr")]
+    pub input_token_mint:   Pubkey,
+    /// The mint address of the token the user will receive in the swap.
+    #[param(example = "So11111111111111111111111111111111111111112", value_type = String)]
+    #[serde_as(as = "DisplayFromStr")]
+    pub output_token_mint:  Pubkey,
+    /// The amount of the input token the user will provide, in the smallest denomination of that token.
+    #[param(example = 1000)]
+    pub input_token_amount: u64,
+    /// The referral fee in parts per million to deduct from the estimated output amount, as in the quote request.
+    #[param(example = 0)]
+    #[serde(default)]
+    pub referral_fee_ppm:   u64,
+}
+
+/// An estimated price for a swap, built from recent auctions and market prices without running an auction.
+#[derive(Serialize, Deserialize, ToSchema, Clone, PartialEq, Debug)]
+pub struct IndicativeQuote {
+    /// The token and amount that the user provides in the swap.
+    pub input_token:  TokenAmountSvm,
+    /// The token and estimated amount that the user would receive in the swap.
+    pub output_token: TokenAmountSvm,
+    /// The confidence in the estimated output amount, between 0 and 1.
+    #[schema(example = 0.8)]
+    pub confidence:   f64,
+    /// The chain id for the indicative quote.
+    #[schema(example = "solana", value_type = String)]
+    pub chain_id:     ChainId,
 }
 
 #[derive(Serialize, Deserialize, ToSchema, Clone, PartialEq, Debug)]
@@ -635,6 +700,8 @@ pub enum Route {
     OpportunityBid,
     #[strum(serialize = "")]
     DeleteOpportunities,
+    #[strum(serialize = "indicative")]
+    GetIndicativeQuote,
 }
 
 impl Routable for Route {
@@ -647,6 +714,12 @@ impl Routable for Route {
         )
         .trim_end_matches("/")
         .to_string();
+        let full_path_quote_with_chain = format!(
+            "{}{}{}",
+            crate::Route::V1Chain.as_ref(),
+            crate::Route::Quote.as_ref(),
+            self.as_ref()
+        );
         match self {
             Route::PostOpportunity => crate::RouteProperties {
                 access_level: AccessLevel::Public,
@@ -673,6 +746,11 @@ impl Routable for Route {
                 method: http::Method::DELETE,
                 full_path,
             },
+            Route::GetIndicativeQuote => crate::RouteProperties {
+                access_level: AccessLevel::Public,
+                method:       http::Method::GET,
+                full_path:    full_path_quote_with_chain,
+            },
         }
     }
 }
@@ -1,4 +1,5 @@
 use {
+    crate::ChainId,
     serde::{
         Deserialize,
         Serialize,
@@ -38,3 +39,71 @@ pub struct SubmitQuoteResponse {
     #[serde(with = "crate::serde::transaction_svm")]
     pub transaction: VersionedTransaction,
 }
+
+/// The status of a quote after it is returned to the integrator.
+#[serde_as]
+#[derive(Serialize, Deserialize, ToSchema, Clone, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QuoteStatus {
+    /// The quote is waiting for the user signature to be submitted back.
+    #[schema(title = "AwaitingSignature")]
+    AwaitingSignature,
+    /// The quote transaction was sent to the user to be signed and broadcast by the user.
+    #[schema(title = "SentToUserForSubmission")]
+    SentToUserForSubmission,
+    /// The quote was submitted to the chain with the signature of the transaction provided in the result field.
+    #[schema(title = "Submitted")]
+    Submitted {
+        #[schema(example = "Jb2urXPyEh4xiBgzYvwEFe4q1iMxG1DNxWGGQg94AmKgqFTwLAiTiHrYiYxwHUB4DV8u5ahNEVtMMDm3sNSRdTg", value_type = String)]
+        #[serde_as(as = "DisplayFromStr")]
+        result: Signature,
+    },
+    /// The quote transaction was included in a block successfully.
+    #[schema(title = "Won")]
+    Won {
+        #[schema(example = "Jb2urXPyEh4xiBgzYvwEFe4q1iMxG1DNxWGGQg94AmKgqFTwLAiTiHrYiYxwHUB4DV8u5ahNEVtMMDm3sNSRdTg", value_type = String)]
+        #[serde_as(as = "DisplayFromStr")]
+        result: Signature,
+    },
+    /// The quote transaction was included in a block but failed.
+    #[schema(title = "Failed")]
+    Failed {
+        #[schema(example = "Jb2urXPyEh4xiBgzYvwEFe4q1iMxG1DNxWGGQg94AmKgqFTwLAiTiHrYiYxwHUB4DV8u5ahNEVtMMDm3sNSRdTg", value_type = String)]
+        #[serde_as(as = "DisplayFromStr")]
+        result: Signature,
+    },
+    /// The quote was cancelled by the searcher and can no longer be submitted.
+    #[schema(title = "Cancelled")]
+    Cancelled,
+    /// The quote expired before being submitted or included in a block.
+    #[schema(title = "Expired")]
+    Expired,
+    /// Another quote returned for the same quote request was submitted instead.
+    #[schema(title = "Lost")]
+    Lost,
+}
+
+impl QuoteStatus {
+    /// Whether the quote will not have any more status updates.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            QuoteStatus::Won { .. }
+                | QuoteStatus::Failed { .. }
+                | QuoteStatus::Cancelled
+                | QuoteStatus::Expired
+                | QuoteStatus::Lost
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, PartialEq, Debug)]
+pub struct QuoteStatusWithReferenceId {
+    /// The reference id of the quote.
+    #[schema(example = "beedbeed-58cc-4372-a567-0e02b2c3d479", value_type = String)]
+    pub reference_id: Uuid,
+    /// The chain id of the quote.
+    #[schema(example = "solana", value_type = String)]
+    pub chain_id:     ChainId,
+    pub status:       QuoteStatus,
+}
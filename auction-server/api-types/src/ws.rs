@@ -5,12 +5,17 @@ use {
             BidCreate,
             BidResult,
             BidStatusWithId,
+            BidVerificationResult,
         },
         opportunity::{
             Opportunity,
             OpportunityDelete,
+            Quote,
+            QuoteCreate,
         },
+        quote::QuoteStatusWithReferenceId,
         ChainId,
+        ErrorBodyResponse,
         Routable,
         SvmChainUpdate,
     },
@@ -21,6 +26,7 @@ use {
     },
     strum::AsRefStr,
     utoipa::ToSchema,
+    uuid::Uuid,
 };
 
 
@@ -42,6 +48,31 @@ pub enum ClientMessage {
 
     #[serde(rename = "cancel_bid")]
     CancelBid { data: BidCancel },
+
+    #[serde(rename = "verify_bid")]
+    VerifyBid { bid: BidCreate },
+
+    /// Subscribe to a stream of improving quotes for the quote request.
+    /// The id of the request is used as the subscription id of the quote updates.
+    #[serde(rename = "subscribe_quote")]
+    SubscribeQuote { quote: QuoteCreate },
+
+    #[serde(rename = "unsubscribe_quote")]
+    UnsubscribeQuote { subscription_id: String },
+
+    /// Subscribe to the status updates of the quotes with the given reference ids.
+    /// The current status of each quote is sent right after the subscription is confirmed.
+    #[serde(rename = "subscribe_quote_status")]
+    SubscribeQuoteStatus {
+        #[schema(value_type = Vec<String>)]
+        reference_ids: Vec<Uuid>,
+    },
+
+    #[serde(rename = "unsubscribe_quote_status")]
+    UnsubscribeQuoteStatus {
+        #[schema(value_type = Vec<String>)]
+        reference_ids: Vec<Uuid>,
+    },
 }
 
 #[derive(Deserialize, Clone, ToSchema, Serialize)]
@@ -66,20 +97,35 @@ pub enum ServerUpdateResponse {
     RemoveOpportunities {
         opportunity_delete: OpportunityDelete,
     },
+    /// A quote which improves on the previous quotes of the subscription.
+    #[serde(rename = "quote_update")]
+    QuoteUpdate {
+        subscription_id: String,
+        quote:           Quote,
+    },
+    /// The subscription will not receive any more quotes.
+    #[serde(rename = "quote_subscription_ended")]
+    QuoteSubscriptionEnded { subscription_id: String },
+    /// The status of a quote subscribed to with `subscribe_quote_status` has changed.
+    /// No more updates are sent for a quote after its status is final.
+    #[serde(rename = "quote_status_update")]
+    QuoteStatusUpdate { status: QuoteStatusWithReferenceId },
 }
 
 #[derive(Serialize, Clone, ToSchema, Deserialize, Debug)]
 #[serde(untagged)]
 pub enum APIResponse {
     BidResult(BidResult),
+    BidVerificationResult(BidVerificationResult),
 }
 #[derive(Serialize, Clone, ToSchema, Deserialize, Debug)]
 #[serde(tag = "status", content = "result")]
 pub enum ServerResultMessage {
     #[serde(rename = "success")]
     Success(Option<APIResponse>),
+    /// The error message with its machine readable details, same as the body of the REST errors.
     #[serde(rename = "error")]
-    Err(String),
+    Err(ErrorBodyResponse),
 }
 
 /// This enum is used to send the result for a specific client request with the same id.
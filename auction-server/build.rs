@@ -15,6 +15,7 @@ use {
 const SUBMIT_BID_INSTRUCTION_SVM: &str = "submit_bid";
 const SUBMIT_BID_PERMISSION_ACCOUNT_SVM: &str = "permission";
 const SUBMIT_BID_ROUTER_ACCOUNT_SVM: &str = "router";
+const SUBMIT_BID_SEARCHER_ACCOUNT_SVM: &str = "searcher";
 
 const SWAP_INSTRUCTION_SVM: &str = "swap";
 const SWAP_ROUTER_TOKEN_ACCOUNT_SVM: &str = "router_fee_receiver_ta";
@@ -67,6 +68,14 @@ fn verify_and_extract_idl_data() {
             SUBMIT_BID_ROUTER_ACCOUNT_SVM,
         )
     );
+    println!(
+        "cargo:rustc-env=SUBMIT_BID_SEARCHER_ACCOUNT_POSITION={}",
+        extract_account_position(
+            express_relay_idl.clone(),
+            SUBMIT_BID_INSTRUCTION_SVM,
+            SUBMIT_BID_SEARCHER_ACCOUNT_SVM,
+        )
+    );
     println!(
         "cargo::rustc-env=SWAP_ROUTER_TOKEN_ACCOUNT_POSITION={}",
         extract_account_position(
@@ -0,0 +1 @@
+ALTER TABLE auction ADD COLUMN pricing_rule JSONB;
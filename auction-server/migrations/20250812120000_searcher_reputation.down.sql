@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS update_updated_at ON searcher_reputation;
+DROP TABLE IF EXISTS searcher_reputation;
@@ -0,0 +1,18 @@
+CREATE TABLE searcher_reputation
+(
+    profile_id     UUID         NOT NULL REFERENCES profile(id) ON DELETE CASCADE,
+    chain_id       VARCHAR(255) NOT NULL,
+    auctions_won   BIGINT       NOT NULL DEFAULT 0,
+    bids_landed    BIGINT       NOT NULL DEFAULT 0,
+    bids_failed    BIGINT       NOT NULL DEFAULT 0,
+    bids_expired   BIGINT       NOT NULL DEFAULT 0,
+    banned_until   TIMESTAMP,
+    created_at     TIMESTAMP    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at     TIMESTAMP    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (profile_id, chain_id)
+);
+
+CREATE TRIGGER update_updated_at
+BEFORE UPDATE ON searcher_reputation
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,8 @@
+CREATE TABLE relayer_spend
+(
+    profile_id UUID         NOT NULL REFERENCES profile(id) ON DELETE CASCADE,
+    chain_id   VARCHAR(255) NOT NULL,
+    day        DATE         NOT NULL,
+    lamports   BIGINT       NOT NULL DEFAULT 0,
+    PRIMARY KEY (profile_id, chain_id, day)
+);
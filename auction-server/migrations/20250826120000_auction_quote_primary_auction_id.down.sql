@@ -0,0 +1 @@
+ALTER TABLE auction DROP COLUMN quote_primary_auction_id;
@@ -0,0 +1 @@
+ALTER TABLE auction ADD COLUMN quote_primary_auction_id UUID;
@@ -23,7 +23,9 @@ use {
         },
         handler::Handler,
         http::{
+            header,
             request::Parts,
+            HeaderValue,
             Method,
             StatusCode,
         },
@@ -60,8 +62,11 @@ use {
         profile::Route as ProfileRoute,
         AccessLevel,
         ErrorBodyResponse,
+        ErrorCode,
+        ErrorDetails,
         Routable,
         Route,
+        SimulationErrorDetails,
     },
     solana_sdk::{
         program_error::ProgramError,
@@ -77,6 +82,7 @@ use {
             atomic::Ordering,
             Arc,
         },
+        time::Duration,
     },
     time::OffsetDateTime,
     tokio::time::Instant,
@@ -112,6 +118,9 @@ pub enum InstructionError {
     InvalidAssociatedTokenAccountInstruction(String),
     UnsupportedAssociatedTokenAccountInstruction(AssociatedTokenAccountInstruction),
     UnsupportedProgram(Pubkey),
+    UnsupportedInstruction(Pubkey),
+    AccountNotAllowed { position: usize, account: Pubkey },
+    WritableAccountNotAllowed { position: usize, account: Pubkey },
     TransferInstructionNotAllowed,
     CloseAccountInstructionNotAllowed,
     InvalidTransferInstructionsCount,
@@ -158,6 +167,23 @@ impl std::fmt::Display for InstructionError {
             InstructionError::UnsupportedProgram(program) => {
                 write!(f, "Unsupported program {}", program)
             }
+            InstructionError::UnsupportedInstruction(program) => {
+                write!(f, "Unsupported instruction for program {}", program)
+            }
+            InstructionError::AccountNotAllowed { position, account } => {
+                write!(
+                    f,
+                    "Account {} is not allowed at position {} of the instruction",
+                    account, position
+                )
+            }
+            InstructionError::WritableAccountNotAllowed { position, account } => {
+                write!(
+                    f,
+                    "Account {} at position {} of the instruction must not be writable",
+                    account, position
+                )
+            }
             InstructionError::TransferInstructionNotAllowed => {
                 write!(f, "Transfer instruction is not allowed")
             }
@@ -282,6 +308,124 @@ impl std::fmt::Display for InstructionError {
     }
 }
 
+impl InstructionError {
+    /// Returns the reason of the error with the expected and found values, if any.
+    fn get_reason(&self) -> (&'static str, Option<String>, Option<String>) {
+        fn pair(expected: impl Display, found: impl Display) -> (Option<String>, Option<String>) {
+            (Some(expected.to_string()), Some(found.to_string()))
+        }
+        let (reason, (expected, found)) = match self {
+            InstructionError::ProgramIdIndexOutOfBounds => {
+                ("program_id_index_out_of_bounds", (None, None))
+            }
+            InstructionError::UnsupportedSystemProgramInstruction => {
+                ("unsupported_system_program_instruction", (None, None))
+            }
+            InstructionError::InvalidSplTokenInstruction(_) => {
+                ("invalid_spl_token_instruction", (None, None))
+            }
+            InstructionError::UnsupportedSplTokenInstruction(instruction) => (
+                "unsupported_spl_token_instruction",
+                (None, Some(instruction.clone())),
+            ),
+            InstructionError::InvalidAssociatedTokenAccountInstruction(_) => {
+                ("invalid_associated_token_account_instruction", (None, None))
+            }
+            InstructionError::UnsupportedAssociatedTokenAccountInstruction(instruction) => (
+                "unsupported_associated_token_account_instruction",
+                (None, Some(format!("{:?}", instruction))),
+            ),
+            InstructionError::UnsupportedProgram(program) => {
+                ("unsupported_program", (None, Some(program.to_string())))
+            }
+            InstructionError::UnsupportedInstruction(program) => {
+                ("unsupported_instruction", (None, Some(program.to_string())))
+            }
+            InstructionError::AccountNotAllowed { account, .. } => {
+                ("account_not_allowed", (None, Some(account.to_string())))
+            }
+            InstructionError::WritableAccountNotAllowed { account, .. } => (
+                "writable_account_not_allowed",
+                (None, Some(account.to_string())),
+            ),
+            InstructionError::TransferInstructionNotAllowed => {
+                ("transfer_instruction_not_allowed", (None, None))
+            }
+            InstructionError::CloseAccountInstructionNotAllowed => {
+                ("close_account_instruction_not_allowed", (None, None))
+            }
+            InstructionError::InvalidTransferInstructionsCount => {
+                ("invalid_transfer_instructions_count", (None, None))
+            }
+            InstructionError::InvalidFromAccountTransferInstruction { expected, found } => (
+                "invalid_from_account_transfer_instruction",
+                pair(expected, found),
+            ),
+            InstructionError::InvalidToAccountTransferInstruction { expected, found } => (
+                "invalid_to_account_transfer_instruction",
+                pair(expected, found),
+            ),
+            InstructionError::InvalidAmountTransferInstruction { expected, found } => {
+                ("invalid_amount_transfer_instruction", pair(expected, found))
+            }
+            InstructionError::InvalidSyncNativeInstructionCount(account) => (
+                "invalid_sync_native_instruction_count",
+                (None, Some(account.to_string())),
+            ),
+            InstructionError::InvalidCloseAccountInstructionCountUser(found) => (
+                "invalid_close_account_instruction_count_user",
+                pair(1, found),
+            ),
+            InstructionError::InvalidCloseAccountInstructionCountSearcher(found) => (
+                "invalid_close_account_instruction_count_searcher",
+                (None, Some(found.to_string())),
+            ),
+            InstructionError::InvalidAccountToCloseInCloseAccountInstruction(account) => (
+                "invalid_account_to_close_in_close_account_instruction",
+                (None, Some(account.to_string())),
+            ),
+            InstructionError::InvalidDestinationCloseAccountInstruction { expected, found } => (
+                "invalid_destination_close_account_instruction",
+                pair(expected, found),
+            ),
+            InstructionError::InvalidOwnerCloseAccountInstruction { expected, found } => (
+                "invalid_owner_close_account_instruction",
+                pair(expected, found),
+            ),
+            InstructionError::InvalidMintInCreateAtaInstruction { expected, found } => (
+                "invalid_mint_in_create_ata_instruction",
+                pair(expected, found),
+            ),
+            InstructionError::InvalidOwnerInCreateAtaInstruction { expected, found } => (
+                "invalid_owner_in_create_ata_instruction",
+                pair(expected, found),
+            ),
+            InstructionError::InvalidPayerInCreateAtaInstruction { expected, found } => (
+                "invalid_payer_in_create_ata_instruction",
+                pair(expected, found),
+            ),
+            InstructionError::InvalidTokenProgramInCreateAtaInstruction { expected, found } => (
+                "invalid_token_program_in_create_ata_instruction",
+                pair(expected, found),
+            ),
+            InstructionError::InvalidSystemProgramInCreateAtaInstruction(program) => (
+                "invalid_system_program_in_create_ata_instruction",
+                pair(solana_sdk::system_program::id(), program),
+            ),
+            InstructionError::MissingCreateAtaInstruction(account) => (
+                "missing_create_ata_instruction",
+                (None, Some(account.to_string())),
+            ),
+            InstructionError::InvalidMemoInstructionCount { expected, found } => {
+                ("invalid_memo_instruction_count", pair(expected, found))
+            }
+            InstructionError::InvalidMemoString { expected, found } => {
+                ("invalid_memo_string", pair(expected, found))
+            }
+        };
+        (reason, expected, found)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SwapInstructionError {
@@ -329,6 +473,16 @@ pub enum SwapInstructionError {
         expected: Pubkey,
         found:    Pubkey,
     },
+    MinimumAmountSearcher {
+        expected: u64,
+        found:    u64,
+    },
+    /// The transfer fee of the mint withholds the whole transferred amount
+    AmountBelowTransferFee {
+        mint:         Pubkey,
+        transfer_fee: u64,
+        found:        u64,
+    },
 }
 
 impl std::fmt::Display for SwapInstructionError {
@@ -389,6 +543,85 @@ impl std::fmt::Display for SwapInstructionError {
                 "Invalid platform fee ppm {} in swap instruction data. Value does not match the platform fee ppm in swap opportunity {}",
                 found, expected
             ),
+            SwapInstructionError::MinimumAmountSearcher { expected, found } => write!(
+                f,
+                "Invalid minimum searcher amount {} in swap instruction data. Value is lower than the minimum searcher amount allowed by the partial fill tolerance in swap opportunity {}",
+                found, expected
+            ),
+            SwapInstructionError::AmountBelowTransferFee {
+                mint,
+                transfer_fee,
+                found,
+            } => write!(
+                f,
+                "Invalid amount {} of mint {} in swap instruction data. Value does not exceed the transfer fee {} of the mint",
+                found, mint, transfer_fee
+            ),
+        }
+    }
+}
+
+impl SwapInstructionError {
+    /// Returns the reason of the error with the expected and found values.
+    fn get_reason(&self) -> (&'static str, String, String) {
+        match self {
+            SwapInstructionError::UserWalletAddress { expected, found } => (
+                "user_wallet_address",
+                expected.to_string(),
+                found.to_string(),
+            ),
+            SwapInstructionError::MintSearcher { expected, found } => {
+                ("mint_searcher", expected.to_string(), found.to_string())
+            }
+            SwapInstructionError::MintUser { expected, found } => {
+                ("mint_user", expected.to_string(), found.to_string())
+            }
+            SwapInstructionError::TokenProgramSearcher { expected, found } => (
+                "token_program_searcher",
+                expected.to_string(),
+                found.to_string(),
+            ),
+            SwapInstructionError::TokenProgramUser { expected, found } => (
+                "token_program_user",
+                expected.to_string(),
+                found.to_string(),
+            ),
+            SwapInstructionError::AmountSearcher { expected, found } => {
+                ("amount_searcher", expected.to_string(), found.to_string())
+            }
+            SwapInstructionError::AmountUser { expected, found } => {
+                ("amount_user", expected.to_string(), found.to_string())
+            }
+            SwapInstructionError::FeeToken { expected, found } => (
+                "fee_token",
+                format!("{:?}", expected),
+                format!("{:?}", found),
+            ),
+            SwapInstructionError::ReferralFee { expected, found } => {
+                ("referral_fee", expected.to_string(), found.to_string())
+            }
+            SwapInstructionError::PlatformFee { expected, found } => {
+                ("platform_fee", expected.to_string(), found.to_string())
+            }
+            SwapInstructionError::AssociatedRouterTokenAccount { expected, found } => (
+                "associated_router_token_account",
+                expected.to_string(),
+                found.to_string(),
+            ),
+            SwapInstructionError::MinimumAmountSearcher { expected, found } => (
+                "minimum_amount_searcher",
+                expected.to_string(),
+                found.to_string(),
+            ),
+            SwapInstructionError::AmountBelowTransferFee {
+                transfer_fee,
+                found,
+                ..
+            } => (
+                "amount_below_transfer_fee",
+                format!("greater than {}", transfer_fee),
+                found.to_string(),
+            ),
         }
     }
 }
@@ -402,7 +635,10 @@ pub enum RestError {
     /// The chain id is not supported.
     InvalidChainId,
     /// The simulation failed.
-    SimulationError { reason: String },
+    SimulationError {
+        reason:  String,
+        details: Box<SimulationErrorDetails>,
+    },
     /// The opportunity was not found.
     OpportunityNotFound,
     /// The bid was not found.
@@ -467,6 +703,21 @@ pub enum RestError {
     TokenMintNotAllowed(String, String),
     /// Access to cancel quote feature is revoked
     CancelQuoteAccessRevoked,
+    /// The token mint has a Token-2022 extension that swaps do not support
+    UnsupportedTokenExtension {
+        mint:      Pubkey,
+        extension: &'static str,
+    },
+    /// Too many requests, the request can be retried after the given duration
+    RateLimited { retry_after: Duration },
+    /// The searcher is banned because of its failed or unsigned winning bids
+    SearcherBanned { banned_until: OffsetDateTime },
+    /// The bids of the searcher must be simulated because of its failed or unsigned winning bids
+    SimulationRequired,
+    /// The relayer would pay more lamports for the bid than allowed
+    RelayerSpendTooHigh { spend: u64, maximum: u64 },
+    /// The relayer paid the daily maximum of lamports for the bids of the profile
+    RelayerDailySpendLimitReached { limit: u64 },
 }
 
 
@@ -484,7 +735,7 @@ impl RestError {
                 StatusCode::NOT_FOUND,
                 "The chain id is not found".to_string(),
             ),
-            RestError::SimulationError { reason } => (
+            RestError::SimulationError { reason, .. } => (
                 StatusCode::BAD_REQUEST,
                 format!("Simulation failed: {}", reason),
             ),
@@ -608,14 +859,191 @@ impl RestError {
                 StatusCode::FORBIDDEN,
                 "Access to cancel quote feature is revoked".to_string(),
             ),
+            RestError::UnsupportedTokenExtension { mint, extension } => (
+                StatusCode::BAD_REQUEST,
+                format!("Token mint {} has unsupported token extension: {}", mint, extension),
+            ),
+            RestError::RateLimited { retry_after } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Rate limit exceeded, retry after {} ms", retry_after.as_millis()),
+            ),
+            RestError::SearcherBanned { banned_until } => (
+                StatusCode::FORBIDDEN,
+                format!(
+                    "Searcher is banned until {} because of failed or unsigned winning bids",
+                    banned_until
+                ),
+            ),
+            RestError::SimulationRequired => (
+                StatusCode::FORBIDDEN,
+                "Bids of this searcher must be simulated, bids on indicative quotes are not allowed".to_string(),
+            ),
+            RestError::RelayerSpendTooHigh { spend, maximum } => (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "The relayer would pay {} lamports for the bid, the maximum is {} lamports",
+                    spend, maximum
+                ),
+            ),
+            RestError::RelayerDailySpendLimitReached { limit } => (
+                StatusCode::FORBIDDEN,
+                format!(
+                    "The relayer paid the daily limit of {} lamports for the bids of this profile",
+                    limit
+                ),
+            ),
+        }
+    }
+}
+
+impl RestError {
+    /// Returns the machine readable details of the error.
+    pub fn to_error_details(&self) -> ErrorDetails {
+        let code = match self {
+            RestError::BadParameters(_) => ErrorCode::BadParameters,
+            RestError::InvalidOpportunity(_) => ErrorCode::InvalidOpportunity,
+            RestError::InvalidChainId => ErrorCode::InvalidChainId,
+            RestError::SimulationError { .. } => ErrorCode::SimulationFailed,
+            RestError::OpportunityNotFound => ErrorCode::OpportunityNotFound,
+            RestError::BidNotFound => ErrorCode::BidNotFound,
+            RestError::TemporarilyUnavailable => ErrorCode::TemporarilyUnavailable,
+            RestError::InvalidToken => ErrorCode::InvalidToken,
+            RestError::Forbidden => ErrorCode::Forbidden,
+            RestError::Unauthorized => ErrorCode::Unauthorized,
+            RestError::ProfileNotFound => ErrorCode::ProfileNotFound,
+            RestError::QuoteNotFound => ErrorCode::QuoteNotFound,
+            RestError::DuplicateOpportunity => ErrorCode::DuplicateOpportunity,
+            RestError::SwapOpportunityNotFound => ErrorCode::SwapOpportunityNotFound,
+            RestError::TransactionSizeTooLarge(..) => ErrorCode::TransactionSizeTooLarge,
+            RestError::MultipleSetComputeUnitPriceInstructions => {
+                ErrorCode::MultipleSetComputeUnitPriceInstructions
+            }
+            RestError::SetComputeUnitPriceInstructionNotFound(_) => {
+                ErrorCode::SetComputeUnitPriceInstructionNotFound
+            }
+            RestError::LowComputeUnitPrice(_) => ErrorCode::LowComputeUnitPrice,
+            RestError::InvalidInstruction(..) => ErrorCode::InvalidInstruction,
+            RestError::InvalidExpressRelayInstructionCount(_) => {
+                ErrorCode::InvalidExpressRelayInstructionCount
+            }
+            RestError::InvalidSwapInstruction(_) => ErrorCode::InvalidSwapInstruction,
+            RestError::InvalidDeadline { .. } => ErrorCode::InvalidDeadline,
+            RestError::DeadlineTooLate { .. } => ErrorCode::DeadlineTooLate,
+            RestError::InvalidSignature(_) => ErrorCode::InvalidSignature,
+            RestError::RelayerNotSigner(_) => ErrorCode::RelayerNotSigner,
+            RestError::InvalidFirstSigner(_) => ErrorCode::InvalidFirstSigner,
+            RestError::DuplicateBid => ErrorCode::DuplicateBid,
+            RestError::TooManyOpenWebsocketConnections => {
+                ErrorCode::TooManyOpenWebsocketConnections
+            }
+            RestError::QuoteIsExpired => ErrorCode::QuoteIsExpired,
+            RestError::QuoteIsCancelled => ErrorCode::QuoteIsCancelled,
+            RestError::QuoteIsFinalized => ErrorCode::QuoteIsFinalized,
+            RestError::TokenMintNotAllowed(..) => ErrorCode::TokenMintNotAllowed,
+            RestError::CancelQuoteAccessRevoked => ErrorCode::CancelQuoteAccessRevoked,
+            RestError::UnsupportedTokenExtension { .. } => ErrorCode::UnsupportedTokenExtension,
+            RestError::RateLimited { .. } => ErrorCode::RateLimited,
+            RestError::SearcherBanned { .. } => ErrorCode::SearcherBanned,
+            RestError::SimulationRequired => ErrorCode::SimulationRequired,
+            RestError::RelayerSpendTooHigh { .. } => ErrorCode::RelayerSpendTooHigh,
+            RestError::RelayerDailySpendLimitReached { .. } => {
+                ErrorCode::RelayerDailySpendLimitReached
+            }
+        };
+        let mut details = ErrorDetails::new(code);
+        match self {
+            RestError::InvalidInstruction(index, error) => {
+                let (reason, expected, found) = error.get_reason();
+                details.instruction_index = *index;
+                details.reason = Some(reason.to_string());
+                details.expected = expected;
+                details.found = found;
+            }
+            RestError::InvalidSwapInstruction(error) => {
+                let (reason, expected, found) = error.get_reason();
+                details.reason = Some(reason.to_string());
+                details.expected = Some(expected);
+                details.found = Some(found);
+            }
+            RestError::SimulationError {
+                details: simulation,
+                ..
+            } => {
+                details.simulation = Some(simulation.as_ref().clone());
+            }
+            RestError::TransactionSizeTooLarge(size, limit) => {
+                details.expected = Some(limit.to_string());
+                details.found = Some(size.to_string());
+            }
+            RestError::SetComputeUnitPriceInstructionNotFound(minimum)
+            | RestError::LowComputeUnitPrice(minimum) => {
+                details.expected = Some(minimum.to_string());
+            }
+            RestError::InvalidExpressRelayInstructionCount(count) => {
+                details.expected = Some(1.to_string());
+                details.found = Some(count.to_string());
+            }
+            RestError::InvalidDeadline { deadline, minimum } => {
+                details.expected = Some(minimum.unix_timestamp().to_string());
+                details.found = Some(deadline.unix_timestamp().to_string());
+            }
+            RestError::DeadlineTooLate { deadline, maximum } => {
+                details.expected = Some(maximum.unix_timestamp().to_string());
+                details.found = Some(deadline.unix_timestamp().to_string());
+            }
+            RestError::InvalidSignature(account) | RestError::RelayerNotSigner(account) => {
+                details.found = Some(account.to_string());
+            }
+            RestError::TokenMintNotAllowed(_, mint) => {
+                details.found = Some(mint.clone());
+            }
+            RestError::UnsupportedTokenExtension { mint, extension } => {
+                details.reason = Some(extension.to_string());
+                details.found = Some(mint.to_string());
+            }
+            RestError::RateLimited { retry_after } => {
+                details.retry_after_ms =
+                    Some(u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX));
+            }
+            RestError::SearcherBanned { banned_until } => {
+                details.banned_until = Some(*banned_until);
+            }
+            RestError::RelayerSpendTooHigh { spend, maximum } => {
+                details.expected = Some(maximum.to_string());
+                details.found = Some(spend.to_string());
+            }
+            RestError::RelayerDailySpendLimitReached { limit } => {
+                details.expected = Some(limit.to_string());
+            }
+            _ => {}
+        }
+        details
+    }
+
+    /// Returns the body of the error, with the message and the machine readable details.
+    /// The same body is sent for the errors of the websocket requests.
+    pub fn to_error_body(&self) -> ErrorBodyResponse {
+        ErrorBodyResponse {
+            error:   self.to_status_and_message().1,
+            details: Some(self.to_error_details()),
         }
     }
 }
 
 impl IntoResponse for RestError {
     fn into_response(self) -> Response {
-        let (status, msg) = self.to_status_and_message();
-        (status, Json(ErrorBodyResponse { error: msg })).into_response()
+        let (status, _) = self.to_status_and_message();
+        let mut response = (status, Json(self.to_error_body())).into_response();
+        if let RestError::RateLimited { retry_after } = self {
+            // Retry-After is in whole seconds, so round up to not retry too early
+            let seconds = retry_after
+                .as_secs()
+                .saturating_add(u64::from(retry_after.subsec_nanos() > 0));
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        }
+        response
     }
 }
 
@@ -796,10 +1224,12 @@ pub async fn start_api(run_options: RunOptions, store: Arc<StoreNew>) -> Result<
     bid::get_bids_by_time_deprecated,
     bid::get_bid_status_deprecated,
     bid::post_cancel_bid,
+    bid::post_verify_bid,
 
     opportunity::post_opportunity,
     opportunity::get_opportunities,
     opportunity::post_quote,
+    opportunity::get_indicative_quote,
     opportunity::delete_opportunities,
 
     bid::post_submit_quote,
@@ -818,6 +1248,7 @@ pub async fn start_api(run_options: RunOptions, store: Arc<StoreNew>) -> Result<
     api_types::bid::BidStatusSvm,
     api_types::bid::BidStatusWithId,
     api_types::bid::BidResult,
+    api_types::bid::BidVerificationResult,
     api_types::bid::Bid,
     api_types::bid::BidSvm,
     api_types::bid::Bids,
@@ -844,6 +1275,7 @@ pub async fn start_api(run_options: RunOptions, store: Arc<StoreNew>) -> Result<
     api_types::opportunity::QuoteSvm,
     api_types::opportunity::QuoteTokens,
     api_types::opportunity::QuoteV1Svm,
+    api_types::opportunity::IndicativeQuote,
     api_types::opportunity::OpportunityDelete,
     api_types::opportunity::OpportunityDeleteSvm,
     api_types::opportunity::OpportunityDeleteV1Svm,
@@ -853,8 +1285,14 @@ pub async fn start_api(run_options: RunOptions, store: Arc<StoreNew>) -> Result<
 
     api_types::quote::SubmitQuote,
     api_types::quote::SubmitQuoteResponse,
+    api_types::quote::QuoteStatus,
+    api_types::quote::QuoteStatusWithReferenceId,
 
     ErrorBodyResponse,
+    ErrorCode,
+    ErrorDetails,
+    SimulationErrorDetails,
+    api_types::SimulationInnerInstruction,
     api_types::ws::ClientRequest,
     api_types::ws::ClientMessage,
     api_types::ws::ServerResultMessage,
@@ -936,3 +1374,267 @@ pub async fn start_api(run_options: RunOptions, store: Arc<StoreNew>) -> Result<
         .await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{
+            InstructionError,
+            RestError,
+            SwapInstructionError,
+        },
+        axum::{
+            body::to_bytes,
+            http::StatusCode,
+            response::IntoResponse,
+        },
+        express_relay_api_types::{
+            ws::{
+                ServerResultMessage,
+                ServerResultResponse,
+            },
+            ErrorCode,
+            ErrorDetails,
+            SimulationErrorDetails,
+        },
+        solana_sdk::pubkey::Pubkey,
+        std::time::Duration,
+        time::OffsetDateTime,
+    };
+
+    fn get_errors() -> Vec<(RestError, &'static str)> {
+        let deadline = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        vec![
+            (
+                RestError::BadParameters("bad".to_string()),
+                "bad_parameters",
+            ),
+            (
+                RestError::InvalidOpportunity("invalid".to_string()),
+                "invalid_opportunity",
+            ),
+            (RestError::InvalidChainId, "invalid_chain_id"),
+            (
+                RestError::SimulationError {
+                    reason:  "failed".to_string(),
+                    details: Box::new(SimulationErrorDetails {
+                        error:                  "InstructionError(0, Custom(6001))".to_string(),
+                        logs:                   vec![],
+                        compute_units_consumed: None,
+                        inner_instructions:     vec![],
+                        truncated:              false,
+                    }),
+                },
+                "simulation_failed",
+            ),
+            (RestError::OpportunityNotFound, "opportunity_not_found"),
+            (RestError::BidNotFound, "bid_not_found"),
+            (RestError::TemporarilyUnavailable, "temporarily_unavailable"),
+            (RestError::InvalidToken, "invalid_token"),
+            (RestError::Forbidden, "forbidden"),
+            (RestError::Unauthorized, "unauthorized"),
+            (RestError::ProfileNotFound, "profile_not_found"),
+            (RestError::QuoteNotFound, "quote_not_found"),
+            (RestError::DuplicateOpportunity, "duplicate_opportunity"),
+            (
+                RestError::SwapOpportunityNotFound,
+                "swap_opportunity_not_found",
+            ),
+            (
+                RestError::TransactionSizeTooLarge(2000, 1232),
+                "transaction_size_too_large",
+            ),
+            (
+                RestError::MultipleSetComputeUnitPriceInstructions,
+                "multiple_set_compute_unit_price_instructions",
+            ),
+            (
+                RestError::SetComputeUnitPriceInstructionNotFound(100),
+                "set_compute_unit_price_instruction_not_found",
+            ),
+            (
+                RestError::LowComputeUnitPrice(100),
+                "low_compute_unit_price",
+            ),
+            (
+                RestError::InvalidInstruction(
+                    Some(1),
+                    InstructionError::TransferInstructionNotAllowed,
+                ),
+                "invalid_instruction",
+            ),
+            (
+                RestError::InvalidExpressRelayInstructionCount(2),
+                "invalid_express_relay_instruction_count",
+            ),
+            (
+                RestError::InvalidSwapInstruction(SwapInstructionError::UserWalletAddress {
+                    expected: Pubkey::new_unique(),
+                    found:    Pubkey::new_unique(),
+                }),
+                "invalid_swap_instruction",
+            ),
+            (
+                RestError::InvalidDeadline {
+                    deadline,
+                    minimum: deadline + Duration::from_secs(1),
+                },
+                "invalid_deadline",
+            ),
+            (
+                RestError::DeadlineTooLate {
+                    deadline,
+                    maximum: deadline - Duration::from_secs(1),
+                },
+                "deadline_too_late",
+            ),
+            (
+                RestError::InvalidSignature(Pubkey::new_unique()),
+                "invalid_signature",
+            ),
+            (
+                RestError::RelayerNotSigner(Pubkey::new_unique()),
+                "relayer_not_signer",
+            ),
+            (
+                RestError::InvalidFirstSigner("signer".to_string()),
+                "invalid_first_signer",
+            ),
+            (RestError::DuplicateBid, "duplicate_bid"),
+            (
+                RestError::TooManyOpenWebsocketConnections,
+                "too_many_open_websocket_connections",
+            ),
+            (RestError::QuoteIsExpired, "quote_is_expired"),
+            (RestError::QuoteIsCancelled, "quote_is_cancelled"),
+            (RestError::QuoteIsFinalized, "quote_is_finalized"),
+            (
+                RestError::TokenMintNotAllowed("searcher".to_string(), "mint".to_string()),
+                "token_mint_not_allowed",
+            ),
+            (
+                RestError::CancelQuoteAccessRevoked,
+                "cancel_quote_access_revoked",
+            ),
+            (
+                RestError::UnsupportedTokenExtension {
+                    mint:      Pubkey::new_unique(),
+                    extension: "confidential_transfer",
+                },
+                "unsupported_token_extension",
+            ),
+            (
+                RestError::RateLimited {
+                    retry_after: Duration::from_millis(1500),
+                },
+                "rate_limited",
+            ),
+            (
+                RestError::SearcherBanned {
+                    banned_until: deadline,
+                },
+                "searcher_banned",
+            ),
+            (RestError::SimulationRequired, "simulation_required"),
+            (
+                RestError::RelayerSpendTooHigh {
+                    spend:   20,
+                    maximum: 10,
+                },
+                "relayer_spend_too_high",
+            ),
+            (
+                RestError::RelayerDailySpendLimitReached { limit: 10 },
+                "relayer_daily_spend_limit_reached",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_error_codes_are_stable() {
+        for (error, code) in get_errors() {
+            let details = error.to_error_details();
+            assert_eq!(
+                serde_json::to_value(details.code).unwrap(),
+                serde_json::json!(code),
+                "unexpected error code for {:?}",
+                error
+            );
+            assert_ne!(details.code, ErrorCode::Unknown);
+        }
+    }
+
+    #[test]
+    fn test_error_codes_are_unique() {
+        let errors = get_errors();
+        for (i, (error, _)) in errors.iter().enumerate() {
+            for (other, _) in errors.iter().skip(i + 1) {
+                assert_ne!(
+                    error.to_error_details().code,
+                    other.to_error_details().code,
+                    "{:?} and {:?} have the same error code",
+                    error,
+                    other
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_response_body() {
+        for (error, code) in get_errors() {
+            let (status, message) = error.to_status_and_message();
+            let details = error.to_error_details();
+            let response = error.into_response();
+            assert_eq!(response.status(), status);
+
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["error"], serde_json::json!(message));
+            assert_eq!(body["code"], serde_json::json!(code));
+            assert_eq!(
+                serde_json::from_value::<ErrorDetails>(body).unwrap(),
+                details
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ws_error_response_has_rest_error_body() {
+        for (error, _) in get_errors() {
+            let ws_response = ServerResultResponse {
+                id:     Some("1".to_string()),
+                result: ServerResultMessage::Err(error.to_error_body()),
+            };
+            let ws_response = serde_json::to_value(ws_response).unwrap();
+            assert_eq!(ws_response["status"], serde_json::json!("error"));
+
+            let body = to_bytes(error.into_response().into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(ws_response["result"], body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_response() {
+        let response = RestError::RateLimited {
+            retry_after: Duration::from_millis(1500),
+        }
+        .into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            response
+                .headers()
+                .get(axum::http::header::RETRY_AFTER)
+                .unwrap(),
+            "2"
+        );
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], serde_json::json!("rate_limited"));
+        assert_eq!(body["retry_after_ms"], serde_json::json!(1500));
+    }
+}
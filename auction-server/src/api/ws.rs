@@ -8,11 +8,17 @@ use {
         auction::{
             api::{
                 cancel_bid,
+                get_quote_statuses,
                 process_bid,
+                verify_bid,
             },
             entities::BidId,
         },
         config::ChainId,
+        opportunity::{
+            api::subscribe_quote,
+            entities::Quote,
+        },
         server::{
             EXIT_CHECK_INTERVAL,
             SHOULD_EXIT,
@@ -45,7 +51,9 @@ use {
         opportunity::{
             Opportunity,
             OpportunityDelete,
+            QuoteCreate,
         },
+        quote::QuoteStatusWithReferenceId,
         ws::{
             APIResponse,
             ClientMessage,
@@ -55,6 +63,9 @@ use {
             ServerResultResponse,
             ServerUpdateResponse,
         },
+        ErrorBodyResponse,
+        ErrorCode,
+        ErrorDetails,
         SvmChainUpdate,
     },
     futures::{
@@ -83,13 +94,16 @@ use {
     },
     tokio::sync::{
         broadcast,
+        mpsc,
         RwLock,
         Semaphore,
     },
+    tokio_util::sync::CancellationToken,
     tracing::{
         instrument,
         Instrument,
     },
+    uuid::Uuid,
 };
 
 pub struct WsState {
@@ -208,6 +222,7 @@ pub enum UpdateEvent {
     BidStatusUpdate(BidStatusWithId),
     SvmChainUpdate(SvmChainUpdate),
     RemoveOpportunities(OpportunityDelete),
+    QuoteStatusUpdate(QuoteStatusWithReferenceId),
 }
 
 pub type SubscriberId = usize;
@@ -215,28 +230,33 @@ pub type SubscriberId = usize;
 
 #[derive(Debug, Clone)]
 struct DeferredResponse {
-    response:      ServerResultResponse,
-    bid_id_to_add: Option<BidId>,
+    response:                     ServerResultResponse,
+    bid_id_to_add:                Option<BidId>,
+    quote_subscription_to_remove: Option<String>,
 }
 
 /// Subscriber is an actor that handles a single websocket connection.
 /// It listens to the store for updates and sends them to the client.
 pub struct Subscriber {
-    id:                  SubscriberId,
-    closed:              bool,
-    store:               Arc<StoreNew>,
-    notify_receiver:     broadcast::Receiver<UpdateEvent>,
-    receiver:            SplitStream<WebSocket>,
-    sender:              SplitSink<WebSocket, Message>,
-    chain_ids:           HashSet<ChainId>,
-    bid_ids:             HashSet<BidId>,
-    ping_interval:       tokio::time::Interval,
-    exit_check_interval: tokio::time::Interval,
-    responded_to_ping:   bool,
-    auth:                Auth,
-    active_requests:     Arc<Semaphore>,
-    response_sender:     broadcast::Sender<DeferredResponse>,
-    response_receiver:   broadcast::Receiver<DeferredResponse>,
+    id:                    SubscriberId,
+    closed:                bool,
+    store:                 Arc<StoreNew>,
+    notify_receiver:       broadcast::Receiver<UpdateEvent>,
+    receiver:              SplitStream<WebSocket>,
+    sender:                SplitSink<WebSocket, Message>,
+    chain_ids:             HashSet<ChainId>,
+    bid_ids:               HashSet<BidId>,
+    quote_reference_ids:   HashSet<Uuid>,
+    ping_interval:         tokio::time::Interval,
+    exit_check_interval:   tokio::time::Interval,
+    responded_to_ping:     bool,
+    auth:                  Auth,
+    active_requests:       Arc<Semaphore>,
+    response_sender:       broadcast::Sender<DeferredResponse>,
+    response_receiver:     broadcast::Receiver<DeferredResponse>,
+    quote_subscriptions:   HashMap<String, CancellationToken>,
+    quote_update_sender:   mpsc::Sender<ServerUpdateResponse>,
+    quote_update_receiver: mpsc::Receiver<ServerUpdateResponse>,
 }
 
 const PING_INTERVAL_DURATION: Duration = Duration::from_secs(30);
@@ -249,6 +269,35 @@ fn ok_response(id: String) -> ServerResultResponse {
 }
 
 const MAX_ACTIVE_REQUESTS: usize = 150;
+const MAX_QUOTE_SUBSCRIPTIONS: usize = 10;
+const MAX_QUOTE_STATUS_SUBSCRIPTIONS: usize = 100;
+
+/// Forwards the quotes of a quote subscription to the subscriber until the subscription is cancelled or ended.
+async fn forward_quote_updates(
+    subscription_id: String,
+    mut receiver: mpsc::Receiver<Quote>,
+    quote_update_sender: mpsc::Sender<ServerUpdateResponse>,
+    cancellation_token: CancellationToken,
+) {
+    loop {
+        let update = tokio::select! {
+            _ = cancellation_token.cancelled() => return,
+            maybe_quote = receiver.recv() => match maybe_quote {
+                Some(quote) => ServerUpdateResponse::QuoteUpdate {
+                    subscription_id: subscription_id.clone(),
+                    quote:           quote.into(),
+                },
+                None => ServerUpdateResponse::QuoteSubscriptionEnded {
+                    subscription_id: subscription_id.clone(),
+                },
+            },
+        };
+        let ended = matches!(update, ServerUpdateResponse::QuoteSubscriptionEnded { .. });
+        if quote_update_sender.send(update).await.is_err() || ended {
+            return;
+        }
+    }
+}
 
 impl Subscriber {
     pub fn new(
@@ -260,6 +309,7 @@ impl Subscriber {
         auth: Auth,
     ) -> Self {
         let (response_sender, response_receiver) = broadcast::channel(100);
+        let (quote_update_sender, quote_update_receiver) = mpsc::channel(100);
         Self {
             id,
             closed: false,
@@ -269,6 +319,7 @@ impl Subscriber {
             sender,
             chain_ids: HashSet::new(),
             bid_ids: HashSet::new(),
+            quote_reference_ids: HashSet::new(),
             ping_interval: tokio::time::interval(PING_INTERVAL_DURATION),
             exit_check_interval: tokio::time::interval(EXIT_CHECK_INTERVAL),
             responded_to_ping: true, // We start with true so we don't close the connection immediately
@@ -276,6 +327,9 @@ impl Subscriber {
             active_requests: Arc::new(Semaphore::new(MAX_ACTIVE_REQUESTS)),
             response_receiver,
             response_sender,
+            quote_subscriptions: HashMap::new(),
+            quote_update_sender,
+            quote_update_receiver,
         }
     }
 
@@ -303,10 +357,13 @@ impl Subscriber {
             },
             response_received = self.response_receiver.recv() => {
                 match response_received {
-                    Ok(DeferredResponse { response, bid_id_to_add }) => {
+                    Ok(DeferredResponse { response, bid_id_to_add, quote_subscription_to_remove }) => {
                         if let Some(bid_id) = bid_id_to_add {
                             self.bid_ids.insert(bid_id);
                         }
+                        if let Some(subscription_id) = quote_subscription_to_remove {
+                            self.quote_subscriptions.remove(&subscription_id);
+                        }
                         self.sender.send(serde_json::to_string(&response)?.into()).await?;
                     }
                     Err(e) => {
@@ -319,6 +376,13 @@ impl Subscriber {
                 }
                 Ok(())
             },
+            maybe_quote_update = self.quote_update_receiver.recv() => {
+                // The subscriber holds a sender, so the channel is never closed
+                match maybe_quote_update {
+                    Some(update) => self.handle_quote_update(update).await,
+                    None => Ok(()),
+                }
+            },
             _  = self.ping_interval.tick() => {
                 if let Auth::Authorized(token, _) = self.auth.clone() {
                     if self.store.store.get_profile_by_token(&token).await.is_err() {
@@ -363,6 +427,23 @@ impl Subscriber {
         Ok(())
     }
 
+    async fn handle_quote_status_update(
+        &mut self,
+        status: QuoteStatusWithReferenceId,
+    ) -> Result<()> {
+        if !self.quote_reference_ids.contains(&status.reference_id) {
+            // Irrelevant update
+            return Ok(());
+        }
+        // No more updates are sent for a quote after its final status
+        if status.status.is_final() {
+            self.quote_reference_ids.remove(&status.reference_id);
+        }
+        let message = serde_json::to_string(&ServerUpdateResponse::QuoteStatusUpdate { status })?;
+        self.sender.send(message.into()).await?;
+        Ok(())
+    }
+
     async fn handle_svm_chain_update(&mut self, svm_chain_update: SvmChainUpdate) -> Result<()> {
         if !self.chain_ids.contains(&svm_chain_update.chain_id) {
             // Irrelevant update
@@ -390,6 +471,29 @@ impl Subscriber {
         Ok(())
     }
 
+    async fn handle_quote_update(&mut self, update: ServerUpdateResponse) -> Result<()> {
+        match &update {
+            ServerUpdateResponse::QuoteUpdate {
+                subscription_id, ..
+            } => {
+                if !self.quote_subscriptions.contains_key(subscription_id) {
+                    // The client has already unsubscribed
+                    return Ok(());
+                }
+            }
+            ServerUpdateResponse::QuoteSubscriptionEnded { subscription_id } => {
+                if self.quote_subscriptions.remove(subscription_id).is_none() {
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+        self.sender
+            .send(serde_json::to_string(&update)?.into())
+            .await?;
+        Ok(())
+    }
+
     #[instrument(
         target = "metrics",
         fields(category = "ws_update", result = "success", profile, name),
@@ -417,6 +521,10 @@ impl Subscriber {
                 tracing::Span::current().record("name", "remove_opportunity");
                 self.handle_remove_opportunities(opportunity_delete).await
             }
+            UpdateEvent::QuoteStatusUpdate(status) => {
+                tracing::Span::current().record("name", "quote_status_update");
+                self.handle_quote_status_update(status).await
+            }
         };
         if result.is_err() {
             tracing::Span::current().record("result", "error");
@@ -435,10 +543,10 @@ impl Subscriber {
         let resp = if !not_found_chain_ids.is_empty() {
             ServerResultResponse {
                 id:     Some(message_id),
-                result: ServerResultMessage::Err(format!(
-                    "Chain id(s) with id(s) {:?} not found",
-                    not_found_chain_ids
-                )),
+                result: ServerResultMessage::Err(ErrorBodyResponse {
+                    error:   format!("Chain id(s) with id(s) {:?} not found", not_found_chain_ids),
+                    details: Some(ErrorDetails::new(ErrorCode::InvalidChainId)),
+                }),
             }
         } else {
             self.chain_ids.extend(chain_ids);
@@ -447,8 +555,9 @@ impl Subscriber {
         Self::send_response(
             &self.response_sender,
             DeferredResponse {
-                response:      resp,
-                bid_id_to_add: None,
+                response:                     resp,
+                bid_id_to_add:                None,
+                quote_subscription_to_remove: None,
             },
         );
     }
@@ -460,8 +569,9 @@ impl Subscriber {
         Self::send_response(
             &self.response_sender,
             DeferredResponse {
-                response:      resp,
-                bid_id_to_add: None,
+                response:                     resp,
+                bid_id_to_add:                None,
+                quote_subscription_to_remove: None,
             },
         );
     }
@@ -507,8 +617,9 @@ impl Subscriber {
         self.spawn_deferred(async move {
             match process_bid(auth, store, bid).await {
                 Ok(bid_result) => DeferredResponse {
-                    bid_id_to_add: Some(bid_result.id),
-                    response:      ServerResultResponse {
+                    bid_id_to_add:                Some(bid_result.id),
+                    quote_subscription_to_remove: None,
+                    response:                     ServerResultResponse {
                         id:     Some(message_id.clone()),
                         result: ServerResultMessage::Success(Some(APIResponse::BidResult(
                             bid_result.0,
@@ -516,12 +627,37 @@ impl Subscriber {
                     },
                 },
                 Err(e) => DeferredResponse {
-                    response:      ServerResultResponse {
+                    response:                     ServerResultResponse {
                         id:     Some(message_id),
-                        result: ServerResultMessage::Err(e.to_status_and_message().1),
+                        result: ServerResultMessage::Err(e.to_error_body()),
                     },
-                    bid_id_to_add: None,
+                    bid_id_to_add:                None,
+                    quote_subscription_to_remove: None,
+                },
+            }
+        })
+        .await;
+    }
+
+    async fn handle_verify_bid(&mut self, message_id: String, bid: BidCreate) {
+        let (auth, store) = (self.auth.clone(), self.store.clone());
+        self.spawn_deferred(async move {
+            let resp = match verify_bid(auth, store, bid).await {
+                Ok(result) => ServerResultResponse {
+                    id:     Some(message_id),
+                    result: ServerResultMessage::Success(Some(APIResponse::BidVerificationResult(
+                        result.0,
+                    ))),
+                },
+                Err(e) => ServerResultResponse {
+                    id:     Some(message_id),
+                    result: ServerResultMessage::Err(e.to_error_body()),
                 },
+            };
+            DeferredResponse {
+                response:                     resp,
+                bid_id_to_add:                None,
+                quote_subscription_to_remove: None,
             }
         })
         .await;
@@ -534,17 +670,184 @@ impl Subscriber {
                 Ok(_) => ok_response(message_id),
                 Err(e) => ServerResultResponse {
                     id:     Some(message_id),
-                    result: ServerResultMessage::Err(e.to_status_and_message().1),
+                    result: ServerResultMessage::Err(e.to_error_body()),
                 },
             };
             DeferredResponse {
-                response:      resp,
-                bid_id_to_add: None,
+                response:                     resp,
+                bid_id_to_add:                None,
+                quote_subscription_to_remove: None,
             }
         })
         .await;
     }
 
+    async fn handle_subscribe_quote(&mut self, message_id: String, quote: QuoteCreate) {
+        if self.quote_subscriptions.contains_key(&message_id)
+            || self.quote_subscriptions.len() >= MAX_QUOTE_SUBSCRIPTIONS
+        {
+            Self::send_response(
+                &self.response_sender,
+                DeferredResponse {
+                    response:                     ServerResultResponse {
+                        id:      Some(message_id),
+                        result:  ServerResultMessage::Err(ErrorBodyResponse {
+                            error:   format!(
+                            "Quote subscription id must be unique and at most {} quote subscriptions are allowed",
+                            MAX_QUOTE_SUBSCRIPTIONS
+                        ),
+                            details: Some(ErrorDetails::new(ErrorCode::BadParameters)),
+                        }),
+                    },
+                    bid_id_to_add:                None,
+                    quote_subscription_to_remove: None,
+                },
+            );
+            return;
+        }
+
+        let cancellation_token = CancellationToken::new();
+        self.quote_subscriptions
+            .insert(message_id.clone(), cancellation_token.clone());
+        let (auth, store, quote_update_sender) = (
+            self.auth.clone(),
+            self.store.clone(),
+            self.quote_update_sender.clone(),
+        );
+        self.spawn_deferred(async move {
+            match subscribe_quote(auth, store.clone(), quote).await {
+                Ok(receiver) => {
+                    store.task_tracker.spawn(
+                        forward_quote_updates(
+                            message_id.clone(),
+                            receiver,
+                            quote_update_sender,
+                            cancellation_token,
+                        )
+                        .in_current_span(),
+                    );
+                    DeferredResponse {
+                        response:                     ok_response(message_id),
+                        bid_id_to_add:                None,
+                        quote_subscription_to_remove: None,
+                    }
+                }
+                Err(e) => DeferredResponse {
+                    response:                     ServerResultResponse {
+                        id:     Some(message_id.clone()),
+                        result: ServerResultMessage::Err(e.to_error_body()),
+                    },
+                    bid_id_to_add:                None,
+                    quote_subscription_to_remove: Some(message_id),
+                },
+            }
+        })
+        .await;
+    }
+
+    /// Subscribes to the status updates of the quotes and sends their current statuses right away,
+    /// so the updates before the subscription are not missed.
+    async fn handle_subscribe_quote_status(
+        &mut self,
+        message_id: String,
+        reference_ids: Vec<Uuid>,
+    ) -> Result<()> {
+        let new_reference_ids: HashSet<Uuid> = reference_ids
+            .into_iter()
+            .filter(|reference_id| !self.quote_reference_ids.contains(reference_id))
+            .collect();
+        if self.quote_reference_ids.len() + new_reference_ids.len() > MAX_QUOTE_STATUS_SUBSCRIPTIONS
+        {
+            Self::send_response(
+                &self.response_sender,
+                DeferredResponse {
+                    response:                     ServerResultResponse {
+                        id:     Some(message_id),
+                        result: ServerResultMessage::Err(ErrorBodyResponse {
+                            error:   format!(
+                                "At most {} quote status subscriptions are allowed",
+                                MAX_QUOTE_STATUS_SUBSCRIPTIONS
+                            ),
+                            details: Some(ErrorDetails::new(ErrorCode::BadParameters)),
+                        }),
+                    },
+                    bid_id_to_add:                None,
+                    quote_subscription_to_remove: None,
+                },
+            );
+            return Ok(());
+        }
+
+        let statuses =
+            match get_quote_statuses(self.store.clone(), new_reference_ids.into_iter().collect())
+                .await
+            {
+                Ok(statuses) => statuses,
+                Err(e) => {
+                    Self::send_response(
+                        &self.response_sender,
+                        DeferredResponse {
+                            response:                     ServerResultResponse {
+                                id:     Some(message_id),
+                                result: ServerResultMessage::Err(e.to_error_body()),
+                            },
+                            bid_id_to_add:                None,
+                            quote_subscription_to_remove: None,
+                        },
+                    );
+                    return Ok(());
+                }
+            };
+        // No more updates are sent for a quote after its final status
+        self.quote_reference_ids.extend(
+            statuses
+                .iter()
+                .filter(|status| !status.status.is_final())
+                .map(|status| status.reference_id),
+        );
+        // The response is sent before the current statuses of the quotes
+        self.sender
+            .send(serde_json::to_string(&ok_response(message_id))?.into())
+            .await?;
+        for status in statuses {
+            let message =
+                serde_json::to_string(&ServerUpdateResponse::QuoteStatusUpdate { status })?;
+            self.sender.send(message.into()).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_unsubscribe_quote_status(
+        &mut self,
+        message_id: String,
+        reference_ids: Vec<Uuid>,
+    ) {
+        self.quote_reference_ids
+            .retain(|reference_id| !reference_ids.contains(reference_id));
+        Self::send_response(
+            &self.response_sender,
+            DeferredResponse {
+                response:                     ok_response(message_id),
+                bid_id_to_add:                None,
+                quote_subscription_to_remove: None,
+            },
+        );
+    }
+
+    async fn handle_unsubscribe_quote(&mut self, message_id: String, subscription_id: String) {
+        if let Some(cancellation_token) = self.quote_subscriptions.remove(&subscription_id) {
+            cancellation_token.cancel();
+        }
+        Self::send_response(
+            &self.response_sender,
+            DeferredResponse {
+                response:                     ok_response(message_id),
+                bid_id_to_add:                None,
+                quote_subscription_to_remove: None,
+            },
+        );
+    }
+
     #[instrument(
         target = "metrics",
         fields(category = "ws_client_message", result = "success", profile, name),
@@ -589,11 +892,15 @@ impl Subscriber {
         match maybe_client_message {
             Err(e) => {
                 let resp = DeferredResponse {
-                    response:      ServerResultResponse {
+                    response:                     ServerResultResponse {
                         id:     None,
-                        result: ServerResultMessage::Err(e.to_string()),
+                        result: ServerResultMessage::Err(ErrorBodyResponse {
+                            error:   e.to_string(),
+                            details: Some(ErrorDetails::new(ErrorCode::BadParameters)),
+                        }),
                     },
-                    bid_id_to_add: None,
+                    bid_id_to_add:                None,
+                    quote_subscription_to_remove: None,
                 };
                 Self::send_response(&self.response_sender, resp);
             }
@@ -614,6 +921,28 @@ impl Subscriber {
                     tracing::Span::current().record("name", "cancel_bid");
                     self.handle_cancel_bid(id, data).await
                 }
+                ClientMessage::VerifyBid { bid } => {
+                    tracing::Span::current().record("name", "verify_bid");
+                    self.handle_verify_bid(id, bid).await
+                }
+                ClientMessage::SubscribeQuote { quote } => {
+                    tracing::Span::current().record("name", "subscribe_quote");
+                    self.handle_subscribe_quote(id, quote).await
+                }
+                ClientMessage::UnsubscribeQuote { subscription_id } => {
+                    tracing::Span::current().record("name", "unsubscribe_quote");
+                    self.handle_unsubscribe_quote(id, subscription_id).await
+                }
+                ClientMessage::SubscribeQuoteStatus { reference_ids } => {
+                    tracing::Span::current().record("name", "subscribe_quote_status");
+                    self.handle_subscribe_quote_status(id, reference_ids)
+                        .await?
+                }
+                ClientMessage::UnsubscribeQuoteStatus { reference_ids } => {
+                    tracing::Span::current().record("name", "unsubscribe_quote_status");
+                    self.handle_unsubscribe_quote_status(id, reference_ids)
+                        .await
+                }
             },
         };
 
@@ -621,6 +950,15 @@ impl Subscriber {
     }
 }
 
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        // Stop the rolling auctions of the quote subscriptions of the closed connection
+        for cancellation_token in self.quote_subscriptions.values() {
+            cancellation_token.cancel();
+        }
+    }
+}
+
 
 pub fn get_routes(store: Arc<StoreNew>) -> Router<Arc<StoreNew>> {
     WrappedRouter::new(store)
@@ -5,10 +5,14 @@ use {
         },
         service::{
             cancel_bid::CancelBidInput,
+            check_bid_rate_limits::CheckBidRateLimitsInput,
             get_bid::GetBidInput,
             get_bids::GetBidsInput,
+            get_quote_status::GetQuoteStatusInput,
             handle_bid::HandleBidInput,
+            searcher_reputation::GetSearcherReputationsInput,
             submit_quote::SubmitQuoteInput,
+            verify_bid::DryRunBidInput,
             Service,
             ServiceEnum,
         },
@@ -49,12 +53,18 @@ use {
             BidStatus,
             BidStatusSvm,
             BidSvm,
+            BidVerificationResult,
             Bids,
             GetBidStatusParams,
             GetBidsByTimeQueryParams,
+            GetSearcherReputationsQueryParams,
             Route,
+            SearcherReputation,
+            SearcherReputations,
         },
         quote::{
+            QuoteStatus,
+            QuoteStatusWithReferenceId,
             SubmitQuote,
             SubmitQuoteResponse,
         },
@@ -62,6 +72,7 @@ use {
     },
     sqlx::types::time::OffsetDateTime,
     std::sync::Arc,
+    uuid::Uuid,
 };
 
 /// Bid on a specific permission key for a specific chain.
@@ -95,6 +106,50 @@ pub async fn process_bid(
     }
 }
 
+/// Verify a bid without placing it.
+///
+/// The bid goes through the same verification and simulation as a placed bid, but it is neither stored
+/// nor added to any auction. The simulation logs and the compute units consumed are returned on success.
+/// Verifications require authentication and count towards the bid rate limits of the profile.
+#[utoipa::path(post, path = "/v1/{chain_id}/bids/verify", request_body = BidCreate,
+    params(("chain_id"=String, Path, description = "The chain id to verify the bid for", example = "solana")),
+    responses(
+    (status = 200, description = "Bid passed the verification", body = BidVerificationResult),
+    (status = 400, response = ErrorBodyResponse),
+    (status = 404, description = "Chain id was not found", body = ErrorBodyResponse),
+),)]
+pub async fn post_verify_bid(
+    auth: Auth,
+    State(store): State<Arc<StoreNew>>,
+    Path(chain_id): Path<ChainId>,
+    Json(bid_create): Json<BidCreate>,
+) -> Result<Json<BidVerificationResult>, RestError> {
+    if bid_create.get_chain_id() != chain_id {
+        return Err(RestError::BadParameters(
+            "Chain id of the bid does not match the chain id of the path".to_string(),
+        ));
+    }
+    verify_bid(auth, store, bid_create).await
+}
+
+// We cannot be sure that the user is authorized here because this can be called by the ws as well.
+pub async fn verify_bid(
+    auth: Auth,
+    store: Arc<StoreNew>,
+    bid_create: BidCreate,
+) -> Result<Json<BidVerificationResult>, RestError> {
+    match auth {
+        Auth::Authorized(_, profile) => {
+            match store.get_auction_service(&bid_create.get_chain_id())? {
+                ServiceEnum::Svm(service) => {
+                    Svm::verify_bid(&service, &bid_create, Some(profile)).await
+                }
+            }
+        }
+        _ => Err(RestError::Unauthorized),
+    }
+}
+
 /// Cancel a specific bid.
 ///
 /// Bids can only be cancelled if they are in the awaiting signature state.
@@ -147,6 +202,8 @@ pub async fn cancel_bid(
 }
 
 /// Query the status of a specific bid.
+///
+/// Bids with equal amounts are ranked by their priority fee per compute unit and then by their arrival time.
 #[utoipa::path(get, path = "/v1/{chain_id}/bids/{bid_id}",
     responses(
     (status = 200, body = BidStatus),
@@ -267,6 +324,50 @@ pub async fn get_bids_by_time_deprecated(
     }
 }
 
+/// Returns the reputations of the searchers on the chain, based on the outcomes of their winning bids.
+#[utoipa::path(get, path = "/v1/{chain_id}/bids/reputations",
+    security(
+        ("bearerAuth" = []),
+    ),
+    responses(
+    (status = 200, description = "Reputations of the searchers", body = SearcherReputations),
+    (status = 400, response = ErrorBodyResponse),
+),  params(
+        ("chain_id"=String, Path, description = "The chain id to query for", example = "solana"),
+        GetSearcherReputationsQueryParams
+    ),
+)]
+pub async fn get_searcher_reputations(
+    State(store): State<Arc<StoreNew>>,
+    Path(chain_id): Path<ChainId>,
+    query: Query<GetSearcherReputationsQueryParams>,
+) -> Result<Json<SearcherReputations>, RestError> {
+    match store.get_auction_service(&chain_id)? {
+        ServiceEnum::Svm(service) => {
+            let reputations = service
+                .get_searcher_reputations(GetSearcherReputationsInput {
+                    profile_id: query.profile_id,
+                })
+                .await;
+            Ok(Json(SearcherReputations {
+                items: reputations
+                    .into_iter()
+                    .map(|reputation| SearcherReputation {
+                        profile_id:   reputation.profile_id,
+                        auctions_won: reputation.auctions_won,
+                        bids_landed:  reputation.bids_landed,
+                        bids_failed:  reputation.bids_failed,
+                        bids_expired: reputation.bids_expired,
+                        failure_rate: reputation.get_failure_rate(),
+                        penalized:    service.is_searcher_penalized(&reputation),
+                        banned_until: reputation.banned_until,
+                    })
+                    .collect(),
+            }))
+        }
+    }
+}
+
 /// Signs and submits the transaction for the specified quote.
 ///
 /// Server will verify the quote and checks if the quote is still valid.
@@ -298,6 +399,33 @@ pub async fn post_submit_quote(
     }
 }
 
+/// Returns the current statuses of the quotes with the reference ids.
+///
+/// Fails if any of the quotes is not found on any chain.
+pub async fn get_quote_statuses(
+    store: Arc<StoreNew>,
+    reference_ids: Vec<Uuid>,
+) -> Result<Vec<QuoteStatusWithReferenceId>, RestError> {
+    let mut statuses = Vec::with_capacity(reference_ids.len());
+    'reference_ids: for reference_id in reference_ids {
+        for service in store.get_all_auction_services().values() {
+            let ServiceEnum::Svm(service) = service;
+            if let Some(status) = service
+                .get_quote_status(GetQuoteStatusInput { reference_id })
+                .await?
+            {
+                statuses.push(status);
+                continue 'reference_ids;
+            }
+        }
+        return Err(RestError::BadParameters(format!(
+            "Quote with reference id {} was not found",
+            reference_id
+        )));
+    }
+    Ok(statuses)
+}
+
 pub fn get_routes(store: Arc<StoreNew>) -> Router<Arc<StoreNew>> {
     #[allow(deprecated)]
     WrappedRouter::new(store)
@@ -314,6 +442,8 @@ pub fn get_routes(store: Arc<StoreNew>) -> Router<Arc<StoreNew>> {
         )
         .route(Route::PostSubmitQuote, post_submit_quote)
         .route(Route::PostCancelBid, post_cancel_bid)
+        .route(Route::PostVerifyBid, post_verify_bid)
+        .route(Route::GetSearcherReputations, get_searcher_reputations)
         .router
 }
 
@@ -389,6 +519,68 @@ impl From<entities::BidStatusSvm> for BidStatusSvm {
     }
 }
 
+/// Returns the status update of the quote built from the bid, if the new status of the bid is about the quote.
+///
+/// Bids that lost the auction of a quote get the transaction hash of the winner bid, so they are not reported.
+pub fn get_quote_status_update(
+    bid: &entities::Bid,
+    new_status: &entities::BidStatusSvm,
+) -> Option<QuoteStatusWithReferenceId> {
+    if bid.chain_data.bid_payment_instruction_type != entities::BidPaymentInstructionType::Swap {
+        return None;
+    }
+    let (auction, status) = match new_status {
+        entities::BidStatusSvm::Pending | entities::BidStatusSvm::Lost { auction: None } => {
+            return None
+        }
+        entities::BidStatusSvm::AwaitingSignature { auction } => {
+            (auction, QuoteStatus::AwaitingSignature)
+        }
+        entities::BidStatusSvm::SentToUserForSubmission { auction } => {
+            (auction, QuoteStatus::SentToUserForSubmission)
+        }
+        entities::BidStatusSvm::Submitted { auction } => (
+            auction,
+            QuoteStatus::Submitted {
+                result: auction.tx_hash,
+            },
+        ),
+        entities::BidStatusSvm::Won { auction } => (
+            auction,
+            QuoteStatus::Won {
+                result: auction.tx_hash,
+            },
+        ),
+        entities::BidStatusSvm::Failed { auction, .. } => (
+            auction,
+            QuoteStatus::Failed {
+                result: auction.tx_hash,
+            },
+        ),
+        entities::BidStatusSvm::Expired { auction }
+        | entities::BidStatusSvm::SubmissionFailed {
+            auction,
+            reason: entities::BidSubmissionFailedReason::DeadlinePassed,
+        } => (auction, QuoteStatus::Expired),
+        entities::BidStatusSvm::Cancelled { auction }
+        | entities::BidStatusSvm::SubmissionFailed {
+            auction,
+            reason: entities::BidSubmissionFailedReason::Cancelled,
+        } => (auction, QuoteStatus::Cancelled),
+        entities::BidStatusSvm::Lost {
+            auction: Some(auction),
+        } => (auction, QuoteStatus::Lost),
+    };
+    if bid.chain_data.transaction.signatures.first() != Some(&auction.tx_hash) {
+        return None;
+    }
+    Some(QuoteStatusWithReferenceId {
+        reference_id: auction.id,
+        chain_id: bid.chain_id.clone(),
+        status,
+    })
+}
+
 fn get_core_fields(bid: &entities::Bid) -> BidCoreFields {
     BidCoreFields {
         id:              bid.id,
@@ -425,6 +617,11 @@ impl Svm {
         profile: Option<models::Profile>,
     ) -> Result<Json<BidResult>, RestError> {
         let bid = Self::get_bid_create_entity(bid_create, profile)?;
+        service
+            .check_bid_rate_limits(CheckBidRateLimitsInput {
+                bid_create: bid.clone(),
+            })
+            .await?;
         let bid = service
             .handle_bid(HandleBidInput { bid_create: bid })
             .await?;
@@ -434,6 +631,30 @@ impl Svm {
         }))
     }
 
+    async fn verify_bid(
+        service: &Service,
+        bid_create: &BidCreate,
+        profile: Option<models::Profile>,
+    ) -> Result<Json<BidVerificationResult>, RestError> {
+        let bid = Self::get_bid_create_entity(bid_create, profile)?;
+        let (chain_data, amount, simulation) = service
+            .dry_run_bid(DryRunBidInput { bid_create: bid })
+            .await?;
+        Ok(Json(BidVerificationResult {
+            permission_key:         express_relay_api_types::PermissionKeySvm(
+                chain_data.get_permission_key().0,
+            ),
+            bid_amount:             amount,
+            simulated:              simulation.is_some(),
+            compute_units_consumed: simulation
+                .as_ref()
+                .map(|simulation| simulation.compute_units_consumed),
+            logs:                   simulation
+                .map(|simulation| simulation.logs)
+                .unwrap_or_default(),
+        }))
+    }
+
     async fn get_bid_status(
         service: &Service,
         bid_id: entities::BidId,
@@ -485,3 +706,181 @@ impl Svm {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            pubkey::Pubkey,
+            signature::Signature,
+            transaction::VersionedTransaction,
+        },
+    };
+
+    fn make_bid(
+        signature: Signature,
+        bid_payment_instruction_type: entities::BidPaymentInstructionType,
+    ) -> entities::Bid {
+        entities::Bid {
+            id:              Uuid::new_v4(),
+            chain_id:        "solana".to_string(),
+            initiation_time: OffsetDateTime::now_utc(),
+            profile_id:      None,
+            opportunity_id:  None,
+            creation_time:   OffsetDateTime::now_utc(),
+            conclusion_time: None,
+            submission_time: None,
+            amount:          100,
+            status:          entities::BidStatusSvm::Pending,
+            chain_data:      entities::BidChainDataSvm {
+                transaction: VersionedTransaction {
+                    signatures: vec![signature],
+                    message:    Default::default(),
+                },
+                bid_payment_instruction_type,
+                router: Pubkey::new_unique(),
+                permission_account: Pubkey::new_unique(),
+                last_valid_block_height: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_get_quote_status_update() {
+        let signature = Signature::new_unique();
+        let bid = make_bid(signature, entities::BidPaymentInstructionType::Swap);
+        let auction = entities::BidStatusAuction {
+            id:      Uuid::new_v4(),
+            tx_hash: signature,
+        };
+        let cases = vec![
+            (
+                entities::BidStatusSvm::AwaitingSignature {
+                    auction: auction.clone(),
+                },
+                QuoteStatus::AwaitingSignature,
+            ),
+            (
+                entities::BidStatusSvm::SentToUserForSubmission {
+                    auction: auction.clone(),
+                },
+                QuoteStatus::SentToUserForSubmission,
+            ),
+            (
+                entities::BidStatusSvm::Submitted {
+                    auction: auction.clone(),
+                },
+                QuoteStatus::Submitted { result: signature },
+            ),
+            (
+                entities::BidStatusSvm::Won {
+                    auction: auction.clone(),
+                },
+                QuoteStatus::Won { result: signature },
+            ),
+            (
+                entities::BidStatusSvm::Failed {
+                    auction: auction.clone(),
+                    reason:  Some(entities::BidFailedReason::InsufficientUserFunds),
+                },
+                QuoteStatus::Failed { result: signature },
+            ),
+            (
+                entities::BidStatusSvm::Expired {
+                    auction: auction.clone(),
+                },
+                QuoteStatus::Expired,
+            ),
+            (
+                entities::BidStatusSvm::SubmissionFailed {
+                    auction: auction.clone(),
+                    reason:  entities::BidSubmissionFailedReason::DeadlinePassed,
+                },
+                QuoteStatus::Expired,
+            ),
+            (
+                entities::BidStatusSvm::Cancelled {
+                    auction: auction.clone(),
+                },
+                QuoteStatus::Cancelled,
+            ),
+            (
+                entities::BidStatusSvm::SubmissionFailed {
+                    auction: auction.clone(),
+                    reason:  entities::BidSubmissionFailedReason::Cancelled,
+                },
+                QuoteStatus::Cancelled,
+            ),
+            (
+                entities::BidStatusSvm::Lost {
+                    auction: Some(auction.clone()),
+                },
+                QuoteStatus::Lost,
+            ),
+        ];
+        for (new_status, expected) in cases {
+            assert_eq!(
+                get_quote_status_update(&bid, &new_status),
+                Some(QuoteStatusWithReferenceId {
+                    reference_id: auction.id,
+                    chain_id:     "solana".to_string(),
+                    status:       expected,
+                }),
+                "Unexpected quote status for {:?}",
+                new_status
+            );
+        }
+
+        assert_eq!(
+            get_quote_status_update(&bid, &entities::BidStatusSvm::Pending),
+            None
+        );
+        assert_eq!(
+            get_quote_status_update(&bid, &entities::BidStatusSvm::Lost { auction: None }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_quote_status_update_ignores_other_bids() {
+        let auction = entities::BidStatusAuction {
+            id:      Uuid::new_v4(),
+            tx_hash: Signature::new_unique(),
+        };
+
+        // The bids which lost the auction of the quote get the transaction hash of the winner bid
+        let loser_bid = make_bid(
+            Signature::new_unique(),
+            entities::BidPaymentInstructionType::Swap,
+        );
+        assert_eq!(
+            get_quote_status_update(
+                &loser_bid,
+                &entities::BidStatusSvm::Lost {
+                    auction: Some(auction.clone()),
+                }
+            ),
+            None
+        );
+        assert_eq!(
+            get_quote_status_update(
+                &loser_bid,
+                &entities::BidStatusSvm::Won {
+                    auction: auction.clone(),
+                }
+            ),
+            None
+        );
+
+        // Bids which are not swaps are not quotes
+        let submit_bid = make_bid(
+            auction.tx_hash,
+            entities::BidPaymentInstructionType::SubmitBid,
+        );
+        assert_eq!(
+            get_quote_status_update(&submit_bid, &entities::BidStatusSvm::Won { auction }),
+            None
+        );
+    }
+}
@@ -3,9 +3,17 @@ use {
         Bid,
         BidStatus,
     },
-    crate::kernel::entities::{
-        ChainId,
-        PermissionKeySvm,
+    crate::{
+        config::PricingRule,
+        kernel::entities::{
+            ChainId,
+            PermissionKeySvm,
+        },
+        models::ProfileId,
+    },
+    serde::{
+        Deserialize,
+        Serialize,
     },
     solana_sdk::signature::Signature,
     std::{
@@ -31,10 +39,37 @@ pub struct Auction {
     pub bid_collection_time: OffsetDateTime,
     pub submission_time:     Option<OffsetDateTime>,
     pub tx_hash:             Option<Signature>,
+    /// Pricing rule the winners were chosen with, if the auction was run by the server
+    pub pricing:             Option<AuctionPricing>,
 
     pub bids: Vec<Bid>,
 }
 
+/// Pricing rule applied to an auction, stored for audit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuctionPricing {
+    #[serde(default)]
+    pub price:                  PricingRule,
+    pub reserve_price:          u64,
+    pub minimum_bid_increment:  u64,
+    /// Price the winners are charged with the second price rule,
+    /// the relayer refunds the winners the amount they bid above it
+    #[serde(default)]
+    pub clearing_price:         Option<u64>,
+    /// Profiles whose ties were broken against them because of their reputation
+    #[serde(default)]
+    pub deprioritized_profiles: Vec<ProfileId>,
+}
+
+impl AuctionPricing {
+    /// Returns the amount the relayer refunds a winner that bid the given amount.
+    pub fn get_refund(&self, bid_amount: u64) -> u64 {
+        self.clearing_price
+            .map(|clearing_price| bid_amount.saturating_sub(clearing_price))
+            .unwrap_or_default()
+    }
+}
+
 #[derive(PartialEq, Debug)]
 pub enum SubmitType {
     ByServer,
@@ -60,6 +95,7 @@ impl Auction {
             bid_collection_time,
             submission_time: None,
             tx_hash: None,
+            pricing: None,
             bids,
         })
     }
@@ -11,6 +11,7 @@ use {
         },
         opportunity::entities::OpportunityId,
     },
+    borsh::BorshDeserialize,
     express_relay::{
         error::ErrorCode,
         SubmitBidArgs,
@@ -19,14 +20,27 @@ use {
     express_relay_api_types::bid as api,
     solana_sdk::{
         clock::Slot,
-        instruction::InstructionError,
+        compute_budget::{
+            self,
+            ComputeBudgetInstruction,
+        },
+        instruction::{
+            CompiledInstruction,
+            InstructionError,
+        },
+        program_pack::Pack,
         pubkey::Pubkey,
+        rent::Rent,
         signature::Signature,
+        system_instruction::SystemInstruction,
+        system_program,
         transaction::{
             TransactionError,
             VersionedTransaction,
         },
     },
+    spl_associated_token_account::instruction::AssociatedTokenAccountInstruction,
+    spl_token_2022::state::Account as TokenAccount,
     std::{
         fmt::{
             Debug,
@@ -36,7 +50,10 @@ use {
         sync::Arc,
     },
     strum::FromRepr,
-    time::OffsetDateTime,
+    time::{
+        Date,
+        OffsetDateTime,
+    },
     tokio::sync::Mutex,
     uuid::Uuid,
 };
@@ -44,6 +61,12 @@ use {
 pub type BidId = Uuid;
 pub type BidLock = Arc<Mutex<()>>;
 
+/// Lamports paid by the fee payer for each signature of a transaction.
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+/// Compute unit limit of each instruction when the transaction does not set a limit.
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
 pub trait BidStatus:
     Clone
     + Debug
@@ -225,6 +248,10 @@ pub struct BidChainDataSvm {
     pub bid_payment_instruction_type: BidPaymentInstructionType,
     pub router:                       Pubkey,
     pub permission_account:           Pubkey,
+    /// The last block height at which the recent blockhash of the transaction is valid.
+    /// The transaction can not land on chain after this block height.
+    /// None if the blockhash is not one of the recent blockhashes or for bids accepted before this value was recorded.
+    pub last_valid_block_height:      Option<u64>,
 }
 
 impl BidChainDataSvm {
@@ -235,6 +262,119 @@ impl BidChainDataSvm {
         permission_key[33..].copy_from_slice(&self.permission_account.to_bytes());
         PermissionKeySvm(permission_key)
     }
+
+    fn get_program_instructions<'a>(
+        &'a self,
+        program_id: &'a Pubkey,
+    ) -> impl Iterator<Item = &'a CompiledInstruction> {
+        let account_keys = self.transaction.message.static_account_keys();
+        self.transaction
+            .message
+            .instructions()
+            .iter()
+            .filter(move |instruction| {
+                account_keys.get(instruction.program_id_index as usize) == Some(program_id)
+            })
+    }
+
+    /// Returns the static account of the instruction at the given position.
+    /// Accounts loaded from lookup tables are not returned, they can not be signers.
+    fn get_instruction_signer(
+        &self,
+        instruction: &CompiledInstruction,
+        position: usize,
+    ) -> Option<&Pubkey> {
+        instruction.accounts.get(position).and_then(|index| {
+            self.transaction
+                .message
+                .static_account_keys()
+                .get(*index as usize)
+        })
+    }
+
+    /// Returns the priority fee per compute unit set by the transaction, in micro-lamports.
+    pub fn get_compute_unit_price(&self) -> u64 {
+        self.get_program_instructions(&compute_budget::id())
+            .find_map(|instruction| {
+                match ComputeBudgetInstruction::try_from_slice(&instruction.data) {
+                    Ok(ComputeBudgetInstruction::SetComputeUnitPrice(price)) => Some(price),
+                    _ => None,
+                }
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the compute unit limit of the transaction.
+    /// Without a limit set by the transaction, each instruction that is not a compute budget instruction
+    /// gets the default limit.
+    pub fn get_compute_unit_limit(&self) -> u32 {
+        let limit = self
+            .get_program_instructions(&compute_budget::id())
+            .find_map(|instruction| {
+                match ComputeBudgetInstruction::try_from_slice(&instruction.data) {
+                    Ok(ComputeBudgetInstruction::SetComputeUnitLimit(limit)) => Some(limit),
+                    _ => None,
+                }
+            });
+        limit
+            .unwrap_or_else(|| {
+                let instruction_count = self.transaction.message.instructions().len()
+                    - self.get_program_instructions(&compute_budget::id()).count();
+                DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT.saturating_mul(instruction_count as u32)
+            })
+            .min(MAX_COMPUTE_UNIT_LIMIT)
+    }
+
+    /// Returns the lamports the relayer pays if the transaction lands:
+    /// the signature and priority fees when the relayer is the fee payer,
+    /// the sol transfers and created accounts funded by the relayer,
+    /// and the rent of the associated token accounts created with the relayer as payer.
+    pub fn get_relayer_spend(&self, relayer: &Pubkey) -> u64 {
+        let mut spend: u64 = 0;
+        if self.transaction.message.static_account_keys().first() == Some(relayer) {
+            let signature_count = self.transaction.message.header().num_required_signatures;
+            let priority_fee = (self.get_compute_unit_price() as u128
+                * self.get_compute_unit_limit() as u128)
+                .div_ceil(1_000_000);
+            spend = spend
+                .saturating_add(LAMPORTS_PER_SIGNATURE * signature_count as u64)
+                .saturating_add(u64::try_from(priority_fee).unwrap_or(u64::MAX));
+        }
+        for instruction in self.get_program_instructions(&system_program::id()) {
+            let lamports = match bincode::deserialize::<SystemInstruction>(&instruction.data) {
+                Ok(SystemInstruction::Transfer { lamports })
+                | Ok(SystemInstruction::CreateAccount { lamports, .. })
+                | Ok(SystemInstruction::CreateAccountWithSeed { lamports, .. }) => lamports,
+                _ => continue,
+            };
+            if self.get_instruction_signer(instruction, 0) == Some(relayer) {
+                spend = spend.saturating_add(lamports);
+            }
+        }
+        let token_account_rent = Rent::default().minimum_balance(TokenAccount::LEN);
+        for instruction in self.get_program_instructions(&spl_associated_token_account::id()) {
+            // An empty instruction data is the legacy create instruction
+            let is_create = instruction.data.is_empty()
+                || matches!(
+                    AssociatedTokenAccountInstruction::try_from_slice(&instruction.data),
+                    Ok(AssociatedTokenAccountInstruction::Create)
+                        | Ok(AssociatedTokenAccountInstruction::CreateIdempotent)
+                );
+            if is_create && self.get_instruction_signer(instruction, 0) == Some(relayer) {
+                spend = spend.saturating_add(token_account_rent);
+            }
+        }
+        spend
+    }
+}
+
+/// Lamports paid by the relayer for the bids of a searcher profile during a UTC day
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelayerDailySpend {
+    pub day:      Date,
+    pub lamports: u64,
+    /// Lamports of the accepted bids which may still be signed by the relayer
+    pub reserved: u64,
 }
 
 #[derive(Clone, Debug, PartialEq, FromRepr)]
@@ -320,6 +460,13 @@ impl BidChainDataCreateSvm {
 
 pub type BidAmountSvm = u64;
 
+/// Outcome of a successful bid simulation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BidSimulation {
+    pub logs:                   Vec<String>,
+    pub compute_units_consumed: u64,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SwapAccounts {
     pub searcher:               Pubkey,
@@ -340,10 +487,37 @@ pub struct SubmitBidAccounts {
 #[derive(Clone, Debug, PartialEq)]
 pub struct BidTransactionDataSwap {
     pub data:                            SwapV2Args,
+    /// The minimum amount the searcher provides if the swap is partially filled.
+    /// Equal to the searcher amount if the swap instruction does not support partial fills.
+    pub minimum_amount_searcher:         u64,
     pub accounts:                        SwapAccounts,
     pub express_relay_instruction_index: usize,
 }
 
+impl BidTransactionDataSwap {
+    /// Returns the searcher amount the bid commits to, the swap may be partially filled down to this amount.
+    pub fn get_committed_amount_searcher(&self) -> u64 {
+        self.minimum_amount_searcher.min(self.data.amount_searcher)
+    }
+
+    /// Returns the user amount the bid would charge for the full searcher amount
+    /// if only the committed searcher amount was provided, rounded up.
+    pub fn get_committed_amount_user(&self) -> u64 {
+        let committed_amount_searcher = self.get_committed_amount_searcher();
+        if committed_amount_searcher == self.data.amount_searcher {
+            return self.data.amount_user;
+        }
+        if committed_amount_searcher == 0 {
+            return u64::MAX;
+        }
+        // this multiplication is safe because both values are u64
+        let amount_user = (u128::from(self.data.amount_user)
+            * u128::from(self.data.amount_searcher))
+        .div_ceil(u128::from(committed_amount_searcher));
+        u64::try_from(amount_user).unwrap_or(u64::MAX)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum BidTransactionData {
     SubmitBid(BidTransactionDataSubmitBid),
@@ -377,3 +551,123 @@ impl Display for BidContainerTracing<'_> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            instruction::Instruction,
+            message::Message,
+            system_instruction,
+            transaction::Transaction,
+        },
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    };
+
+    fn make_chain_data(instructions: &[Instruction], payer: &Pubkey) -> BidChainDataSvm {
+        BidChainDataSvm {
+            transaction:                  VersionedTransaction::from(Transaction::new_unsigned(
+                Message::new(instructions, Some(payer)),
+            )),
+            bid_payment_instruction_type: BidPaymentInstructionType::SubmitBid,
+            router:                       Pubkey::default(),
+            permission_account:           Pubkey::default(),
+            last_valid_block_height:      None,
+        }
+    }
+
+    #[test]
+    fn test_get_relayer_spend_fee_payer() {
+        let relayer = Pubkey::new_unique();
+        let instructions = [
+            ComputeBudgetInstruction::set_compute_unit_limit(100_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1_000_001),
+            Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]),
+        ];
+
+        let chain_data = make_chain_data(&instructions, &relayer);
+        assert_eq!(chain_data.get_compute_unit_limit(), 100_000);
+        // 1 signature and 100_000.1 lamports of priority fee, rounded up
+        assert_eq!(chain_data.get_relayer_spend(&relayer), 5_000 + 100_001);
+
+        // The relayer pays nothing when it is not the fee payer
+        let chain_data = make_chain_data(&instructions, &Pubkey::new_unique());
+        assert_eq!(chain_data.get_relayer_spend(&relayer), 0);
+    }
+
+    #[test]
+    fn test_get_relayer_spend_funded_accounts() {
+        let relayer = Pubkey::new_unique();
+        let searcher = Pubkey::new_unique();
+        let instructions = [
+            system_instruction::transfer(&relayer, &searcher, 1_000),
+            system_instruction::transfer(&searcher, &relayer, 5_000),
+            create_associated_token_account_idempotent(
+                &relayer,
+                &searcher,
+                &Pubkey::new_unique(),
+                &spl_token::id(),
+            ),
+        ];
+
+        let chain_data = make_chain_data(&instructions, &searcher);
+        assert_eq!(
+            chain_data.get_relayer_spend(&relayer),
+            1_000 + Rent::default().minimum_balance(TokenAccount::LEN)
+        );
+        // Without a compute unit limit each instruction gets the default limit
+        assert_eq!(chain_data.get_compute_unit_limit(), 600_000);
+    }
+
+    fn make_swap_transaction_data(
+        amount_searcher: u64,
+        minimum_amount_searcher: u64,
+        amount_user: u64,
+    ) -> BidTransactionDataSwap {
+        BidTransactionDataSwap {
+            data: SwapV2Args {
+                deadline: 0,
+                amount_searcher,
+                amount_user,
+                referral_fee_ppm: 0,
+                fee_token: express_relay::FeeToken::Searcher,
+                swap_platform_fee_ppm: 0,
+            },
+            minimum_amount_searcher,
+            accounts: SwapAccounts {
+                searcher:               Pubkey::new_unique(),
+                user_wallet:            Pubkey::new_unique(),
+                mint_searcher:          Pubkey::new_unique(),
+                mint_user:              Pubkey::new_unique(),
+                router_token_account:   Pubkey::new_unique(),
+                token_program_searcher: spl_token::id(),
+                token_program_user:     spl_token::id(),
+            },
+            express_relay_instruction_index: 0,
+        }
+    }
+
+    #[test]
+    fn test_get_committed_amounts() {
+        // Without partial fills the bid commits to its amounts
+        let transaction_data = make_swap_transaction_data(1_000, 1_000, 500);
+        assert_eq!(transaction_data.get_committed_amount_searcher(), 1_000);
+        assert_eq!(transaction_data.get_committed_amount_user(), 500);
+
+        // The bid only commits to the minimum searcher amount, at the price of the full amounts
+        let transaction_data = make_swap_transaction_data(1_000, 900, 500);
+        assert_eq!(transaction_data.get_committed_amount_searcher(), 900);
+        // 500 * 1_000 / 900 = 555.5, rounded up against the searcher
+        assert_eq!(transaction_data.get_committed_amount_user(), 556);
+
+        // A minimum above the searcher amount does not commit to more than the searcher amount
+        let transaction_data = make_swap_transaction_data(1_000, 2_000, 500);
+        assert_eq!(transaction_data.get_committed_amount_searcher(), 1_000);
+        assert_eq!(transaction_data.get_committed_amount_user(), 500);
+
+        let transaction_data = make_swap_transaction_data(1_000, 0, 500);
+        assert_eq!(transaction_data.get_committed_amount_searcher(), 0);
+        assert_eq!(transaction_data.get_committed_amount_user(), u64::MAX);
+    }
+}
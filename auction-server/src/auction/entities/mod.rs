@@ -1,7 +1,9 @@
 mod auction;
 mod bid;
+mod reputation;
 
 pub use {
     auction::*,
     bid::*,
+    reputation::*,
 };
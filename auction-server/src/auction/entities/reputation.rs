@@ -0,0 +1,173 @@
+use {
+    super::{
+        BidStatus,
+        BidStatusSvm,
+    },
+    crate::{
+        config::SearcherReputationConfig,
+        models::ProfileId,
+    },
+    std::sync::Arc,
+    time::OffsetDateTime,
+    tokio::sync::Mutex,
+};
+
+pub type ReputationLock = Arc<Mutex<()>>;
+
+/// Outcome of a bid that affects the reputation of its searcher
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReputationEvent {
+    /// The bid won an auction.
+    AuctionWon,
+    /// The winning bid was included in a block successfully.
+    BidLanded,
+    /// The winning bid failed on-chain.
+    BidFailed,
+    /// The winning bid of a quote was never signed and expired.
+    BidExpired,
+}
+
+impl ReputationEvent {
+    /// Returns the reputation event of a bid moving from the old status to the new status, if any.
+    pub fn from_status_update(
+        old_status: &BidStatusSvm,
+        new_status: &BidStatusSvm,
+    ) -> Option<Self> {
+        match new_status {
+            BidStatusSvm::Submitted { .. }
+            | BidStatusSvm::AwaitingSignature { .. }
+            | BidStatusSvm::SentToUserForSubmission { .. }
+                if old_status.is_pending() =>
+            {
+                Some(ReputationEvent::AuctionWon)
+            }
+            BidStatusSvm::Won { .. } => Some(ReputationEvent::BidLanded),
+            BidStatusSvm::Failed { .. } => Some(ReputationEvent::BidFailed),
+            BidStatusSvm::Expired { .. } if old_status.is_awaiting_signature() => {
+                Some(ReputationEvent::BidExpired)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        matches!(
+            self,
+            ReputationEvent::BidFailed | ReputationEvent::BidExpired
+        )
+    }
+}
+
+/// Win, fail and expire counts of a searcher profile on a chain
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearcherReputation {
+    pub profile_id:   ProfileId,
+    pub auctions_won: u64,
+    pub bids_landed:  u64,
+    pub bids_failed:  u64,
+    pub bids_expired: u64,
+    pub banned_until: Option<OffsetDateTime>,
+}
+
+impl SearcherReputation {
+    /// Share of the won auctions whose bids failed on-chain or expired without being signed.
+    pub fn get_failure_rate(&self) -> f64 {
+        if self.auctions_won == 0 {
+            return 0.0;
+        }
+        (self.bids_failed + self.bids_expired) as f64 / self.auctions_won as f64
+    }
+
+    /// Whether the reputation is bad enough for the configured penalties to apply.
+    pub fn is_penalized(&self, config: &SearcherReputationConfig) -> bool {
+        self.auctions_won >= config.minimum_auctions_won
+            && self.get_failure_rate() > config.maximum_failure_rate
+    }
+
+    pub fn is_banned(&self, now: OffsetDateTime) -> bool {
+        self.banned_until
+            .is_some_and(|banned_until| banned_until > now)
+    }
+}
+
+/// Penalties that currently apply to the bids of a searcher profile
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReputationPenalties {
+    /// Bids are ranked after the other bids with the same amount.
+    pub deprioritized:      bool,
+    /// Bids are rejected until this time.
+    pub banned_until:       Option<OffsetDateTime>,
+    /// Bids can not skip the simulation.
+    pub require_simulation: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::auction::entities::BidStatusAuction,
+        solana_sdk::signature::Signature,
+        uuid::Uuid,
+    };
+
+    #[test]
+    fn test_reputation_event_from_status_update() {
+        let auction = BidStatusAuction {
+            id:      Uuid::new_v4(),
+            tx_hash: Signature::default(),
+        };
+        let awaiting_signature = BidStatusSvm::AwaitingSignature {
+            auction: auction.clone(),
+        };
+        let submitted = BidStatusSvm::Submitted {
+            auction: auction.clone(),
+        };
+        let expired = BidStatusSvm::Expired { auction };
+
+        assert_eq!(
+            ReputationEvent::from_status_update(&BidStatusSvm::Pending, &awaiting_signature),
+            Some(ReputationEvent::AuctionWon)
+        );
+        // A signed quote is not a new win
+        assert_eq!(
+            ReputationEvent::from_status_update(&awaiting_signature, &submitted),
+            None
+        );
+        assert_eq!(
+            ReputationEvent::from_status_update(&awaiting_signature, &expired),
+            Some(ReputationEvent::BidExpired)
+        );
+        // Submitted transactions that expire are not the fault of the searcher
+        assert_eq!(
+            ReputationEvent::from_status_update(&submitted, &expired),
+            None
+        );
+    }
+
+    #[test]
+    fn test_searcher_reputation_is_penalized() {
+        let config = SearcherReputationConfig {
+            minimum_auctions_won: 10,
+            maximum_failure_rate: 0.2,
+            ..Default::default()
+        };
+        let mut reputation = SearcherReputation {
+            profile_id:   Uuid::new_v4(),
+            auctions_won: 5,
+            bids_landed:  0,
+            bids_failed:  5,
+            bids_expired: 0,
+            banned_until: None,
+        };
+        // Not enough wins to judge the searcher
+        assert!(!reputation.is_penalized(&config));
+
+        reputation.auctions_won = 20;
+        assert!(reputation.is_penalized(&config));
+
+        reputation.bids_failed = 2;
+        reputation.bids_expired = 2;
+        assert_eq!(reputation.get_failure_rate(), 0.2);
+        assert!(!reputation.is_penalized(&config));
+    }
+}
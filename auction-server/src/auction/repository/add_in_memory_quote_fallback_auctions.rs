@@ -0,0 +1,35 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+};
+
+impl Repository {
+    /// Links the auctions of the quotes returned for the same quote request
+    #[tracing::instrument(skip_all)]
+    pub async fn add_in_memory_quote_fallback_auctions(
+        &self,
+        primary_auction_id: entities::AuctionId,
+        fallback_auction_ids: &[entities::AuctionId],
+    ) {
+        let auction_ids: Vec<entities::AuctionId> = std::iter::once(primary_auction_id)
+            .chain(fallback_auction_ids.iter().cloned())
+            .collect();
+        let mut write_guard = self.in_memory_store.quote_fallback_auctions.write().await;
+        for auction_id in auction_ids.iter() {
+            write_guard.insert(
+                *auction_id,
+                auction_ids
+                    .iter()
+                    .filter(|id| *id != auction_id)
+                    .cloned()
+                    .collect(),
+            );
+        }
+        drop(write_guard);
+        self.in_memory_store
+            .fallback_quote_auctions
+            .write()
+            .await
+            .extend(fallback_auction_ids.iter().cloned());
+    }
+}
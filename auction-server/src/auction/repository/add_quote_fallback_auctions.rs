@@ -0,0 +1,21 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+};
+
+impl Repository {
+    /// Links the auctions of the quotes returned for the same quote request
+    /// and stores the link so it can be restored after a restart.
+    #[tracing::instrument(skip_all, fields(primary_auction_id = %primary_auction_id))]
+    pub async fn add_quote_fallback_auctions(
+        &self,
+        primary_auction_id: entities::AuctionId,
+        fallback_auction_ids: &[entities::AuctionId],
+    ) -> anyhow::Result<()> {
+        self.add_in_memory_quote_fallback_auctions(primary_auction_id, fallback_auction_ids)
+            .await;
+        self.db
+            .add_quote_fallback_auctions(primary_auction_id, fallback_auction_ids)
+            .await
+    }
+}
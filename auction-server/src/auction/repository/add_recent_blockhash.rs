@@ -0,0 +1,27 @@
+use {
+    super::Repository,
+    solana_sdk::hash::Hash,
+};
+
+/// Number of recent blockhashes to keep in memory.
+/// Only the finalized blockhashes seen by the watcher loop are kept, so a transaction can use
+/// a blockhash outside this window, in which case its last valid block height is resolved with the rpc.
+const RECENT_BLOCKHASHES_WINDOW: usize = 32;
+
+impl Repository {
+    pub async fn add_recent_blockhash(&self, blockhash: Hash, last_valid_block_height: u64) {
+        let mut write_guard = self
+            .in_memory_store
+            .chain_store
+            .recent_blockhashes
+            .write()
+            .await;
+        if write_guard.iter().any(|(hash, _)| *hash == blockhash) {
+            return;
+        }
+        write_guard.push_back((blockhash, last_valid_block_height));
+        if write_guard.len() > RECENT_BLOCKHASHES_WINDOW {
+            write_guard.pop_front();
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use {
+    super::Repository,
+    crate::{
+        auction::entities,
+        models::ProfileId,
+    },
+    time::Date,
+};
+
+impl Repository {
+    /// Adds the lamports to the relayer spend of the profile for the day and returns the total spend of the day.
+    pub async fn add_relayer_spend(
+        &self,
+        profile_id: ProfileId,
+        day: Date,
+        lamports: u64,
+    ) -> anyhow::Result<u64> {
+        let total = self
+            .db
+            .add_relayer_spend(self.chain_id.clone(), profile_id, day, lamports)
+            .await?;
+        let mut write_guard = self.in_memory_store.relayer_spends.write().await;
+        let spend = write_guard
+            .entry(profile_id)
+            .or_insert(entities::RelayerDailySpend {
+                day,
+                lamports: 0,
+                reserved: 0,
+            });
+        // Concurrent updates may be applied out of order, the larger total is the latest one
+        if spend.day < day {
+            *spend = entities::RelayerDailySpend {
+                day,
+                lamports: total,
+                reserved: 0,
+            };
+        } else if spend.day == day && spend.lamports < total {
+            spend.lamports = total;
+        }
+        Ok(total)
+    }
+}
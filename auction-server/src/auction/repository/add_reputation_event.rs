@@ -0,0 +1,27 @@
+use {
+    super::Repository,
+    crate::{
+        auction::entities,
+        models::ProfileId,
+    },
+};
+
+impl Repository {
+    /// Counts the event in the reputation of the searcher and returns the updated reputation.
+    pub async fn add_reputation_event(
+        &self,
+        profile_id: ProfileId,
+        event: entities::ReputationEvent,
+    ) -> anyhow::Result<entities::SearcherReputation> {
+        let reputation = self
+            .db
+            .add_reputation_event(self.chain_id.clone(), profile_id, event)
+            .await?;
+        self.in_memory_store
+            .searcher_reputations
+            .write()
+            .await
+            .insert(profile_id, reputation.clone());
+        Ok(reputation)
+    }
+}
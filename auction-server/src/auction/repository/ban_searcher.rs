@@ -0,0 +1,27 @@
+use {
+    super::Repository,
+    crate::models::ProfileId,
+    time::OffsetDateTime,
+};
+
+impl Repository {
+    pub async fn ban_searcher(
+        &self,
+        profile_id: ProfileId,
+        banned_until: OffsetDateTime,
+    ) -> anyhow::Result<()> {
+        self.db
+            .ban_searcher(self.chain_id.clone(), profile_id, banned_until)
+            .await?;
+        if let Some(reputation) = self
+            .in_memory_store
+            .searcher_reputations
+            .write()
+            .await
+            .get_mut(&profile_id)
+        {
+            reputation.banned_until = Some(banned_until);
+        }
+        Ok(())
+    }
+}
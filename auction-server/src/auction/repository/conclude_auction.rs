@@ -11,6 +11,8 @@ impl Repository {
         tracing::Span::current().record("auction_id", auction_id.to_string());
         self.db.conclude_auction(auction_id).await?;
         self.remove_in_memory_auction(auction_id);
+        self.remove_in_memory_quote_fallback_auctions(&auction_id)
+            .await;
         Ok(())
     }
 }
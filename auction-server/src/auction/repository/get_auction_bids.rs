@@ -0,0 +1,39 @@
+use {
+    super::Repository,
+    crate::{
+        api::RestError,
+        auction::entities,
+    },
+};
+
+impl Repository {
+    /// Returns the bids of the auction from the database, or an empty list if the auction does not belong to the chain.
+    pub async fn get_auction_bids(
+        &self,
+        auction_id: entities::AuctionId,
+    ) -> Result<Vec<entities::Bid>, RestError> {
+        let bids: Vec<_> = self
+            .db
+            .get_auction_bids(auction_id)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = e.to_string(), auction_id = %auction_id, "Failed to get auction bids");
+                RestError::TemporarilyUnavailable
+            })?
+            .into_iter()
+            .filter(|bid| bid.chain_id == self.chain_id)
+            .collect();
+        if bids.is_empty() {
+            return Ok(vec![]);
+        }
+        let auction = self.db.get_auction(auction_id).await?;
+        bids.into_iter()
+            .map(|bid| {
+                bid.get_bid_entity(Some(auction.clone()), None).map_err(|e| {
+                    tracing::error!(error = e.to_string(), bid = ?bid, "Failed to convert bid to entity");
+                    RestError::TemporarilyUnavailable
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,43 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+    time::OffsetDateTime,
+};
+
+impl Repository {
+    /// Returns the bids that were submitted to the chain or sent to the user for submission
+    /// after the given time and are not concluded yet.
+    pub async fn get_in_flight_bids(
+        &self,
+        submitted_after: OffsetDateTime,
+    ) -> anyhow::Result<Vec<entities::Bid>> {
+        let bids = self
+            .db
+            .get_in_flight_bids(self.chain_id.clone(), submitted_after)
+            .await?;
+        let auctions = self
+            .db
+            .get_auctions_by_bids(&bids)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get auctions of in flight bids: {}", e))?;
+
+        Ok(bids
+            .into_iter()
+            .filter_map(|b| {
+                let auction = b
+                    .auction_id
+                    .and_then(|auction_id| auctions.iter().find(|a| a.id == auction_id).cloned());
+                b.get_bid_entity(auction.clone(), None)
+                    .map_err(|e| {
+                        tracing::error!(
+                            error = e.to_string(),
+                            auction = ?auction,
+                            bid = ?b,
+                            "Failed to convert bid to entity"
+                        );
+                    })
+                    .ok()
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,19 @@
+use {
+    super::Repository,
+    crate::models::ProfileId,
+    time::Date,
+};
+
+impl Repository {
+    /// Returns the lamports paid or reserved by the relayer for the bids of the profile during the day.
+    pub async fn get_in_memory_relayer_spend(&self, profile_id: &ProfileId, day: Date) -> u64 {
+        self.in_memory_store
+            .relayer_spends
+            .read()
+            .await
+            .get(profile_id)
+            .filter(|spend| spend.day == day)
+            .map(|spend| spend.lamports.saturating_add(spend.reserved))
+            .unwrap_or_default()
+    }
+}
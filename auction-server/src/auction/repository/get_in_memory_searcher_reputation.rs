@@ -0,0 +1,21 @@
+use {
+    super::Repository,
+    crate::{
+        auction::entities,
+        models::ProfileId,
+    },
+};
+
+impl Repository {
+    pub async fn get_in_memory_searcher_reputation(
+        &self,
+        profile_id: &ProfileId,
+    ) -> Option<entities::SearcherReputation> {
+        self.in_memory_store
+            .searcher_reputations
+            .read()
+            .await
+            .get(profile_id)
+            .cloned()
+    }
+}
@@ -0,0 +1,16 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+};
+
+impl Repository {
+    pub async fn get_in_memory_searcher_reputations(&self) -> Vec<entities::SearcherReputation> {
+        self.in_memory_store
+            .searcher_reputations
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect()
+    }
+}
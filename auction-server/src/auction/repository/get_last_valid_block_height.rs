@@ -0,0 +1,18 @@
+use {
+    super::Repository,
+    solana_sdk::hash::Hash,
+};
+
+impl Repository {
+    /// Returns the last valid block height of the blockhash if it is one of the recent blockhashes.
+    pub async fn get_last_valid_block_height(&self, blockhash: &Hash) -> Option<u64> {
+        self.in_memory_store
+            .chain_store
+            .recent_blockhashes
+            .read()
+            .await
+            .iter()
+            .find(|(hash, _)| hash == blockhash)
+            .map(|(_, last_valid_block_height)| *last_valid_block_height)
+    }
+}
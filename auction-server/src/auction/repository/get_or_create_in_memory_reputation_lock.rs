@@ -0,0 +1,23 @@
+use {
+    super::Repository,
+    crate::{
+        auction::entities,
+        models::ProfileId,
+    },
+};
+
+impl Repository {
+    #[tracing::instrument(skip_all)]
+    pub async fn get_or_create_in_memory_reputation_lock(
+        &self,
+        profile_id: ProfileId,
+    ) -> entities::ReputationLock {
+        self.in_memory_store
+            .reputation_lock
+            .lock()
+            .await
+            .entry(profile_id)
+            .or_default()
+            .clone()
+    }
+}
@@ -0,0 +1,19 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+};
+
+impl Repository {
+    /// Returns true if the auction is of a fallback quote of a quote request
+    #[tracing::instrument(skip_all)]
+    pub async fn is_in_memory_fallback_quote_auction(
+        &self,
+        auction_id: &entities::AuctionId,
+    ) -> bool {
+        self.in_memory_store
+            .fallback_quote_auctions
+            .read()
+            .await
+            .contains(auction_id)
+    }
+}
@@ -0,0 +1,47 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+    std::collections::HashMap,
+    time::OffsetDateTime,
+};
+
+impl Repository {
+    /// Loads the links between the quotes of the quote requests created after the given time from the database into memory.
+    /// The quotes of a request which already has a submitted quote are loaded as finalized.
+    pub async fn load_quote_fallback_auctions(
+        &self,
+        created_after: OffsetDateTime,
+    ) -> anyhow::Result<()> {
+        let links = self
+            .db
+            .get_quote_fallback_auctions(self.chain_id.clone(), created_after)
+            .await?;
+
+        let mut quote_requests: HashMap<entities::AuctionId, (Vec<entities::AuctionId>, bool)> =
+            HashMap::new();
+        for (primary_auction_id, auction_id, is_submitted) in links {
+            let (fallback_auction_ids, is_finalized) =
+                quote_requests.entry(primary_auction_id).or_default();
+            if auction_id != primary_auction_id {
+                fallback_auction_ids.push(auction_id);
+            }
+            *is_finalized |= is_submitted;
+        }
+
+        for (primary_auction_id, (fallback_auction_ids, is_finalized)) in quote_requests {
+            self.add_in_memory_quote_fallback_auctions(primary_auction_id, &fallback_auction_ids)
+                .await;
+            if is_finalized {
+                let mut write_guard = self.in_memory_store.quote_fallback_auctions.write().await;
+                for auction_id in
+                    std::iter::once(&primary_auction_id).chain(fallback_auction_ids.iter())
+                {
+                    if let Some(other_auction_ids) = write_guard.get_mut(auction_id) {
+                        other_auction_ids.clear();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+    time::Date,
+};
+
+impl Repository {
+    /// Loads the relayer spends of the day from the database into memory.
+    pub async fn load_relayer_spends(&self, day: Date) -> anyhow::Result<()> {
+        let spends = self
+            .db
+            .get_relayer_spends(self.chain_id.clone(), day)
+            .await?;
+        *self.in_memory_store.relayer_spends.write().await = spends
+            .into_iter()
+            .map(|(profile_id, lamports)| {
+                (
+                    profile_id,
+                    entities::RelayerDailySpend {
+                        day,
+                        lamports,
+                        reserved: 0,
+                    },
+                )
+            })
+            .collect();
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+use super::Repository;
+
+impl Repository {
+    /// Loads the reputations of the searchers of the chain from the database into memory.
+    pub async fn load_searcher_reputations(&self) -> anyhow::Result<()> {
+        let reputations = self
+            .db
+            .get_searcher_reputations(self.chain_id.clone())
+            .await?;
+        *self.in_memory_store.searcher_reputations.write().await = reputations
+            .into_iter()
+            .map(|reputation| (reputation.profile_id, reputation))
+            .collect();
+        Ok(())
+    }
+}
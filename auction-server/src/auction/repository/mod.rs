@@ -1,14 +1,18 @@
 use {
     super::entities,
-    crate::kernel::{
-        analytics_db::ClickhouseInserter,
-        entities::ChainId,
+    crate::{
+        kernel::{
+            analytics_db::ClickhouseInserter,
+            entities::ChainId,
+        },
+        models::ProfileId,
     },
     axum_prometheus::metrics,
     dashmap::DashMap,
-    solana_sdk::pubkey::Pubkey,
+    solana_sdk::hash::Hash,
     std::collections::{
         HashMap,
+        HashSet,
         VecDeque,
     },
     time::OffsetDateTime,
@@ -21,26 +25,45 @@ use {
 mod add_auction;
 mod add_bid;
 mod add_bid_analytics;
-mod add_lookup_table;
+mod add_in_memory_quote_fallback_auctions;
+mod add_quote_fallback_auctions;
+mod add_recent_blockhash;
 mod add_recent_prioritization_fee;
+mod add_relayer_spend;
+mod add_reputation_event;
+mod ban_searcher;
 mod conclude_auction;
+mod get_auction_bids;
 mod get_bid;
 mod get_bids;
+mod get_in_flight_bids;
 mod get_in_memory_auction_by_bid_id;
 mod get_in_memory_auction_by_id;
 mod get_in_memory_auctions;
 mod get_in_memory_pending_bids;
 mod get_in_memory_pending_bids_by_permission_key;
-mod get_lookup_table;
+mod get_in_memory_relayer_spend;
+mod get_in_memory_searcher_reputation;
+mod get_in_memory_searcher_reputations;
+mod get_last_valid_block_height;
 mod get_or_create_in_memory_auction_lock;
 mod get_or_create_in_memory_bid_lock;
+mod get_or_create_in_memory_reputation_lock;
 mod get_priority_fees;
+mod is_in_memory_fallback_quote_auction;
+mod load_quote_fallback_auctions;
+mod load_relayer_spends;
+mod load_searcher_reputations;
 mod models;
+mod release_in_memory_relayer_spend;
 mod remove_in_memory_auction;
 mod remove_in_memory_auction_lock;
 mod remove_in_memory_bid_lock;
 mod remove_in_memory_pending_bids;
+mod remove_in_memory_quote_fallback_auctions;
+mod reserve_in_memory_relayer_spend;
 mod submit_auction;
+mod take_in_memory_quote_fallback_auctions;
 mod update_bid_status;
 mod update_in_memory_auction;
 
@@ -49,8 +72,9 @@ pub use models::*;
 
 #[derive(Debug, Default)]
 pub struct ChainStoreSvm {
-    lookup_table:               RwLock<HashMap<Pubkey, Vec<Pubkey>>>,
     recent_prioritization_fees: RwLock<VecDeque<PrioritizationFeeSample>>,
+    /// Recent blockhashes with their last valid block heights, ordered from oldest to newest.
+    recent_blockhashes:         RwLock<VecDeque<(Hash, u64)>>,
 }
 
 pub type MicroLamports = u64;
@@ -72,6 +96,16 @@ pub struct InMemoryStore {
     pub auction_lock: Mutex<HashMap<PermissionKeySvm, entities::AuctionLock>>,
     pub bid_lock:     Mutex<HashMap<entities::BidId, entities::BidLock>>,
 
+    pub searcher_reputations: RwLock<HashMap<ProfileId, entities::SearcherReputation>>,
+    pub reputation_lock:      Mutex<HashMap<ProfileId, entities::ReputationLock>>,
+    pub relayer_spends:       RwLock<HashMap<ProfileId, entities::RelayerDailySpend>>,
+
+    /// The auctions of the other quotes returned for the same quote request, by auction id.
+    /// An empty list means that another quote of the request is already being submitted.
+    pub quote_fallback_auctions: RwLock<HashMap<entities::AuctionId, Vec<entities::AuctionId>>>,
+    /// The auctions of the fallback quotes, which are not the primary quote of their quote request.
+    pub fallback_quote_auctions: RwLock<HashSet<entities::AuctionId>>,
+
     pub chain_store: ChainStoreSvm,
 }
 
@@ -82,7 +116,15 @@ impl Default for InMemoryStore {
             auctions:     DashMap::new(),
             auction_lock: Mutex::new(HashMap::new()),
             bid_lock:     Mutex::new(HashMap::new()),
-            chain_store:  ChainStoreSvm::default(),
+
+            searcher_reputations: RwLock::new(HashMap::new()),
+            reputation_lock:      Mutex::new(HashMap::new()),
+            relayer_spends:       RwLock::new(HashMap::new()),
+
+            quote_fallback_auctions: RwLock::new(HashMap::new()),
+            fallback_quote_auctions: RwLock::new(HashSet::new()),
+
+            chain_store: ChainStoreSvm::default(),
         }
     }
 }
@@ -121,17 +163,27 @@ impl Repository {
     }
 }
 
-#[derive(Debug)]
 pub struct AnalyticsDatabaseInserter {
+    client:            clickhouse::Client,
     inserter_bid_swap: ClickhouseInserter<BidAnalyticsSwap>,
     inserter_bid_limo: ClickhouseInserter<BidAnalyticsLimo>,
 }
 
+impl std::fmt::Debug for AnalyticsDatabaseInserter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnalyticsDatabaseInserter")
+            .field("inserter_bid_swap", &self.inserter_bid_swap)
+            .field("inserter_bid_limo", &self.inserter_bid_limo)
+            .finish_non_exhaustive()
+    }
+}
+
 impl AnalyticsDatabaseInserter {
     pub fn new(client: clickhouse::Client) -> Self {
         let inserter_bid_swap = ClickhouseInserter::new(client.clone(), "bid_swap".to_string());
         let inserter_bid_limo = ClickhouseInserter::new(client.clone(), "bid_limo".to_string());
         Self {
+            client,
             inserter_bid_swap,
             inserter_bid_limo,
         }
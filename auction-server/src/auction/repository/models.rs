@@ -22,6 +22,10 @@ use {
         models::ProfileId,
     },
     axum::async_trait,
+    base64::{
+        engine::general_purpose::STANDARD,
+        Engine as _,
+    },
     serde::{
         Deserialize,
         Serialize,
@@ -47,6 +51,7 @@ use {
         ops::Deref,
     },
     time::{
+        Date,
         OffsetDateTime,
         PrimitiveDateTime,
         UtcOffset,
@@ -74,6 +79,7 @@ pub struct Auction {
     pub tx_hash:             Option<Vec<u8>>,
     pub bid_collection_time: Option<PrimitiveDateTime>,
     pub submission_time:     Option<PrimitiveDateTime>,
+    pub pricing_rule:        Option<sqlx::types::Json<entities::AuctionPricing>>,
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, sqlx::Type, Serialize, Deserialize)]
@@ -153,7 +159,9 @@ impl Deref for BundleIndex {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BidMetadataSvm {
     #[serde(with = "express_relay_api_types::serde::transaction_svm")]
-    pub transaction: VersionedTransaction,
+    pub transaction:             VersionedTransaction,
+    #[serde(default)]
+    pub last_valid_block_height: Option<u64>,
 }
 
 impl Svm {
@@ -345,6 +353,7 @@ impl Svm {
             permission_account:           entities::BidChainDataSvm::get_permission_account(
                 &permission_key,
             ),
+            last_valid_block_height:      bid.metadata.last_valid_block_height,
         })
     }
 
@@ -358,7 +367,8 @@ impl Svm {
 
     fn get_metadata(chain_data: &entities::BidChainDataSvm) -> BidMetadataSvm {
         BidMetadataSvm {
-            transaction: chain_data.transaction.clone(),
+            transaction:             chain_data.transaction.clone(),
+            last_valid_block_height: chain_data.last_valid_block_height,
         }
     }
 
@@ -394,14 +404,16 @@ impl Svm {
                 BidStatus::AwaitingSignature as _,
                 BidStatus::SentToUserForSubmission as _,
             ), None)),
+            // Bids held for fallback quotes are lost once another quote of the request is submitted
             entities::BidStatusSvm::Lost { auction: Some(auction) } => Ok((sqlx::query!(
-                    "UPDATE bid SET status = $1, auction_id = $2, conclusion_time = $3 WHERE id = $4 AND status = $5",
-                    BidStatus::Lost as _,
-                    auction.id,
-                    PrimitiveDateTime::new(now.date(), now.time()),
-                    bid.id,
-                    BidStatus::Pending as _
-                ), Some(now))),
+                "UPDATE bid SET status = $1, auction_id = $2, conclusion_time = $3 WHERE id = $4 AND status IN ($5, $6)",
+                BidStatus::Lost as _,
+                auction.id,
+                PrimitiveDateTime::new(now.date(), now.time()),
+                bid.id,
+                BidStatus::Pending as _,
+                BidStatus::AwaitingSignature as _,
+            ), Some(now))),
             entities::BidStatusSvm::Lost { auction: None } => Ok((sqlx::query!(
                     "UPDATE bid SET status = $1, conclusion_time = $2 WHERE id = $3 AND status = $4",
                     BidStatus::Lost as _,
@@ -552,6 +564,31 @@ impl Bid {
     }
 }
 
+#[derive(Clone, FromRow, Debug)]
+pub struct SearcherReputation {
+    pub profile_id:   ProfileId,
+    pub chain_id:     String,
+    pub auctions_won: i64,
+    pub bids_landed:  i64,
+    pub bids_failed:  i64,
+    pub bids_expired: i64,
+    pub banned_until: Option<PrimitiveDateTime>,
+}
+
+impl From<SearcherReputation> for entities::SearcherReputation {
+    fn from(reputation: SearcherReputation) -> Self {
+        Self {
+            profile_id:   reputation.profile_id,
+            auctions_won: reputation.auctions_won.max(0) as u64,
+            bids_landed:  reputation.bids_landed.max(0) as u64,
+            bids_failed:  reputation.bids_failed.max(0) as u64,
+            bids_expired: reputation.bids_expired.max(0) as u64,
+            banned_until: reputation
+                .banned_until
+                .map(|t| t.assume_offset(UtcOffset::UTC)),
+        }
+    }
+}
 
 #[cfg_attr(test, automock)]
 #[async_trait]
@@ -568,6 +605,12 @@ pub trait Database: Debug + Send + Sync + 'static {
         profile_id: ProfileId,
         from_time: Option<OffsetDateTime>,
     ) -> Result<Vec<Bid>, RestError>;
+    async fn get_in_flight_bids(
+        &self,
+        chain_id: ChainId,
+        submitted_after: OffsetDateTime,
+    ) -> anyhow::Result<Vec<Bid>>;
+    async fn get_auction_bids(&self, auction_id: entities::AuctionId) -> anyhow::Result<Vec<Bid>>;
     async fn submit_auction(
         &self,
         auction: &entities::Auction,
@@ -578,6 +621,44 @@ pub trait Database: Debug + Send + Sync + 'static {
         bid: &entities::Bid,
         new_status: &BidStatusSvm,
     ) -> anyhow::Result<(bool, Option<OffsetDateTime>)>;
+    async fn add_reputation_event(
+        &self,
+        chain_id: ChainId,
+        profile_id: ProfileId,
+        event: entities::ReputationEvent,
+    ) -> anyhow::Result<entities::SearcherReputation>;
+    async fn ban_searcher(
+        &self,
+        chain_id: ChainId,
+        profile_id: ProfileId,
+        banned_until: OffsetDateTime,
+    ) -> anyhow::Result<()>;
+    async fn get_searcher_reputations(
+        &self,
+        chain_id: ChainId,
+    ) -> anyhow::Result<Vec<entities::SearcherReputation>>;
+    async fn add_relayer_spend(
+        &self,
+        chain_id: ChainId,
+        profile_id: ProfileId,
+        day: Date,
+        lamports: u64,
+    ) -> anyhow::Result<u64>;
+    async fn get_relayer_spends(
+        &self,
+        chain_id: ChainId,
+        day: Date,
+    ) -> anyhow::Result<Vec<(ProfileId, u64)>>;
+    async fn add_quote_fallback_auctions(
+        &self,
+        primary_auction_id: entities::AuctionId,
+        fallback_auction_ids: &[entities::AuctionId],
+    ) -> anyhow::Result<()>;
+    async fn get_quote_fallback_auctions(
+        &self,
+        chain_id: ChainId,
+        created_after: OffsetDateTime,
+    ) -> anyhow::Result<Vec<(entities::AuctionId, entities::AuctionId, bool)>>;
 }
 
 #[async_trait]
@@ -595,14 +676,15 @@ impl Database for DB {
     )]
     async fn add_auction(&self, auction: &entities::Auction) -> anyhow::Result<()> {
         sqlx::query!(
-            "INSERT INTO auction (id, creation_time, permission_key, chain_id, chain_type, bid_collection_time, tx_hash) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            "INSERT INTO auction (id, creation_time, permission_key, chain_id, chain_type, bid_collection_time, tx_hash, pricing_rule) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
             auction.id,
             PrimitiveDateTime::new(auction.creation_time.date(), auction.creation_time.time()),
             Svm::convert_permission_key(&auction.permission_key),
             auction.chain_id,
             Svm::get_chain_type() as _,
             PrimitiveDateTime::new(auction.bid_collection_time.date(), auction.bid_collection_time.time()),
-            auction.tx_hash.clone().map(|tx_hash| BidStatusSvm::convert_tx_hash(&tx_hash)),
+            auction.tx_hash.map(|tx_hash| BidStatusSvm::convert_tx_hash(&tx_hash)),
+            auction.pricing.as_ref().map(|pricing| serde_json::to_value(pricing).expect("Failed to serialize auction pricing")),
         )
         .execute(self)
         .await
@@ -788,6 +870,60 @@ impl Database for DB {
         })
     }
 
+    #[instrument(
+        target = "metrics",
+        name = "db_get_in_flight_bids",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "get_in_flight_bids",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn get_in_flight_bids(
+        &self,
+        chain_id: ChainId,
+        submitted_after: OffsetDateTime,
+    ) -> anyhow::Result<Vec<Bid>> {
+        sqlx::query_as("SELECT bid.* FROM bid JOIN auction ON bid.auction_id = auction.id WHERE bid.chain_id = $1 AND bid.status IN ($2, $3) AND auction.submission_time >= $4")
+        .bind(&chain_id)
+        .bind(BidStatus::Submitted)
+        .bind(BidStatus::SentToUserForSubmission)
+        .bind(PrimitiveDateTime::new(
+            submitted_after.date(),
+            submitted_after.time(),
+        ))
+        .fetch_all(self)
+        .await
+        .inspect_err(|_| {
+            tracing::Span::current().record("result", "error");
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to get in flight bids: {}", e))
+    }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_get_auction_bids",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "get_auction_bids",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn get_auction_bids(&self, auction_id: entities::AuctionId) -> anyhow::Result<Vec<Bid>> {
+        sqlx::query_as("SELECT * FROM bid WHERE auction_id = $1")
+            .bind(auction_id)
+            .fetch_all(self)
+            .await
+            .inspect_err(|_| {
+                tracing::Span::current().record("result", "error");
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to get auction bids: {}", e))
+    }
+
     #[instrument(
         target = "metrics",
         name = "db_submit_auction",
@@ -841,6 +977,228 @@ impl Database for DB {
         })?;
         Ok((result.rows_affected() > 0, conclusion_time_new))
     }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_add_reputation_event",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "add_reputation_event",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn add_reputation_event(
+        &self,
+        chain_id: ChainId,
+        profile_id: ProfileId,
+        event: entities::ReputationEvent,
+    ) -> anyhow::Result<entities::SearcherReputation> {
+        // The event is counted by adding one to its column and zero to the others
+        let count = |counted_event: entities::ReputationEvent| i64::from(event == counted_event);
+        let reputation = sqlx::query_as!(
+            SearcherReputation,
+            "INSERT INTO searcher_reputation (profile_id, chain_id, auctions_won, bids_landed, bids_failed, bids_expired) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (profile_id, chain_id) DO UPDATE SET auctions_won = searcher_reputation.auctions_won + EXCLUDED.auctions_won, bids_landed = searcher_reputation.bids_landed + EXCLUDED.bids_landed, bids_failed = searcher_reputation.bids_failed + EXCLUDED.bids_failed, bids_expired = searcher_reputation.bids_expired + EXCLUDED.bids_expired RETURNING profile_id, chain_id, auctions_won, bids_landed, bids_failed, bids_expired, banned_until",
+            profile_id,
+            chain_id,
+            count(entities::ReputationEvent::AuctionWon),
+            count(entities::ReputationEvent::BidLanded),
+            count(entities::ReputationEvent::BidFailed),
+            count(entities::ReputationEvent::BidExpired),
+        )
+        .fetch_one(self)
+        .await
+        .inspect_err(|_| {
+            tracing::Span::current().record("result", "error");
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to add reputation event: {}", e))?;
+        Ok(reputation.into())
+    }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_ban_searcher",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "ban_searcher",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn ban_searcher(
+        &self,
+        chain_id: ChainId,
+        profile_id: ProfileId,
+        banned_until: OffsetDateTime,
+    ) -> anyhow::Result<()> {
+        sqlx::query("UPDATE searcher_reputation SET banned_until = $1 WHERE profile_id = $2 AND chain_id = $3")
+            .bind(PrimitiveDateTime::new(banned_until.date(), banned_until.time()))
+            .bind(profile_id)
+            .bind(&chain_id)
+            .execute(self)
+            .await
+            .inspect_err(|_| {
+                tracing::Span::current().record("result", "error");
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to ban searcher: {}", e))?;
+        Ok(())
+    }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_get_searcher_reputations",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "get_searcher_reputations",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn get_searcher_reputations(
+        &self,
+        chain_id: ChainId,
+    ) -> anyhow::Result<Vec<entities::SearcherReputation>> {
+        let reputations: Vec<SearcherReputation> =
+            sqlx::query_as("SELECT * FROM searcher_reputation WHERE chain_id = $1")
+                .bind(&chain_id)
+                .fetch_all(self)
+                .await
+                .inspect_err(|_| {
+                    tracing::Span::current().record("result", "error");
+                })
+                .map_err(|e| anyhow::anyhow!("Failed to get searcher reputations: {}", e))?;
+        Ok(reputations.into_iter().map(Into::into).collect())
+    }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_add_relayer_spend",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "add_relayer_spend",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn add_relayer_spend(
+        &self,
+        chain_id: ChainId,
+        profile_id: ProfileId,
+        day: Date,
+        lamports: u64,
+    ) -> anyhow::Result<u64> {
+        let lamports = i64::try_from(lamports)?;
+        let (total,): (i64,) = sqlx::query_as("INSERT INTO relayer_spend (profile_id, chain_id, day, lamports) VALUES ($1, $2, $3, $4) ON CONFLICT (profile_id, chain_id, day) DO UPDATE SET lamports = relayer_spend.lamports + $4 RETURNING lamports")
+            .bind(profile_id)
+            .bind(&chain_id)
+            .bind(day)
+            .bind(lamports)
+            .fetch_one(self)
+            .await
+            .inspect_err(|_| {
+                tracing::Span::current().record("result", "error");
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to add relayer spend: {}", e))?;
+        Ok(total.max(0) as u64)
+    }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_get_relayer_spends",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "get_relayer_spends",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn get_relayer_spends(
+        &self,
+        chain_id: ChainId,
+        day: Date,
+    ) -> anyhow::Result<Vec<(ProfileId, u64)>> {
+        let spends: Vec<(ProfileId, i64)> = sqlx::query_as(
+            "SELECT profile_id, lamports FROM relayer_spend WHERE chain_id = $1 AND day = $2",
+        )
+        .bind(&chain_id)
+        .bind(day)
+        .fetch_all(self)
+        .await
+        .inspect_err(|_| {
+            tracing::Span::current().record("result", "error");
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to get relayer spends: {}", e))?;
+        Ok(spends
+            .into_iter()
+            .map(|(profile_id, lamports)| (profile_id, lamports.max(0) as u64))
+            .collect())
+    }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_add_quote_fallback_auctions",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "add_quote_fallback_auctions",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn add_quote_fallback_auctions(
+        &self,
+        primary_auction_id: entities::AuctionId,
+        fallback_auction_ids: &[entities::AuctionId],
+    ) -> anyhow::Result<()> {
+        let auction_ids: Vec<entities::AuctionId> = std::iter::once(primary_auction_id)
+            .chain(fallback_auction_ids.iter().cloned())
+            .collect();
+        sqlx::query("UPDATE auction SET quote_primary_auction_id = $1 WHERE id = ANY($2)")
+            .bind(primary_auction_id)
+            .bind(auction_ids)
+            .execute(self)
+            .await
+            .inspect_err(|_| {
+                tracing::Span::current().record("result", "error");
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to add quote fallback auctions: {}", e))?;
+        Ok(())
+    }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_get_quote_fallback_auctions",
+        fields(
+            category = "db_queries",
+            result = "success",
+            name = "get_quote_fallback_auctions",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn get_quote_fallback_auctions(
+        &self,
+        chain_id: ChainId,
+        created_after: OffsetDateTime,
+    ) -> anyhow::Result<Vec<(entities::AuctionId, entities::AuctionId, bool)>> {
+        sqlx::query_as("SELECT quote_primary_auction_id, id, submission_time IS NOT NULL FROM auction WHERE chain_id = $1 AND quote_primary_auction_id IS NOT NULL AND creation_time >= $2")
+            .bind(&chain_id)
+            .bind(PrimitiveDateTime::new(
+                created_after.date(),
+                created_after.time(),
+            ))
+            .fetch_all(self)
+            .await
+            .inspect_err(|_| {
+                tracing::Span::current().record("result", "error");
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to get quote fallback auctions: {}", e))
+    }
 }
 
 #[derive(clickhouse::Row, Serialize, Deserialize, Debug)]
@@ -891,6 +1249,54 @@ pub struct BidAnalyticsSwap {
     pub profile_id: Option<Uuid>,
 }
 
+impl BidAnalyticsSwap {
+    /// Restores the bid from its analytics row, e.g. after the bid is deleted from the postgres history.
+    /// The status is only stored by its type, so the restored bid is pending and the recorded status is in `status`.
+    pub fn get_bid_entity(&self) -> anyhow::Result<entities::Bid> {
+        let permission_key = PermissionKeySvm::try_from(
+            STANDARD
+                .decode(&self.permission_key)
+                .map_err(|e| anyhow::anyhow!("Failed to decode permission key: {}", e))?
+                .as_slice(),
+        )
+        .map_err(|e| anyhow::anyhow!("Invalid permission key: {}", e))?;
+        let transaction: VersionedTransaction = bincode::deserialize(
+            &STANDARD
+                .decode(&self.transaction)
+                .map_err(|e| anyhow::anyhow!("Failed to decode transaction: {}", e))?,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to deserialize transaction: {}", e))?;
+
+        Ok(entities::Bid {
+            id:              self.id,
+            chain_id:        self.chain_id.clone(),
+            initiation_time: self.initiation_time,
+            profile_id:      self.profile_id,
+            opportunity_id:  self.opportunity_id,
+            creation_time:   self.creation_time,
+            conclusion_time: self.conclusion_time,
+            submission_time: self.submission_time,
+            amount:          self.bid_amount,
+            status:          entities::BidStatusSvm::Pending,
+            chain_data:      entities::BidChainDataSvm {
+                transaction,
+                bid_payment_instruction_type:
+                    entities::BidChainDataSvm::get_bid_payment_instruction_type(&permission_key)
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Failed to get bid payment instruction type from permission key"
+                            )
+                        })?,
+                router: entities::BidChainDataSvm::get_router(&permission_key),
+                permission_account: entities::BidChainDataSvm::get_permission_account(
+                    &permission_key,
+                ),
+                last_valid_block_height: None,
+            },
+        })
+    }
+}
+
 #[derive(clickhouse::Row, Serialize, Deserialize, Debug)]
 pub struct BidAnalyticsLimo {
     #[serde(with = "clickhouse::serde::uuid")]
@@ -932,6 +1338,10 @@ pub enum BidAnalytics {
 #[async_trait]
 pub trait AnalyticsDatabase: Debug + Send + Sync + 'static {
     async fn add_bid(&self, bid: BidAnalytics) -> Result<(), anyhow::Error>;
+    async fn get_auction_swap_bids(
+        &self,
+        auction_id: entities::AuctionId,
+    ) -> Result<Vec<BidAnalyticsSwap>, anyhow::Error>;
 }
 
 #[async_trait]
@@ -963,4 +1373,30 @@ impl AnalyticsDatabase for AnalyticsDatabaseInserter {
                 .map_err(|e| anyhow::anyhow!("Failed to send limo bid analytics {:?}", e)),
         }
     }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_analytics_get_auction_swap_bids",
+        fields(
+            category = "db_analytics_queries",
+            result = "success",
+            name = "get_auction_swap_bids",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn get_auction_swap_bids(
+        &self,
+        auction_id: entities::AuctionId,
+    ) -> Result<Vec<BidAnalyticsSwap>, anyhow::Error> {
+        self.client
+            .query("SELECT ?fields FROM bid_swap FINAL WHERE auction_id = ?")
+            .bind(auction_id.to_string())
+            .fetch_all::<BidAnalyticsSwap>()
+            .await
+            .map_err(|e| {
+                tracing::Span::current().record("result", "error");
+                anyhow::anyhow!("Failed to get auction swap bids {:?}", e)
+            })
+    }
 }
@@ -0,0 +1,26 @@
+use {
+    super::Repository,
+    crate::models::ProfileId,
+    time::Date,
+};
+
+impl Repository {
+    /// Releases the lamports reserved for the relayer spend of the profile for the day.
+    pub async fn release_in_memory_relayer_spend(
+        &self,
+        profile_id: &ProfileId,
+        day: Date,
+        lamports: u64,
+    ) {
+        if let Some(spend) = self
+            .in_memory_store
+            .relayer_spends
+            .write()
+            .await
+            .get_mut(profile_id)
+            .filter(|spend| spend.day == day)
+        {
+            spend.reserved = spend.reserved.saturating_sub(lamports);
+        }
+    }
+}
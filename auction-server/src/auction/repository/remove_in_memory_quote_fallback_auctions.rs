@@ -0,0 +1,20 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+};
+
+impl Repository {
+    #[tracing::instrument(skip_all)]
+    pub async fn remove_in_memory_quote_fallback_auctions(&self, auction_id: &entities::AuctionId) {
+        self.in_memory_store
+            .quote_fallback_auctions
+            .write()
+            .await
+            .remove(auction_id);
+        self.in_memory_store
+            .fallback_quote_auctions
+            .write()
+            .await
+            .remove(auction_id);
+    }
+}
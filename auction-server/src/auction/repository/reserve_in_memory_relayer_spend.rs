@@ -0,0 +1,47 @@
+use {
+    super::Repository,
+    crate::{
+        auction::entities,
+        models::ProfileId,
+    },
+    time::Date,
+};
+
+impl Repository {
+    /// Reserves the lamports for the relayer spend of the profile for the day if the total stays within the limit.
+    /// Returns whether the lamports are reserved.
+    pub async fn reserve_in_memory_relayer_spend(
+        &self,
+        profile_id: ProfileId,
+        day: Date,
+        lamports: u64,
+        limit: u64,
+    ) -> bool {
+        let mut write_guard = self.in_memory_store.relayer_spends.write().await;
+        let spend = write_guard
+            .entry(profile_id)
+            .or_insert(entities::RelayerDailySpend {
+                day,
+                lamports: 0,
+                reserved: 0,
+            });
+        if spend.day < day {
+            *spend = entities::RelayerDailySpend {
+                day,
+                lamports: 0,
+                reserved: 0,
+            };
+        } else if spend.day > day {
+            return false;
+        }
+        let total = spend
+            .lamports
+            .saturating_add(spend.reserved)
+            .saturating_add(lamports);
+        if total > limit {
+            return false;
+        }
+        spend.reserved += lamports;
+        true
+    }
+}
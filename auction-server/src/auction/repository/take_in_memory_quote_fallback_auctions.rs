@@ -0,0 +1,23 @@
+use {
+    super::Repository,
+    crate::auction::entities,
+};
+
+impl Repository {
+    /// Takes the auctions of the other quotes of the quote request of the auction.
+    /// Returns None if the auction has no other quotes, and an empty list if another quote of the request was taken first.
+    #[tracing::instrument(skip_all)]
+    pub async fn take_in_memory_quote_fallback_auctions(
+        &self,
+        auction_id: &entities::AuctionId,
+    ) -> Option<Vec<entities::AuctionId>> {
+        let mut write_guard = self.in_memory_store.quote_fallback_auctions.write().await;
+        let fallback_auction_ids = write_guard.remove(auction_id)?;
+        for fallback_auction_id in fallback_auction_ids.iter() {
+            if let Some(other_auction_ids) = write_guard.get_mut(fallback_auction_id) {
+                other_auction_ids.clear();
+            }
+        }
+        Some(fallback_auction_ids)
+    }
+}
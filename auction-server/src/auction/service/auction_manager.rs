@@ -1,5 +1,8 @@
 use {
-    super::Service,
+    super::{
+        BundleSubmissionSvm,
+        Service,
+    },
     crate::{
         auction::entities::{
             self,
@@ -8,36 +11,61 @@ use {
             BidStatus,
             BidStatusAuction,
         },
-        kernel::entities::PermissionKeySvm,
+        config::AuctionTriggerMode,
+        kernel::{
+            block_engine::{
+                BundleError,
+                MAX_BUNDLE_SIZE,
+            },
+            entities::PermissionKeySvm,
+        },
+        models::ProfileId,
         opportunity::{
             self,
             service::get_live_opportunities::GetLiveOpportunitiesInput,
         },
-        per_metrics::TRANSACTION_LANDING_TIME_SVM_METRIC,
+        per_metrics::{
+            BUNDLE_SUBMISSION_TOTAL,
+            TRANSACTION_LANDING_TIME_SVM_METRIC,
+        },
+    },
+    anyhow::{
+        anyhow,
+        Result,
     },
-    anyhow::Result,
     axum::async_trait,
     axum_prometheus::metrics,
     futures::{
         future::join_all,
+        stream::BoxStream,
         Stream,
+        StreamExt,
     },
+    rand::seq::SliceRandom,
     solana_client::{
         nonblocking::pubsub_client::PubsubClient,
         rpc_config::RpcSendTransactionConfig,
+        rpc_response::SlotInfo,
     },
     solana_sdk::{
         commitment_config::CommitmentConfig,
+        hash::Hash,
         signature::{
             Signature,
             Signer,
         },
+        system_instruction,
         transaction::{
+            Transaction,
             TransactionError,
             VersionedTransaction,
         },
     },
     std::{
+        collections::{
+            HashMap,
+            HashSet,
+        },
         fmt::Debug,
         pin::Pin,
         result,
@@ -75,10 +103,20 @@ pub trait AuctionManager {
     /// Get the ws client for the chain.
     async fn get_ws_client(&self) -> Result<Self::WsClient>;
     /// Get the trigger stream for the ws client to subscribe to new triggers.
-    async fn get_trigger_stream<'a>(client: &'a Self::WsClient) -> Result<Self::TriggerStream<'a>>;
+    /// The way the triggers are generated depends on the auction trigger mode of the chain.
+    async fn get_trigger_stream<'a>(
+        &self,
+        client: &'a Self::WsClient,
+    ) -> Result<Self::TriggerStream<'a>>;
 
     /// Get the winner bids for the auction. Sorting bids by bid amount and simulating the bids to determine the winner bids.
-    async fn get_winner_bids(&self, auction: &entities::Auction) -> Result<Vec<entities::Bid>>;
+    /// The way the winner bids are chosen depends on the winner selection mode of the chain.
+    /// Ties are broken against the bids of the deprioritized profiles.
+    async fn get_winner_bids(
+        &self,
+        auction: &entities::Auction,
+        deprioritized_profiles: &HashSet<ProfileId>,
+    ) -> Result<Vec<entities::Bid>>;
     /// Submit the bids for the auction on the chain.
     async fn submit_bids(
         &self,
@@ -114,39 +152,123 @@ pub trait AuctionManager {
 
 const BID_MAXIMUM_LIFE_TIME_SVM: Duration = Duration::from_secs(120);
 const TRIGGER_DURATION_SVM: Duration = Duration::from_millis(400);
+/// Maximum duration without a new slot before the slot driven triggers fall back to the interval
+const SLOT_TRIGGER_FALLBACK_DURATION_SVM: Duration = Duration::from_secs(1);
+/// Initial delay before resubscribing to the slot updates, doubled after every failed attempt
+const SLOT_RESUBSCRIBE_MIN_BACKOFF_SVM: Duration = Duration::from_millis(100);
+const SLOT_RESUBSCRIBE_MAX_BACKOFF_SVM: Duration = Duration::from_secs(5);
+
+/// Returns the slot updates of a new slot subscription on its own websocket connection.
+/// The stream yields an error and ends if the connection or the subscription fails.
+fn subscribe_to_slots(ws_address: String) -> BoxStream<'static, Result<SlotInfo>> {
+    Box::pin(async_stream::stream! {
+        let client = match PubsubClient::new(&ws_address).await {
+            Ok(client) => client,
+            Err(e) => {
+                yield Err(anyhow!(e));
+                return;
+            }
+        };
+        match client.slot_subscribe().await {
+            Ok((mut slots, _)) => {
+                while let Some(slot) = slots.next().await {
+                    yield Ok(slot);
+                }
+            }
+            Err(e) => {
+                yield Err(anyhow!(e));
+            }
+        }
+    })
+}
+
+/// Returns the items of the streams created by `subscribe`, creating a new stream with an exponential backoff
+/// whenever the current one fails or ends (e.g. the socket drops). The returned stream never ends.
+fn resubscribe_with_backoff<T, F>(mut subscribe: F) -> BoxStream<'static, T>
+where
+    T: Send + 'static,
+    F: FnMut() -> BoxStream<'static, Result<T>> + Send + 'static,
+{
+    Box::pin(async_stream::stream! {
+        let mut backoff = SLOT_RESUBSCRIBE_MIN_BACKOFF_SVM;
+        loop {
+            let mut stream = subscribe();
+            while let Some(item) = stream.next().await {
+                match item {
+                    Ok(item) => {
+                        backoff = SLOT_RESUBSCRIBE_MIN_BACKOFF_SVM;
+                        yield item;
+                    }
+                    Err(e) => {
+                        tracing::error!(error = ?e, "Slot subscription failed");
+                        break;
+                    }
+                }
+            }
+            tracing::warn!(backoff = ?backoff, "Slot subscription ended, resubscribing");
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(SLOT_RESUBSCRIBE_MAX_BACKOFF_SVM);
+        }
+    })
+}
 
-pub struct TriggerStreamSvm {
-    number:   u64,
-    interval: Interval,
+/// Triggers on every new slot if subscribed to the slot updates, otherwise on every interval tick.
+/// If the slot subscription stalls (e.g. while resubscribing after the socket drops), the interval ticks are used instead.
+pub struct TriggerStreamSvm<'a> {
+    number:       u64,
+    interval:     Interval,
+    slots:        Option<BoxStream<'a, SlotInfo>>,
+    last_trigger: Instant,
 }
 
-impl TriggerStreamSvm {
-    fn new(interval: Interval) -> Self {
+impl<'a> TriggerStreamSvm<'a> {
+    fn new(interval: Interval, slots: Option<BoxStream<'a, SlotInfo>>) -> Self {
         Self {
             number: 0,
             interval,
+            slots,
+            last_trigger: Instant::now(),
         }
     }
+
+    fn next_trigger(&mut self) -> Poll<Option<u64>> {
+        self.number += 1;
+        self.last_trigger = Instant::now();
+        Poll::Ready(Some(self.number))
+    }
 }
 
-impl Stream for TriggerStreamSvm {
+impl Stream for TriggerStreamSvm<'_> {
     type Item = u64;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        match self.interval.poll_tick(cx) {
-            Poll::Ready(_) => {
-                self.number += 1;
-                Poll::Ready(Some(self.number))
+        let this = &mut *self;
+        if let Some(slots) = this.slots.as_mut() {
+            match slots.poll_next_unpin(cx) {
+                Poll::Ready(Some(_)) => return this.next_trigger(),
+                Poll::Ready(None) => {
+                    tracing::warn!("Slot subscription ended, falling back to interval triggers");
+                    this.slots = None;
+                }
+                Poll::Pending => {}
             }
-            Poll::Pending => Poll::Pending,
         }
+        // Poll until pending so the interval always wakes up the stream for the fallback
+        while this.interval.poll_tick(cx).is_ready() {
+            if this.slots.is_none()
+                || this.last_trigger.elapsed() >= SLOT_TRIGGER_FALLBACK_DURATION_SVM
+            {
+                return this.next_trigger();
+            }
+        }
+        Poll::Pending
     }
 }
 
 #[async_trait]
 impl AuctionManager for Service {
     type Trigger = u64;
-    type TriggerStream<'a> = TriggerStreamSvm;
+    type TriggerStream<'a> = TriggerStreamSvm<'a>;
     type WsClient = PubsubClient;
     type ConclusionResult = result::Result<(), TransactionError>;
 
@@ -161,21 +283,41 @@ impl AuctionManager for Service {
             })
     }
 
+    /// The slot updates are subscribed to on a dedicated connection, so that it can be reconnected independently of the client.
     async fn get_trigger_stream<'a>(
+        &self,
         _client: &'a Self::WsClient,
     ) -> Result<Self::TriggerStream<'a>> {
-        Ok(TriggerStreamSvm::new(interval(TRIGGER_DURATION_SVM)))
+        let slots = match self.config.chain_config.auction_trigger_mode {
+            AuctionTriggerMode::Interval => None,
+            AuctionTriggerMode::Slot => {
+                let ws_address = self.config.chain_config.ws_address.clone();
+                Some(resubscribe_with_backoff(move || {
+                    subscribe_to_slots(ws_address.clone())
+                }))
+            }
+        };
+        Ok(TriggerStreamSvm::new(interval(TRIGGER_DURATION_SVM), slots))
     }
 
     #[tracing::instrument(skip_all, fields(auction_id, bid_ids))]
-    async fn get_winner_bids(&self, auction: &entities::Auction) -> Result<Vec<entities::Bid>> {
+    async fn get_winner_bids(
+        &self,
+        auction: &entities::Auction,
+        deprioritized_profiles: &HashSet<ProfileId>,
+    ) -> Result<Vec<entities::Bid>> {
         tracing::Span::current().record("auction_id", auction.id.to_string());
         tracing::Span::current().record(
             "bid_ids",
             tracing::field::display(entities::BidContainerTracing(&auction.bids)),
         );
-        let mut bids = auction.bids.clone();
-        bids.sort_by(|a, b| b.amount.cmp(&a.amount));
+        let bids = self
+            .get_auction_pricing_rule()
+            .rank_bids_with_deprioritized(
+                &auction.permission_key,
+                auction.bids.clone(),
+                deprioritized_profiles,
+            );
         return Ok(self
             .optimize_bids(&bids)
             .await
@@ -185,9 +327,9 @@ impl AuctionManager for Service {
             .unwrap_or(bids.first().cloned().map(|b| vec![b]).unwrap_or_default()));
     }
 
-    /// Submit all the svm bids as separate transactions concurrently
-    /// Returns Ok if at least one of the transactions is successful
-    /// Returns Err if all transactions are failed
+    /// Submit all the svm bids as separate transactions concurrently,
+    /// or as atomic bundles if bundle submission is configured for the chain.
+    /// Returns the signature of the first bid.
     #[tracing::instrument(skip_all, fields(tx_hash))]
     async fn submit_bids(
         &self,
@@ -198,20 +340,38 @@ impl AuctionManager for Service {
             return Err(anyhow::anyhow!("No bids to submit"));
         }
 
-        let send_futures: Vec<_> = bids
+        let bids: Vec<_> = bids
             .into_iter()
             .map(|mut bid| {
                 self.add_relayer_signature(&mut bid);
-                async move { self.send_transaction(&bid).await }
+                bid
             })
             .collect();
-        let results = join_all(send_futures).await;
-        Ok(*results
+        let signature = bids
             .first()
-            .expect("results should not be empty because bids is not empty"))
+            .expect("bids should not be empty")
+            .chain_data
+            .transaction
+            .signatures[0];
+        match &self.config.chain_config.bundle_submission {
+            Some(bundle_submission) => {
+                join_all(
+                    bids.chunks(MAX_BUNDLE_SIZE - 1)
+                        .map(|chunk| self.send_bundle(bundle_submission, chunk)),
+                )
+                .await;
+            }
+            None => {
+                join_all(bids.iter().map(|bid| self.send_transaction(bid))).await;
+            }
+        }
+        Ok(signature)
     }
 
-    #[tracing::instrument(skip_all, fields(bid_ids, tx_hash, auction_id, bid_statuses))]
+    #[tracing::instrument(
+        skip_all,
+        fields(bid_ids, tx_hash, auction_id, bid_statuses, block_height)
+    )]
     async fn get_bid_results(
         &self,
         bids: Vec<entities::Bid>,
@@ -237,17 +397,33 @@ impl AuctionManager for Service {
                     .expect("Signature array is empty on svm bid tx")
             })
             .collect();
-        let statuses = if bids
+        let is_any_bid_submitted = bids
             .iter()
-            .any(|bid| bid.status.is_submitted() || bid.status.is_sent_to_user_for_submission())
+            .any(|bid| bid.status.is_submitted() || bid.status.is_sent_to_user_for_submission());
+        // The block height should be fetched before the statuses
+        // so that an unconfirmed transaction is never expired after it lands
+        let block_height = if is_any_bid_submitted
+            && bids
+                .iter()
+                .any(|bid| bid.chain_data.last_valid_block_height.is_some())
         {
             self.config
                 .chain_config
                 .client
-                // TODO: Chunk this if signatures.len() > 256, RPC can only handle 256 signatures at a time
+                .get_block_height_with_commitment(CommitmentConfig::confirmed())
+                .await
+                .inspect_err(|e| tracing::warn!(error = ?e, "Failed to get block height"))
+                .ok()
+        } else {
+            None
+        };
+        tracing::Span::current().record("block_height", block_height);
+        let statuses = if is_any_bid_submitted {
+            self.config
+                .chain_config
+                .signature_status_poller
                 .get_signature_statuses(&signatures)
                 .await?
-                .value
                 .into_iter()
                 .map(|status| {
                     status
@@ -304,13 +480,7 @@ impl AuctionManager for Service {
                     }),
                     None => {
                         // not yet confirmed
-                        // TODO Use the correct version of the expiration algorithm, which is:
-                        // the tx is not expired as long as the block hash is still recent.
-                        // Assuming a certain block time, the two minute threshold is good enough but in some cases, it's not correct.
-                        if bid.initiation_time + BID_MAXIMUM_LIFE_TIME_SVM
-                            < OffsetDateTime::now_utc()
-                        {
-                            // If the bid is older than the maximum lifetime, it means that the block hash is now too old and the transaction is expired.
+                        if Self::is_bid_expired(bid, block_height) {
                             Some(entities::BidStatusSvm::Expired { auction })
                         } else {
                             None
@@ -387,8 +557,25 @@ const RETRY_DURATION: Duration = Duration::from_secs(2);
 const METRIC_LABEL_SUCCESS: &str = "success";
 const METRIC_LABEL_FAILED: &str = "failed";
 const METRIC_LABEL_EXPIRED: &str = "expired";
+const METRIC_LABEL_ACCEPTED: &str = "accepted";
+const METRIC_LABEL_REJECTED: &str = "rejected";
 
 impl Service {
+    /// Checks if a bid that is not yet confirmed on chain is expired.
+    ///
+    /// A transaction can not land once the chain block height passes the last valid block height of its blockhash.
+    /// For the bids without a recorded last valid block height, the bid is considered expired
+    /// once it's older than `BID_MAXIMUM_LIFE_TIME_SVM`, which assumes a certain block time.
+    fn is_bid_expired(bid: &entities::Bid, block_height: Option<u64>) -> bool {
+        match bid.chain_data.last_valid_block_height {
+            Some(last_valid_block_height) => {
+                // If the block height is not available, we wait for the next conclusion attempt
+                block_height.is_some_and(|block_height| block_height > last_valid_block_height)
+            }
+            None => bid.initiation_time + BID_MAXIMUM_LIFE_TIME_SVM < OffsetDateTime::now_utc(),
+        }
+    }
+
     #[tracing::instrument(skip_all)]
     pub fn add_relayer_signature(&self, bid: &mut entities::Bid) {
         let relayer = &self.config.chain_config.express_relay.relayer;
@@ -413,29 +600,17 @@ impl Service {
         }
     }
 
+    /// Sends the transaction with the submission strategy of the chain.
+    /// Returns the indices of the tx broadcaster endpoints that accepted the transaction.
     async fn send_transaction_to_network(
         &self,
         transaction: &VersionedTransaction,
-    ) -> solana_client::client_error::Result<Signature> {
-        let result = join_all(
-            self.config.chain_config.tx_broadcaster_clients.iter().map(|tx_broadcaster_client| async {
-                let result = tx_broadcaster_client
-                    .send_transaction_with_config(
-                        transaction,
-                        self.get_send_transaction_config(),
-                    ).await;
-                if let Err(e) = &result {
-                    tracing::error!(error = ?e, client = ?tx_broadcaster_client.url(), "Failed to send transaction to network");
-                }
-                result
-            }),
-        ).await;
-        result.into_iter().find(|res| res.is_ok()).unwrap_or({
-            Err(solana_client::client_error::ClientErrorKind::Custom(
-                "All tx broadcasters failed".to_string(),
-            )
-            .into())
-        })
+    ) -> solana_client::client_error::Result<Vec<(usize, Instant)>> {
+        self.config
+            .chain_config
+            .tx_broadcaster
+            .send_transaction(transaction, self.get_send_transaction_config())
+            .await
     }
 
     /// Returns Some() if the transaction has landed, None if:
@@ -445,7 +620,7 @@ impl Service {
         &self,
         signature: &Signature,
     ) -> Option<Result<(), TransactionError>> {
-        let result = join_all(self.config.chain_config.tx_broadcaster_clients.iter().map(
+        let result = join_all(self.config.chain_config.tx_broadcaster.clients().map(
             |tx_broadcaster_client| async {
                 let result = tx_broadcaster_client.get_signature_status(signature).await;
                 if let Err(e) = &result {
@@ -462,12 +637,22 @@ impl Service {
             .flatten()
     }
 
-    #[tracing::instrument(skip_all, fields(bid_id, total_tries, tx_hash))]
-    async fn blocking_send_transaction(&self, bid: entities::Bid, start: Instant) {
+    /// Waits for the transaction of the bids to land and resubmits it until it lands or the retry limit is reached.
+    /// All the bids are expected to land together, so only the transaction of the first bid is watched.
+    #[tracing::instrument(skip_all, fields(bid_ids, total_tries, tx_hash))]
+    async fn blocking_send_transaction(
+        &self,
+        bids: Vec<entities::Bid>,
+        mut resubmission: Resubmission,
+        start: Instant,
+    ) {
         let mut result_label = METRIC_LABEL_EXPIRED;
         let mut reason_label = None;
-        let signature = bid.chain_data.transaction.signatures[0];
-        tracing::Span::current().record("bid_id", bid.id.to_string());
+        let signature = bids[0].chain_data.transaction.signatures[0];
+        tracing::Span::current().record(
+            "bid_ids",
+            tracing::field::display(entities::BidContainerTracing(&bids)),
+        );
         tracing::Span::current().record("tx_hash", signature.to_string());
         let mut receiver = self.config.chain_config.log_sender.subscribe();
         let mut retry_interval = tokio::time::interval(RETRY_DURATION);
@@ -499,9 +684,7 @@ impl Service {
                     }
 
                     retry_count += 1;
-                    if let Err(e) = self.send_transaction_to_network(&bid.chain_data.transaction).await {
-                        tracing::error!(error = ?e, "Failed to resubmit transaction");
-                    }
+                    self.resubmit(&mut resubmission).await;
                 }
             }
         }
@@ -518,12 +701,48 @@ impl Service {
             labels.push(("reason", label.to_string()))
         }
 
-        metrics::histogram!(TRANSACTION_LANDING_TIME_SVM_METRIC, &labels)
-            .record(start.elapsed().as_secs_f64());
+        for _ in &bids {
+            metrics::histogram!(TRANSACTION_LANDING_TIME_SVM_METRIC, &labels)
+                .record(start.elapsed().as_secs_f64());
+        }
+        if let Resubmission::Transaction { sent_at, .. } = &resubmission {
+            self.config
+                .chain_config
+                .tx_broadcaster
+                .record_landing(sent_at, result_label != METRIC_LABEL_EXPIRED);
+        }
 
         tracing::Span::current().record("total_tries", retry_count + 1);
     }
 
+    async fn resubmit(&self, resubmission: &mut Resubmission) {
+        match resubmission {
+            Resubmission::Transaction {
+                transaction,
+                sent_at,
+            } => match self.send_transaction_to_network(transaction).await {
+                Ok(endpoints) => {
+                    for (endpoint, accepted_at) in endpoints {
+                        sent_at.entry(endpoint).or_insert(accepted_at);
+                    }
+                }
+                Err(e) => tracing::error!(error = ?e, "Failed to resubmit transaction"),
+            },
+            Resubmission::Bundle(transactions) => {
+                let Some(bundle_submission) = &self.config.chain_config.bundle_submission else {
+                    return;
+                };
+                if let Err(e) = bundle_submission
+                    .block_engine
+                    .send_bundle(transactions)
+                    .await
+                {
+                    tracing::error!(error = %e, "Failed to resubmit bundle");
+                }
+            }
+        }
+    }
+
     /// Sends the transaction to the network and adds it to the pending transactions.
     ///
     /// If the first try fails, it will retry for multiple times.
@@ -533,12 +752,16 @@ impl Service {
         let start = Instant::now();
         let tx = &bid.chain_data.transaction;
         // Do not propagate the error because we retry more in the blocking_send_transaction
-        if let Err(e) = self
+        let sent_at = match self
             .send_transaction_to_network(&bid.chain_data.transaction)
             .await
         {
-            tracing::warn!(error = ?e, "Failed to send transaction to network");
-        }
+            Ok(endpoints) => endpoints.into_iter().collect(),
+            Err(e) => {
+                tracing::warn!(error = ?e, "Failed to send transaction to network");
+                HashMap::new()
+            }
+        };
         self.config
             .chain_config
             .simulator
@@ -548,9 +771,484 @@ impl Service {
         self.task_tracker.spawn({
             let (service, bid) = (self.clone(), bid.clone());
             async move {
-                service.blocking_send_transaction(bid, start).await;
+                let resubmission = Resubmission::Transaction {
+                    transaction: bid.chain_data.transaction.clone(),
+                    sent_at,
+                };
+                service
+                    .blocking_send_transaction(vec![bid], resubmission, start)
+                    .await;
             }
         });
         tx.signatures[0]
     }
+
+    /// Sends a transaction signed only by the relayer and resubmits it until it lands or the retry limit is reached.
+    /// Returns None if the transaction did not land.
+    pub async fn send_relayer_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Option<Result<(), TransactionError>> {
+        let signature = transaction.signatures[0];
+        let mut retry_interval = tokio::time::interval(RETRY_DURATION);
+        for _ in 0..SEND_TRANSACTION_RETRY_COUNT_SVM {
+            if let Err(e) = self.send_transaction_to_network(transaction).await {
+                tracing::warn!(error = ?e, "Failed to send relayer transaction to network");
+            }
+            retry_interval.tick().await;
+            if let Some(status) = self.get_signature_status(&signature).await {
+                return Some(status);
+            }
+        }
+        None
+    }
+
+    /// Creates the transaction that pays the bundle tip from the relayer to one of the tip accounts.
+    fn get_tip_transaction(
+        &self,
+        bundle_submission: &BundleSubmissionSvm,
+        recent_blockhash: Hash,
+    ) -> VersionedTransaction {
+        let relayer = &self.config.chain_config.express_relay.relayer;
+        let tip_account = bundle_submission
+            .tip_accounts
+            .choose(&mut rand::thread_rng())
+            .expect("Tip accounts should not be empty");
+        let instruction = system_instruction::transfer(
+            &relayer.pubkey(),
+            tip_account,
+            bundle_submission.tip_lamports,
+        );
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&relayer.pubkey()),
+            &[relayer],
+            recent_blockhash,
+        )
+        .into()
+    }
+
+    /// Sends the bids as one atomic bundle to the block engine, with the tip transaction at the end of the bundle.
+    ///
+    /// If the bundle is accepted, the bids are added to the pending transactions and the bundle is resubmitted until it lands.
+    /// If the bundle is rejected, the bids are sent as separate transactions instead.
+    #[tracing::instrument(skip_all, fields(bid_ids, bundle_id))]
+    async fn send_bundle(&self, bundle_submission: &BundleSubmissionSvm, bids: &[entities::Bid]) {
+        tracing::Span::current().record(
+            "bid_ids",
+            tracing::field::display(entities::BidContainerTracing(bids)),
+        );
+        let start = Instant::now();
+        let recent_blockhash = *bids[0].chain_data.transaction.message.recent_blockhash();
+        let mut transactions: Vec<_> = bids
+            .iter()
+            .map(|bid| bid.chain_data.transaction.clone())
+            .collect();
+        transactions.push(self.get_tip_transaction(bundle_submission, recent_blockhash));
+
+        let result = bundle_submission
+            .block_engine
+            .send_bundle(&transactions)
+            .await;
+        let result_label = match &result {
+            Ok(_) => METRIC_LABEL_ACCEPTED,
+            Err(BundleError::Rejected { .. }) => METRIC_LABEL_REJECTED,
+            Err(BundleError::Request(_)) => METRIC_LABEL_FAILED,
+        };
+        metrics::counter!(
+            BUNDLE_SUBMISSION_TOTAL,
+            &[
+                ("chain_id", self.config.chain_id.clone()),
+                ("result", result_label.to_string()),
+            ]
+        )
+        .increment(1);
+
+        match result {
+            Ok(bundle_id) => {
+                tracing::Span::current().record("bundle_id", &bundle_id);
+                for transaction in transactions.iter() {
+                    self.config
+                        .chain_config
+                        .simulator
+                        .add_pending_transaction(transaction)
+                        .await;
+                }
+                self.task_tracker.spawn({
+                    let (service, bids) = (self.clone(), bids.to_vec());
+                    async move {
+                        service
+                            .blocking_send_transaction(
+                                bids,
+                                Resubmission::Bundle(transactions),
+                                start,
+                            )
+                            .await;
+                    }
+                });
+            }
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    block_engine = bundle_submission.block_engine.url(),
+                    "Failed to submit bundle, sending the bids as separate transactions"
+                );
+                join_all(bids.iter().map(|bid| self.send_transaction(bid))).await;
+            }
+        }
+    }
+}
+
+/// The way a submitted transaction is resubmitted until it lands
+enum Resubmission {
+    /// Resend the transaction to the tx broadcaster rpc nodes
+    Transaction {
+        transaction: VersionedTransaction,
+        /// The first time each tx broadcaster endpoint accepted the transaction
+        sent_at:     HashMap<usize, Instant>,
+    },
+    /// Resend the whole bundle to the block engine
+    Bundle(Vec<VersionedTransaction>),
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction::repository::MockDatabase,
+            kernel::{
+                block_engine::{
+                    tests::BlockEngineStandIn,
+                    BlockEngineClient,
+                },
+                traced_sender_svm::tests::MockRpcClient,
+            },
+            opportunity::service::MockService as MockOpportunityService,
+        },
+        base64::{
+            prelude::BASE64_STANDARD,
+            Engine,
+        },
+        futures::stream,
+        serde_json::json,
+        solana_client::rpc_request::RpcRequest,
+        solana_sdk::{
+            pubkey::Pubkey,
+            signature::Keypair,
+        },
+        std::{
+            collections::HashSet,
+            sync::{
+                atomic::{
+                    AtomicUsize,
+                    Ordering,
+                },
+                Arc,
+                Mutex,
+            },
+        },
+        tokio::time::timeout,
+        uuid::Uuid,
+    };
+
+    fn slot_info(slot: u64) -> SlotInfo {
+        SlotInfo {
+            slot,
+            parent: slot.saturating_sub(1),
+            root: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resubscribe_with_backoff() {
+        let subscriptions = Arc::new(AtomicUsize::new(0));
+        let mut slots = resubscribe_with_backoff({
+            let subscriptions = subscriptions.clone();
+            move || match subscriptions.fetch_add(1, Ordering::SeqCst) {
+                0 => stream::iter([Err(anyhow!("Connection refused"))]).boxed(),
+                1 => stream::iter([Ok(slot_info(1)), Ok(slot_info(2))]).boxed(),
+                _ => stream::iter([Ok(slot_info(3))])
+                    .chain(stream::pending())
+                    .boxed(),
+            }
+        });
+
+        // The failed and the ended subscriptions are replaced with new ones
+        for slot in 1..=3 {
+            let slot_info = timeout(Duration::from_secs(1), slots.next())
+                .await
+                .expect("Slot update should be received")
+                .expect("Slot stream should not end");
+            assert_eq!(slot_info.slot, slot);
+        }
+        assert_eq!(subscriptions.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_trigger_stream_falls_back_to_interval() {
+        let slots = stream::iter([slot_info(1), slot_info(2)])
+            .chain(stream::pending())
+            .boxed();
+        let mut triggers = TriggerStreamSvm::new(interval(Duration::from_millis(10)), Some(slots));
+
+        // New slots trigger immediately
+        assert_eq!(
+            timeout(Duration::from_millis(100), triggers.next()).await,
+            Ok(Some(1))
+        );
+        assert_eq!(
+            timeout(Duration::from_millis(100), triggers.next()).await,
+            Ok(Some(2))
+        );
+
+        // The interval triggers once the slot updates stall
+        let start = Instant::now();
+        assert_eq!(
+            timeout(SLOT_TRIGGER_FALLBACK_DURATION_SVM * 2, triggers.next()).await,
+            Ok(Some(3))
+        );
+        assert!(start.elapsed() >= SLOT_TRIGGER_FALLBACK_DURATION_SVM / 2);
+    }
+
+    fn get_bid(recent_blockhash: Hash) -> entities::Bid {
+        let searcher = Keypair::new();
+        let instruction =
+            system_instruction::transfer(&searcher.pubkey(), &Pubkey::new_unique(), 1);
+        entities::Bid {
+            id:              Uuid::new_v4(),
+            chain_id:        "solana".to_string(),
+            initiation_time: OffsetDateTime::now_utc(),
+            profile_id:      None,
+            opportunity_id:  None,
+            creation_time:   OffsetDateTime::now_utc(),
+            conclusion_time: None,
+            submission_time: None,
+            amount:          100,
+            status:          entities::BidStatusSvm::Pending,
+            chain_data:      entities::BidChainDataSvm {
+                transaction:                  Transaction::new_signed_with_payer(
+                    &[instruction],
+                    Some(&searcher.pubkey()),
+                    &[&searcher],
+                    recent_blockhash,
+                )
+                .into(),
+                bid_payment_instruction_type: BidPaymentInstructionType::Swap,
+                router:                       Pubkey::new_unique(),
+                permission_account:           Pubkey::new_unique(),
+                last_valid_block_height:      None,
+            },
+        }
+    }
+
+    /// Returns a tx broadcaster client that records the sent transactions and reports every transaction as landed.
+    fn get_broadcaster_client(sent: Arc<Mutex<Vec<VersionedTransaction>>>) -> MockRpcClient {
+        let mut client = MockRpcClient::new();
+        client
+            .expect_send()
+            .withf(|request, _| *request == RpcRequest::SendTransaction)
+            .returning(move |_, params| {
+                let bytes = BASE64_STANDARD
+                    .decode(params[0].as_str().expect("Transaction should be encoded"))
+                    .expect("Failed to decode transaction");
+                let transaction: VersionedTransaction =
+                    bincode::deserialize(&bytes).expect("Failed to deserialize transaction");
+                let signature = transaction.signatures[0].to_string();
+                sent.lock()
+                    .expect("Lock should not be poisoned")
+                    .push(transaction);
+                Ok(json!(signature))
+            });
+        client
+            .expect_send()
+            .withf(|request, _| *request == RpcRequest::GetSignatureStatuses)
+            .returning(|_, _| {
+                Ok(json!({
+                    "context": { "slot": 1 },
+                    "value": [{
+                        "slot": 1,
+                        "confirmations": null,
+                        "err": null,
+                        "status": { "Ok": null },
+                        "confirmationStatus": "finalized",
+                    }],
+                }))
+            });
+        client
+            .expect_send()
+            .withf(|request, _| *request == RpcRequest::GetVersion)
+            .returning(|_, _| Ok(json!({ "solana-core": "2.2.0", "feature-set": 1 })));
+        client.expect_url().returning(|| "https://test".to_string());
+        client
+    }
+
+    fn get_service(block_engine_url: &str, broadcaster_client: MockRpcClient) -> Service {
+        let mut service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            MockDatabase::default(),
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            broadcaster_client,
+        );
+        Arc::get_mut(&mut service.0)
+            .expect("Only one reference should exist at setup")
+            .config
+            .chain_config
+            .bundle_submission = Some(BundleSubmissionSvm {
+            block_engine: BlockEngineClient::new(block_engine_url, 2),
+            tip_accounts: vec![Pubkey::new_unique()],
+            tip_lamports: 1000,
+        });
+        service
+    }
+
+    fn get_signatures(transactions: &[VersionedTransaction]) -> HashSet<Signature> {
+        transactions.iter().map(|tx| tx.signatures[0]).collect()
+    }
+
+    fn get_bid_transactions(bids: &[entities::Bid]) -> Vec<VersionedTransaction> {
+        bids.iter()
+            .map(|bid| bid.chain_data.transaction.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_send_bundle_accepted() {
+        let stand_in = BlockEngineStandIn::start(true).await;
+        let sent = Arc::new(Mutex::new(vec![]));
+        let service = get_service(&stand_in.url, get_broadcaster_client(sent.clone()));
+        let bundle_submission = service
+            .config
+            .chain_config
+            .bundle_submission
+            .as_ref()
+            .expect("Bundle submission should be configured");
+        let recent_blockhash = Hash::new_unique();
+        let bids = vec![get_bid(recent_blockhash), get_bid(recent_blockhash)];
+
+        service.send_bundle(bundle_submission, &bids).await;
+
+        // The bundle has the bid transactions in order, followed by the tip transaction
+        let bundles = stand_in.bundles.lock().await.clone();
+        assert_eq!(bundles.len(), 1);
+        let bundle = &bundles[0];
+        assert_eq!(bundle.len(), bids.len() + 1);
+        assert_eq!(bundle[..bids.len()], get_bid_transactions(&bids));
+        let tip_transaction = &bundle[bids.len()];
+        let relayer = service.config.chain_config.express_relay.relayer.pubkey();
+        assert_eq!(tip_transaction.message.static_account_keys()[0], relayer);
+        assert!(tip_transaction
+            .message
+            .static_account_keys()
+            .contains(&bundle_submission.tip_accounts[0]));
+        assert_eq!(
+            *tip_transaction.message.recent_blockhash(),
+            recent_blockhash
+        );
+        assert!(tip_transaction.verify_with_results().iter().all(|ok| *ok));
+
+        // All the transactions of the bundle are pending and none is sent separately
+        assert_eq!(
+            service
+                .config
+                .chain_config
+                .simulator
+                .fetch_pending_and_remove_old_txs()
+                .await,
+            *bundle
+        );
+        assert!(sent.lock().expect("Lock should not be poisoned").is_empty());
+    }
+
+    async fn assert_bundle_falls_back_to_transactions(
+        service: &Service,
+        sent: Arc<Mutex<Vec<VersionedTransaction>>>,
+    ) {
+        let bundle_submission = service
+            .config
+            .chain_config
+            .bundle_submission
+            .as_ref()
+            .expect("Bundle submission should be configured");
+        let recent_blockhash = Hash::new_unique();
+        let bids = vec![get_bid(recent_blockhash), get_bid(recent_blockhash)];
+
+        service.send_bundle(bundle_submission, &bids).await;
+
+        // The bid transactions are sent separately without the tip transaction
+        let bid_signatures = get_signatures(&get_bid_transactions(&bids));
+        assert_eq!(
+            get_signatures(&sent.lock().expect("Lock should not be poisoned")),
+            bid_signatures
+        );
+        assert_eq!(
+            get_signatures(
+                &service
+                    .config
+                    .chain_config
+                    .simulator
+                    .fetch_pending_and_remove_old_txs()
+                    .await
+            ),
+            bid_signatures
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_bundle_rejected_falls_back_to_transactions() {
+        let stand_in = BlockEngineStandIn::start(false).await;
+        let sent = Arc::new(Mutex::new(vec![]));
+        let service = get_service(&stand_in.url, get_broadcaster_client(sent.clone()));
+
+        assert_bundle_falls_back_to_transactions(&service, sent).await;
+        assert_eq!(stand_in.bundles.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_bundle_request_failure_falls_back_to_transactions() {
+        // Nothing listens on this address, so the request to the block engine fails
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind address");
+        let url = format!(
+            "http://{}",
+            listener.local_addr().expect("Failed to get address")
+        );
+        drop(listener);
+        let sent = Arc::new(Mutex::new(vec![]));
+        let service = get_service(&url, get_broadcaster_client(sent.clone()));
+
+        assert_bundle_falls_back_to_transactions(&service, sent).await;
+    }
+
+    #[test]
+    fn test_is_bid_expired_by_block_height() {
+        let mut bid = get_bid(Hash::new_unique());
+        bid.chain_data.last_valid_block_height = Some(150);
+
+        assert!(!Service::is_bid_expired(&bid, Some(149)));
+        assert!(!Service::is_bid_expired(&bid, Some(150)));
+        assert!(Service::is_bid_expired(&bid, Some(151)));
+        // Without the block height, the bid is kept until the next conclusion attempt
+        assert!(!Service::is_bid_expired(&bid, None));
+
+        // The age of the bid is not considered when the last valid block height is known
+        bid.initiation_time =
+            OffsetDateTime::now_utc() - BID_MAXIMUM_LIFE_TIME_SVM - Duration::from_secs(1);
+        assert!(!Service::is_bid_expired(&bid, Some(150)));
+    }
+
+    #[test]
+    fn test_is_bid_expired_by_age() {
+        let mut bid = get_bid(Hash::new_unique());
+
+        assert!(!Service::is_bid_expired(&bid, Some(1_000)));
+        assert!(!Service::is_bid_expired(&bid, None));
+
+        bid.initiation_time =
+            OffsetDateTime::now_utc() - BID_MAXIMUM_LIFE_TIME_SVM - Duration::from_secs(1);
+        assert!(Service::is_bid_expired(&bid, Some(1_000)));
+        assert!(Service::is_bid_expired(&bid, None));
+    }
 }
@@ -0,0 +1,434 @@
+use {
+    super::Service,
+    crate::{
+        auction::entities,
+        config::{
+            AuctionPricingRule,
+            PricingRule,
+        },
+        kernel::entities::PermissionKeySvm,
+        models::ProfileId,
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        cmp::Reverse,
+        collections::{
+            HashMap,
+            HashSet,
+        },
+    },
+    time::OffsetDateTime,
+};
+
+/// Orders bids of equal amounts: bids of deprioritized profiles rank last,
+/// then a higher priority fee per compute unit ranks first, then the earlier arrival.
+pub fn get_bid_tie_break_key(
+    bid: &entities::Bid,
+    deprioritized_profiles: &HashSet<ProfileId>,
+) -> (bool, Reverse<u64>, OffsetDateTime) {
+    (
+        bid.profile_id
+            .is_some_and(|profile_id| deprioritized_profiles.contains(&profile_id)),
+        Reverse(bid.chain_data.get_compute_unit_price()),
+        bid.initiation_time,
+    )
+}
+
+impl AuctionPricingRule {
+    /// Returns the rule an auction was run with, given the pricing recorded with the auction.
+    pub fn from_recorded_pricing(
+        permission_key: &PermissionKeySvm,
+        pricing: &entities::AuctionPricing,
+    ) -> Self {
+        Self {
+            price:                 pricing.price,
+            reserve_prices:        Pubkey::try_from(&permission_key.0[33..])
+                .map(|permission_account| {
+                    HashMap::from([(permission_account, pricing.reserve_price)])
+                })
+                .unwrap_or_default(),
+            minimum_bid_increment: pricing.minimum_bid_increment,
+        }
+    }
+
+    pub fn get_reserve_price(&self, permission_key: &PermissionKeySvm) -> u64 {
+        Pubkey::try_from(&permission_key.0[33..])
+            .ok()
+            .and_then(|permission_account| self.reserve_prices.get(&permission_account))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Drops the bids below the reserve price and sorts the rest by priority, highest first.
+    /// A bid only outranks another bid on amount if it is higher by at least the minimum bid increment.
+    /// The bids within the increment of the best remaining bid are tied and ordered by [`get_bid_tie_break_key`].
+    pub fn rank_bids(
+        &self,
+        permission_key: &PermissionKeySvm,
+        bids: Vec<entities::Bid>,
+    ) -> Vec<entities::Bid> {
+        self.rank_bids_with_deprioritized(permission_key, bids, &HashSet::new())
+    }
+
+    /// Same as [`Self::rank_bids`], but the ties are broken against the bids of the deprioritized profiles.
+    pub fn rank_bids_with_deprioritized(
+        &self,
+        permission_key: &PermissionKeySvm,
+        bids: Vec<entities::Bid>,
+        deprioritized_profiles: &HashSet<ProfileId>,
+    ) -> Vec<entities::Bid> {
+        let reserve_price = self.get_reserve_price(permission_key);
+        let mut bids: Vec<_> = bids
+            .into_iter()
+            .filter(|bid| bid.amount >= reserve_price)
+            .collect();
+        bids.sort_by_key(|bid| Reverse(bid.amount));
+
+        // Without an increment only the bids of equal amounts are tied
+        let increment = self.minimum_bid_increment.max(1);
+        let mut ranked_bids = Vec::with_capacity(bids.len());
+        while let Some(best_amount) = bids.first().map(|bid| bid.amount) {
+            let tied_count = bids
+                .iter()
+                .take_while(|bid| best_amount - bid.amount < increment)
+                .count();
+            let mut tied_bids: Vec<_> = bids.drain(..tied_count).collect();
+            tied_bids.sort_by_cached_key(|bid| get_bid_tie_break_key(bid, deprioritized_profiles));
+            ranked_bids.extend(tied_bids);
+        }
+        ranked_bids
+    }
+
+    /// Returns the pricing of the auction given its bids, the chosen winners
+    /// and the profiles deprioritized when the bids were ranked.
+    pub fn get_auction_pricing(
+        &self,
+        permission_key: &PermissionKeySvm,
+        bids: &[entities::Bid],
+        winner_bids: &[entities::Bid],
+        deprioritized_profiles: &HashSet<ProfileId>,
+    ) -> entities::AuctionPricing {
+        let reserve_price = self.get_reserve_price(permission_key);
+        let clearing_price = match self.price {
+            PricingRule::FirstPrice => None,
+            PricingRule::SecondPrice => {
+                let highest_losing_bid = bids
+                    .iter()
+                    .filter(|bid| bid.amount >= reserve_price)
+                    .filter(|bid| !winner_bids.iter().any(|winner| winner.id == bid.id))
+                    .map(|bid| bid.amount)
+                    .max()
+                    .unwrap_or_default();
+                // Winners never pay more than their own bid
+                let lowest_winning_bid = winner_bids.iter().map(|bid| bid.amount).min();
+                lowest_winning_bid.map(|lowest| highest_losing_bid.max(reserve_price).min(lowest))
+            }
+        };
+        let mut deprioritized_profiles: Vec<_> = deprioritized_profiles.iter().copied().collect();
+        deprioritized_profiles.sort();
+        entities::AuctionPricing {
+            price: self.price,
+            reserve_price,
+            minimum_bid_increment: self.minimum_bid_increment,
+            clearing_price,
+            deprioritized_profiles,
+        }
+    }
+}
+
+impl Service {
+    pub fn get_auction_pricing_rule(&self) -> &AuctionPricingRule {
+        &self.config.chain_config.auction_pricing_rule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::auction::entities::{
+            BidChainDataSvm,
+            BidPaymentInstructionType,
+            BidStatusSvm,
+        },
+        solana_sdk::{
+            compute_budget::ComputeBudgetInstruction,
+            transaction::{
+                Transaction,
+                VersionedTransaction,
+            },
+        },
+        std::collections::HashMap,
+        time::{
+            Duration,
+            OffsetDateTime,
+        },
+        uuid::Uuid,
+    };
+
+    fn get_permission_key(permission_account: Pubkey) -> PermissionKeySvm {
+        let mut permission_key = [0; 65];
+        permission_key[33..].copy_from_slice(&permission_account.to_bytes());
+        PermissionKeySvm(permission_key)
+    }
+
+    fn make_bid(amount: u64, initiation_offset_ms: i64) -> entities::Bid {
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        entities::Bid {
+            id: Uuid::new_v4(),
+            chain_id: "solana".to_string(),
+            initiation_time: now + Duration::milliseconds(initiation_offset_ms),
+            profile_id: None,
+            opportunity_id: None,
+            creation_time: now,
+            conclusion_time: None,
+            submission_time: None,
+            amount,
+            status: BidStatusSvm::Pending,
+            chain_data: BidChainDataSvm {
+                transaction:                  VersionedTransaction::default(),
+                bid_payment_instruction_type: BidPaymentInstructionType::SubmitBid,
+                router:                       Pubkey::default(),
+                permission_account:           Pubkey::default(),
+                last_valid_block_height:      None,
+            },
+        }
+    }
+
+    fn make_bid_with_compute_unit_price(
+        amount: u64,
+        initiation_offset_ms: i64,
+        compute_unit_price: u64,
+    ) -> entities::Bid {
+        let mut bid = make_bid(amount, initiation_offset_ms);
+        bid.chain_data.transaction = VersionedTransaction::from(Transaction::new_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_price(
+                compute_unit_price,
+            )],
+            None,
+        ));
+        bid
+    }
+
+    fn amounts(bids: &[entities::Bid]) -> Vec<u64> {
+        bids.iter().map(|bid| bid.amount).collect()
+    }
+
+    #[test]
+    fn test_rank_bids_drops_bids_below_reserve_price() {
+        let permission_account = Pubkey::new_unique();
+        let rule = AuctionPricingRule {
+            reserve_prices: HashMap::from([(permission_account, 50)]),
+            ..Default::default()
+        };
+        let bids = vec![make_bid(10, 0), make_bid(100, 0), make_bid(50, 0)];
+
+        let ranked = rule.rank_bids(&get_permission_key(permission_account), bids.clone());
+        assert_eq!(amounts(&ranked), vec![100, 50]);
+
+        // The reserve price only applies to its own permission account
+        let ranked = rule.rank_bids(&get_permission_key(Pubkey::new_unique()), bids);
+        assert_eq!(amounts(&ranked), vec![100, 50, 10]);
+    }
+
+    #[test]
+    fn test_rank_bids_with_minimum_bid_increment() {
+        let rule = AuctionPricingRule {
+            minimum_bid_increment: 10,
+            ..Default::default()
+        };
+        // All the bids are within the increment of the best bid, so the earliest bid wins
+        let bids = vec![make_bid(105, 20), make_bid(101, 10), make_bid(110, 30)];
+
+        let ranked = rule.rank_bids(&get_permission_key(Pubkey::new_unique()), bids);
+        assert_eq!(amounts(&ranked), vec![101, 105, 110]);
+    }
+
+    #[test]
+    fn test_rank_bids_with_minimum_bid_increment_edges() {
+        let rule = AuctionPricingRule {
+            minimum_bid_increment: 100,
+            ..Default::default()
+        };
+        let permission_key = get_permission_key(Pubkey::new_unique());
+
+        // 199 is not higher than 100 by the increment, so the earlier bid wins
+        let ranked = rule.rank_bids(&permission_key, vec![make_bid(199, 10), make_bid(100, 0)]);
+        assert_eq!(amounts(&ranked), vec![100, 199]);
+
+        // 200 is higher than 100 by the increment
+        let ranked = rule.rank_bids(&permission_key, vec![make_bid(100, 0), make_bid(200, 10)]);
+        assert_eq!(amounts(&ranked), vec![200, 100]);
+
+        // The priority fee decides between 200 and 199,
+        // but it does not let a bid win against a bid that is higher by the increment
+        let bids = vec![
+            make_bid_with_compute_unit_price(199, 0, 20),
+            make_bid_with_compute_unit_price(200, 0, 10),
+            make_bid_with_compute_unit_price(100, 0, 1_000),
+        ];
+        let ranked = rule.rank_bids(&permission_key, bids);
+        assert_eq!(amounts(&ranked), vec![199, 200, 100]);
+
+        // The ties are relative to the best remaining bid, not to fixed buckets
+        let bids = vec![
+            make_bid(250, 30),
+            make_bid(160, 20),
+            make_bid(149, 10),
+            make_bid(60, 0),
+        ];
+        let ranked = rule.rank_bids(&permission_key, bids);
+        assert_eq!(amounts(&ranked), vec![160, 250, 60, 149]);
+    }
+
+    #[test]
+    fn test_rank_bids_tie_break() {
+        let rule = AuctionPricingRule::default();
+        let bids = vec![
+            make_bid_with_compute_unit_price(100, 0, 10),
+            make_bid_with_compute_unit_price(100, 10, 20),
+            make_bid_with_compute_unit_price(100, 5, 20),
+            make_bid(100, 0),
+            make_bid_with_compute_unit_price(90, 0, 1_000),
+        ];
+        let expected_ids = vec![bids[2].id, bids[1].id, bids[0].id, bids[3].id, bids[4].id];
+
+        let ranked = rule.rank_bids(&get_permission_key(Pubkey::new_unique()), bids);
+        assert_eq!(
+            ranked.iter().map(|bid| bid.id).collect::<Vec<_>>(),
+            expected_ids
+        );
+    }
+
+    #[test]
+    fn test_rank_bids_with_deprioritized() {
+        let rule = AuctionPricingRule::default();
+        let deprioritized_profile = Uuid::new_v4();
+        let mut bids = vec![
+            make_bid_with_compute_unit_price(100, 0, 20),
+            make_bid(100, 10),
+            make_bid(90, 20),
+        ];
+        bids[0].profile_id = Some(deprioritized_profile);
+        // The deprioritized bid only loses the tie, not to lower bids
+        let expected_ids = vec![bids[1].id, bids[0].id, bids[2].id];
+
+        let ranked = rule.rank_bids_with_deprioritized(
+            &get_permission_key(Pubkey::new_unique()),
+            bids,
+            &HashSet::from([deprioritized_profile]),
+        );
+        assert_eq!(
+            ranked.iter().map(|bid| bid.id).collect::<Vec<_>>(),
+            expected_ids
+        );
+    }
+
+    #[test]
+    fn test_get_auction_pricing_first_price() {
+        let permission_account = Pubkey::new_unique();
+        let rule = AuctionPricingRule {
+            reserve_prices: HashMap::from([(permission_account, 20)]),
+            minimum_bid_increment: 5,
+            ..Default::default()
+        };
+        let bids = vec![make_bid(100, 0), make_bid(60, 0)];
+
+        let deprioritized_profile = Uuid::new_v4();
+        let pricing = rule.get_auction_pricing(
+            &get_permission_key(permission_account),
+            &bids,
+            &bids[..1],
+            &HashSet::from([deprioritized_profile]),
+        );
+        assert_eq!(
+            pricing,
+            entities::AuctionPricing {
+                price:                  PricingRule::FirstPrice,
+                reserve_price:          20,
+                minimum_bid_increment:  5,
+                clearing_price:         None,
+                deprioritized_profiles: vec![deprioritized_profile],
+            }
+        );
+
+        assert_eq!(pricing.get_refund(100), 0);
+
+        let pricing = rule.get_auction_pricing(
+            &get_permission_key(Pubkey::new_unique()),
+            &bids,
+            &bids[..1],
+            &HashSet::new(),
+        );
+        assert_eq!(pricing.reserve_price, 0);
+    }
+
+    #[test]
+    fn test_get_auction_pricing_second_price() {
+        let permission_account = Pubkey::new_unique();
+        let permission_key = get_permission_key(permission_account);
+        let rule = AuctionPricingRule {
+            price: PricingRule::SecondPrice,
+            reserve_prices: HashMap::from([(permission_account, 20)]),
+            ..Default::default()
+        };
+        let bids = vec![
+            make_bid(100, 0),
+            make_bid(60, 0),
+            make_bid(30, 0),
+            make_bid(10, 0),
+        ];
+
+        let pricing = rule.get_auction_pricing(&permission_key, &bids, &bids[..1], &HashSet::new());
+        assert_eq!(pricing.clearing_price, Some(60));
+        assert_eq!(pricing.reserve_price, 20);
+        assert_eq!(pricing.get_refund(100), 40);
+
+        // With several winners the clearing price is the highest losing bid
+        let pricing = rule.get_auction_pricing(&permission_key, &bids, &bids[..2], &HashSet::new());
+        assert_eq!(pricing.clearing_price, Some(30));
+
+        // Bids below the reserve price do not set the clearing price
+        let pricing = rule.get_auction_pricing(&permission_key, &bids, &bids[..3], &HashSet::new());
+        assert_eq!(pricing.clearing_price, Some(20));
+
+        // Winners never pay more than their own bid, even if a higher bid lost
+        let pricing =
+            rule.get_auction_pricing(&permission_key, &bids, &bids[1..2], &HashSet::new());
+        assert_eq!(pricing.clearing_price, Some(60));
+
+        // Without winners there is nothing to charge
+        let pricing = rule.get_auction_pricing(&permission_key, &bids, &[], &HashSet::new());
+        assert_eq!(pricing.clearing_price, None);
+    }
+
+    #[test]
+    fn test_from_recorded_pricing() {
+        let permission_account = Pubkey::new_unique();
+        let permission_key = get_permission_key(permission_account);
+        let rule = AuctionPricingRule {
+            price:                 PricingRule::SecondPrice,
+            reserve_prices:        HashMap::from([
+                (permission_account, 20),
+                (Pubkey::new_unique(), 50),
+            ]),
+            minimum_bid_increment: 5,
+        };
+        let bids = vec![make_bid(100, 0), make_bid(60, 0)];
+        let pricing = rule.get_auction_pricing(&permission_key, &bids, &bids[..1], &HashSet::new());
+
+        let recorded_rule = AuctionPricingRule::from_recorded_pricing(&permission_key, &pricing);
+        assert_eq!(
+            recorded_rule,
+            AuctionPricingRule {
+                price:                 PricingRule::SecondPrice,
+                reserve_prices:        HashMap::from([(permission_account, 20)]),
+                minimum_bid_increment: 5,
+            }
+        );
+        assert_eq!(
+            recorded_rule.get_auction_pricing(&permission_key, &bids, &bids[..1], &HashSet::new()),
+            pricing
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use {
+    super::Service,
+    crate::{
+        api::RestError,
+        auction::entities::{
+            self,
+            BidPaymentInstructionType,
+        },
+        config::BidRateLimitsConfig,
+        kernel::{
+            entities::PermissionKeySvm,
+            rate_limiter::RateLimiter,
+        },
+        models::ProfileId,
+        opportunity::{
+            entities::OpportunitySvm,
+            service::get_opportunities::GetLiveOpportunityByIdInput,
+        },
+        per_metrics::BID_RATE_LIMITED_TOTAL,
+    },
+    axum_prometheus::metrics,
+    std::time::Duration,
+};
+
+/// Token bucket rate limiters for the bids of the chain
+pub struct BidRateLimiters {
+    pub profile:        Option<RateLimiter<ProfileId>>,
+    /// Each profile has its own buckets for the permission keys, so a searcher can not use up the limit of the others.
+    /// The bids without a profile share one bucket per permission key.
+    pub permission_key: Option<RateLimiter<(Option<ProfileId>, PermissionKeySvm)>>,
+}
+
+impl BidRateLimiters {
+    pub fn new(config: &BidRateLimitsConfig) -> Self {
+        Self {
+            profile:        config.profile.as_ref().map(RateLimiter::new),
+            permission_key: config.permission_key.as_ref().map(RateLimiter::new),
+        }
+    }
+}
+
+pub struct CheckBidRateLimitsInput {
+    pub bid_create: entities::BidCreate,
+}
+
+impl Service {
+    fn record_rate_limited(&self, limit: &str, retry_after: Duration) -> RestError {
+        metrics::counter!(
+            BID_RATE_LIMITED_TOTAL,
+            &[
+                ("chain_id", self.config.chain_id.clone()),
+                ("limit", limit.to_string()),
+            ]
+        )
+        .increment(1);
+        RestError::RateLimited { retry_after }
+    }
+
+    /// Returns the permission key the bid is for, without verifying the bid.
+    /// None if the permission key can not be found, in which case the verification rejects the bid.
+    async fn get_bid_create_permission_key(
+        &self,
+        bid_create: &entities::BidCreate,
+    ) -> Option<PermissionKeySvm> {
+        match &bid_create.chain_data {
+            entities::BidChainDataCreateSvm::OnChain(bid_data) => {
+                let transaction_data = self
+                    .get_bid_transaction_data_submit_bid(bid_data.transaction.clone())
+                    .await
+                    .ok()?;
+                Some(OpportunitySvm::get_permission_key(
+                    BidPaymentInstructionType::SubmitBid,
+                    transaction_data.accounts.router,
+                    transaction_data.accounts.permission_account,
+                ))
+            }
+            entities::BidChainDataCreateSvm::Swap(bid_data) => self
+                .opportunity_service
+                .get_live_opportunity_by_id(GetLiveOpportunityByIdInput {
+                    opportunity_id: bid_data.opportunity_id,
+                })
+                .await
+                .map(|opportunity| opportunity.permission_key),
+        }
+    }
+
+    /// Takes a token from the rate limits of the searcher profile and of the permission key of the bid.
+    /// Both limits are checked before a token is taken from either, so a limited bid does not use up any limit.
+    /// This should be called before the verification of the bid, to not spend resources on limited bids.
+    pub async fn check_bid_rate_limits(
+        &self,
+        input: CheckBidRateLimitsInput,
+    ) -> Result<(), RestError> {
+        let limiters = &self.config.chain_config.bid_rate_limiters;
+        let profile_id = input.bid_create.profile.as_ref().map(|profile| profile.id);
+        let permission_key = match &limiters.permission_key {
+            Some(_) => self
+                .get_bid_create_permission_key(&input.bid_create)
+                .await
+                .map(|permission_key| (profile_id, permission_key)),
+            None => None,
+        };
+
+        // The limiters are always locked in the same order
+        let mut profile_guard = limiters
+            .profile
+            .as_ref()
+            .zip(profile_id)
+            .map(|(limiter, profile_id)| (limiter.lock(), profile_id));
+        let mut permission_key_guard = limiters
+            .permission_key
+            .as_ref()
+            .zip(permission_key)
+            .map(|(limiter, key)| (limiter.lock(), key));
+        if let Some((guard, profile_id)) = &mut profile_guard {
+            guard
+                .check(profile_id)
+                .map_err(|retry_after| self.record_rate_limited("profile", retry_after))?;
+        }
+        if let Some((guard, key)) = &mut permission_key_guard {
+            guard
+                .check(key)
+                .map_err(|retry_after| self.record_rate_limited("permission_key", retry_after))?;
+        }
+        if let Some((guard, profile_id)) = &mut profile_guard {
+            guard.take(profile_id);
+        }
+        if let Some((guard, key)) = &mut permission_key_guard {
+            guard.take(key);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction::{
+                entities::BidChainDataSwapCreateSvm,
+                repository::MockDatabase,
+            },
+            config::RateLimitConfig,
+            kernel::traced_sender_svm::tests::MockRpcClient,
+            models,
+            opportunity::{
+                entities::{
+                    OpportunitySvmProgram,
+                    OpportunitySvmProgramSwap,
+                    TokenAmountSvm,
+                },
+                service::MockService as MockOpportunityService,
+            },
+        },
+        axum::{
+            http::{
+                header,
+                StatusCode,
+            },
+            response::IntoResponse,
+        },
+        solana_sdk::pubkey::Pubkey,
+        std::sync::Arc,
+        time::{
+            OffsetDateTime,
+            PrimitiveDateTime,
+        },
+        uuid::Uuid,
+    };
+
+    #[tokio::test]
+    async fn test_check_bid_rate_limits_rejects_with_retry_after() {
+        let mut service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            MockDatabase::default(),
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+        Arc::get_mut(&mut service.0)
+            .expect("Only one reference should exist at setup")
+            .config
+            .chain_config
+            .bid_rate_limiters = BidRateLimiters::new(&BidRateLimitsConfig {
+            profile:        Some(RateLimitConfig {
+                capacity:          1,
+                refill_per_second: 0.5,
+            }),
+            permission_key: None,
+        });
+
+        let bid_create = entities::BidCreate {
+            chain_id:        "solana".to_string(),
+            initiation_time: OffsetDateTime::now_utc(),
+            profile:         Some(models::Profile {
+                id:         Uuid::new_v4(),
+                name:       "searcher".to_string(),
+                email:      models::EmailAddress::try_from("searcher@example.com".to_string())
+                    .unwrap(),
+                role:       models::ProfileRole::Searcher,
+                created_at: PrimitiveDateTime::MIN,
+                updated_at: PrimitiveDateTime::MIN,
+            }),
+            chain_data:      entities::BidChainDataCreateSvm::Swap(BidChainDataSwapCreateSvm {
+                opportunity_id: Uuid::new_v4(),
+                transaction:    Default::default(),
+            }),
+        };
+        service
+            .check_bid_rate_limits(CheckBidRateLimitsInput {
+                bid_create: bid_create.clone(),
+            })
+            .await
+            .expect("The first bid should not be limited");
+
+        let error = service
+            .check_bid_rate_limits(CheckBidRateLimitsInput { bid_create })
+            .await
+            .expect_err("The second bid should be limited");
+        assert!(
+            matches!(error, RestError::RateLimited { retry_after } if retry_after <= Duration::from_secs(2))
+        );
+
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "2");
+    }
+
+    fn make_opportunity() -> OpportunitySvm {
+        let (router, permission_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        OpportunitySvm {
+            id: Uuid::new_v4(),
+            permission_key: OpportunitySvm::get_permission_key(
+                BidPaymentInstructionType::Swap,
+                router,
+                permission_account,
+            ),
+            chain_id: "solana".to_string(),
+            sell_tokens: vec![TokenAmountSvm {
+                token:  Pubkey::new_unique(),
+                amount: 0,
+            }],
+            buy_tokens: vec![TokenAmountSvm {
+                token:  Pubkey::new_unique(),
+                amount: 100,
+            }],
+            creation_time: OffsetDateTime::now_utc(),
+            refresh_time: OffsetDateTime::now_utc(),
+            router,
+            permission_account,
+            program: OpportunitySvmProgram::Swap(
+                OpportunitySvmProgramSwap::default_test_with_user_wallet_address(
+                    Pubkey::new_unique(),
+                ),
+            ),
+            profile_id: None,
+        }
+    }
+
+    fn make_bid_create(profile_id: ProfileId, opportunity: &OpportunitySvm) -> entities::BidCreate {
+        entities::BidCreate {
+            chain_id:        "solana".to_string(),
+            initiation_time: OffsetDateTime::now_utc(),
+            profile:         Some(models::Profile {
+                id:         profile_id,
+                name:       "searcher".to_string(),
+                email:      models::EmailAddress::try_from("searcher@example.com".to_string())
+                    .unwrap(),
+                role:       models::ProfileRole::Searcher,
+                created_at: PrimitiveDateTime::MIN,
+                updated_at: PrimitiveDateTime::MIN,
+            }),
+            chain_data:      entities::BidChainDataCreateSvm::Swap(BidChainDataSwapCreateSvm {
+                opportunity_id: opportunity.id,
+                transaction:    Default::default(),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_bid_rate_limits_with_profile_and_permission_key() {
+        let opportunities = vec![make_opportunity(), make_opportunity(), make_opportunity()];
+        let mut opportunity_service = MockOpportunityService::default();
+        let opportunities_cloned = opportunities.clone();
+        opportunity_service
+            .expect_get_live_opportunity_by_id()
+            .returning(move |input| {
+                opportunities_cloned
+                    .iter()
+                    .find(|opportunity| opportunity.id == input.opportunity_id)
+                    .cloned()
+            });
+        let mut service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            MockDatabase::default(),
+            opportunity_service,
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+        // The limits are not refilled during the test
+        Arc::get_mut(&mut service.0)
+            .expect("Only one reference should exist at setup")
+            .config
+            .chain_config
+            .bid_rate_limiters = BidRateLimiters::new(&BidRateLimitsConfig {
+            profile:        Some(RateLimitConfig {
+                capacity:          2,
+                refill_per_second: 0.001,
+            }),
+            permission_key: Some(RateLimitConfig {
+                capacity:          1,
+                refill_per_second: 0.001,
+            }),
+        });
+        let (searcher, other_searcher) = (Uuid::new_v4(), Uuid::new_v4());
+        let check = |profile_id: ProfileId, opportunity: &OpportunitySvm| {
+            service.check_bid_rate_limits(CheckBidRateLimitsInput {
+                bid_create: make_bid_create(profile_id, opportunity),
+            })
+        };
+
+        assert!(check(searcher, &opportunities[0]).await.is_ok());
+        assert!(check(searcher, &opportunities[0]).await.is_err());
+        // The bid limited by the permission key did not take a token from the profile limit
+        assert!(check(searcher, &opportunities[1]).await.is_ok());
+        assert!(check(searcher, &opportunities[2]).await.is_err());
+        // The permission key limit of a searcher does not limit the other searchers
+        assert!(check(other_searcher, &opportunities[0]).await.is_ok());
+    }
+}
@@ -1,6 +1,7 @@
 use {
     super::{
         auction_manager::AuctionManager,
+        refund_second_price::RefundSecondPriceInput,
         update_bid_status::UpdateBidStatusInput,
         Service,
     },
@@ -32,7 +33,7 @@ impl Service {
         );
         tracing::Span::current().record("auction_id", input.auction.id.to_string());
         tracing::Span::current().record("bid_statuses", format!("{:?}", input.bid_statuses));
-        join_all(input.bid_statuses.into_iter().map(|(status, bid)| {
+        let results = join_all(input.bid_statuses.iter().map(|(status, bid)| {
             self.update_bid_status(UpdateBidStatusInput {
                 bid:        bid.clone(),
                 new_status: status.clone(),
@@ -40,6 +41,23 @@ impl Service {
         }))
         .await;
 
+        // Only refund the bids this call concluded as won, so that a winner is never refunded twice
+        for ((status, bid), result) in input.bid_statuses.into_iter().zip(results) {
+            if matches!(status, entities::BidStatusSvm::Won { .. }) && matches!(result, Ok(true)) {
+                self.task_tracker.spawn({
+                    let (service, auction) = (self.clone(), input.auction.clone());
+                    async move {
+                        if let Err(e) = service
+                            .refund_second_price(RefundSecondPriceInput { auction, bid })
+                            .await
+                        {
+                            tracing::error!(error = ?e, "Failed to refund second price auction winner");
+                        }
+                    }
+                });
+            }
+        }
+
         // Refetch the auction from the in-memory store to check if all bids are finalized
         if let Some(auction) = self.repo.get_in_memory_auction_by_id(input.auction.id) {
             if auction.bids.iter().all(|bid| bid.status.is_concluded()) {
@@ -87,13 +105,20 @@ impl Service {
                 )
                 .await?;
 
+            let bid_statuses = self
+                .resolve_concluded_quote_bid_statuses(
+                    auction.id,
+                    bid_statuses
+                        .into_iter()
+                        .zip(bids)
+                        .filter_map(|(status, bid)| status.map(|status| (status, bid)))
+                        .collect(),
+                )
+                .await;
+
             self.conclude_auction_with_statuses(ConcludeAuctionWithStatusesInput {
                 auction,
-                bid_statuses: bid_statuses
-                    .into_iter()
-                    .zip(bids)
-                    .filter_map(|(status, bid)| status.map(|status| (status, bid)))
-                    .collect(),
+                bid_statuses,
             })
             .await?;
         } else if Self::is_auction_expired(&auction) {
@@ -33,7 +33,7 @@ impl Service {
         })
     }
 
-    fn extract_express_relay_instruction(
+    pub fn extract_express_relay_instruction(
         &self,
         transaction: VersionedTransaction,
         instruction_type: entities::BidPaymentInstructionType,
@@ -45,6 +45,7 @@ impl Service {
             entities::BidPaymentInstructionType::Swap => vec![
                 express_relay_svm::instruction::Swap::DISCRIMINATOR,
                 express_relay_svm::instruction::SwapV2::DISCRIMINATOR,
+                express_relay_svm::instruction::SwapV3::DISCRIMINATOR,
             ],
         };
         let instructions = Self::extract_program_instructions(
@@ -159,11 +160,25 @@ impl Service {
         })
     }
 
+    /// Extracts the swap arguments and the minimum searcher amount of the swap instruction.
     pub async fn extract_swap_data(
         &self,
         instruction: &CompiledInstruction,
-    ) -> Result<express_relay_svm::SwapV2Args, RestError> {
+    ) -> Result<(express_relay_svm::SwapV2Args, u64), RestError> {
         if instruction
+            .data
+            .starts_with(express_relay_svm::instruction::SwapV3::DISCRIMINATOR)
+        {
+            let discriminator = express_relay_svm::instruction::SwapV3::DISCRIMINATOR;
+            let swap_args = express_relay_svm::SwapV3Args::try_from_slice(
+                &instruction.data.as_slice()[discriminator.len()..],
+            )
+            .map_err(|e| {
+                RestError::BadParameters(format!("Invalid swap instruction data: {}", e))
+            })?;
+            return Ok((swap_args.convert_to_v2(), swap_args.minimum_amount_searcher));
+        }
+        let swap_args = if instruction
             .data
             .starts_with(express_relay_svm::instruction::Swap::DISCRIMINATOR)
         {
@@ -180,14 +195,17 @@ impl Service {
             .map_err(|e| {
                 RestError::BadParameters(format!("Invalid swap instruction data: {}", e))
             })?;
-            Ok(swap_args.convert_to_v2(express_relay_metadata.swap_platform_fee_bps))
+            swap_args.convert_to_v2(express_relay_metadata.swap_platform_fee_bps)
         } else {
             let discriminator = express_relay_svm::instruction::SwapV2::DISCRIMINATOR;
             express_relay_svm::SwapV2Args::try_from_slice(
                 &instruction.data.as_slice()[discriminator.len()..],
             )
-            .map_err(|e| RestError::BadParameters(format!("Invalid swap instruction data: {}", e)))
-        }
+            .map_err(|e| {
+                RestError::BadParameters(format!("Invalid swap instruction data: {}", e))
+            })?
+        };
+        Ok((swap_args, swap_args.amount_searcher))
     }
 
     #[tracing::instrument(skip_all)]
@@ -199,9 +217,11 @@ impl Service {
             transaction.clone(),
             entities::BidPaymentInstructionType::Swap,
         )?;
+        let (data, minimum_amount_searcher) = self.extract_swap_data(&instruction).await?;
         Ok(entities::BidTransactionDataSwap {
-            data:                            self.extract_swap_data(&instruction).await?,
-            accounts:                        self
+            data,
+            minimum_amount_searcher,
+            accounts: self
                 .extract_swap_accounts(&transaction, &instruction)
                 .await?,
             express_relay_instruction_index: index,
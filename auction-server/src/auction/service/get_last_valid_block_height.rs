@@ -0,0 +1,192 @@
+use {
+    super::Service,
+    solana_sdk::{
+        clock::MAX_PROCESSING_AGE,
+        commitment_config::CommitmentConfig,
+        transaction::VersionedTransaction,
+    },
+};
+
+impl Service {
+    /// Returns the last block height at which the transaction can land on chain.
+    ///
+    /// The value is exact if the recent blockhash of the transaction is one of the blockhashes polled by the watcher loop.
+    /// Otherwise the rpc is asked whether the blockhash is still valid: a valid blockhash can not be used
+    /// after `MAX_PROCESSING_AGE` blocks from the current block height, so this bounds its last valid block height.
+    /// Returns None if the rpc can not resolve the blockhash, in which case the bid expires based on its age instead.
+    #[tracing::instrument(skip_all, fields(blockhash))]
+    pub async fn get_last_valid_block_height(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Option<u64> {
+        let blockhash = transaction.message.recent_blockhash();
+        if let Some(last_valid_block_height) =
+            self.repo.get_last_valid_block_height(blockhash).await
+        {
+            return Some(last_valid_block_height);
+        }
+
+        tracing::Span::current().record("blockhash", blockhash.to_string());
+        let client = &self.config.chain_config.client;
+        // The blockhash can be newer than the polled finalized blockhashes, so the processed commitment is used
+        match client
+            .is_blockhash_valid(blockhash, CommitmentConfig::processed())
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                tracing::debug!("Blockhash is not valid according to the rpc");
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!(error = ?e, "Failed to check if the blockhash is valid");
+                return None;
+            }
+        }
+        match client
+            .get_block_height_with_commitment(CommitmentConfig::processed())
+            .await
+        {
+            Ok(block_height) => Some(block_height + MAX_PROCESSING_AGE as u64),
+            Err(e) => {
+                tracing::warn!(error = ?e, "Failed to get the block height");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction::repository::MockDatabase,
+            kernel::traced_sender_svm::tests::MockRpcClient,
+            opportunity::service::MockService as MockOpportunityService,
+        },
+        serde_json::json,
+        solana_client::rpc_request::RpcRequest,
+        solana_sdk::{
+            hash::Hash,
+            message::{
+                v0,
+                VersionedMessage,
+            },
+            signature::Signature,
+        },
+    };
+
+    fn get_transaction(recent_blockhash: Hash) -> VersionedTransaction {
+        VersionedTransaction {
+            signatures: vec![Signature::new_unique()],
+            message:    VersionedMessage::V0(v0::Message {
+                recent_blockhash,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn get_service_with_rpc_client(rpc_client: MockRpcClient) -> Service {
+        Service::new_with_mocks_svm(
+            "solana".to_string(),
+            MockDatabase::default(),
+            MockOpportunityService::default(),
+            rpc_client,
+            MockRpcClient::default(),
+        )
+    }
+
+    /// Returns an rpc client that reports whether the blockhashes are valid and the current block height.
+    fn get_rpc_client(is_blockhash_valid: bool, block_height: u64) -> MockRpcClient {
+        let mut rpc_client = MockRpcClient::new();
+        rpc_client
+            .expect_send()
+            .withf(|request, _| *request == RpcRequest::IsBlockhashValid)
+            .times(1)
+            .returning(move |_, _| {
+                Ok(json!({
+                    "context": { "slot": 1 },
+                    "value": is_blockhash_valid,
+                }))
+            });
+        rpc_client
+            .expect_send()
+            .withf(|request, _| *request == RpcRequest::GetBlockHeight)
+            .times(usize::from(is_blockhash_valid))
+            .returning(move |_, _| Ok(json!(block_height)));
+        rpc_client
+            .expect_url()
+            .returning(|| "https://test".to_string());
+        rpc_client
+    }
+
+    #[tokio::test]
+    async fn test_get_last_valid_block_height() {
+        let service = get_service_with_rpc_client(get_rpc_client(false, 0));
+        let (blockhash, unknown_blockhash) = (Hash::new_unique(), Hash::new_unique());
+        service.repo.add_recent_blockhash(blockhash, 150).await;
+        // Polling the same blockhash again does not change its last valid block height
+        service.repo.add_recent_blockhash(blockhash, 160).await;
+
+        assert_eq!(
+            service
+                .get_last_valid_block_height(&get_transaction(blockhash))
+                .await,
+            Some(150)
+        );
+        assert_eq!(
+            service
+                .get_last_valid_block_height(&get_transaction(unknown_blockhash))
+                .await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_last_valid_block_height_of_pruned_blockhash() {
+        let service = get_service_with_rpc_client(get_rpc_client(false, 0));
+        let blockhashes: Vec<_> = (0..100).map(|_| Hash::new_unique()).collect();
+        for (i, blockhash) in blockhashes.iter().enumerate() {
+            service
+                .repo
+                .add_recent_blockhash(*blockhash, 150 + i as u64)
+                .await;
+        }
+
+        // Only the most recent blockhashes are kept
+        assert_eq!(
+            service
+                .get_last_valid_block_height(&get_transaction(blockhashes[0]))
+                .await,
+            None
+        );
+        assert_eq!(
+            service
+                .get_last_valid_block_height(&get_transaction(blockhashes[99]))
+                .await,
+            Some(249)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_last_valid_block_height_outside_window() {
+        let service = get_service_with_rpc_client(get_rpc_client(true, 1_000));
+        let blockhashes: Vec<_> = (0..100).map(|_| Hash::new_unique()).collect();
+        for (i, blockhash) in blockhashes.iter().enumerate() {
+            service
+                .repo
+                .add_recent_blockhash(*blockhash, 150 + i as u64)
+                .await;
+        }
+
+        // The pruned blockhash is still valid according to the rpc,
+        // so it can not be used after MAX_PROCESSING_AGE blocks from the current block height
+        assert_eq!(
+            service
+                .get_last_valid_block_height(&get_transaction(blockhashes[0]))
+                .await,
+            Some(1_150)
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use {
+    super::Service,
+    crate::{
+        api::RestError,
+        auction::{
+            api::get_quote_status_update,
+            entities,
+        },
+    },
+    express_relay_api_types::quote::QuoteStatusWithReferenceId,
+};
+
+pub struct GetQuoteStatusInput {
+    pub reference_id: entities::AuctionId,
+}
+
+impl Service {
+    /// Returns the current status of the quote with the reference id, or `None` if the quote is not found on the chain.
+    #[tracing::instrument(skip_all, err(level = tracing::Level::TRACE))]
+    pub async fn get_quote_status(
+        &self,
+        input: GetQuoteStatusInput,
+    ) -> Result<Option<QuoteStatusWithReferenceId>, RestError> {
+        let bids = match self.repo.get_in_memory_auction_by_id(input.reference_id) {
+            Some(auction) => auction.bids,
+            // The auction is already concluded
+            None => self.repo.get_auction_bids(input.reference_id).await?,
+        };
+        Ok(bids
+            .iter()
+            .find_map(|bid| get_quote_status_update(bid, &bid.status)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction::repository::MockDatabase,
+            kernel::{
+                entities::PermissionKeySvm,
+                traced_sender_svm::tests::MockRpcClient,
+            },
+            opportunity::service::MockService as MockOpportunityService,
+        },
+        express_relay_api_types::quote::QuoteStatus,
+        solana_sdk::{
+            pubkey::Pubkey,
+            signature::Signature,
+            transaction::VersionedTransaction,
+        },
+        time::OffsetDateTime,
+        uuid::Uuid,
+    };
+
+    fn make_bid(signature: Signature, status: entities::BidStatusSvm) -> entities::Bid {
+        entities::Bid {
+            id: Uuid::new_v4(),
+            chain_id: "solana".to_string(),
+            initiation_time: OffsetDateTime::now_utc(),
+            profile_id: None,
+            opportunity_id: None,
+            creation_time: OffsetDateTime::now_utc(),
+            conclusion_time: None,
+            submission_time: None,
+            amount: 100,
+            status,
+            chain_data: entities::BidChainDataSvm {
+                transaction:                  VersionedTransaction {
+                    signatures: vec![signature],
+                    message:    Default::default(),
+                },
+                bid_payment_instruction_type: entities::BidPaymentInstructionType::Swap,
+                router:                       Pubkey::new_unique(),
+                permission_account:           Pubkey::new_unique(),
+                last_valid_block_height:      None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_quote_status_of_in_memory_auction() {
+        let auction_id = Uuid::new_v4();
+        let signature = Signature::new_unique();
+        let status_auction = entities::BidStatusAuction {
+            id:      auction_id,
+            tx_hash: signature,
+        };
+        let loser_bid = make_bid(
+            Signature::new_unique(),
+            entities::BidStatusSvm::Lost {
+                auction: Some(status_auction.clone()),
+            },
+        );
+        let quote_bid = make_bid(
+            signature,
+            entities::BidStatusSvm::AwaitingSignature {
+                auction: status_auction,
+            },
+        );
+        let service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            MockDatabase::default(),
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+        service.repo.in_memory_store.auctions.insert(
+            auction_id,
+            entities::Auction {
+                id:                  auction_id,
+                chain_id:            "solana".to_string(),
+                permission_key:      PermissionKeySvm([0; 65]),
+                creation_time:       OffsetDateTime::now_utc(),
+                conclusion_time:     None,
+                bid_collection_time: OffsetDateTime::now_utc(),
+                submission_time:     None,
+                tx_hash:             Some(signature),
+                pricing:             None,
+                bids:                vec![loser_bid, quote_bid],
+            },
+        );
+
+        let status = service
+            .get_quote_status(GetQuoteStatusInput {
+                reference_id: auction_id,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            Some(QuoteStatusWithReferenceId {
+                reference_id: auction_id,
+                chain_id:     "solana".to_string(),
+                status:       QuoteStatus::AwaitingSignature,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_quote_status_of_unknown_auction() {
+        let auction_id = Uuid::new_v4();
+        let mut db = MockDatabase::default();
+        db.expect_get_auction_bids()
+            .withf(move |id| *id == auction_id)
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        let service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            db,
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+
+        let status = service
+            .get_quote_status(GetQuoteStatusInput {
+                reference_id: auction_id,
+            })
+            .await
+            .unwrap();
+        assert_eq!(status, None);
+    }
+}
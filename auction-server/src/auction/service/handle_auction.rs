@@ -30,7 +30,7 @@ impl Service {
     #[tracing::instrument(skip_all, fields(auction_id, bid_ids, winner_bid_ids), err(level = tracing::Level::TRACE))]
     async fn submit_auction<'a>(
         &self,
-        auction: entities::Auction,
+        mut auction: entities::Auction,
         _auction_mutex_guard: MutexGuard<'a, ()>,
     ) -> anyhow::Result<()> {
         tracing::Span::current().record("auction_id", auction.id.to_string());
@@ -48,7 +48,12 @@ impl Service {
             return Ok(());
         }
 
-        let winner_bids = self.get_winner_bids(&auction).await?;
+        // The deprioritized profiles are recorded with the auction pricing,
+        // so the auction can be replayed with the reputations of the time
+        let deprioritized_profiles = self.get_deprioritized_profiles(&auction.bids).await;
+        let winner_bids = self
+            .get_winner_bids(&auction, &deprioritized_profiles)
+            .await?;
         tracing::Span::current().record(
             "winner_bid_ids",
             tracing::field::display(entities::BidContainerTracing(&winner_bids)),
@@ -64,6 +69,12 @@ impl Service {
             return Ok(());
         }
 
+        auction.pricing = Some(self.get_auction_pricing_rule().get_auction_pricing(
+            &permission_key,
+            &auction.bids,
+            &winner_bids,
+            &deprioritized_profiles,
+        ));
         let auction = self
             .add_auction(AddAuctionInput { auction })
             .await
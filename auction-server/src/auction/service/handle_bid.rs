@@ -1,5 +1,9 @@
 use {
     super::{
+        relayer_spend::{
+            ReleaseRelayerSpendInput,
+            ReserveRelayerSpendInput,
+        },
         verification::{
             Verification,
             VerifyBidInput,
@@ -26,15 +30,35 @@ impl Service {
         if let Some(profile) = &input.bid_create.profile {
             tracing::Span::current().record("profile_name", &profile.name);
         }
-        let (chain_data, amount) = self
+        let (mut chain_data, amount, _) = self
             .verify_bid(VerifyBidInput {
                 bid_create: input.bid_create.clone(),
             })
             .await?;
-        let bid = self
+        chain_data.last_valid_block_height = self
+            .get_last_valid_block_height(&chain_data.transaction)
+            .await;
+        let profile_id = input.bid_create.profile.as_ref().map(|profile| profile.id);
+        self.reserve_relayer_spend(ReserveRelayerSpendInput {
+            profile_id,
+            chain_data: chain_data.clone(),
+        })
+        .await?;
+        let bid = match self
             .repo
             .add_bid(input.bid_create, &chain_data, &amount)
-            .await?;
+            .await
+        {
+            Ok(bid) => bid,
+            Err(e) => {
+                self.release_relayer_spend(ReleaseRelayerSpendInput {
+                    profile_id,
+                    chain_data,
+                })
+                .await;
+                return Err(e);
+            }
+        };
         tracing::Span::current().record("bid_id", bid.id.to_string());
         Ok(bid)
     }
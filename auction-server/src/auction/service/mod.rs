@@ -9,10 +9,25 @@ use {
     },
     crate::{
         api::ws::UpdateEvent,
-        auction::service::simulator::Simulator,
+        auction::service::{
+            check_bid_rate_limits::BidRateLimiters,
+            signature_status_poller::SignatureStatusPoller,
+            simulator::Simulator,
+            transaction_sender::TransactionBroadcaster,
+        },
+        config::{
+            AuctionPricingRule,
+            AuctionTriggerMode,
+            InstructionPolicy,
+            RelayerSpendLimitsConfig,
+            SearcherReputationConfig,
+            WinnerSelectionMode,
+        },
         kernel::{
+            block_engine::BlockEngineClient,
             db::DB,
             entities::ChainId,
+            lookup_table_resolver::LookupTableResolver,
         },
         state::Store,
     },
@@ -38,24 +53,38 @@ use {
 
 pub mod add_auction;
 pub mod auction_manager;
+pub mod auction_pricing_rule;
 pub mod cancel_bid;
+pub mod check_bid_rate_limits;
 pub mod conclude_auction;
 pub mod get_auction_by_id;
 pub mod get_bid;
 pub mod get_bid_transaction_data;
 pub mod get_bids;
 pub mod get_express_relay_program_id;
+pub mod get_last_valid_block_height;
 pub mod get_pending_bids;
 pub mod get_permission_keys_for_auction;
+pub mod get_quote_status;
 pub mod handle_auction;
 pub mod handle_auctions;
 pub mod handle_bid;
 pub mod optimize_bids;
+pub mod quote_fallback_auctions;
+pub mod refund_second_price;
+pub mod relayer_spend;
+pub mod replay;
+pub mod restore_pending_transactions;
+pub mod searcher_reputation;
+pub mod signature_status_poller;
 pub mod simulator;
 pub mod submit_quote;
+pub mod subscribed_account_cache;
+pub mod transaction_sender;
 pub mod update_bid_status;
 pub mod update_recent_prioritization_fee;
 pub mod verification;
+pub mod verify_bid;
 pub mod workers;
 
 pub struct SwapInstructionAccountPositions {
@@ -71,6 +100,7 @@ pub struct SwapInstructionAccountPositions {
 pub struct SubmitBidInstructionAccountPositions {
     pub permission_account: usize,
     pub router_account:     usize,
+    pub searcher_account:   usize,
 }
 
 pub struct ExpressRelaySvm {
@@ -80,14 +110,32 @@ pub struct ExpressRelaySvm {
     pub swap_instruction_account_positions:       SwapInstructionAccountPositions,
 }
 
+/// Submission of the winner bids as atomic bundles to a block engine
+pub struct BundleSubmissionSvm {
+    pub block_engine: BlockEngineClient,
+    /// The tip of each bundle is paid by the relayer to one of these accounts chosen at random
+    pub tip_accounts: Vec<Pubkey>,
+    pub tip_lamports: u64,
+}
+
 pub struct ConfigSvm {
     pub client:                        RpcClient,
     pub express_relay:                 ExpressRelaySvm,
     pub simulator:                     Simulator,
+    pub signature_status_poller:       SignatureStatusPoller,
     pub ws_address:                    String,
-    pub tx_broadcaster_clients:        Vec<RpcClient>,
+    pub tx_broadcaster:                TransactionBroadcaster,
     pub log_sender:                    Sender<Response<RpcLogsResponse>>,
     pub prioritization_fee_percentile: Option<u64>,
+    pub winner_selection_mode:         WinnerSelectionMode,
+    pub bundle_submission:             Option<BundleSubmissionSvm>,
+    pub auction_trigger_mode:          AuctionTriggerMode,
+    pub auction_pricing_rule:          AuctionPricingRule,
+    pub swap_instruction_policy:       InstructionPolicy,
+    pub lookup_table_resolver:         Arc<LookupTableResolver>,
+    pub bid_rate_limiters:             BidRateLimiters,
+    pub searcher_reputation:           SearcherReputationConfig,
+    pub relayer_spend_limits:          RelayerSpendLimitsConfig,
 }
 
 pub struct Config {
@@ -241,6 +289,28 @@ mod mock_service {
                 &self,
                 transaction: VersionedTransaction,
             ) -> Result<entities::BidTransactionDataSwap, RestError>;
+
+            pub async fn get_deprioritized_profiles(
+                &self,
+                bids: &[entities::Bid],
+            ) -> std::collections::HashSet<crate::models::ProfileId>;
+
+            pub async fn add_quote_fallback_auctions(
+                &self,
+                input: quote_fallback_auctions::AddQuoteFallbackAuctionsInput,
+            );
+
+            pub async fn resolve_replaced_quote(
+                &self,
+                input: quote_fallback_auctions::ResolveReplacedQuoteInput,
+            ) -> Result<(), RestError>;
+
+            pub fn get_auction_by_id(
+                &self,
+                input: get_auction_by_id::GetAuctionByIdInput,
+            ) -> Option<entities::Auction>;
+
+            pub async fn get_bid(&self, input: get_bid::GetBidInput) -> Result<entities::Bid, RestError>;
         }
 
         impl Clone for ServiceInner {
@@ -253,7 +323,10 @@ mod mock_service {
 pub mod tests {
     use {
         super::{
+            signature_status_poller::SignatureStatusPoller,
             simulator::Simulator,
+            subscribed_account_cache::SubscribedAccountCache,
+            transaction_sender::TransactionBroadcaster,
             Config,
             ConfigSvm,
             ExpressRelaySvm,
@@ -267,9 +340,19 @@ pub mod tests {
                 MockAnalyticsDatabase,
                 Repository,
             },
+            config::{
+                AuctionPricingRule,
+                AuctionTriggerMode,
+                InstructionPolicy,
+                RelayerSpendLimitsConfig,
+                SearcherReputationConfig,
+                TransactionSubmissionStrategy,
+                WinnerSelectionMode,
+            },
             kernel::{
                 db::DB,
                 entities::ChainId,
+                lookup_table_resolver::LookupTableResolver,
                 traced_sender_svm::{
                     tests::MockRpcClient,
                     TracedSenderSvm,
@@ -315,17 +398,23 @@ pub mod tests {
                 privileges:    RwLock::new(HashMap::new()),
                 prices:        RwLock::new(HashMap::new()),
             });
+            let lookup_table_resolver = Arc::new(LookupTableResolver::new(
+                chain_id.clone(),
+                TracedSenderSvm::new_client(
+                    chain_id.clone(),
+                    "https://test",
+                    2,
+                    RpcClientConfig::default(),
+                ),
+            ));
             Service(Arc::new(ServiceInner {
                 store,
                 opportunity_service: Arc::new(opportunity_service),
                 config: Config {
                     chain_id:     chain_id.clone(),
                     chain_config: ConfigSvm {
-                        client:                        RpcClient::new_sender(
-                            rpc_client,
-                            RpcClientConfig::default(),
-                        ),
-                        express_relay:                 ExpressRelaySvm {
+                        client: RpcClient::new_sender(rpc_client, RpcClientConfig::default()),
+                        express_relay: ExpressRelaySvm {
                             program_id: express_relay::id(),
 
                             relayer:                                  Keypair::new(),
@@ -334,19 +423,44 @@ pub mod tests {
                             swap_instruction_account_positions:
                                 get_swap_instruction_account_positions(),
                         },
-                        simulator:                     Simulator::new(TracedSenderSvm::new_client(
+                        simulator: Simulator::new(
+                            TracedSenderSvm::new_client(
+                                chain_id.clone(),
+                                "https://test",
+                                2,
+                                RpcClientConfig::default(),
+                            ),
+                            SubscribedAccountCache::new(chain_id.clone(), express_relay::id()),
+                            lookup_table_resolver.clone(),
+                        ),
+                        lookup_table_resolver,
+                        signature_status_poller: SignatureStatusPoller::new(
+                            TracedSenderSvm::new_client(
+                                chain_id.clone(),
+                                "https://test",
+                                2,
+                                RpcClientConfig::default(),
+                            ),
+                        ),
+                        ws_address: "ws://test".to_string(),
+                        tx_broadcaster: TransactionBroadcaster::new(
                             chain_id.clone(),
-                            "https://test",
-                            2,
-                            RpcClientConfig::default(),
-                        )),
-                        ws_address:                    "ws://test".to_string(),
-                        tx_broadcaster_clients:        vec![RpcClient::new_sender(
-                            broadcaster_client,
-                            RpcClientConfig::default(),
-                        )],
-                        log_sender:                    broadcast::channel(1).0,
+                            vec![RpcClient::new_sender(
+                                broadcaster_client,
+                                RpcClientConfig::default(),
+                            )],
+                            TransactionSubmissionStrategy::default(),
+                        ),
+                        log_sender: broadcast::channel(1).0,
                         prioritization_fee_percentile: None,
+                        winner_selection_mode: WinnerSelectionMode::default(),
+                        bundle_submission: None,
+                        auction_trigger_mode: AuctionTriggerMode::default(),
+                        auction_pricing_rule: AuctionPricingRule::default(),
+                        swap_instruction_policy: InstructionPolicy::default(),
+                        bid_rate_limiters: BidRateLimiters::new(&Default::default()),
+                        searcher_reputation: SearcherReputationConfig::default(),
+                        relayer_spend_limits: RelayerSpendLimitsConfig::default(),
                     },
                 },
                 repo: Arc::new(Repository::new(
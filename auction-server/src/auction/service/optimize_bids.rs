@@ -7,11 +7,13 @@ use {
 impl Service {
     /// Given a list of bids, tries to find the optimal set of bids that can be submitted to the chain
     /// considering the current state of the chain and the pending transactions.
-    /// Right now, for simplicity, the method assume the bids are sorted, and tries to submit them in order
-    /// and only return the ones that are successfully submitted.
-    #[tracing::instrument(skip_all)]
+    /// The bids are expected to be sorted by amount in descending order.
+    /// The selection strategy is configured per chain with the winner selection mode.
+    #[tracing::instrument(skip_all, fields(winner_selection_mode))]
     pub async fn optimize_bids(&self, bids_sorted: &[Bid]) -> RpcResult<Vec<Bid>> {
+        let mode = self.config.chain_config.winner_selection_mode;
+        tracing::Span::current().record("winner_selection_mode", format!("{:?}", mode));
         let simulator = &self.config.chain_config.simulator;
-        simulator.optimize_bids(bids_sorted).await
+        simulator.optimize_bids(bids_sorted, mode).await
     }
 }
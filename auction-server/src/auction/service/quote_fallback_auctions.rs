@@ -0,0 +1,538 @@
+use {
+    super::{
+        conclude_auction::ConcludeAuctionWithStatusesInput,
+        verification::BID_MAXIMUM_LIFE_TIME_SVM,
+        Service,
+    },
+    crate::{
+        api::RestError,
+        auction::entities::{
+            self,
+            BidStatus,
+        },
+    },
+    futures::future::join_all,
+    time::OffsetDateTime,
+};
+
+pub struct AddQuoteFallbackAuctionsInput {
+    pub primary_auction_id:   entities::AuctionId,
+    pub fallback_auction_ids: Vec<entities::AuctionId>,
+}
+
+pub struct ResolveReplacedQuoteInput {
+    pub auction_id: entities::AuctionId,
+}
+
+impl Service {
+    /// Resolves the bids of a quote auction which are still awaiting signature as lost.
+    /// Returns an error if a bid of the auction is already submitted.
+    async fn resolve_quote_auction_as_lost(
+        &self,
+        auction_id: entities::AuctionId,
+    ) -> Result<(), RestError> {
+        let bid_ids: Vec<entities::BidId> = match self.repo.get_in_memory_auction_by_id(auction_id)
+        {
+            Some(auction) => auction
+                .bids
+                .iter()
+                .filter(|bid| bid.status.is_awaiting_signature())
+                .map(|bid| bid.id)
+                .collect(),
+            None => return Ok(()),
+        };
+        // Make sure the bids are not being submitted at the same time
+        let bid_locks = join_all(
+            bid_ids
+                .iter()
+                .map(|bid_id| self.repo.get_or_create_in_memory_bid_lock(*bid_id)),
+        )
+        .await;
+        let guards = join_all(bid_locks.iter().map(|bid_lock| bid_lock.lock())).await;
+
+        // Refetch the auction as the bids may be submitted before the locks are acquired
+        let result = match self.repo.get_in_memory_auction_by_id(auction_id) {
+            Some(auction)
+                if auction.bids.iter().any(|bid| {
+                    bid.status.is_submitted() || bid.status.is_sent_to_user_for_submission()
+                }) =>
+            {
+                Err(RestError::QuoteIsFinalized)
+            }
+            Some(auction) => {
+                let bid_statuses: Vec<(entities::BidStatusSvm, entities::Bid)> = auction
+                    .bids
+                    .iter()
+                    .filter(|bid| bid.status.is_awaiting_signature())
+                    .map(|bid| {
+                        (
+                            entities::BidStatusSvm::Lost {
+                                auction: Some(entities::BidStatusAuction {
+                                    id:      auction.id,
+                                    tx_hash: bid.chain_data.transaction.signatures[0],
+                                }),
+                            },
+                            bid.clone(),
+                        )
+                    })
+                    .collect();
+                if bid_statuses.is_empty() {
+                    Ok(())
+                } else {
+                    self.conclude_auction_with_statuses(ConcludeAuctionWithStatusesInput {
+                        auction,
+                        bid_statuses,
+                    })
+                    .await
+                    .map_err(|e| {
+                        tracing::error!(error = ?e, auction_id = %auction_id, "Failed to resolve quote auction as lost");
+                        RestError::TemporarilyUnavailable
+                    })
+                }
+            }
+            None => Ok(()),
+        };
+        drop(guards);
+        drop(bid_locks);
+        for bid_id in bid_ids.iter() {
+            self.repo.remove_in_memory_bid_lock(bid_id).await;
+        }
+        result
+    }
+
+    /// Links the auctions of the quotes returned for the same quote request,
+    /// so that only one of them can be submitted.
+    pub async fn add_quote_fallback_auctions(&self, input: AddQuoteFallbackAuctionsInput) {
+        if let Err(e) = self
+            .repo
+            .add_quote_fallback_auctions(input.primary_auction_id, &input.fallback_auction_ids)
+            .await
+        {
+            tracing::error!(error = ?e, auction_id = %input.primary_auction_id, "Failed to store quote fallback auctions");
+        }
+    }
+
+    /// Loads the links between the quotes of the recent quote requests from the database.
+    /// This should be called on startup, so that a quote request answered before the restart
+    /// still can't have more than one of its quotes submitted.
+    #[tracing::instrument(skip_all, fields(chain_id = self.config.chain_id))]
+    pub async fn load_quote_fallback_auctions(&self) -> anyhow::Result<()> {
+        self.repo
+            .load_quote_fallback_auctions(OffsetDateTime::now_utc() - BID_MAXIMUM_LIFE_TIME_SVM)
+            .await
+    }
+
+    /// Resolves the quotes of a quote request which are concluded without being used.
+    /// A fallback quote which expires without being submitted is lost, as the user picked another quote of the request.
+    /// When any other quote concludes, the fallback quotes of its quote request are resolved as lost.
+    pub(super) async fn resolve_concluded_quote_bid_statuses(
+        &self,
+        auction_id: entities::AuctionId,
+        bid_statuses: Vec<(entities::BidStatusSvm, entities::Bid)>,
+    ) -> Vec<(entities::BidStatusSvm, entities::Bid)> {
+        if bid_statuses.is_empty() {
+            return bid_statuses;
+        }
+        if self
+            .repo
+            .is_in_memory_fallback_quote_auction(&auction_id)
+            .await
+        {
+            return bid_statuses
+                .into_iter()
+                .map(|(status, bid)| match status {
+                    entities::BidStatusSvm::Expired { auction }
+                        if bid.status.is_awaiting_signature() =>
+                    {
+                        (
+                            entities::BidStatusSvm::Lost {
+                                auction: Some(auction),
+                            },
+                            bid,
+                        )
+                    }
+                    status => (status, bid),
+                })
+                .collect();
+        }
+        // Fails only if another quote of the request is already submitted, which resolves the fallback quotes itself
+        if let Err(e) = self.resolve_quote_fallback_auctions(auction_id).await {
+            tracing::debug!(error = ?e, auction_id = %auction_id, "Quote fallback auctions already resolved");
+        }
+        bid_statuses
+    }
+
+    /// Resolves a quote of a quote subscription which is replaced by a better quote as lost,
+    /// so that the searcher is not penalized for the quote expiring.
+    /// Returns an error if the replaced quote is already submitted.
+    #[tracing::instrument(skip_all, fields(auction_id = %input.auction_id), err(level = tracing::Level::TRACE))]
+    pub async fn resolve_replaced_quote(
+        &self,
+        input: ResolveReplacedQuoteInput,
+    ) -> Result<(), RestError> {
+        self.resolve_quote_auction_as_lost(input.auction_id).await
+    }
+
+    /// Resolves the bids of the other quotes of the quote request of the auction as lost.
+    /// This should be called while holding the lock of the bid to submit.
+    /// Returns an error if another quote of the quote request is already submitted.
+    #[tracing::instrument(skip_all, fields(auction_id = %auction_id), err(level = tracing::Level::TRACE))]
+    pub(super) async fn resolve_quote_fallback_auctions(
+        &self,
+        auction_id: entities::AuctionId,
+    ) -> Result<(), RestError> {
+        let fallback_auction_ids = match self
+            .repo
+            .take_in_memory_quote_fallback_auctions(&auction_id)
+            .await
+        {
+            None => return Ok(()),
+            Some(fallback_auction_ids) if fallback_auction_ids.is_empty() => {
+                return Err(RestError::QuoteIsFinalized)
+            }
+            Some(fallback_auction_ids) => fallback_auction_ids,
+        };
+
+        for fallback_auction_id in fallback_auction_ids {
+            if let Err(e) = self
+                .resolve_quote_auction_as_lost(fallback_auction_id)
+                .await
+            {
+                tracing::error!(error = ?e, auction_id = %fallback_auction_id, "Failed to resolve quote fallback auction");
+            }
+            self.repo
+                .remove_in_memory_quote_fallback_auctions(&fallback_auction_id)
+                .await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction::repository::MockDatabase,
+            kernel::{
+                entities::PermissionKeySvm,
+                traced_sender_svm::tests::MockRpcClient,
+            },
+            opportunity::service::MockService as MockOpportunityService,
+        },
+        solana_sdk::{
+            pubkey::Pubkey,
+            signature::Signature,
+            transaction::VersionedTransaction,
+        },
+        std::collections::HashSet,
+        time::OffsetDateTime,
+        uuid::Uuid,
+    };
+
+    fn make_quote_auction() -> entities::Auction {
+        let auction_id = Uuid::new_v4();
+        let bid = entities::Bid {
+            id:              Uuid::new_v4(),
+            chain_id:        "solana".to_string(),
+            initiation_time: OffsetDateTime::now_utc(),
+            profile_id:      None,
+            opportunity_id:  None,
+            creation_time:   OffsetDateTime::now_utc(),
+            conclusion_time: None,
+            submission_time: None,
+            amount:          100,
+            status:          entities::BidStatusSvm::AwaitingSignature {
+                auction: entities::BidStatusAuction {
+                    id:      auction_id,
+                    tx_hash: Signature::new_unique(),
+                },
+            },
+            chain_data:      entities::BidChainDataSvm {
+                transaction:                  VersionedTransaction {
+                    signatures: vec![Signature::new_unique()],
+                    message:    Default::default(),
+                },
+                bid_payment_instruction_type: entities::BidPaymentInstructionType::Swap,
+                router:                       Pubkey::new_unique(),
+                permission_account:           Pubkey::new_unique(),
+                last_valid_block_height:      None,
+            },
+        };
+        entities::Auction {
+            id:                  auction_id,
+            chain_id:            "solana".to_string(),
+            permission_key:      PermissionKeySvm([0; 65]),
+            creation_time:       OffsetDateTime::now_utc(),
+            conclusion_time:     None,
+            bid_collection_time: OffsetDateTime::now_utc(),
+            submission_time:     None,
+            tx_hash:             None,
+            pricing:             None,
+            bids:                vec![bid],
+        }
+    }
+
+    /// Returns a service with the auctions of the quotes of a quote request, the first one being the primary quote.
+    /// The bids of the auctions in `lost_auctions` are expected to be resolved as lost.
+    async fn get_service(
+        auctions: &[entities::Auction],
+        lost_auctions: &[entities::Auction],
+    ) -> Service {
+        let lost_bid_ids: HashSet<entities::BidId> = lost_auctions
+            .iter()
+            .flat_map(|auction| auction.bids.iter().map(|bid| bid.id))
+            .collect();
+        let lost_auction_ids: HashSet<entities::AuctionId> =
+            lost_auctions.iter().map(|auction| auction.id).collect();
+
+        let mut db = MockDatabase::default();
+        db.expect_update_bid_status()
+            .withf(move |bid, status| {
+                lost_bid_ids.contains(&bid.id)
+                    && matches!(status, entities::BidStatusSvm::Lost { auction: Some(_) })
+            })
+            .times(lost_auctions.len())
+            .returning(|_, _| Ok((true, Some(OffsetDateTime::now_utc()))));
+        db.expect_conclude_auction()
+            .withf(move |auction_id| lost_auction_ids.contains(auction_id))
+            .times(lost_auctions.len())
+            .returning(|_| Ok(()));
+        let primary_auction_id = auctions[0].id;
+        db.expect_add_quote_fallback_auctions()
+            .withf(move |auction_id, _| *auction_id == primary_auction_id)
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            db,
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+        for auction in auctions {
+            service
+                .repo
+                .in_memory_store
+                .auctions
+                .insert(auction.id, auction.clone());
+        }
+        service
+            .add_quote_fallback_auctions(AddQuoteFallbackAuctionsInput {
+                primary_auction_id:   auctions[0].id,
+                fallback_auction_ids: auctions[1..].iter().map(|auction| auction.id).collect(),
+            })
+            .await;
+        service
+    }
+
+    fn is_in_memory_auction(service: &Service, auction: &entities::Auction) -> bool {
+        service
+            .repo
+            .get_in_memory_auction_by_id(auction.id)
+            .is_some()
+    }
+
+    #[tokio::test]
+    async fn test_resolve_quote_fallback_auctions_without_fallbacks() {
+        let auction = make_quote_auction();
+        let service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            MockDatabase::default(),
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+
+        assert!(service
+            .resolve_quote_fallback_auctions(auction.id)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_quote_fallback_auctions_on_fallback_selection() {
+        let (primary, selected, other) = (
+            make_quote_auction(),
+            make_quote_auction(),
+            make_quote_auction(),
+        );
+        let service = get_service(
+            &[primary.clone(), selected.clone(), other.clone()],
+            &[primary.clone(), other.clone()],
+        )
+        .await;
+
+        service
+            .resolve_quote_fallback_auctions(selected.id)
+            .await
+            .unwrap();
+
+        // The other quotes are lost and concluded, the selected quote is left to be submitted
+        assert!(!is_in_memory_auction(&service, &primary));
+        assert!(!is_in_memory_auction(&service, &other));
+        assert!(is_in_memory_auction(&service, &selected));
+        assert!(service
+            .repo
+            .in_memory_store
+            .quote_fallback_auctions
+            .read()
+            .await
+            .is_empty());
+
+        // The quotes of the request are not linked anymore
+        assert!(service
+            .resolve_quote_fallback_auctions(selected.id)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_quote_fallback_auctions_is_finalized() {
+        let (primary, fallback) = (make_quote_auction(), make_quote_auction());
+        let service = get_service(&[primary.clone(), fallback.clone()], &[]).await;
+
+        // The fallback quote is being submitted at the same time
+        service
+            .repo
+            .take_in_memory_quote_fallback_auctions(&fallback.id)
+            .await;
+
+        assert!(matches!(
+            service.resolve_quote_fallback_auctions(primary.id).await,
+            Err(RestError::QuoteIsFinalized)
+        ));
+        assert!(is_in_memory_auction(&service, &primary));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_replaced_quote_already_submitted() {
+        let mut auction = make_quote_auction();
+        let bid = &mut auction.bids[0];
+        bid.status = entities::BidStatusSvm::Submitted {
+            auction: entities::BidStatusAuction {
+                id:      auction.id,
+                tx_hash: bid.chain_data.transaction.signatures[0],
+            },
+        };
+        let service = get_service(&[auction.clone()], &[]).await;
+
+        assert!(matches!(
+            service
+                .resolve_replaced_quote(ResolveReplacedQuoteInput {
+                    auction_id: auction.id,
+                })
+                .await,
+            Err(RestError::QuoteIsFinalized)
+        ));
+        assert!(is_in_memory_auction(&service, &auction));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_concluded_quote_bid_statuses_of_fallback_quote() {
+        let (primary, fallback) = (make_quote_auction(), make_quote_auction());
+        let service = get_service(&[primary.clone(), fallback.clone()], &[]).await;
+
+        let bid = fallback.bids[0].clone();
+        let auction = entities::BidStatusAuction {
+            id:      fallback.id,
+            tx_hash: bid.chain_data.transaction.signatures[0],
+        };
+        let bid_statuses = service
+            .resolve_concluded_quote_bid_statuses(
+                fallback.id,
+                vec![(
+                    entities::BidStatusSvm::Expired {
+                        auction: auction.clone(),
+                    },
+                    bid.clone(),
+                )],
+            )
+            .await;
+
+        assert_eq!(
+            bid_statuses,
+            vec![(
+                entities::BidStatusSvm::Lost {
+                    auction: Some(auction),
+                },
+                bid
+            )]
+        );
+        assert!(is_in_memory_auction(&service, &primary));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_concluded_quote_bid_statuses_of_primary_quote() {
+        let (primary, fallback) = (make_quote_auction(), make_quote_auction());
+        let service = get_service(&[primary.clone(), fallback.clone()], &[fallback.clone()]).await;
+
+        let bid = primary.bids[0].clone();
+        let expired_status = entities::BidStatusSvm::Expired {
+            auction: entities::BidStatusAuction {
+                id:      primary.id,
+                tx_hash: bid.chain_data.transaction.signatures[0],
+            },
+        };
+        let bid_statuses = service
+            .resolve_concluded_quote_bid_statuses(
+                primary.id,
+                vec![(expired_status.clone(), bid.clone())],
+            )
+            .await;
+
+        // The primary quote keeps its status and the unused fallback quote is lost
+        assert_eq!(bid_statuses, vec![(expired_status, bid)]);
+        assert!(!is_in_memory_auction(&service, &fallback));
+    }
+
+    #[tokio::test]
+    async fn test_load_quote_fallback_auctions() {
+        let (primary, fallback) = (Uuid::new_v4(), Uuid::new_v4());
+        let (submitted_primary, submitted_fallback) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut db = MockDatabase::default();
+        db.expect_get_quote_fallback_auctions()
+            .times(1)
+            .returning(move |_, _| {
+                Ok(vec![
+                    (primary, primary, false),
+                    (primary, fallback, false),
+                    (submitted_primary, submitted_primary, false),
+                    (submitted_primary, submitted_fallback, true),
+                ])
+            });
+        let service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            db,
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+
+        service.load_quote_fallback_auctions().await.unwrap();
+
+        // The quotes of the request without a submitted quote are linked again
+        assert!(
+            service
+                .repo
+                .is_in_memory_fallback_quote_auction(&fallback)
+                .await
+        );
+        assert_eq!(
+            service
+                .repo
+                .take_in_memory_quote_fallback_auctions(&fallback)
+                .await,
+            Some(vec![primary])
+        );
+
+        // The other quotes of a request with a submitted quote can't be submitted
+        assert!(matches!(
+            service
+                .resolve_quote_fallback_auctions(submitted_primary)
+                .await,
+            Err(RestError::QuoteIsFinalized)
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use {
+    super::Service,
+    crate::{
+        auction::entities,
+        per_metrics::SECOND_PRICE_REFUND_LAMPORTS_TOTAL,
+    },
+    axum_prometheus::metrics,
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        signer::Signer,
+        system_instruction,
+        transaction::{
+            Transaction,
+            VersionedTransaction,
+        },
+    },
+};
+
+pub struct RefundSecondPriceInput {
+    pub auction: entities::Auction,
+    pub bid:     entities::Bid,
+}
+
+impl Service {
+    /// Refunds the winner of a second price auction the amount it bid above the clearing price.
+    ///
+    /// The searcher signs the full bid amount in its transaction, so the second price is enforced
+    /// by the relayer paying the difference back to the searcher once the bid is won.
+    #[tracing::instrument(skip_all, fields(auction_id, bid_id, refund, tx_hash))]
+    pub async fn refund_second_price(&self, input: RefundSecondPriceInput) -> anyhow::Result<()> {
+        tracing::Span::current().record("auction_id", input.auction.id.to_string());
+        tracing::Span::current().record("bid_id", input.bid.id.to_string());
+        let refund = input
+            .auction
+            .pricing
+            .as_ref()
+            .map(|pricing| pricing.get_refund(input.bid.amount))
+            .unwrap_or_default();
+        tracing::Span::current().record("refund", refund);
+        if refund == 0
+            || input.bid.chain_data.bid_payment_instruction_type
+                != entities::BidPaymentInstructionType::SubmitBid
+        {
+            return Ok(());
+        }
+
+        let transaction = &input.bid.chain_data.transaction;
+        let (_, instruction) = self
+            .extract_express_relay_instruction(
+                transaction.clone(),
+                entities::BidPaymentInstructionType::SubmitBid,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to extract submit bid instruction: {:?}", e))?;
+        let searcher = self
+            .extract_account(
+                transaction,
+                &instruction,
+                self.config
+                    .chain_config
+                    .express_relay
+                    .submit_bid_instruction_account_positions
+                    .searcher_account,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to extract searcher account: {:?}", e))?;
+
+        let relayer = &self.config.chain_config.express_relay.relayer;
+        let (recent_blockhash, _) = self
+            .config
+            .chain_config
+            .client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .await?;
+        let refund_transaction: VersionedTransaction = Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &relayer.pubkey(),
+                &searcher,
+                refund,
+            )],
+            Some(&relayer.pubkey()),
+            &[relayer],
+            recent_blockhash,
+        )
+        .into();
+        tracing::Span::current().record("tx_hash", refund_transaction.signatures[0].to_string());
+
+        let result = self.send_relayer_transaction(&refund_transaction).await;
+        let result_label = match &result {
+            Some(Ok(())) => "success",
+            Some(Err(_)) => "failed",
+            None => "expired",
+        };
+        metrics::counter!(
+            SECOND_PRICE_REFUND_LAMPORTS_TOTAL,
+            &[
+                ("chain_id", self.config.chain_id.clone()),
+                ("result", result_label.to_string()),
+            ]
+        )
+        .increment(refund);
+        match result {
+            Some(Ok(())) => {
+                tracing::info!(searcher = %searcher, "Refunded second price auction winner");
+                Ok(())
+            }
+            Some(Err(err)) => Err(anyhow::anyhow!(
+                "Refund to searcher {} failed: {:?}",
+                searcher,
+                err
+            )),
+            None => Err(anyhow::anyhow!(
+                "Refund to searcher {} did not land",
+                searcher
+            )),
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use {
+    super::Service,
+    crate::{
+        api::RestError,
+        auction::entities,
+        models::ProfileId,
+        per_metrics::RELAYER_SPEND_LAMPORTS_TOTAL,
+    },
+    axum_prometheus::metrics,
+    solana_sdk::signer::Signer,
+    time::OffsetDateTime,
+};
+
+pub struct CheckRelayerSpendInput {
+    pub profile_id: Option<ProfileId>,
+    pub chain_data: entities::BidChainDataSvm,
+}
+
+pub struct ReserveRelayerSpendInput {
+    pub profile_id: Option<ProfileId>,
+    pub chain_data: entities::BidChainDataSvm,
+}
+
+pub struct ReleaseRelayerSpendInput {
+    pub profile_id: Option<ProfileId>,
+    pub chain_data: entities::BidChainDataSvm,
+}
+
+pub struct RecordRelayerSpendInput {
+    pub bid: entities::Bid,
+}
+
+impl Service {
+    /// Loads the relayer spends of the current day from the database.
+    /// This should be called on startup, before the daily limits are checked.
+    #[tracing::instrument(skip_all, fields(chain_id = self.config.chain_id))]
+    pub async fn load_relayer_spends(&self) -> anyhow::Result<()> {
+        self.repo
+            .load_relayer_spends(OffsetDateTime::now_utc().date())
+            .await
+    }
+
+    fn get_relayer_spend(&self, chain_data: &entities::BidChainDataSvm) -> u64 {
+        chain_data.get_relayer_spend(&self.config.chain_config.express_relay.relayer.pubkey())
+    }
+
+    /// Checks that the lamports the relayer pays for the bid stay within the per bid limit
+    /// and the daily limit of the searcher profile.
+    ///
+    /// This does not reserve the lamports, use `reserve_relayer_spend` before accepting the bid.
+    pub async fn check_relayer_spend(
+        &self,
+        input: CheckRelayerSpendInput,
+    ) -> Result<(), RestError> {
+        let spend = self.get_relayer_spend(&input.chain_data);
+        if spend == 0 {
+            return Ok(());
+        }
+        let limits = &self.config.chain_config.relayer_spend_limits;
+        if let Some(maximum) = limits.per_bid {
+            if spend > maximum {
+                return Err(RestError::RelayerSpendTooHigh { spend, maximum });
+            }
+        }
+        if let Some(limit) = limits.per_profile_daily {
+            let Some(profile_id) = input.profile_id else {
+                return Err(RestError::Unauthorized);
+            };
+            let spent = self
+                .repo
+                .get_in_memory_relayer_spend(&profile_id, OffsetDateTime::now_utc().date())
+                .await;
+            if spent.saturating_add(spend) > limit {
+                return Err(RestError::RelayerDailySpendLimitReached { limit });
+            }
+        }
+        Ok(())
+    }
+
+    /// Atomically checks the daily limit of the searcher profile and reserves the lamports the relayer pays for the bid.
+    /// The reservation must be released with `release_relayer_spend` if the bid is not accepted
+    /// or concluded before the relayer signs it, and is turned into spend by `record_relayer_spend`.
+    pub async fn reserve_relayer_spend(
+        &self,
+        input: ReserveRelayerSpendInput,
+    ) -> Result<(), RestError> {
+        let spend = self.get_relayer_spend(&input.chain_data);
+        if spend == 0 {
+            return Ok(());
+        }
+        let Some(limit) = self
+            .config
+            .chain_config
+            .relayer_spend_limits
+            .per_profile_daily
+        else {
+            return Ok(());
+        };
+        let Some(profile_id) = input.profile_id else {
+            return Err(RestError::Unauthorized);
+        };
+        if !self
+            .repo
+            .reserve_in_memory_relayer_spend(
+                profile_id,
+                OffsetDateTime::now_utc().date(),
+                spend,
+                limit,
+            )
+            .await
+        {
+            return Err(RestError::RelayerDailySpendLimitReached { limit });
+        }
+        Ok(())
+    }
+
+    /// Releases the lamports reserved for the bid by `reserve_relayer_spend`.
+    pub async fn release_relayer_spend(&self, input: ReleaseRelayerSpendInput) {
+        let spend = self.get_relayer_spend(&input.chain_data);
+        if spend == 0
+            || self
+                .config
+                .chain_config
+                .relayer_spend_limits
+                .per_profile_daily
+                .is_none()
+        {
+            return;
+        }
+        if let Some(profile_id) = input.profile_id {
+            self.repo
+                .release_in_memory_relayer_spend(
+                    &profile_id,
+                    OffsetDateTime::now_utc().date(),
+                    spend,
+                )
+                .await;
+        }
+    }
+
+    /// Adds the lamports the relayer pays for the bid to the daily spend of its searcher profile
+    /// and releases their reservation.
+    /// This should be called once the relayer has signed the bid transaction.
+    pub async fn record_relayer_spend(&self, input: RecordRelayerSpendInput) -> anyhow::Result<()> {
+        let spend = self.get_relayer_spend(&input.bid.chain_data);
+        if spend == 0 {
+            return Ok(());
+        }
+        self.release_relayer_spend(ReleaseRelayerSpendInput {
+            profile_id: input.bid.profile_id,
+            chain_data: input.bid.chain_data.clone(),
+        })
+        .await;
+        metrics::counter!(
+            RELAYER_SPEND_LAMPORTS_TOTAL,
+            &[("chain_id", self.config.chain_id.clone())]
+        )
+        .increment(spend);
+        if let Some(profile_id) = input.bid.profile_id {
+            self.repo
+                .add_relayer_spend(profile_id, OffsetDateTime::now_utc().date(), spend)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction::repository::MockDatabase,
+            config::RelayerSpendLimitsConfig,
+            kernel::traced_sender_svm::tests::MockRpcClient,
+            opportunity::service::MockService as MockOpportunityService,
+        },
+        solana_sdk::{
+            pubkey::Pubkey,
+            transaction::{
+                Transaction,
+                VersionedTransaction,
+            },
+        },
+        std::sync::Arc,
+        uuid::Uuid,
+    };
+
+    const LIMIT: u64 = 12_000;
+
+    fn get_service() -> Service {
+        let mut service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            MockDatabase::default(),
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+        Arc::get_mut(&mut service.0)
+            .expect("Only one reference should exist at setup")
+            .config
+            .chain_config
+            .relayer_spend_limits = RelayerSpendLimitsConfig {
+            per_bid:           None,
+            per_profile_daily: Some(LIMIT),
+        };
+        service
+    }
+
+    /// Returns the chain data of a bid whose only relayer spend is the fee of its signature.
+    fn get_chain_data(service: &Service) -> entities::BidChainDataSvm {
+        let relayer = service.config.chain_config.express_relay.relayer.pubkey();
+        entities::BidChainDataSvm {
+            permission_account:           Pubkey::new_unique(),
+            router:                       Pubkey::new_unique(),
+            bid_payment_instruction_type: entities::BidPaymentInstructionType::Swap,
+            transaction:                  VersionedTransaction::from(Transaction::new_with_payer(
+                &[],
+                Some(&relayer),
+            )),
+            last_valid_block_height:      None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reserve_relayer_spend_up_to_daily_limit() {
+        let service = get_service();
+        let chain_data = get_chain_data(&service);
+        let profile_id = Uuid::new_v4();
+        assert_eq!(service.get_relayer_spend(&chain_data), 5_000);
+
+        for _ in 0..2 {
+            service
+                .reserve_relayer_spend(ReserveRelayerSpendInput {
+                    profile_id: Some(profile_id),
+                    chain_data: chain_data.clone(),
+                })
+                .await
+                .expect("The reservations should stay within the limit");
+        }
+        let error = service
+            .reserve_relayer_spend(ReserveRelayerSpendInput {
+                profile_id: Some(profile_id),
+                chain_data: chain_data.clone(),
+            })
+            .await
+            .expect_err("The third reservation should exceed the limit");
+        assert_eq!(
+            error,
+            RestError::RelayerDailySpendLimitReached { limit: LIMIT }
+        );
+        // The check also accounts for the reserved lamports
+        assert_eq!(
+            service
+                .check_relayer_spend(CheckRelayerSpendInput {
+                    profile_id: Some(profile_id),
+                    chain_data: chain_data.clone(),
+                })
+                .await,
+            Err(RestError::RelayerDailySpendLimitReached { limit: LIMIT })
+        );
+        // Other profiles have their own limit
+        service
+            .reserve_relayer_spend(ReserveRelayerSpendInput {
+                profile_id: Some(Uuid::new_v4()),
+                chain_data,
+            })
+            .await
+            .expect("The reservation of another profile should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_release_relayer_spend() {
+        let service = get_service();
+        let chain_data = get_chain_data(&service);
+        let profile_id = Uuid::new_v4();
+
+        for _ in 0..2 {
+            service
+                .reserve_relayer_spend(ReserveRelayerSpendInput {
+                    profile_id: Some(profile_id),
+                    chain_data: chain_data.clone(),
+                })
+                .await
+                .expect("The reservations should stay within the limit");
+        }
+        service
+            .release_relayer_spend(ReleaseRelayerSpendInput {
+                profile_id: Some(profile_id),
+                chain_data: chain_data.clone(),
+            })
+            .await;
+        assert_eq!(
+            service
+                .repo
+                .get_in_memory_relayer_spend(&profile_id, OffsetDateTime::now_utc().date())
+                .await,
+            5_000
+        );
+        service
+            .reserve_relayer_spend(ReserveRelayerSpendInput {
+                profile_id: Some(profile_id),
+                chain_data,
+            })
+            .await
+            .expect("The released lamports should be available again");
+    }
+
+    #[tokio::test]
+    async fn test_relayer_spend_without_profile_is_unauthorized() {
+        let service = get_service();
+        let chain_data = get_chain_data(&service);
+        assert_eq!(
+            service
+                .check_relayer_spend(CheckRelayerSpendInput {
+                    profile_id: None,
+                    chain_data: chain_data.clone(),
+                })
+                .await,
+            Err(RestError::Unauthorized)
+        );
+        assert_eq!(
+            service
+                .reserve_relayer_spend(ReserveRelayerSpendInput {
+                    profile_id: None,
+                    chain_data,
+                })
+                .await,
+            Err(RestError::Unauthorized)
+        );
+    }
+}
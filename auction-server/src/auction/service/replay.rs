@@ -0,0 +1,503 @@
+use {
+    super::{
+        repository::{
+            self,
+            AnalyticsDatabase,
+            AnalyticsDatabaseInserter,
+            Database,
+        },
+        simulator::{
+            AccountsSnapshot,
+            BidReplayOutcome,
+            Simulator,
+        },
+    },
+    crate::{
+        auction::entities,
+        config::{
+            AuctionPricingRule,
+            Config,
+            ConfigMap,
+        },
+        kernel::{
+            db::DB,
+            entities::PermissionKeySvm,
+        },
+        models::ProfileId,
+    },
+    anyhow::anyhow,
+    serde::{
+        Deserialize,
+        Serialize,
+    },
+    solana_client::rpc_response::RpcKeyedAccount,
+    solana_sdk::{
+        account::Account,
+        pubkey::Pubkey,
+    },
+    std::{
+        collections::{
+            HashMap,
+            HashSet,
+        },
+        fs,
+        str::FromStr,
+    },
+    time::UtcOffset,
+};
+
+/// Snapshot of the accounts touched by an auction.
+/// Each account has the same format as the output of `solana account <pubkey> --output json`.
+#[derive(Debug, Deserialize)]
+pub struct AccountsSnapshotFile {
+    pub slot:      u64,
+    /// Unix timestamp of the chain clock, defaults to the bid collection time of the auction
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    pub accounts:  Vec<RpcKeyedAccount>,
+}
+
+impl AccountsSnapshotFile {
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn into_snapshot(self, default_timestamp: i64) -> anyhow::Result<AccountsSnapshot> {
+        let accounts = self
+            .accounts
+            .into_iter()
+            .map(|keyed_account| {
+                let key = Pubkey::from_str(&keyed_account.pubkey)
+                    .map_err(|e| anyhow!("Invalid account key {}: {}", keyed_account.pubkey, e))?;
+                let account = keyed_account
+                    .account
+                    .decode::<Account>()
+                    .ok_or_else(|| anyhow!("Failed to decode account {}", key))?;
+                Ok((key, account))
+            })
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+        Ok(AccountsSnapshot {
+            slot: self.slot,
+            timestamp: self.timestamp.unwrap_or(default_timestamp),
+            accounts,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BidReplayReport {
+    pub bid_id:          entities::BidId,
+    pub amount:          entities::BidAmountSvm,
+    /// Status of the bid stored in the database
+    pub recorded_status: repository::BidStatus,
+    pub won:             bool,
+    /// Why the bid would win or lose on the snapshot
+    pub reason:          String,
+    /// Transaction logs of the failed simulation, if any
+    pub logs:            Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayReport {
+    pub auction_id:            entities::AuctionId,
+    pub chain_id:              String,
+    pub slot:                  u64,
+    pub timestamp:             i64,
+    pub winner_selection_mode: String,
+    /// Whether the bids are ranked with the pricing rule and deprioritized profiles recorded with the auction.
+    /// Auctions restored from the analytics database alone are ranked with the current config and no deprioritized profiles.
+    pub recorded_pricing_rule: bool,
+    pub pricing_rule:          AuctionPricingRule,
+    /// Outcome of every bid of the auction, in the order they are considered by the auction,
+    /// followed by the bids below the reserve price
+    pub bids:                  Vec<BidReplayReport>,
+}
+
+/// The auction and its bids, with the recorded status of each bid
+struct AuctionHistory {
+    chain_id:          String,
+    permission_key:    PermissionKeySvm,
+    /// Unix timestamp of the bid collection of the auction
+    timestamp:         i64,
+    bids:              Vec<entities::Bid>,
+    recorded_statuses: HashMap<entities::BidId, repository::BidStatus>,
+    /// Pricing the auction was run with, if it is recorded
+    pricing:           Option<entities::AuctionPricing>,
+}
+
+/// Loads the auction history from postgres, completed with the `bid_swap` rows of the analytics database.
+/// Postgres only keeps the recent history, so the older auctions are restored from the analytics rows alone.
+async fn get_auction_history(
+    db: &DB,
+    analytics_db: &AnalyticsDatabaseInserter,
+    auction_id: entities::AuctionId,
+) -> anyhow::Result<AuctionHistory> {
+    let auction = db.get_auction(auction_id).await.ok();
+    let bid_models = match &auction {
+        Some(_) => db.get_auction_bids(auction_id).await?,
+        None => vec![],
+    };
+    let analytics_bids = analytics_db.get_auction_swap_bids(auction_id).await?;
+
+    let mut recorded_statuses = bid_models
+        .iter()
+        .map(|bid| (bid.id, bid.status.clone()))
+        .collect::<HashMap<_, _>>();
+    let mut bids = bid_models
+        .iter()
+        .map(|bid| bid.get_bid_entity(auction.clone(), None))
+        .collect::<anyhow::Result<Vec<entities::Bid>>>()?;
+    for analytics_bid in analytics_bids.iter() {
+        if recorded_statuses.contains_key(&analytics_bid.id) {
+            continue;
+        }
+        let status = serde_json::from_str(&analytics_bid.status).map_err(|e| {
+            anyhow!(
+                "Invalid status {} of bid {}: {}",
+                analytics_bid.status,
+                analytics_bid.id,
+                e
+            )
+        })?;
+        recorded_statuses.insert(analytics_bid.id, status);
+        bids.push(analytics_bid.get_bid_entity()?);
+    }
+
+    match auction {
+        Some(auction) => Ok(AuctionHistory {
+            chain_id: auction.chain_id,
+            permission_key: PermissionKeySvm::try_from(auction.permission_key.as_slice())
+                .map_err(|e| anyhow!("Invalid permission key of auction {}: {}", auction_id, e))?,
+            timestamp: auction
+                .bid_collection_time
+                .unwrap_or(auction.creation_time)
+                .assume_offset(UtcOffset::UTC)
+                .unix_timestamp(),
+            bids,
+            recorded_statuses,
+            pricing: auction.pricing_rule.map(|pricing| pricing.0),
+        }),
+        None => {
+            let bid = bids.first().ok_or_else(|| {
+                anyhow!(
+                    "Auction {} not found in the database or the analytics database",
+                    auction_id
+                )
+            })?;
+            // The bid collection time is not recorded in the analytics database,
+            // but it is after all the bids of the auction are created
+            let timestamp = bids
+                .iter()
+                .map(|bid| bid.creation_time)
+                .max()
+                .unwrap_or(bid.creation_time)
+                .unix_timestamp();
+            Ok(AuctionHistory {
+                chain_id: bid.chain_id.clone(),
+                permission_key: bid.chain_data.get_permission_key(),
+                timestamp,
+                bids,
+                recorded_statuses,
+                pricing: None,
+            })
+        }
+    }
+}
+
+/// Ranks the bids the same way as the live auction and returns them with the bids below the reserve price,
+/// which are not eligible to win.
+fn rank_bids(
+    pricing_rule: &AuctionPricingRule,
+    permission_key: &PermissionKeySvm,
+    bids: Vec<entities::Bid>,
+    deprioritized_profiles: &HashSet<ProfileId>,
+) -> (Vec<entities::Bid>, Vec<entities::Bid>) {
+    let reserve_price = pricing_rule.get_reserve_price(permission_key);
+    let mut below_reserve_bids: Vec<_> = bids
+        .iter()
+        .filter(|bid| bid.amount < reserve_price)
+        .cloned()
+        .collect();
+    below_reserve_bids.sort_by_key(|bid| std::cmp::Reverse(bid.amount));
+    let ranked_bids =
+        pricing_rule.rank_bids_with_deprioritized(permission_key, bids, deprioritized_profiles);
+    (ranked_bids, below_reserve_bids)
+}
+
+/// Replays the winner selection of an auction on a snapshot of the chain state.
+/// The bids are loaded from the auction history and ranked with the pricing rule and the deprioritized
+/// profiles recorded with the auction, so the result only depends on the snapshot and the winner selection mode of the chain.
+pub async fn replay_auction(
+    db: &DB,
+    analytics_db: &AnalyticsDatabaseInserter,
+    config_map: &ConfigMap,
+    auction_id: entities::AuctionId,
+    snapshot: AccountsSnapshotFile,
+) -> anyhow::Result<ReplayReport> {
+    let AuctionHistory {
+        chain_id,
+        permission_key,
+        timestamp,
+        bids,
+        recorded_statuses,
+        pricing,
+    } = get_auction_history(db, analytics_db, auction_id).await?;
+    let Some(Config::Svm(chain_config)) = config_map.chains.get(&chain_id) else {
+        return Err(anyhow!("No svm config found for chain {}", chain_id));
+    };
+    let mode = chain_config.winner_selection_mode;
+
+    let (pricing_rule, deprioritized_profiles) = match &pricing {
+        Some(pricing) => (
+            AuctionPricingRule::from_recorded_pricing(&permission_key, pricing),
+            pricing.deprioritized_profiles.iter().copied().collect(),
+        ),
+        None => (chain_config.auction_pricing_rule.clone(), HashSet::new()),
+    };
+    let reserve_price = pricing_rule.get_reserve_price(&permission_key);
+    let (bids, below_reserve_bids) = rank_bids(
+        &pricing_rule,
+        &permission_key,
+        bids,
+        &deprioritized_profiles,
+    );
+    let snapshot = snapshot.into_snapshot(timestamp)?;
+
+    let results = Simulator::replay_bids(&snapshot, &bids, mode);
+    let bids = results
+        .into_iter()
+        .map(|result| {
+            let won = result.outcome == BidReplayOutcome::Won;
+            let (reason, logs) = match result.outcome {
+                BidReplayOutcome::Won => ("Selected as a winner".to_string(), vec![]),
+                BidReplayOutcome::SimulationFailed { error, logs } => {
+                    (format!("Simulation failed: {}", error), logs)
+                }
+                BidReplayOutcome::FailedAfterWinners { error, logs } => (
+                    format!("Fails after the winning bids are executed: {}", error),
+                    logs,
+                ),
+                BidReplayOutcome::NotSelected => (
+                    "Conflicts with a set of bids paying more".to_string(),
+                    vec![],
+                ),
+            };
+            BidReplayReport {
+                bid_id: result.bid.id,
+                amount: result.bid.amount,
+                recorded_status: recorded_statuses[&result.bid.id].clone(),
+                won,
+                reason,
+                logs,
+            }
+        })
+        .chain(below_reserve_bids.into_iter().map(|bid| BidReplayReport {
+            bid_id:          bid.id,
+            amount:          bid.amount,
+            recorded_status: recorded_statuses[&bid.id].clone(),
+            won:             false,
+            reason:          format!("Below the reserve price of {}", reserve_price),
+            logs:            vec![],
+        }))
+        .collect();
+
+    Ok(ReplayReport {
+        auction_id,
+        chain_id,
+        slot: snapshot.slot,
+        timestamp: snapshot.timestamp,
+        winner_selection_mode: format!("{:?}", mode),
+        recorded_pricing_rule: pricing.is_some(),
+        pricing_rule,
+        bids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        base64::{
+            engine::general_purpose::STANDARD,
+            Engine as _,
+        },
+        solana_sdk::{
+            signature::Signature,
+            system_program,
+            transaction::VersionedTransaction,
+        },
+        time::OffsetDateTime,
+        uuid::Uuid,
+    };
+
+    #[test]
+    fn test_snapshot_file_into_snapshot() {
+        let key = Pubkey::new_unique();
+        let snapshot: AccountsSnapshotFile = serde_json::from_value(serde_json::json!({
+            "slot": 100,
+            "accounts": [{
+                "pubkey": key.to_string(),
+                "account": {
+                    "lamports": 5,
+                    "data": ["AQID", "base64"],
+                    "owner": system_program::id().to_string(),
+                    "executable": false,
+                    "rentEpoch": 0,
+                    "space": 3,
+                },
+            }],
+        }))
+        .unwrap();
+
+        let snapshot = snapshot.into_snapshot(1_700_000_000).unwrap();
+
+        assert_eq!(snapshot.slot, 100);
+        assert_eq!(snapshot.timestamp, 1_700_000_000);
+        assert_eq!(
+            snapshot.accounts,
+            HashMap::from([(
+                key,
+                Account {
+                    lamports:   5,
+                    data:       vec![1, 2, 3],
+                    owner:      system_program::id(),
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )])
+        );
+    }
+
+    #[test]
+    fn test_analytics_bid_into_bid_entity() {
+        let (router, permission_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut permission_key = [0; 65];
+        permission_key[0] = entities::BidPaymentInstructionType::Swap.into();
+        permission_key[1..33].copy_from_slice(&router.to_bytes());
+        permission_key[33..].copy_from_slice(&permission_account.to_bytes());
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::new_unique()],
+            message:    Default::default(),
+        };
+        let creation_time = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let analytics_bid = repository::BidAnalyticsSwap {
+            id: Uuid::new_v4(),
+            creation_time,
+            initiation_time: creation_time,
+            permission_key: STANDARD.encode(permission_key),
+            chain_id: "solana".to_string(),
+            transaction: STANDARD.encode(bincode::serialize(&transaction).unwrap()),
+            bid_amount: 100,
+            auction_id: Some(Uuid::new_v4()),
+            tx_hash: None,
+            submission_time: None,
+            opportunity_id: None,
+            conclusion_time: None,
+            searcher_token_mint: Pubkey::new_unique().to_string(),
+            searcher_token_amount: 10,
+            searcher_token_notional_usd_value: None,
+            user_token_mint: Pubkey::new_unique().to_string(),
+            user_token_amount: 20,
+            user_token_notional_usd_value: None,
+            status: serde_json::to_string(&repository::BidStatus::Lost).unwrap(),
+            status_reason: None,
+            user_wallet_address: Pubkey::new_unique().to_string(),
+            searcher_wallet_address: Pubkey::new_unique().to_string(),
+            fee_token: "user".to_string(),
+            referral_fee_ppm: 0,
+            platform_fee_ppm: 0,
+            deadline: 0,
+            token_program_user: Pubkey::new_unique().to_string(),
+            token_program_searcher: Pubkey::new_unique().to_string(),
+            router_token_account: Pubkey::new_unique().to_string(),
+            profile_id: None,
+        };
+
+        let bid = analytics_bid.get_bid_entity().unwrap();
+
+        assert_eq!(bid.id, analytics_bid.id);
+        assert_eq!(bid.amount, 100);
+        assert_eq!(bid.creation_time, creation_time);
+        assert_eq!(bid.chain_data.transaction, transaction);
+        assert_eq!(
+            bid.chain_data.bid_payment_instruction_type,
+            entities::BidPaymentInstructionType::Swap
+        );
+        assert_eq!(bid.chain_data.router, router);
+        assert_eq!(bid.chain_data.permission_account, permission_account);
+        assert_eq!(bid.chain_data.get_permission_key().0, permission_key);
+        assert_eq!(
+            serde_json::from_str::<repository::BidStatus>(&analytics_bid.status).unwrap(),
+            repository::BidStatus::Lost
+        );
+    }
+
+    fn make_bid(amount: u64, profile_id: Option<ProfileId>) -> entities::Bid {
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        entities::Bid {
+            id: Uuid::new_v4(),
+            chain_id: "solana".to_string(),
+            initiation_time: now,
+            profile_id,
+            opportunity_id: None,
+            creation_time: now,
+            conclusion_time: None,
+            submission_time: None,
+            amount,
+            status: entities::BidStatusSvm::Pending,
+            chain_data: entities::BidChainDataSvm {
+                transaction:                  VersionedTransaction::default(),
+                bid_payment_instruction_type: entities::BidPaymentInstructionType::SubmitBid,
+                router:                       Pubkey::default(),
+                permission_account:           Pubkey::default(),
+                last_valid_block_height:      None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_rank_bids_with_recorded_pricing() {
+        let mut permission_key = [0; 65];
+        let permission_account = Pubkey::new_unique();
+        permission_key[33..].copy_from_slice(&permission_account.to_bytes());
+        let permission_key = PermissionKeySvm(permission_key);
+        let deprioritized_profile = Uuid::new_v4();
+        let pricing = entities::AuctionPricing {
+            price:                  Default::default(),
+            reserve_price:          50,
+            minimum_bid_increment:  10,
+            clearing_price:         None,
+            deprioritized_profiles: vec![deprioritized_profile],
+        };
+        let bids = vec![
+            make_bid(105, Some(deprioritized_profile)),
+            make_bid(100, None),
+            make_bid(20, None),
+            make_bid(60, None),
+            make_bid(40, None),
+        ];
+        let expected_ranked_ids = vec![bids[1].id, bids[0].id, bids[3].id];
+        let expected_below_reserve_ids = vec![bids[4].id, bids[2].id];
+
+        let pricing_rule = AuctionPricingRule::from_recorded_pricing(&permission_key, &pricing);
+        let (ranked_bids, below_reserve_bids) = rank_bids(
+            &pricing_rule,
+            &permission_key,
+            bids,
+            &pricing.deprioritized_profiles.iter().copied().collect(),
+        );
+
+        assert_eq!(
+            ranked_bids.iter().map(|bid| bid.id).collect::<Vec<_>>(),
+            expected_ranked_ids
+        );
+        assert_eq!(
+            below_reserve_bids
+                .iter()
+                .map(|bid| bid.id)
+                .collect::<Vec<_>>(),
+            expected_below_reserve_ids
+        );
+    }
+}
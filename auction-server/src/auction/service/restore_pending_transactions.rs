@@ -0,0 +1,34 @@
+use {
+    super::{
+        simulator::MAX_PENDING_DURATION,
+        Service,
+    },
+    time::OffsetDateTime,
+};
+
+impl Service {
+    /// Rebuilds the pending transactions of the simulator from the bids that are still in flight.
+    /// This should be called on startup so the first auctions don't select bids that conflict
+    /// with transactions submitted before the restart.
+    #[tracing::instrument(skip_all, fields(chain_id = self.config.chain_id, count))]
+    pub async fn restore_pending_transactions(&self) -> anyhow::Result<()> {
+        let bids = self
+            .repo
+            .get_in_flight_bids(OffsetDateTime::now_utc() - MAX_PENDING_DURATION)
+            .await?;
+        tracing::Span::current().record("count", bids.len());
+        for mut bid in bids {
+            let Some(submission_time) = bid.submission_time else {
+                continue;
+            };
+            // The relayer signature is not stored with the bid
+            self.add_relayer_signature(&mut bid);
+            self.config
+                .chain_config
+                .simulator
+                .add_pending_transaction_submitted_at(&bid.chain_data.transaction, submission_time)
+                .await;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,292 @@
+use {
+    super::Service,
+    crate::{
+        auction::entities,
+        models::ProfileId,
+        per_metrics::SEARCHER_BANNED_TOTAL,
+    },
+    axum_prometheus::metrics,
+    std::collections::HashSet,
+    time::OffsetDateTime,
+};
+
+pub struct RecordReputationEventInput {
+    pub profile_id: ProfileId,
+    pub event:      entities::ReputationEvent,
+}
+
+pub struct GetReputationPenaltiesInput {
+    pub profile_id: ProfileId,
+}
+
+pub struct GetSearcherReputationsInput {
+    pub profile_id: Option<ProfileId>,
+}
+
+impl Service {
+    /// Loads the reputations of the searchers from the database.
+    /// This should be called on startup, before the penalties are applied to the bids.
+    #[tracing::instrument(skip_all, fields(chain_id = self.config.chain_id))]
+    pub async fn load_searcher_reputations(&self) -> anyhow::Result<()> {
+        self.repo.load_searcher_reputations().await
+    }
+
+    /// Counts the event in the reputation of the searcher and bans the searcher
+    /// if a negative event makes its reputation penalized and bans are configured.
+    ///
+    /// The events of a searcher are recorded one at a time, so the in memory reputation
+    /// and the ban decision are always based on the latest counts.
+    #[tracing::instrument(skip_all, fields(profile_id = %input.profile_id, event = ?input.event))]
+    pub async fn record_reputation_event(
+        &self,
+        input: RecordReputationEventInput,
+    ) -> anyhow::Result<()> {
+        let reputation_lock = self
+            .repo
+            .get_or_create_in_memory_reputation_lock(input.profile_id)
+            .await;
+        let _lock = reputation_lock.lock().await;
+        let reputation = self
+            .repo
+            .add_reputation_event(input.profile_id, input.event)
+            .await?;
+        let config = &self.config.chain_config.searcher_reputation;
+        let Some(ban_duration) = config.ban_duration else {
+            return Ok(());
+        };
+        let now = OffsetDateTime::now_utc();
+        if input.event.is_negative()
+            && reputation.is_penalized(config)
+            && !reputation.is_banned(now)
+        {
+            let banned_until = now + ban_duration;
+            self.repo
+                .ban_searcher(input.profile_id, banned_until)
+                .await?;
+            metrics::counter!(
+                SEARCHER_BANNED_TOTAL,
+                &[("chain_id", self.config.chain_id.clone())]
+            )
+            .increment(1);
+            tracing::warn!(
+                failure_rate = reputation.get_failure_rate(),
+                banned_until = %banned_until,
+                "Searcher banned because of failed or unsigned winning bids"
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether the reputation is bad enough for the penalties configured for the chain to apply.
+    pub fn is_searcher_penalized(&self, reputation: &entities::SearcherReputation) -> bool {
+        reputation.is_penalized(&self.config.chain_config.searcher_reputation)
+    }
+
+    pub async fn get_reputation_penalties(
+        &self,
+        input: GetReputationPenaltiesInput,
+    ) -> entities::ReputationPenalties {
+        let Some(reputation) = self
+            .repo
+            .get_in_memory_searcher_reputation(&input.profile_id)
+            .await
+        else {
+            return entities::ReputationPenalties::default();
+        };
+        let config = &self.config.chain_config.searcher_reputation;
+        let is_penalized = self.is_searcher_penalized(&reputation);
+        entities::ReputationPenalties {
+            deprioritized:      is_penalized && config.deprioritize_on_ties,
+            banned_until:       reputation
+                .banned_until
+                .filter(|_| reputation.is_banned(OffsetDateTime::now_utc())),
+            require_simulation: is_penalized && config.require_simulation,
+        }
+    }
+
+    /// Returns the profiles of the bids whose ties are broken in favor of the other bids.
+    pub async fn get_deprioritized_profiles(&self, bids: &[entities::Bid]) -> HashSet<ProfileId> {
+        if !self
+            .config
+            .chain_config
+            .searcher_reputation
+            .deprioritize_on_ties
+        {
+            return HashSet::new();
+        }
+        let mut profiles = HashSet::new();
+        for profile_id in bids.iter().filter_map(|bid| bid.profile_id) {
+            if !profiles.contains(&profile_id)
+                && self
+                    .get_reputation_penalties(GetReputationPenaltiesInput { profile_id })
+                    .await
+                    .deprioritized
+            {
+                profiles.insert(profile_id);
+            }
+        }
+        profiles
+    }
+
+    pub async fn get_searcher_reputations(
+        &self,
+        input: GetSearcherReputationsInput,
+    ) -> Vec<entities::SearcherReputation> {
+        match input.profile_id {
+            Some(profile_id) => self
+                .repo
+                .get_in_memory_searcher_reputation(&profile_id)
+                .await
+                .into_iter()
+                .collect(),
+            None => {
+                let mut reputations = self.repo.get_in_memory_searcher_reputations().await;
+                reputations.sort_by_key(|reputation| reputation.profile_id);
+                reputations
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction::repository::MockDatabase,
+            config::SearcherReputationConfig,
+            kernel::traced_sender_svm::tests::MockRpcClient,
+            opportunity::service::MockService as MockOpportunityService,
+        },
+        std::{
+            sync::Arc,
+            time::Duration,
+        },
+        uuid::Uuid,
+    };
+
+    fn make_reputation(
+        profile_id: ProfileId,
+        auctions_won: u64,
+        bids_failed: u64,
+    ) -> entities::SearcherReputation {
+        entities::SearcherReputation {
+            profile_id,
+            auctions_won,
+            bids_landed: auctions_won - bids_failed,
+            bids_failed,
+            bids_expired: 0,
+            banned_until: None,
+        }
+    }
+
+    /// Returns a service whose database counts the event into the reputation
+    /// and expects the searcher to be banned `bans` times.
+    fn get_service(
+        event: entities::ReputationEvent,
+        reputation: entities::SearcherReputation,
+        bans: usize,
+    ) -> Service {
+        let profile_id = reputation.profile_id;
+        let mut db = MockDatabase::default();
+        db.expect_add_reputation_event()
+            .withf(move |_, id, counted_event| *id == profile_id && *counted_event == event)
+            .times(1)
+            .returning(move |_, _, _| Ok(reputation.clone()));
+        db.expect_ban_searcher()
+            .withf(move |_, id, _| *id == profile_id)
+            .times(bans)
+            .returning(|_, _, _| Ok(()));
+        let mut service = Service::new_with_mocks_svm(
+            "solana".to_string(),
+            db,
+            MockOpportunityService::default(),
+            MockRpcClient::default(),
+            MockRpcClient::default(),
+        );
+        Arc::get_mut(&mut service.0)
+            .expect("Only one reference should exist at setup")
+            .config
+            .chain_config
+            .searcher_reputation = SearcherReputationConfig {
+            minimum_auctions_won: 10,
+            maximum_failure_rate: 0.2,
+            deprioritize_on_ties: false,
+            ban_duration:         Some(Duration::from_secs(3600)),
+            require_simulation:   false,
+        };
+        service
+    }
+
+    #[tokio::test]
+    async fn test_record_reputation_event() {
+        let profile_id = Uuid::new_v4();
+        let reputation = make_reputation(profile_id, 1, 0);
+        let service = get_service(entities::ReputationEvent::AuctionWon, reputation.clone(), 0);
+
+        service
+            .record_reputation_event(RecordReputationEventInput {
+                profile_id,
+                event: entities::ReputationEvent::AuctionWon,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            service
+                .get_searcher_reputations(GetSearcherReputationsInput {
+                    profile_id: Some(profile_id),
+                })
+                .await,
+            vec![reputation]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_reputation_event_below_ban_threshold() {
+        let profile_id = Uuid::new_v4();
+        // A failure rate of 0.1 is below the maximum failure rate
+        let service = get_service(
+            entities::ReputationEvent::BidFailed,
+            make_reputation(profile_id, 10, 1),
+            0,
+        );
+
+        service
+            .record_reputation_event(RecordReputationEventInput {
+                profile_id,
+                event: entities::ReputationEvent::BidFailed,
+            })
+            .await
+            .unwrap();
+        let penalties = service
+            .get_reputation_penalties(GetReputationPenaltiesInput { profile_id })
+            .await;
+        assert_eq!(penalties.banned_until, None);
+    }
+
+    #[tokio::test]
+    async fn test_record_reputation_event_bans_searcher_crossing_threshold() {
+        let profile_id = Uuid::new_v4();
+        // A failure rate of 0.3 is above the maximum failure rate
+        let service = get_service(
+            entities::ReputationEvent::BidFailed,
+            make_reputation(profile_id, 10, 3),
+            1,
+        );
+
+        let before = OffsetDateTime::now_utc();
+        service
+            .record_reputation_event(RecordReputationEventInput {
+                profile_id,
+                event: entities::ReputationEvent::BidFailed,
+            })
+            .await
+            .unwrap();
+        let banned_until = service
+            .get_reputation_penalties(GetReputationPenaltiesInput { profile_id })
+            .await
+            .banned_until
+            .expect("The searcher should be banned");
+        assert!(banned_until >= before + Duration::from_secs(3600));
+    }
+}
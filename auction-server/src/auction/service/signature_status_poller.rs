@@ -0,0 +1,177 @@
+use {
+    futures::future::join_all,
+    solana_client::client_error::ClientError,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::signature::Signature,
+    solana_transaction_status::TransactionStatus,
+    std::{
+        collections::HashMap,
+        sync::Arc,
+        time::Duration,
+    },
+    tokio::sync::{
+        oneshot,
+        Mutex,
+    },
+};
+
+/// Maximum number of signatures the RPC accepts in a single `getSignatureStatuses` request
+const MAX_SIGNATURES_PER_REQUEST: usize = 256;
+
+/// Maximum duration to wait for the poller to return the status of a signature
+const SIGNATURE_STATUS_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub type SignatureStatusResult = Result<Option<TransactionStatus>, Arc<ClientError>>;
+
+/// Batches the signature status requests of all the auctions of a chain.
+///
+/// Callers register the signatures they are interested in and wait for the results.
+/// On each poll, the registered signatures are deduplicated and fetched from the RPC
+/// in chunks of at most `MAX_SIGNATURES_PER_REQUEST` signatures.
+pub struct SignatureStatusPoller {
+    client:   RpcClient,
+    requests: Mutex<HashMap<Signature, Vec<oneshot::Sender<SignatureStatusResult>>>>,
+}
+
+impl SignatureStatusPoller {
+    pub fn new(client: RpcClient) -> Self {
+        Self {
+            client,
+            requests: Default::default(),
+        }
+    }
+
+    /// Returns the statuses of the signatures in the same order as the input.
+    /// The statuses are fetched on the next poll of the poller.
+    pub async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> anyhow::Result<Vec<Option<TransactionStatus>>> {
+        let receivers = {
+            let mut requests = self.requests.lock().await;
+            signatures
+                .iter()
+                .map(|signature| {
+                    let (sender, receiver) = oneshot::channel();
+                    requests.entry(*signature).or_default().push(sender);
+                    receiver
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let results = tokio::time::timeout(SIGNATURE_STATUS_TIMEOUT, join_all(receivers))
+            .await
+            .map_err(|_| anyhow::anyhow!("Timed out waiting for signature statuses"))?;
+        results
+            .into_iter()
+            .map(|result| match result {
+                Ok(Ok(status)) => Ok(status),
+                Ok(Err(e)) => Err(anyhow::anyhow!("Failed to get signature status: {:?}", e)),
+                Err(_) => Err(anyhow::anyhow!(
+                    "Signature status poller dropped the request"
+                )),
+            })
+            .collect()
+    }
+
+    /// Fetches the statuses of all the registered signatures and sends them to the waiting callers.
+    pub async fn poll(&self) {
+        let mut requests = std::mem::take(&mut *self.requests.lock().await);
+        if requests.is_empty() {
+            return;
+        }
+
+        let signatures = requests.keys().cloned().collect::<Vec<_>>();
+        let chunk_results = join_all(
+            signatures
+                .chunks(MAX_SIGNATURES_PER_REQUEST)
+                .map(|chunk| self.client.get_signature_statuses(chunk)),
+        )
+        .await;
+
+        for (chunk, chunk_result) in signatures
+            .chunks(MAX_SIGNATURES_PER_REQUEST)
+            .zip(chunk_results)
+        {
+            let chunk_result = chunk_result.map(|result| result.value).map_err(|e| {
+                tracing::error!(error = ?e, "Failed to get signature statuses");
+                Arc::new(e)
+            });
+            for (index, signature) in chunk.iter().enumerate() {
+                let status: SignatureStatusResult = match &chunk_result {
+                    Ok(statuses) => Ok(statuses.get(index).cloned().flatten()),
+                    Err(e) => Err(e.clone()),
+                };
+                for sender in requests.remove(signature).unwrap_or_default() {
+                    // The receiver may be dropped if the caller timed out
+                    let _ = sender.send(status.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::kernel::traced_sender_svm::tests::MockRpcClient,
+        serde_json::json,
+        solana_client::{
+            rpc_client::RpcClientConfig,
+            rpc_request::RpcRequest,
+        },
+    };
+
+    #[tokio::test]
+    async fn test_poll_chunks_and_deduplicates_signatures() {
+        let mut rpc_client = MockRpcClient::new();
+        rpc_client
+            .expect_send()
+            .withf(|request, _| *request == RpcRequest::GetSignatureStatuses)
+            .times(2)
+            .returning(|_, params| {
+                let count = params[0]
+                    .as_array()
+                    .map_or(0, |signatures| signatures.len());
+                assert!(count <= MAX_SIGNATURES_PER_REQUEST);
+                Ok(json!({
+                    "context": { "slot": 1 },
+                    "value": vec![serde_json::Value::Null; count],
+                }))
+            });
+        let poller = Arc::new(SignatureStatusPoller::new(RpcClient::new_sender(
+            rpc_client,
+            RpcClientConfig::default(),
+        )));
+
+        let signatures = (0..300)
+            .map(|_| Signature::new_unique())
+            .collect::<Vec<_>>();
+        let first = tokio::spawn({
+            let (poller, signatures) = (poller.clone(), signatures.clone());
+            async move { poller.get_signature_statuses(&signatures).await }
+        });
+        let second = tokio::spawn({
+            let (poller, signatures) = (poller.clone(), signatures[..10].to_vec());
+            async move { poller.get_signature_statuses(&signatures).await }
+        });
+
+        // Wait until both callers have registered their signatures
+        while poller
+            .requests
+            .lock()
+            .await
+            .values()
+            .map(Vec::len)
+            .sum::<usize>()
+            < 310
+        {
+            tokio::task::yield_now().await;
+        }
+        poller.poll().await;
+
+        assert_eq!(first.await.unwrap().unwrap(), vec![None; 300]);
+        assert_eq!(second.await.unwrap().unwrap(), vec![None; 10]);
+    }
+}
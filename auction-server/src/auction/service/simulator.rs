@@ -1,5 +1,15 @@
 use {
-    crate::auction::entities::Bid,
+    crate::{
+        auction::{
+            entities::Bid,
+            service::subscribed_account_cache::SubscribedAccountCache,
+        },
+        config::WinnerSelectionMode,
+        kernel::lookup_table_resolver::{
+            LookupTableError,
+            LookupTableResolver,
+        },
+    },
     futures::future::join_all,
     litesvm::{
         types::{
@@ -29,14 +39,17 @@ use {
         commitment_config::CommitmentConfig,
         pubkey::Pubkey,
         signature::Signature,
-        slot_hashes::SlotHashes,
-        transaction::VersionedTransaction,
+        transaction::{
+            TransactionError,
+            VersionedTransaction,
+        },
     },
     std::{
         collections::{
             HashMap,
             HashSet,
         },
+        sync::Arc,
         time::Instant,
     },
     time::{
@@ -47,9 +60,11 @@ use {
 };
 
 pub struct Simulator {
-    receiver:      RpcClient,
-    pending_txs:   RwLock<Vec<(VersionedTransaction, Instant)>>,
-    account_cache: RwLock<HashMap<Pubkey, (Account, Instant)>>,
+    receiver:              RpcClient,
+    pending_txs:           RwLock<Vec<(VersionedTransaction, Instant)>>,
+    account_cache:         RwLock<HashMap<Pubkey, (Account, Instant)>>,
+    subscribed_accounts:   SubscribedAccountCache,
+    lookup_table_resolver: Arc<LookupTableResolver>,
 }
 
 struct AccountsConfig {
@@ -65,6 +80,24 @@ impl AccountsConfig {
         }
     }
 
+    /// Splits the accounts into the program data accounts and the rest of the accounts
+    fn from_accounts(accounts: HashMap<Pubkey, Account>) -> Self {
+        let mut accounts_config = Self::new();
+        for (key, account) in accounts {
+            let is_program_data = account.owner == solana_sdk::bpf_loader_upgradeable::id()
+                && matches!(
+                    account.state(),
+                    Ok(UpgradeableLoaderState::ProgramData { .. })
+                );
+            if is_program_data {
+                accounts_config.programs_data.insert(key, account);
+            } else {
+                accounts_config.accounts.insert(key, account);
+            }
+        }
+        accounts_config
+    }
+
     /// Adds all the accounts to the LiteSVM instance according to their type
     fn apply(&self, svm: &mut LiteSVM) {
         // Need to set the program executable data before the program accounts
@@ -81,24 +114,58 @@ impl AccountsConfig {
     }
 }
 
+/// State of the accounts touched by an auction at a given slot, used to replay the auction
+pub struct AccountsSnapshot {
+    pub slot:      u64,
+    pub timestamp: i64,
+    pub accounts:  HashMap<Pubkey, Account>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BidReplayOutcome {
+    /// The bid is selected as a winner
+    Won,
+    /// The bid fails even when it is executed on its own
+    SimulationFailed { error: String, logs: Vec<String> },
+    /// The bid succeeds on its own but fails after the winning bids are executed
+    FailedAfterWinners { error: String, logs: Vec<String> },
+    /// The bid can be executed after the winners but it conflicts with a set of bids paying more
+    NotSelected,
+}
+
+#[derive(Debug, Clone)]
+pub struct BidReplayResult {
+    pub bid:     Bid,
+    pub outcome: BidReplayOutcome,
+}
+
 // TODO: Remove pending transactions if the submit bid deadline is reached
 /// Maximum duration for a transaction to be considered pending without any confirmation on-chain
 /// This value may differ from how long the auction server retries to send the transaction
-const MAX_PENDING_DURATION: Duration = Duration::seconds(15);
+pub const MAX_PENDING_DURATION: Duration = Duration::seconds(15);
 
-/// Cache duration for accounts that are not expected to change frequently
-/// (Program accounts, Lookup Table accounts)
+/// Cache duration for accounts that are not expected to change frequently (Program data accounts)
 const ACCOUNT_CACHE_DURATION: Duration = Duration::hours(1);
 
 impl Simulator {
-    pub fn new(receiver: RpcClient) -> Self {
+    pub fn new(
+        receiver: RpcClient,
+        subscribed_accounts: SubscribedAccountCache,
+        lookup_table_resolver: Arc<LookupTableResolver>,
+    ) -> Self {
         Self {
             receiver,
             pending_txs: Default::default(),
             account_cache: Default::default(),
+            subscribed_accounts,
+            lookup_table_resolver,
         }
     }
 
+    pub fn subscribed_accounts(&self) -> &SubscribedAccountCache {
+        &self.subscribed_accounts
+    }
+
     pub async fn fetch_pending_and_remove_old_txs(&self) -> Vec<VersionedTransaction> {
         let now = Instant::now();
         let mut pending_txs = self.pending_txs.write().await;
@@ -113,6 +180,24 @@ impl Simulator {
         self.pending_txs.write().await.push((tx.clone(), now));
     }
 
+    /// Adds a pending transaction that was submitted to the chain at the given time, e.g. before a restart.
+    /// The transaction is only considered until `MAX_PENDING_DURATION` has passed since its submission.
+    pub async fn add_pending_transaction_submitted_at(
+        &self,
+        tx: &VersionedTransaction,
+        submission_time: OffsetDateTime,
+    ) {
+        let elapsed = OffsetDateTime::now_utc() - submission_time;
+        if elapsed >= MAX_PENDING_DURATION {
+            return;
+        }
+        let now = Instant::now();
+        let time = now
+            .checked_sub(elapsed.try_into().unwrap_or_default())
+            .unwrap_or(now);
+        self.pending_txs.write().await.push((tx.clone(), time));
+    }
+
     /// Removes a pending transaction from the simulator
     /// This function should be called when a transaction is confirmed on-chain
     pub async fn remove_pending_transaction(&self, sig: &Signature) {
@@ -143,17 +228,20 @@ impl Simulator {
 
     /// Tries to get accounts from cache, if any of them are not found, fetches all of them from RPC
     /// and updates the cache.
-    /// You should only use this function for accounts that are not expected to change frequently:
-    /// - Program accounts
-    /// - Lookup Table accounts
+    /// You should only use this function for accounts that are not expected to change frequently,
+    /// e.g. program data accounts.
     async fn get_multiple_accounts_with_cache(
         &self,
         keys: &[Pubkey],
     ) -> client_error::Result<Vec<Option<Account>>> {
+        if let Some(accounts) = self.subscribed_accounts.get_all_accounts(keys).await {
+            return Ok(accounts.into_iter().map(Some).collect());
+        }
         if let Some(accounts) = self.try_get_accounts_from_cache(keys).await {
             return Ok(accounts.into_iter().map(Some).collect());
         }
         let result = self.get_multiple_accounts_chunked(keys).await?;
+        self.subscribed_accounts.track(keys, &result.value).await;
         let mut cache = self.account_cache.write().await;
         for (key, account) in keys.iter().zip(result.value.iter()) {
             if let Some(account) = account {
@@ -200,6 +288,8 @@ impl Simulator {
         })
     }
 
+    /// Returns the lookup tables used by the transactions and the addresses loaded from them.
+    /// Tables that can not be resolved are skipped, the simulation of their transactions will fail.
     async fn resolve_lookup_addresses(
         &self,
         transactions: &[&VersionedTransaction],
@@ -218,27 +308,21 @@ impl Simulator {
                 )
             });
 
-        let accs = self
-            .get_multiple_accounts_with_cache(
-                &lookup_table_keys.keys().cloned().collect::<Vec<_>>(),
-            )
-            .await?;
+        let lookups = lookup_table_keys
+            .into_iter()
+            .map(|(table, indexes)| (table, indexes.into_iter().collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        let resolved = self.lookup_table_resolver.resolve_many(&lookups).await;
         let mut results = vec![];
-        for ((lookup_table_address, indexes), lookup_table_account) in
-            lookup_table_keys.iter().zip(accs.iter())
-        {
+        for ((lookup_table_address, _), addresses) in lookups.iter().zip(resolved) {
             results.push(*lookup_table_address);
-            if let Some(lookup_account) = lookup_table_account {
-                if let Ok(table_data_deserialized) =
-                    AddressLookupTable::deserialize(&lookup_account.data)
-                {
-                    if let Ok(addrs) = table_data_deserialized.lookup(
-                        table_data_deserialized.meta.last_extended_slot + 1,
-                        &indexes.iter().cloned().collect::<Vec<_>>(),
-                        &SlotHashes::default(),
-                    ) {
-                        results.extend(addrs.iter());
-                    }
+            match addresses {
+                Ok(addresses) => results.extend(addresses),
+                Err(LookupTableError::Rpc(e)) => {
+                    return Err(client_error::ClientErrorKind::Custom(e).into());
+                }
+                Err(e) => {
+                    tracing::warn!(error = e.to_string(), "Failed to resolve lookup table");
                 }
             }
         }
@@ -246,7 +330,9 @@ impl Simulator {
     }
 
     /// Fetches all the accounts needed for simulating the transactions via RPC
-    /// Uses the account cache to avoid fetching programs and lookup tables multiple times
+    /// Uses the account cache to avoid fetching programs multiple times, the lookup table resolver
+    /// to resolve the addresses loaded from lookup tables and the subscribed account cache to avoid
+    /// fetching the hot accounts
     /// Returns an AccountsConfig struct that can be used to initialize the LiteSVM instance
     #[tracing::instrument(skip_all, fields(slot))]
     async fn fetch_tx_accounts_via_rpc(
@@ -262,13 +348,20 @@ impl Simulator {
         keys.extend(self.resolve_lookup_addresses(transactions).await?);
         let keys = keys.into_iter().collect::<Vec<_>>();
 
+        let (cached_accounts, keys) = self.subscribed_accounts.get_accounts(&keys).await;
         let accounts_with_context = self.get_multiple_accounts_chunked(&keys).await?;
         tracing::Span::current().record("slot", accounts_with_context.context.slot);
+        self.subscribed_accounts
+            .track(&keys, &accounts_with_context.value)
+            .await;
         let accounts = accounts_with_context.value;
         let mut accounts_config = AccountsConfig::new();
         let mut program_data_addresses = vec![];
 
-        for (account_key, account) in keys.iter().zip(accounts.iter()) {
+        let cached_accounts = cached_accounts
+            .into_iter()
+            .map(|(key, account)| (key, Some(account)));
+        for (account_key, account) in keys.into_iter().zip(accounts).chain(cached_accounts) {
             // it's ok to not have an account (this account is created by the transaction)
             if let Some(account) = account {
                 if account.owner == solana_sdk::bpf_loader_upgradeable::id() {
@@ -279,9 +372,7 @@ impl Simulator {
                         program_data_addresses.push(programdata_address);
                     }
                 }
-                accounts_config
-                    .accounts
-                    .insert(*account_key, account.clone());
+                accounts_config.accounts.insert(account_key, account);
             }
         }
 
@@ -306,22 +397,30 @@ impl Simulator {
 
     #[tracing::instrument(skip_all)]
     fn setup_lite_svm(&self, accounts_config_with_context: &Response<AccountsConfig>) -> LiteSVM {
+        // we grab the timestamp after fetching the accounts to maximize chance of timestamp exceeds any timestamps stored in fetched accounts
+        Self::setup_lite_svm_at(
+            &accounts_config_with_context.value,
+            accounts_config_with_context.context.slot,
+            OffsetDateTime::now_utc().unix_timestamp(),
+        )
+    }
+
+    fn setup_lite_svm_at(accounts_config: &AccountsConfig, slot: u64, timestamp: i64) -> LiteSVM {
         let mut svm = LiteSVM::new()
             .with_sigverify(false)
             .with_blockhash_check(false)
             .with_transaction_history(0);
         // this is necessary for correct lookup table access
         // otherwise 0 = slot < table.last_extended_slot
-        svm.warp_to_slot(accounts_config_with_context.context.slot);
-        // we grab the timestamp after fetching the accounts to maximize chance of timestamp exceeds any timestamps stored in fetched accounts
-        self.warp_to_timestamp(&mut svm, OffsetDateTime::now_utc().unix_timestamp());
-        accounts_config_with_context.value.apply(&mut svm);
+        svm.warp_to_slot(slot);
+        Self::warp_to_timestamp(&mut svm, timestamp);
+        accounts_config.apply(&mut svm);
         svm
     }
 
     /// Warps the LiteSVM object clock to the given timestamp
     /// This is necessary because LiteSVM does not natively support warping to a timestamp
-    fn warp_to_timestamp(&self, svm: &mut LiteSVM, timestamp: i64) {
+    fn warp_to_timestamp(svm: &mut LiteSVM, timestamp: i64) {
         let mut clock = svm.get_sysvar::<Clock>();
         clock.unix_timestamp = timestamp;
         svm.set_sysvar(&clock);
@@ -378,6 +477,25 @@ impl Simulator {
         });
         let res = svm.simulate_transaction(transaction.clone());
         let res = Self::check_rent_exemption(&svm, res);
+        // The cached addresses of the lookup tables may be outdated, e.g. if a table was closed
+        if let Err(FailedTransactionMetadata {
+            err:
+                TransactionError::AddressLookupTableNotFound
+                | TransactionError::InvalidAddressLookupTableOwner
+                | TransactionError::InvalidAddressLookupTableData
+                | TransactionError::InvalidAddressLookupTableIndex,
+            ..
+        }) = &res
+        {
+            let tables = transaction
+                .message
+                .address_table_lookups()
+                .unwrap_or_default()
+                .iter()
+                .map(|lookup| lookup.account_key)
+                .collect::<Vec<_>>();
+            self.lookup_table_resolver.invalidate(&tables).await;
+        }
         Ok(Response {
             value:   res,
             context: accounts_config_with_context.context,
@@ -386,32 +504,533 @@ impl Simulator {
 
     /// Given a list of bids, tries to find the optimal set of bids that can be submitted to the chain
     /// considering the current state of the chain and the pending transactions.
-    /// Right now, for simplicity, the method assume the bids are sorted, and tries to submit them in order
-    /// and only return the ones that are successfully submitted.
-    pub async fn optimize_bids(&self, bids_sorted: &[Bid]) -> RpcResult<Vec<Bid>> {
+    /// The bids are expected to be sorted by amount in descending order.
+    /// The returned bids keep the same relative order as the input.
+    pub async fn optimize_bids(
+        &self,
+        bids_sorted: &[Bid],
+        mode: WinnerSelectionMode,
+    ) -> RpcResult<Vec<Bid>> {
         let pending_txs = self.fetch_pending_and_remove_old_txs().await;
         let txs_to_fetch = pending_txs
             .iter()
             .chain(bids_sorted.iter().map(|bid| &bid.chain_data.transaction))
             .collect::<Vec<_>>();
         let accounts_config_with_context = self.fetch_tx_accounts_via_rpc(&txs_to_fetch).await?;
-        let mut svm = self.setup_lite_svm(&accounts_config_with_context);
+        let setup_svm = || {
+            let mut svm = self.setup_lite_svm(&accounts_config_with_context);
+            pending_txs.iter().for_each(|tx| {
+                let _ = svm.send_transaction(tx.clone());
+            });
+            svm
+        };
 
-        pending_txs.into_iter().for_each(|tx| {
-            let _ = svm.send_transaction(tx);
-        });
-        let mut res = vec![];
-        for bid in bids_sorted {
-            if svm
-                .send_transaction(bid.chain_data.transaction.clone())
-                .is_ok()
-            {
-                res.push(bid.clone());
-            }
-        }
         Ok(Response {
-            value:   res,
+            value:   Self::select_bids(setup_svm, bids_sorted, mode),
             context: accounts_config_with_context.context,
         })
     }
+
+    /// Selects the winning bids with the given mode.
+    /// `setup_svm` must return a fresh LiteSVM instance with the state the bids are executed on.
+    fn select_bids(
+        setup_svm: impl Fn() -> LiteSVM,
+        bids_sorted: &[Bid],
+        mode: WinnerSelectionMode,
+    ) -> Vec<Bid> {
+        match mode {
+            WinnerSelectionMode::Sequential => {
+                Self::select_bids_sequential(setup_svm(), bids_sorted)
+            }
+            WinnerSelectionMode::BundleAware => {
+                let baseline = Self::select_bids_sequential(setup_svm(), bids_sorted);
+                let bundle = Self::select_bids_bundle_aware(&setup_svm(), bids_sorted);
+                // The final replay of the bundle may drop some of the chosen bids,
+                // so we never return a worse result than the sequential selection
+                let bundle = Self::select_bids_sequential(setup_svm(), &bundle);
+                if get_total_amount(&bundle) > get_total_amount(&baseline) {
+                    bundle
+                } else {
+                    baseline
+                }
+            }
+        }
+    }
+
+    /// Runs the winner selection on a snapshot of the chain state instead of the live state.
+    /// Pending transactions are not applied, as the snapshot is expected to already include them.
+    /// Returns the outcome of every bid in the same order as the input, with the reason of the outcome.
+    pub fn replay_bids(
+        snapshot: &AccountsSnapshot,
+        bids_sorted: &[Bid],
+        mode: WinnerSelectionMode,
+    ) -> Vec<BidReplayResult> {
+        let accounts_config = AccountsConfig::from_accounts(snapshot.accounts.clone());
+        let setup_svm =
+            || Self::setup_lite_svm_at(&accounts_config, snapshot.slot, snapshot.timestamp);
+        let winners = Self::select_bids(setup_svm, bids_sorted, mode);
+        let winner_ids = winners.iter().map(|bid| bid.id).collect::<HashSet<_>>();
+
+        let mut svm_after_winners = setup_svm();
+        for bid in winners.iter() {
+            let _ = svm_after_winners.send_transaction(bid.chain_data.transaction.clone());
+        }
+        let svm = setup_svm();
+        bids_sorted
+            .iter()
+            .map(|bid| {
+                let tx = &bid.chain_data.transaction;
+                let outcome = if winner_ids.contains(&bid.id) {
+                    BidReplayOutcome::Won
+                } else if let Err(e) =
+                    Self::check_rent_exemption(&svm, svm.simulate_transaction(tx.clone()))
+                {
+                    BidReplayOutcome::SimulationFailed {
+                        error: e.err.to_string(),
+                        logs:  e.meta.logs,
+                    }
+                } else if let Err(e) = Self::check_rent_exemption(
+                    &svm_after_winners,
+                    svm_after_winners.simulate_transaction(tx.clone()),
+                ) {
+                    BidReplayOutcome::FailedAfterWinners {
+                        error: e.err.to_string(),
+                        logs:  e.meta.logs,
+                    }
+                } else {
+                    BidReplayOutcome::NotSelected
+                };
+                BidReplayResult {
+                    bid: bid.clone(),
+                    outcome,
+                }
+            })
+            .collect()
+    }
+
+    /// Submits the bids in order and returns the ones that are successfully executed.
+    fn select_bids_sequential(mut svm: LiteSVM, bids_sorted: &[Bid]) -> Vec<Bid> {
+        bids_sorted
+            .iter()
+            .filter(|bid| {
+                svm.send_transaction(bid.chain_data.transaction.clone())
+                    .is_ok()
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Chooses the set of non-conflicting bids with the highest total payment.
+    ///
+    /// Every bid is first simulated on its own and failing bids are dropped.
+    /// Two bids conflict if one of them modifies the data of an account that the other one locks.
+    /// Accounts that only receive lamports (e.g. fee receivers) are not considered as conflicts,
+    /// since the order of such transfers does not change the outcome of the transactions.
+    fn select_bids_bundle_aware(svm: &LiteSVM, bids_sorted: &[Bid]) -> Vec<Bid> {
+        let candidates = bids_sorted
+            .iter()
+            .filter_map(|bid| {
+                let tx = &bid.chain_data.transaction;
+                let simulation = svm.simulate_transaction(tx.clone());
+                let simulation = Self::check_rent_exemption(svm, simulation).ok()?;
+                let (writable, readonly) = Self::get_account_locks(svm, tx);
+                let modified = Self::get_modified_accounts(svm, &simulation);
+                Some(BidAccountLocks {
+                    bid,
+                    contended: writable.intersection(&modified).cloned().collect(),
+                    locked: writable.union(&readonly).cloned().collect(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let conflicts = candidates
+            .iter()
+            .map(|a| candidates.iter().map(|b| a.conflicts_with(b)).collect())
+            .collect::<Vec<Vec<bool>>>();
+        let amounts = candidates
+            .iter()
+            .map(|candidate| candidate.bid.amount)
+            .collect::<Vec<_>>();
+        find_max_payment_set(&amounts, &conflicts)
+            .into_iter()
+            .map(|index| candidates[index].bid.clone())
+            .collect()
+    }
+
+    /// Returns the writable and readonly accounts locked by the transaction.
+    /// Accounts loaded from lookup tables are resolved using the lookup table accounts in the LiteSVM instance.
+    fn get_account_locks(
+        svm: &LiteSVM,
+        tx: &VersionedTransaction,
+    ) -> (HashSet<Pubkey>, HashSet<Pubkey>) {
+        let mut writable = HashSet::new();
+        let mut readonly = HashSet::new();
+        for (index, key) in tx.message.static_account_keys().iter().enumerate() {
+            if tx.message.is_maybe_writable(index, None) {
+                writable.insert(*key);
+            } else {
+                readonly.insert(*key);
+            }
+        }
+        for lookup in tx.message.address_table_lookups().unwrap_or_default() {
+            let Some(table_account) = svm.get_account(&lookup.account_key) else {
+                continue;
+            };
+            let Ok(table) = AddressLookupTable::deserialize(&table_account.data) else {
+                continue;
+            };
+            let resolve = |indexes: &[u8]| {
+                indexes
+                    .iter()
+                    .filter_map(|index| table.addresses.get(*index as usize).cloned())
+                    .collect::<Vec<_>>()
+            };
+            writable.extend(resolve(&lookup.writable_indexes));
+            readonly.extend(resolve(&lookup.readonly_indexes));
+        }
+        (writable, readonly)
+    }
+
+    /// Returns the accounts whose data or owner is changed by the simulated transaction,
+    /// including the accounts created by the transaction.
+    fn get_modified_accounts(
+        svm: &LiteSVM,
+        simulation: &SimulatedTransactionInfo,
+    ) -> HashSet<Pubkey> {
+        simulation
+            .post_accounts
+            .iter()
+            .filter(|(key, post)| match svm.get_account(key) {
+                Some(pre) => pre.data != post.data() || pre.owner != *post.owner(),
+                None => post.lamports() > 0,
+            })
+            .map(|(key, _)| *key)
+            .collect()
+    }
+}
+
+/// Maximum number of candidate bids to search exhaustively in the bundle aware selection.
+/// The remaining candidates are added greedily in descending amount order.
+const MAX_BUNDLE_SEARCH_CANDIDATES: usize = 16;
+
+struct BidAccountLocks<'a> {
+    bid:       &'a Bid,
+    /// Locked accounts whose state is modified by the bid
+    contended: HashSet<Pubkey>,
+    /// All the accounts locked by the bid
+    locked:    HashSet<Pubkey>,
+}
+
+impl BidAccountLocks<'_> {
+    fn conflicts_with(&self, other: &BidAccountLocks) -> bool {
+        !self.contended.is_disjoint(&other.locked) || !other.contended.is_disjoint(&self.locked)
+    }
+}
+
+fn get_total_amount(bids: &[Bid]) -> u128 {
+    bids.iter().map(|bid| bid.amount as u128).sum()
+}
+
+/// Finds the set of non-conflicting items with the highest total amount.
+/// `amounts` is expected to be sorted in descending order and `conflicts` to be a symmetric matrix.
+/// Returns the indexes of the chosen items in ascending order.
+fn find_max_payment_set(amounts: &[u64], conflicts: &[Vec<bool>]) -> Vec<usize> {
+    fn search(
+        index: usize,
+        amounts: &[u64],
+        conflicts: &[Vec<bool>],
+        remaining: u128,
+        current: &mut Vec<usize>,
+        current_total: u128,
+        best: &mut (Vec<usize>, u128),
+    ) {
+        if current_total > best.1 {
+            *best = (current.clone(), current_total);
+        }
+        // No way to beat the best set even if all the remaining items are chosen
+        if index == amounts.len() || current_total + remaining <= best.1 {
+            return;
+        }
+        let amount = amounts[index] as u128;
+        if current.iter().all(|chosen| !conflicts[*chosen][index]) {
+            current.push(index);
+            search(
+                index + 1,
+                amounts,
+                conflicts,
+                remaining - amount,
+                current,
+                current_total + amount,
+                best,
+            );
+            current.pop();
+        }
+        search(
+            index + 1,
+            amounts,
+            conflicts,
+            remaining - amount,
+            current,
+            current_total,
+            best,
+        );
+    }
+
+    let search_len = amounts.len().min(MAX_BUNDLE_SEARCH_CANDIDATES);
+    let remaining = amounts[..search_len].iter().map(|x| *x as u128).sum();
+    let mut best = (vec![], 0);
+    search(
+        0,
+        &amounts[..search_len],
+        conflicts,
+        remaining,
+        &mut vec![],
+        0,
+        &mut best,
+    );
+    let mut chosen = best.0;
+    for index in search_len..amounts.len() {
+        if chosen.iter().all(|c| !conflicts[*c][index]) {
+            chosen.push(index);
+        }
+    }
+    chosen
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{
+            find_max_payment_set,
+            AccountsConfig,
+            LookupTableResolver,
+            Simulator,
+            SubscribedAccountCache,
+            MAX_PENDING_DURATION,
+        },
+        crate::{
+            auction::entities::{
+                Bid,
+                BidChainDataSvm,
+                BidPaymentInstructionType,
+                BidStatusSvm,
+            },
+            config::WinnerSelectionMode,
+            kernel::traced_sender_svm::tests::MockRpcClient,
+        },
+        solana_client::{
+            nonblocking::rpc_client::RpcClient,
+            rpc_client::RpcClientConfig,
+        },
+        solana_sdk::{
+            account::Account,
+            hash::Hash,
+            instruction::Instruction,
+            native_token::LAMPORTS_PER_SOL,
+            pubkey::Pubkey,
+            signature::{
+                Keypair,
+                Signer,
+            },
+            system_instruction,
+            transaction::{
+                Transaction,
+                VersionedTransaction,
+            },
+        },
+        std::{
+            collections::HashMap,
+            sync::Arc,
+        },
+        time::{
+            Duration,
+            OffsetDateTime,
+        },
+        uuid::Uuid,
+    };
+
+    fn get_transaction() -> VersionedTransaction {
+        let payer = Keypair::new();
+        let instruction =
+            system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1);
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        )
+        .into()
+    }
+
+    #[tokio::test]
+    async fn test_add_pending_transaction_submitted_at() {
+        let simulator = Simulator::new(
+            RpcClient::new_sender(MockRpcClient::new(), RpcClientConfig::default()),
+            SubscribedAccountCache::new("solana".to_string(), Pubkey::new_unique()),
+            Arc::new(LookupTableResolver::new(
+                "solana".to_string(),
+                RpcClient::new_sender(MockRpcClient::new(), RpcClientConfig::default()),
+            )),
+        );
+        let (recent_tx, old_tx) = (get_transaction(), get_transaction());
+        let now = OffsetDateTime::now_utc();
+
+        simulator
+            .add_pending_transaction_submitted_at(&recent_tx, now - Duration::seconds(1))
+            .await;
+        simulator
+            .add_pending_transaction_submitted_at(
+                &old_tx,
+                now - MAX_PENDING_DURATION - Duration::seconds(1),
+            )
+            .await;
+
+        assert_eq!(
+            simulator.fetch_pending_and_remove_old_txs().await,
+            vec![recent_tx]
+        );
+    }
+
+    fn conflicts_from_pairs(len: usize, pairs: &[(usize, usize)]) -> Vec<Vec<bool>> {
+        let mut conflicts = vec![vec![false; len]; len];
+        for (a, b) in pairs {
+            conflicts[*a][*b] = true;
+            conflicts[*b][*a] = true;
+        }
+        conflicts
+    }
+
+    #[test]
+    fn test_find_max_payment_set_no_conflicts() {
+        let conflicts = conflicts_from_pairs(3, &[]);
+        assert_eq!(find_max_payment_set(&[5, 3, 1], &conflicts), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_find_max_payment_set_big_bid_blocks_smaller_bids() {
+        // The biggest bid conflicts with two smaller bids that together pay more
+        let conflicts = conflicts_from_pairs(3, &[(0, 1), (0, 2)]);
+        assert_eq!(find_max_payment_set(&[10, 6, 5], &conflicts), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_find_max_payment_set_keeps_big_bid() {
+        let conflicts = conflicts_from_pairs(3, &[(0, 1), (0, 2)]);
+        assert_eq!(find_max_payment_set(&[12, 6, 5], &conflicts), vec![0]);
+    }
+
+    #[test]
+    fn test_find_max_payment_set_empty() {
+        assert_eq!(find_max_payment_set(&[], &[]), Vec::<usize>::new());
+    }
+
+    /// Returns a bid whose transaction is signed by a new funded payer and the given signers.
+    fn get_bid(
+        amount: u64,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        accounts: &mut HashMap<Pubkey, Account>,
+    ) -> Bid {
+        let payer = Keypair::new();
+        accounts.insert(
+            payer.pubkey(),
+            Account::new(LAMPORTS_PER_SOL, 0, &solana_sdk::system_program::id()),
+        );
+        let signers = std::iter::once(&payer)
+            .chain(signers.iter().copied())
+            .collect::<Vec<_>>();
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &signers,
+            Hash::new_unique(),
+        );
+        let now = OffsetDateTime::now_utc();
+        Bid {
+            id: Uuid::new_v4(),
+            chain_id: "solana".to_string(),
+            initiation_time: now,
+            profile_id: None,
+            opportunity_id: None,
+            creation_time: now,
+            conclusion_time: None,
+            submission_time: None,
+            amount,
+            status: BidStatusSvm::Pending,
+            chain_data: BidChainDataSvm {
+                transaction:                  transaction.into(),
+                bid_payment_instruction_type: BidPaymentInstructionType::SubmitBid,
+                router:                       Pubkey::default(),
+                permission_account:           Pubkey::default(),
+                last_valid_block_height:      None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_select_bids_bundle_aware_drops_conflicting_top_bid() {
+        // Allocating the data of an account can only succeed once, so the bids allocating the same account conflict
+        let (first_account, second_account) = (Keypair::new(), Keypair::new());
+        let mut accounts = HashMap::new();
+        for account in [&first_account, &second_account] {
+            accounts.insert(
+                account.pubkey(),
+                Account::new(LAMPORTS_PER_SOL, 0, &solana_sdk::system_program::id()),
+            );
+        }
+        let allocate = |account: &Keypair| system_instruction::allocate(&account.pubkey(), 8);
+
+        // The top bid conflicts with both of the lower bids, which pay more together
+        let bids = vec![
+            get_bid(
+                100,
+                &[allocate(&first_account), allocate(&second_account)],
+                &[&first_account, &second_account],
+                &mut accounts,
+            ),
+            get_bid(
+                80,
+                &[allocate(&first_account)],
+                &[&first_account],
+                &mut accounts,
+            ),
+            get_bid(
+                70,
+                &[allocate(&second_account)],
+                &[&second_account],
+                &mut accounts,
+            ),
+        ];
+        let accounts_config = AccountsConfig::from_accounts(accounts);
+        let setup_svm = || {
+            Simulator::setup_lite_svm_at(
+                &accounts_config,
+                100,
+                OffsetDateTime::now_utc().unix_timestamp(),
+            )
+        };
+        let get_ids = |bids: &[Bid]| bids.iter().map(|bid| bid.id).collect::<Vec<_>>();
+
+        assert_eq!(
+            get_ids(&Simulator::select_bids_bundle_aware(&setup_svm(), &bids)),
+            get_ids(&bids[1..])
+        );
+        assert_eq!(
+            get_ids(&Simulator::select_bids(
+                setup_svm,
+                &bids,
+                WinnerSelectionMode::BundleAware
+            )),
+            get_ids(&bids[1..])
+        );
+        // The sequential selection executes the top bid first, so the lower bids fail
+        assert_eq!(
+            get_ids(&Simulator::select_bids(
+                setup_svm,
+                &bids,
+                WinnerSelectionMode::Sequential
+            )),
+            get_ids(&bids[..1])
+        );
+    }
 }
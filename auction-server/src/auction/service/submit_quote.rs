@@ -137,6 +137,9 @@ impl Service {
             return Err(RestError::QuoteIsCancelled);
         }
 
+        // Only one of the quotes returned for the same quote request can be submitted
+        self.resolve_quote_fallback_auctions(auction.id).await?;
+
         let tx_hash = signed_bid.chain_data.transaction.signatures[0];
         let auction = self
             .repo
@@ -0,0 +1,331 @@
+use {
+    crate::{
+        kernel::entities::ChainId,
+        per_metrics::SIMULATOR_ACCOUNT_CACHE_TOTAL,
+    },
+    axum_prometheus::metrics,
+    solana_account_decoder::UiAccount,
+    solana_client::rpc_response::{
+        Response,
+        RpcKeyedAccount,
+    },
+    solana_sdk::{
+        account::Account,
+        address_lookup_table,
+        bpf_loader_upgradeable,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Mint,
+    spl_token_2022::{
+        extension::StateWithExtensions,
+        state::Mint as Mint2022,
+    },
+    std::{
+        collections::HashMap,
+        str::FromStr,
+    },
+    tokio::sync::{
+        mpsc,
+        Mutex,
+        MutexGuard,
+        RwLock,
+    },
+};
+
+/// Maximum number of accounts to keep subscriptions for
+const MAX_SUBSCRIBED_ACCOUNTS: usize = 2000;
+
+const METRIC_LABEL_HIT: &str = "hit";
+const METRIC_LABEL_MISS: &str = "miss";
+
+/// Long-lived cache for hot accounts that rarely change but are needed by most simulations:
+/// token mints, express relay program accounts, lookup tables and program data accounts.
+///
+/// Accounts are only served from the cache while they are kept up to date by the account subscriptions,
+/// which are managed by the account subscription loop of the auction service.
+/// Newly seen hot accounts are queued for subscription and served from the RPC until then.
+pub struct SubscribedAccountCache {
+    chain_id:                 ChainId,
+    express_relay_program_id: Pubkey,
+    /// Up to date accounts with the slot of their last update
+    accounts:                 RwLock<HashMap<Pubkey, (Account, u64)>>,
+    /// Hot accounts that should be subscribed to, with their owners
+    tracked_keys:             Mutex<HashMap<Pubkey, Pubkey>>,
+    new_keys_sender:          mpsc::UnboundedSender<(Pubkey, Pubkey)>,
+    new_keys_receiver:        Mutex<mpsc::UnboundedReceiver<(Pubkey, Pubkey)>>,
+}
+
+impl SubscribedAccountCache {
+    pub fn new(chain_id: ChainId, express_relay_program_id: Pubkey) -> Self {
+        let (new_keys_sender, new_keys_receiver) = mpsc::unbounded_channel();
+        Self {
+            chain_id,
+            express_relay_program_id,
+            accounts: Default::default(),
+            tracked_keys: Default::default(),
+            new_keys_sender,
+            new_keys_receiver: Mutex::new(new_keys_receiver),
+        }
+    }
+
+    pub fn express_relay_program_id(&self) -> Pubkey {
+        self.express_relay_program_id
+    }
+
+    fn record_lookups(&self, hits: usize, misses: usize) {
+        for (result, count) in [(METRIC_LABEL_HIT, hits), (METRIC_LABEL_MISS, misses)] {
+            metrics::counter!(
+                SIMULATOR_ACCOUNT_CACHE_TOTAL,
+                &[
+                    ("chain_id", self.chain_id.clone()),
+                    ("result", result.to_string()),
+                ]
+            )
+            .increment(count as u64);
+        }
+    }
+
+    /// Returns the cached accounts and the keys that are not in the cache.
+    pub async fn get_accounts(&self, keys: &[Pubkey]) -> (HashMap<Pubkey, Account>, Vec<Pubkey>) {
+        let accounts = self.accounts.read().await;
+        let mut cached = HashMap::new();
+        let mut missing = vec![];
+        for key in keys {
+            match accounts.get(key) {
+                Some((account, _)) => {
+                    cached.insert(*key, account.clone());
+                }
+                None => missing.push(*key),
+            }
+        }
+        self.record_lookups(cached.len(), missing.len());
+        (cached, missing)
+    }
+
+    /// Returns all the accounts if they are all in the cache, otherwise returns None.
+    pub async fn get_all_accounts(&self, keys: &[Pubkey]) -> Option<Vec<Account>> {
+        let (mut cached, missing) = self.get_accounts(keys).await;
+        if !missing.is_empty() {
+            return None;
+        }
+        keys.iter().map(|key| cached.remove(key)).collect()
+    }
+
+    fn is_hot_account(&self, account: &Account) -> bool {
+        if account.owner == self.express_relay_program_id
+            || account.owner == address_lookup_table::program::id()
+            || account.owner == bpf_loader_upgradeable::id()
+        {
+            return true;
+        }
+        if account.owner == spl_token::id() {
+            return Mint::unpack(&account.data).is_ok();
+        }
+        if account.owner == spl_token_2022::id() {
+            return StateWithExtensions::<Mint2022>::unpack(&account.data).is_ok();
+        }
+        false
+    }
+
+    /// Queues the hot accounts among the fetched accounts for subscription.
+    pub async fn track(&self, keys: &[Pubkey], accounts: &[Option<Account>]) {
+        let mut tracked_keys = self.tracked_keys.lock().await;
+        for (key, account) in keys.iter().zip(accounts.iter()) {
+            if tracked_keys.len() >= MAX_SUBSCRIBED_ACCOUNTS {
+                break;
+            }
+            let Some(account) = account else {
+                continue;
+            };
+            if self.is_hot_account(account) && tracked_keys.insert(*key, account.owner).is_none() {
+                if let Err(e) = self.new_keys_sender.send((*key, account.owner)) {
+                    tracing::error!(error = ?e, "Failed to queue account for subscription");
+                }
+            }
+        }
+    }
+
+    /// Removes the account from the cache and queues it for subscription again.
+    /// This should be called when the subscription of the account ends, as the account is not kept up to date anymore.
+    pub async fn resubscribe(&self, key: &Pubkey) {
+        self.accounts.write().await.remove(key);
+        let Some(owner) = self.tracked_keys.lock().await.get(key).copied() else {
+            return;
+        };
+        if let Err(e) = self.new_keys_sender.send((*key, owner)) {
+            tracing::error!(error = ?e, "Failed to queue account for subscription");
+        }
+    }
+
+    /// Returns the tracked keys with their owners.
+    pub async fn get_tracked_keys(&self) -> Vec<(Pubkey, Pubkey)> {
+        self.tracked_keys
+            .lock()
+            .await
+            .iter()
+            .map(|(key, owner)| (*key, *owner))
+            .collect()
+    }
+
+    /// Returns the receiver of the keys (with their owners) that are newly queued for subscription.
+    /// Only one subscription loop can hold the receiver at a time.
+    pub async fn lock_new_keys_receiver(
+        &self,
+    ) -> MutexGuard<'_, mpsc::UnboundedReceiver<(Pubkey, Pubkey)>> {
+        self.new_keys_receiver.lock().await
+    }
+
+    /// Stores the account if it's newer than the cached one.
+    pub async fn update(&self, key: Pubkey, account: Option<Account>, slot: u64) {
+        let mut accounts = self.accounts.write().await;
+        if accounts
+            .get(&key)
+            .is_some_and(|(_, cached_slot)| *cached_slot > slot)
+        {
+            return;
+        }
+        match account.filter(|account| account.lamports > 0) {
+            Some(account) => {
+                accounts.insert(key, (account, slot));
+            }
+            // The account is closed
+            None => {
+                accounts.remove(&key);
+            }
+        }
+    }
+
+    /// Stores the account of an account subscription notification.
+    pub async fn update_from_notification(&self, key: Pubkey, notification: Response<UiAccount>) {
+        match notification.value.decode::<Account>() {
+            Some(account) => {
+                self.update(key, Some(account), notification.context.slot)
+                    .await
+            }
+            None => {
+                tracing::warn!(key = %key, "Failed to decode subscribed account");
+                self.accounts.write().await.remove(&key);
+            }
+        }
+    }
+
+    /// Stores the account of a program subscription notification.
+    pub async fn update_from_program_notification(&self, notification: Response<RpcKeyedAccount>) {
+        let Ok(key) = Pubkey::from_str(&notification.value.pubkey) else {
+            tracing::warn!(
+                key = notification.value.pubkey,
+                "Invalid subscribed account key"
+            );
+            return;
+        };
+        self.update_from_notification(
+            key,
+            Response {
+                context: notification.context,
+                value:   notification.value.account,
+            },
+        )
+        .await;
+    }
+
+    /// Removes all the accounts from the cache.
+    /// This should be called when the subscriptions end, as the accounts are not kept up to date anymore.
+    pub async fn clear(&self) {
+        self.accounts.write().await.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::program_option::COption,
+    };
+
+    fn get_mint_account() -> Account {
+        let mut data = vec![0; Mint::LEN];
+        Mint::pack(
+            Mint {
+                mint_authority:   COption::None,
+                supply:           100,
+                decimals:         6,
+                is_initialized:   true,
+                freeze_authority: COption::None,
+            },
+            &mut data,
+        )
+        .unwrap();
+        Account {
+            lamports: 1,
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_track_only_hot_accounts() {
+        let cache = SubscribedAccountCache::new("solana".to_string(), Pubkey::new_unique());
+        let (mint, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        cache
+            .track(
+                &[mint, wallet],
+                &[Some(get_mint_account()), Some(Account::default())],
+            )
+            .await;
+
+        assert_eq!(
+            cache.get_tracked_keys().await,
+            vec![(mint, spl_token::id())]
+        );
+        assert_eq!(
+            cache.lock_new_keys_receiver().await.try_recv(),
+            Ok((mint, spl_token::id()))
+        );
+        // The account is not served before the subscription updates it
+        assert_eq!(cache.get_all_accounts(&[mint]).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_keeps_newest_account() {
+        let cache = SubscribedAccountCache::new("solana".to_string(), Pubkey::new_unique());
+        let key = Pubkey::new_unique();
+        let account = get_mint_account();
+        let stale_account = Account {
+            lamports: 2,
+            ..account.clone()
+        };
+
+        cache.update(key, Some(account.clone()), 10).await;
+        cache.update(key, Some(stale_account), 9).await;
+        assert_eq!(cache.get_all_accounts(&[key]).await, Some(vec![account]));
+
+        cache.update(key, None, 11).await;
+        assert_eq!(cache.get_all_accounts(&[key]).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_resubscribe() {
+        let cache = SubscribedAccountCache::new("solana".to_string(), Pubkey::new_unique());
+        let mint = Pubkey::new_unique();
+        let account = get_mint_account();
+
+        cache.track(&[mint], &[Some(account.clone())]).await;
+        cache.update(mint, Some(account.clone()), 10).await;
+        let mut new_keys_receiver = cache.lock_new_keys_receiver().await;
+        assert_eq!(new_keys_receiver.try_recv(), Ok((mint, spl_token::id())));
+        assert_eq!(cache.get_all_accounts(&[mint]).await, Some(vec![account]));
+
+        // The account is not served until the new subscription updates it
+        cache.resubscribe(&mint).await;
+        assert_eq!(cache.get_all_accounts(&[mint]).await, None);
+        assert_eq!(new_keys_receiver.try_recv(), Ok((mint, spl_token::id())));
+
+        // Untracked accounts are not subscribed to
+        cache.resubscribe(&Pubkey::new_unique()).await;
+        assert!(new_keys_receiver.try_recv().is_err());
+    }
+}
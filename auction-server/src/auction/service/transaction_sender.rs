@@ -0,0 +1,455 @@
+use {
+    crate::{
+        config::TransactionSubmissionStrategy,
+        kernel::entities::ChainId,
+        per_metrics::TRANSACTION_LANDING_TIME_SVM_ENDPOINT_METRIC,
+    },
+    axum::async_trait,
+    axum_prometheus::metrics,
+    futures::future::join_all,
+    rand::distributions::{
+        Distribution,
+        WeightedIndex,
+    },
+    solana_client::{
+        client_error::{
+            ClientError,
+            ClientErrorKind,
+        },
+        nonblocking::rpc_client::RpcClient,
+        rpc_config::RpcSendTransactionConfig,
+    },
+    solana_sdk::transaction::VersionedTransaction,
+    std::{
+        collections::HashMap,
+        sync::Mutex,
+        time::{
+            Duration,
+            Instant,
+        },
+    },
+};
+
+/// Number of endpoints the transaction is sent to with the race fastest strategy
+const RACE_ENDPOINT_COUNT: usize = 2;
+
+const METRIC_LABEL_LANDED: &str = "landed";
+const METRIC_LABEL_EXPIRED: &str = "expired";
+
+#[derive(Debug, Default, Clone, Copy)]
+struct EndpointStats {
+    sent:               u64,
+    landed:             u64,
+    total_landing_time: Duration,
+}
+
+/// A tx submission endpoint with the landing stats of the transactions sent through it
+pub struct TxSubmissionEndpoint {
+    pub client: RpcClient,
+    stats:      Mutex<EndpointStats>,
+}
+
+impl TxSubmissionEndpoint {
+    pub fn new(client: RpcClient) -> Self {
+        Self {
+            client,
+            stats: Default::default(),
+        }
+    }
+
+    /// The ratio of the transactions sent through the endpoint that landed.
+    /// Endpoints without any observation start with a landing rate of 0.5.
+    fn landing_rate(&self) -> f64 {
+        let stats = *self.stats.lock().expect("Endpoint stats lock poisoned");
+        (stats.landed as f64 + 1.0) / (stats.sent as f64 + 2.0)
+    }
+
+    /// The average landing time of the transactions sent through the endpoint.
+    /// Returns None if no transaction sent through the endpoint landed.
+    fn average_landing_time(&self) -> Option<Duration> {
+        let stats = *self.stats.lock().expect("Endpoint stats lock poisoned");
+        if stats.landed == 0 {
+            return None;
+        }
+        Some(stats.total_landing_time / stats.landed as u32)
+    }
+
+    /// The key to rank the endpoints by speed, lower is faster.
+    /// Endpoints without any observation are tried first, and endpoints that never landed a transaction last.
+    fn speed_rank(&self) -> (bool, Duration) {
+        let has_observations = self
+            .stats
+            .lock()
+            .expect("Endpoint stats lock poisoned")
+            .sent
+            > 0;
+        (
+            has_observations,
+            self.average_landing_time().unwrap_or(Duration::MAX),
+        )
+    }
+
+    fn record(&self, landing_time: Option<Duration>) {
+        let mut stats = self.stats.lock().expect("Endpoint stats lock poisoned");
+        stats.sent += 1;
+        if let Some(landing_time) = landing_time {
+            stats.landed += 1;
+            stats.total_landing_time += landing_time;
+        }
+    }
+}
+
+/// Strategy for sending a transaction through the tx submission endpoints of a chain.
+#[async_trait]
+pub trait TransactionSender: Send + Sync {
+    /// Sends the transaction and returns the indices of the endpoints that accepted it,
+    /// with the time each endpoint accepted it.
+    async fn send_transaction(
+        &self,
+        endpoints: &[TxSubmissionEndpoint],
+        transaction: &VersionedTransaction,
+        config: RpcSendTransactionConfig,
+    ) -> Result<Vec<(usize, Instant)>, ClientError>;
+}
+
+/// Sends the transaction to the given endpoints concurrently.
+/// Returns an error if none of the endpoints accepted the transaction.
+async fn send_to_endpoints(
+    endpoints: &[TxSubmissionEndpoint],
+    indices: Vec<usize>,
+    transaction: &VersionedTransaction,
+    config: RpcSendTransactionConfig,
+) -> Result<Vec<(usize, Instant)>, ClientError> {
+    let results = join_all(indices.into_iter().map(|index| async move {
+        let client = &endpoints[index].client;
+        let result = client.send_transaction_with_config(transaction, config).await;
+        if let Err(e) = &result {
+            tracing::error!(error = ?e, client = ?client.url(), "Failed to send transaction to network");
+        }
+        result.map(|_| (index, Instant::now()))
+    }))
+    .await;
+    let accepted: Vec<(usize, Instant)> = results.into_iter().filter_map(Result::ok).collect();
+    if accepted.is_empty() {
+        return Err(ClientErrorKind::Custom("All tx broadcasters failed".to_string()).into());
+    }
+    Ok(accepted)
+}
+
+pub struct BroadcastAll;
+
+#[async_trait]
+impl TransactionSender for BroadcastAll {
+    async fn send_transaction(
+        &self,
+        endpoints: &[TxSubmissionEndpoint],
+        transaction: &VersionedTransaction,
+        config: RpcSendTransactionConfig,
+    ) -> Result<Vec<(usize, Instant)>, ClientError> {
+        send_to_endpoints(
+            endpoints,
+            (0..endpoints.len()).collect(),
+            transaction,
+            config,
+        )
+        .await
+    }
+}
+
+pub struct RaceFastest;
+
+#[async_trait]
+impl TransactionSender for RaceFastest {
+    async fn send_transaction(
+        &self,
+        endpoints: &[TxSubmissionEndpoint],
+        transaction: &VersionedTransaction,
+        config: RpcSendTransactionConfig,
+    ) -> Result<Vec<(usize, Instant)>, ClientError> {
+        let mut indices: Vec<usize> = (0..endpoints.len()).collect();
+        indices.sort_by_key(|index| endpoints[*index].speed_rank());
+        indices.truncate(RACE_ENDPOINT_COUNT);
+        send_to_endpoints(endpoints, indices, transaction, config).await
+    }
+}
+
+pub struct WeightedByLandingRate;
+
+#[async_trait]
+impl TransactionSender for WeightedByLandingRate {
+    async fn send_transaction(
+        &self,
+        endpoints: &[TxSubmissionEndpoint],
+        transaction: &VersionedTransaction,
+        config: RpcSendTransactionConfig,
+    ) -> Result<Vec<(usize, Instant)>, ClientError> {
+        let index = WeightedIndex::new(endpoints.iter().map(|endpoint| endpoint.landing_rate()))
+            .map_err(|e| ClientErrorKind::Custom(format!("Failed to choose endpoint: {}", e)))?
+            .sample(&mut rand::thread_rng());
+        send_to_endpoints(endpoints, vec![index], transaction, config).await
+    }
+}
+
+/// Sends the transactions of a chain through its tx submission endpoints
+/// and feeds the observed landing results back into the endpoint choice.
+pub struct TransactionBroadcaster {
+    chain_id:  ChainId,
+    endpoints: Vec<TxSubmissionEndpoint>,
+    sender:    Box<dyn TransactionSender>,
+}
+
+impl TransactionBroadcaster {
+    pub fn new(
+        chain_id: ChainId,
+        clients: Vec<RpcClient>,
+        strategy: TransactionSubmissionStrategy,
+    ) -> Self {
+        let sender: Box<dyn TransactionSender> = match strategy {
+            TransactionSubmissionStrategy::BroadcastAll => Box::new(BroadcastAll),
+            TransactionSubmissionStrategy::RaceFastest => Box::new(RaceFastest),
+            TransactionSubmissionStrategy::WeightedByLandingRate => Box::new(WeightedByLandingRate),
+        };
+        Self {
+            chain_id,
+            endpoints: clients.into_iter().map(TxSubmissionEndpoint::new).collect(),
+            sender,
+        }
+    }
+
+    pub fn clients(&self) -> impl Iterator<Item = &RpcClient> {
+        self.endpoints.iter().map(|endpoint| &endpoint.client)
+    }
+
+    /// Sends the transaction with the configured strategy.
+    /// Returns the indices of the endpoints that accepted the transaction, with the time each endpoint accepted it.
+    pub async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        config: RpcSendTransactionConfig,
+    ) -> Result<Vec<(usize, Instant)>, ClientError> {
+        self.sender
+            .send_transaction(&self.endpoints, transaction, config)
+            .await
+    }
+
+    /// Records the landing result of a transaction for the endpoints it was sent to.
+    /// `sent_at` maps the index of each endpoint to the first time the endpoint accepted the transaction.
+    ///
+    /// The copies of the transaction sent through the endpoints are identical, so the chain does not tell which
+    /// endpoint landed it. The result is recorded the same way for every endpoint that received the transaction,
+    /// with the landing time measured from the time the endpoint accepted it.
+    pub fn record_landing(&self, sent_at: &HashMap<usize, Instant>, landed: bool) {
+        for (index, sent_at) in sent_at {
+            let Some(endpoint) = self.endpoints.get(*index) else {
+                continue;
+            };
+            let landing_time = sent_at.elapsed();
+            endpoint.record(landed.then_some(landing_time));
+            let result = if landed {
+                METRIC_LABEL_LANDED
+            } else {
+                METRIC_LABEL_EXPIRED
+            };
+            metrics::histogram!(
+                TRANSACTION_LANDING_TIME_SVM_ENDPOINT_METRIC,
+                &[
+                    ("chain_id", self.chain_id.clone()),
+                    ("endpoint", endpoint.client.url()),
+                    ("result", result.to_string()),
+                ]
+            )
+            .record(landing_time.as_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::kernel::traced_sender_svm::tests::MockRpcClient,
+        serde_json::json,
+        solana_client::{
+            rpc_client::RpcClientConfig,
+            rpc_request::RpcRequest,
+        },
+        solana_sdk::{
+            hash::Hash,
+            signature::{
+                Keypair,
+                Signer,
+            },
+            system_instruction,
+            transaction::Transaction,
+        },
+    };
+
+    fn get_transaction() -> VersionedTransaction {
+        let payer = Keypair::new();
+        let instruction =
+            system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1);
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        )
+        .into()
+    }
+
+    fn get_config() -> RpcSendTransactionConfig {
+        RpcSendTransactionConfig {
+            skip_preflight: true,
+            ..RpcSendTransactionConfig::default()
+        }
+    }
+
+    fn get_indices(result: Result<Vec<(usize, Instant)>, ClientError>) -> Vec<usize> {
+        let mut indices: Vec<usize> = result
+            .unwrap()
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        indices.sort();
+        indices
+    }
+
+    fn get_client(expected_sends: usize, transaction: &VersionedTransaction) -> RpcClient {
+        let signature = transaction.signatures[0].to_string();
+        let mut rpc_client = MockRpcClient::new();
+        rpc_client
+            .expect_send()
+            .withf(|request, _| *request == RpcRequest::SendTransaction)
+            .times(expected_sends)
+            .returning(move |_, _| Ok(json!(signature)));
+        rpc_client
+            .expect_url()
+            .returning(|| "https://test".to_string());
+        RpcClient::new_sender(rpc_client, RpcClientConfig::default())
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_all() {
+        let transaction = get_transaction();
+        let broadcaster = TransactionBroadcaster::new(
+            "solana".to_string(),
+            vec![get_client(1, &transaction), get_client(1, &transaction)],
+            TransactionSubmissionStrategy::BroadcastAll,
+        );
+
+        let result = broadcaster
+            .send_transaction(&transaction, get_config())
+            .await;
+
+        assert_eq!(get_indices(result), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_race_fastest() {
+        let transaction = get_transaction();
+        let broadcaster = TransactionBroadcaster::new(
+            "solana".to_string(),
+            vec![
+                get_client(0, &transaction),
+                get_client(1, &transaction),
+                get_client(1, &transaction),
+            ],
+            TransactionSubmissionStrategy::RaceFastest,
+        );
+        broadcaster.endpoints[0].record(Some(Duration::from_secs(10)));
+        broadcaster.endpoints[1].record(Some(Duration::from_secs(1)));
+        broadcaster.endpoints[2].record(Some(Duration::from_secs(2)));
+
+        let result = broadcaster
+            .send_transaction(&transaction, get_config())
+            .await;
+
+        assert_eq!(get_indices(result), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_race_fastest_without_landed_transactions() {
+        let transaction = get_transaction();
+        let broadcaster = TransactionBroadcaster::new(
+            "solana".to_string(),
+            vec![
+                get_client(0, &transaction),
+                get_client(1, &transaction),
+                get_client(1, &transaction),
+            ],
+            TransactionSubmissionStrategy::RaceFastest,
+        );
+        // The first endpoint never landed a transaction, the second endpoint is not tried yet
+        broadcaster.endpoints[0].record(None);
+        broadcaster.endpoints[2].record(Some(Duration::from_secs(5)));
+
+        let result = broadcaster
+            .send_transaction(&transaction, get_config())
+            .await;
+
+        assert_eq!(get_indices(result), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_by_landing_rate() {
+        let transaction = get_transaction();
+        let broadcaster = TransactionBroadcaster::new(
+            "solana".to_string(),
+            vec![get_client(0, &transaction), get_client(1, &transaction)],
+            TransactionSubmissionStrategy::WeightedByLandingRate,
+        );
+        // The first endpoint never lands, so its landing rate is almost zero
+        for _ in 0..100_000 {
+            broadcaster.endpoints[0].record(None);
+            broadcaster.endpoints[1].record(Some(Duration::from_secs(1)));
+        }
+
+        let result = broadcaster
+            .send_transaction(&transaction, get_config())
+            .await;
+
+        assert_eq!(get_indices(result), vec![1]);
+    }
+
+    #[test]
+    fn test_record_landing() {
+        let transaction = get_transaction();
+        let broadcaster = TransactionBroadcaster::new(
+            "solana".to_string(),
+            vec![get_client(0, &transaction), get_client(0, &transaction)],
+            TransactionSubmissionStrategy::WeightedByLandingRate,
+        );
+
+        broadcaster.record_landing(&HashMap::from([(0, Instant::now())]), true);
+        broadcaster.record_landing(&HashMap::from([(1, Instant::now())]), false);
+
+        assert!(broadcaster.endpoints[0].landing_rate() > broadcaster.endpoints[1].landing_rate());
+    }
+
+    #[test]
+    fn test_record_landing_counts_every_endpoint() {
+        let transaction = get_transaction();
+        let broadcaster = TransactionBroadcaster::new(
+            "solana".to_string(),
+            vec![get_client(0, &transaction), get_client(0, &transaction)],
+            TransactionSubmissionStrategy::BroadcastAll,
+        );
+        let now = Instant::now();
+        let sent_at = HashMap::from([(0, now + Duration::from_millis(10)), (1, now)]);
+
+        broadcaster.record_landing(&sent_at, true);
+        let stats = |index: usize| *broadcaster.endpoints[index].stats.lock().unwrap();
+        assert_eq!((stats(0).sent, stats(0).landed), (1, 1));
+        assert_eq!((stats(1).sent, stats(1).landed), (1, 1));
+        // The endpoint that accepted the transaction first is credited with the longer landing time
+        assert!(stats(1).total_landing_time > stats(0).total_landing_time);
+
+        broadcaster.record_landing(&sent_at, false);
+        assert_eq!((stats(0).sent, stats(0).landed), (2, 1));
+        assert_eq!((stats(1).sent, stats(1).landed), (2, 1));
+        assert_eq!(
+            broadcaster.endpoints[0].landing_rate(),
+            broadcaster.endpoints[1].landing_rate()
+        );
+    }
+}
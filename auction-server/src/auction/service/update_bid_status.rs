@@ -1,6 +1,11 @@
 use {
     super::{
         get_bid_transaction_data::GetBidTransactionDataInput,
+        relayer_spend::{
+            RecordRelayerSpendInput,
+            ReleaseRelayerSpendInput,
+        },
+        searcher_reputation::RecordReputationEventInput,
         Service,
     },
     crate::{
@@ -8,7 +13,13 @@ use {
             ws::UpdateEvent,
             RestError,
         },
-        auction::entities,
+        auction::{
+            api::get_quote_status_update,
+            entities::{
+                self,
+                BidStatus,
+            },
+        },
         opportunity::service::get_token_mint::GetTokenMintInput,
     },
     express_relay_api_types::bid::BidStatusWithId,
@@ -100,6 +111,60 @@ impl Service {
                 }
             });
 
+            if matches!(
+                input.new_status,
+                entities::BidStatusSvm::Submitted { .. }
+                    | entities::BidStatusSvm::SentToUserForSubmission { .. }
+            ) {
+                self.task_tracker.spawn({
+                    let (service, bid) = (self.clone(), input.bid.clone());
+                    async move {
+                        if let Err(e) = service
+                            .record_relayer_spend(RecordRelayerSpendInput { bid: bid.clone() })
+                            .await
+                        {
+                            tracing::error!(bid = ?bid, error = ?e, "Failed to record relayer spend");
+                        }
+                    }
+                });
+            }
+
+            // The bid concluded before the relayer signed it, so its reserved relayer spend is not used
+            if (input.bid.status.is_pending() || input.bid.status.is_awaiting_signature())
+                && input.new_status.is_concluded()
+            {
+                self.release_relayer_spend(ReleaseRelayerSpendInput {
+                    profile_id: input.bid.profile_id,
+                    chain_data: input.bid.chain_data.clone(),
+                })
+                .await;
+            }
+
+            let event =
+                entities::ReputationEvent::from_status_update(&input.bid.status, &input.new_status);
+            if let (Some(profile_id), Some(event)) = (input.bid.profile_id, event) {
+                self.task_tracker.spawn({
+                    let service = self.clone();
+                    async move {
+                        if let Err(e) = service
+                            .record_reputation_event(RecordReputationEventInput { profile_id, event })
+                            .await
+                        {
+                            tracing::error!(profile_id = %profile_id, error = ?e, "Failed to record reputation event");
+                        }
+                    }
+                });
+            }
+
+            if let Some(quote_status) = get_quote_status_update(&input.bid, &input.new_status) {
+                if let Err(e) = self
+                    .event_sender
+                    .send(UpdateEvent::QuoteStatusUpdate(quote_status))
+                {
+                    tracing::error!(error = e.to_string(), "Failed to send update event");
+                }
+            }
+
             // TODO remove this line and move BidStatusWithId somewhere else
             if let Err(e) = self
                 .event_sender
@@ -14,14 +14,22 @@ use {
                 BidPaymentInstructionType,
                 SubmitType,
             },
-            service::get_pending_bids::GetLiveBidsInput,
+            service::{
+                get_pending_bids::GetLiveBidsInput,
+                relayer_spend::CheckRelayerSpendInput,
+                searcher_reputation::GetReputationPenaltiesInput,
+            },
+        },
+        kernel::{
+            entities::Svm,
+            lookup_table_resolver::LookupTableError,
         },
-        kernel::entities::Svm,
         opportunity::{
             self as opportunity,
             entities::{
                 get_opportunity_swap_data,
                 get_swap_quote_tokens,
+                OpportunitySvm,
                 OpportunitySvmProgramSwap,
                 QuoteTokens,
                 TokenAccountInitializationConfig,
@@ -34,17 +42,21 @@ use {
                     get_quote_virtual_permission_account,
                     is_indicative_price_taker,
                 },
+                get_token_mint::GetTokenMintInput,
             },
         },
     },
     axum::async_trait,
     borsh::de::BorshDeserialize,
     express_relay::error::ErrorCode,
+    express_relay_api_types::{
+        SimulationErrorDetails,
+        SimulationInnerInstruction,
+    },
     litesvm::types::FailedTransactionMetadata,
+    solana_client::rpc_config::RpcSimulateTransactionConfig,
     solana_sdk::{
-        address_lookup_table::state::AddressLookupTable,
         clock::Slot,
-        commitment_config::CommitmentConfig,
         compute_budget,
         instruction::{
             CompiledInstruction,
@@ -60,6 +72,10 @@ use {
             VersionedTransaction,
         },
     },
+    solana_transaction_status::{
+        UiInnerInstructions,
+        UiInstruction,
+    },
     spl_associated_token_account::{
         get_associated_token_address,
         get_associated_token_address_with_program_id,
@@ -78,7 +94,12 @@ pub struct VerifyBidInput {
     pub bid_create: entities::BidCreate,
 }
 
-pub type VerificationResult = (entities::BidChainDataSvm, entities::BidAmountSvm);
+/// The simulation is None when the bid is not simulated, e.g. for indicative quotes.
+pub type VerificationResult = (
+    entities::BidChainDataSvm,
+    entities::BidAmountSvm,
+    Option<entities::BidSimulation>,
+);
 
 #[async_trait]
 pub trait Verification {
@@ -127,6 +148,75 @@ const BID_MINIMUM_LIFE_TIME_SVM_SERVER: Duration = Duration::from_secs(5);
 pub const BID_MINIMUM_LIFE_TIME_SVM_OTHER: Duration = Duration::from_secs(10);
 pub const BID_MAXIMUM_LIFE_TIME_SVM: Duration = Duration::from_secs(45);
 
+const SIMULATION_LOGS_MAX_SIZE: usize = 8 * 1024;
+const SIMULATION_INNER_INSTRUCTIONS_MAX_COUNT: usize = 64;
+
+/// Returns the inner instructions of a simulation made through the rpc.
+/// Unknown stack heights are reported as 0.
+fn get_rpc_simulation_inner_instructions(
+    inner_instructions: Option<Vec<UiInnerInstructions>>,
+) -> Vec<SimulationInnerInstruction> {
+    inner_instructions
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|inner_instructions| {
+            let index = inner_instructions.index;
+            inner_instructions
+                .instructions
+                .into_iter()
+                .filter_map(move |instruction| match instruction {
+                    UiInstruction::Compiled(instruction) => Some(SimulationInnerInstruction {
+                        index,
+                        program_id_index: instruction.program_id_index,
+                        stack_height: instruction
+                            .stack_height
+                            .and_then(|stack_height| u8::try_from(stack_height).ok())
+                            .unwrap_or_default(),
+                    }),
+                    // Parsed instructions are only returned for the json parsed encoding
+                    UiInstruction::Parsed(_) => None,
+                })
+        })
+        .collect()
+}
+
+/// Builds the simulation error of a failed transaction, keeping the last logs and the first inner instructions
+/// so the size of the reason and of the details stays bounded.
+fn get_simulation_error(
+    error: TransactionError,
+    logs: Vec<String>,
+    compute_units_consumed: Option<u64>,
+    mut inner_instructions: Vec<SimulationInnerInstruction>,
+) -> RestError {
+    // The size includes the line breaks joining the logs in the reason
+    let mut size = 0;
+    let kept_logs_count = logs
+        .iter()
+        .rev()
+        .take_while(|log| {
+            size += log.len() + 1;
+            size <= SIMULATION_LOGS_MAX_SIZE + 1
+        })
+        .count();
+    let mut truncated = kept_logs_count < logs.len();
+    let logs = logs[logs.len() - kept_logs_count..].to_vec();
+    let reason = logs.join("\n");
+    if inner_instructions.len() > SIMULATION_INNER_INSTRUCTIONS_MAX_COUNT {
+        inner_instructions.truncate(SIMULATION_INNER_INSTRUCTIONS_MAX_COUNT);
+        truncated = true;
+    }
+    RestError::SimulationError {
+        reason,
+        details: Box::new(SimulationErrorDetails {
+            error: format!("{:?}", error),
+            logs,
+            compute_units_consumed,
+            inner_instructions,
+            truncated,
+        }),
+    }
+}
+
 // TODO: this uses the time at the server, which can lead to issues if Solana ever experiences clock drift
 // using the time at the server is not ideal, but the alternative is to make an RPC call to get the Solana block time
 // we should make this more robust, possibly by polling the current block time in the background
@@ -140,52 +230,21 @@ pub fn get_current_time_rounded_with_offset(offset: Duration) -> OffsetDateTime
 }
 
 impl Service {
-    //TODO: merge this logic with simulator logic
-    async fn query_lookup_table(&self, table: &Pubkey, index: usize) -> Result<Pubkey, RestError> {
-        if let Some(addresses) = self.repo.get_lookup_table(table).await {
-            if let Some(account) = addresses.get(index) {
-                return Ok(*account);
-            }
-        }
-
-        let table_data = self
+    async fn query_lookup_table(&self, table: &Pubkey, index: u8) -> Result<Pubkey, RestError> {
+        let addresses = self
             .config
             .chain_config
-            .client
-            .get_account_with_commitment(table, CommitmentConfig::processed())
+            .lookup_table_resolver
+            .resolve(table, &[index])
             .await
-            .map_err(|e| {
-                tracing::error!(error = e.to_string(), "Failed to get lookup table account");
-                RestError::TemporarilyUnavailable
-            })?
-            .value
-            .ok_or_else(|| {
-                RestError::BadParameters(format!("Lookup table account {} not found", table))
-            })?;
-
-        let table_data_deserialized =
-            AddressLookupTable::deserialize(&table_data.data).map_err(|e| {
-                tracing::warn!(
-                    error = e.to_string(),
-                    "Failed to deserialize lookup table account data"
-                );
-                RestError::BadParameters(format!(
-                    "Failed deserializing lookup table account data: {}",
-                    e
-                ))
-            })?;
-
-        let account = table_data_deserialized
-            .addresses
-            .get(index)
-            .ok_or_else(|| {
-                RestError::BadParameters("Account not found in lookup table".to_string())
+            .map_err(|e| match e {
+                LookupTableError::Rpc(_) => RestError::TemporarilyUnavailable,
+                LookupTableError::IndexOutOfBounds { .. } => {
+                    RestError::BadParameters("Account not found in lookup table".to_string())
+                }
+                e => RestError::BadParameters(e.to_string()),
             })?;
-
-        self.repo
-            .add_lookup_table(*table, table_data_deserialized.addresses.to_vec())
-            .await;
-        Ok(*account)
+        Ok(addresses[0])
     }
 
     async fn find_and_query_lookup_table(
@@ -198,7 +257,7 @@ impl Service {
                 RestError::BadParameters("Lookup table not found in lookup accounts".to_string())
             })?;
 
-        self.query_lookup_table(table_to_query, *index_to_query as usize)
+        self.query_lookup_table(table_to_query, *index_to_query)
             .await
     }
 
@@ -248,67 +307,105 @@ impl Service {
         }
     }
 
-    fn validate_swap_transaction_instructions(
+    async fn validate_swap_transaction_instructions(
         &self,
         tx: &VersionedTransaction,
     ) -> Result<(), RestError> {
-        tx.message
-            .instructions()
+        for (index, ix) in tx.message.instructions().iter().enumerate() {
+            self.validate_swap_transaction_instruction(tx, index, ix)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Checks the instruction against the swap instruction policy of the chain.
+    async fn validate_swap_transaction_instruction(
+        &self,
+        tx: &VersionedTransaction,
+        index: usize,
+        ix: &CompiledInstruction,
+    ) -> Result<(), RestError> {
+        let program_id = ix.program_id(tx.message.static_account_keys());
+        if *program_id == self.config.chain_config.express_relay.program_id {
+            return Ok(());
+        }
+        let program = self
+            .config
+            .chain_config
+            .swap_instruction_policy
+            .get_allowed_program(program_id)
+            .ok_or_else(|| {
+                RestError::InvalidInstruction(
+                    Some(index),
+                    InstructionError::UnsupportedProgram(*program_id),
+                )
+            })?;
+        let Some(instructions) = &program.instructions else {
+            return Ok(());
+        };
+        let instruction = instructions
             .iter()
-            .enumerate()
-            .try_for_each(|(index, ix)| {
-                self.validate_swap_transaction_instruction(
-                    ix.program_id(tx.message.static_account_keys()),
-                    ix,
+            .find(|instruction| ix.data.starts_with(&instruction.discriminator))
+            .ok_or_else(|| {
+                RestError::InvalidInstruction(
+                    Some(index),
+                    Self::get_unsupported_instruction_error(program_id, ix),
                 )
-                .map_err(|e| RestError::InvalidInstruction(Some(index), e))
             })?;
 
+        for constraint in instruction.accounts.iter() {
+            let account = self.extract_account(tx, ix, constraint.position).await?;
+            if let Some(one_of) = &constraint.one_of {
+                if !one_of.contains(&account) {
+                    return Err(RestError::InvalidInstruction(
+                        Some(index),
+                        InstructionError::AccountNotAllowed {
+                            position: constraint.position,
+                            account,
+                        },
+                    ));
+                }
+            }
+            let is_writable = ix
+                .accounts
+                .get(constraint.position)
+                .is_some_and(|key_index| tx.message.is_maybe_writable(*key_index as usize, None));
+            if constraint.readonly && is_writable {
+                return Err(RestError::InvalidInstruction(
+                    Some(index),
+                    InstructionError::WritableAccountNotAllowed {
+                        position: constraint.position,
+                        account,
+                    },
+                ));
+            }
+        }
         Ok(())
     }
 
-    fn validate_swap_transaction_instruction(
-        &self,
+    /// Returns the most descriptive error for an instruction that is not allowed by the policy.
+    fn get_unsupported_instruction_error(
         program_id: &Pubkey,
         ix: &CompiledInstruction,
-    ) -> Result<(), InstructionError> {
+    ) -> InstructionError {
         if *program_id == system_program::id() {
-            if matches!(
-                bincode::deserialize::<SystemInstruction>(&ix.data),
-                Ok(SystemInstruction::Transfer { .. })
-            ) {
-                Ok(())
-            } else {
-                Err(InstructionError::UnsupportedSystemProgramInstruction)
-            }
+            InstructionError::UnsupportedSystemProgramInstruction
         } else if *program_id == spl_token::id() {
-            let ix_parsed = TokenInstruction::unpack(&ix.data)
-                .map_err(InstructionError::InvalidSplTokenInstruction)?;
-            match ix_parsed {
-                TokenInstruction::CloseAccount { .. } => Ok(()),
-                TokenInstruction::SyncNative { .. } => Ok(()),
-                _ => Err(InstructionError::UnsupportedSplTokenInstruction(format!(
-                    "{:?}",
-                    ix_parsed
-                ))),
+            match TokenInstruction::unpack(&ix.data) {
+                Ok(ix_parsed) => {
+                    InstructionError::UnsupportedSplTokenInstruction(format!("{:?}", ix_parsed))
+                }
+                Err(e) => InstructionError::InvalidSplTokenInstruction(e),
             }
         } else if *program_id == spl_associated_token_account::id() {
-            let ix_parsed =
-                AssociatedTokenAccountInstruction::try_from_slice(&ix.data).map_err(|e| {
-                    InstructionError::InvalidAssociatedTokenAccountInstruction(e.to_string())
-                })?;
-            match ix_parsed {
-                AssociatedTokenAccountInstruction::Create => Ok(()),
-                AssociatedTokenAccountInstruction::CreateIdempotent => Ok(()),
-                _ => Err(InstructionError::UnsupportedAssociatedTokenAccountInstruction(ix_parsed)),
+            match AssociatedTokenAccountInstruction::try_from_slice(&ix.data) {
+                Ok(ix_parsed) => {
+                    InstructionError::UnsupportedAssociatedTokenAccountInstruction(ix_parsed)
+                }
+                Err(e) => InstructionError::InvalidAssociatedTokenAccountInstruction(e.to_string()),
             }
-        } else if *program_id == self.config.chain_config.express_relay.program_id
-            || *program_id == spl_memo_client::ID
-            || *program_id == compute_budget::id()
-        {
-            Ok(())
         } else {
-            Err(InstructionError::UnsupportedProgram(*program_id))
+            InstructionError::UnsupportedInstruction(*program_id)
         }
     }
 
@@ -446,6 +543,59 @@ impl Service {
                 },
             ));
         }
+
+        // The swap can only be partially filled within the partial fill tolerance of the user
+        let minimum_amount_searcher =
+            opportunity_swap_data.get_minimum_amount_searcher(swap_data.amount_searcher);
+        if transaction_data.minimum_amount_searcher < minimum_amount_searcher {
+            return Err(RestError::InvalidSwapInstruction(
+                SwapInstructionError::MinimumAmountSearcher {
+                    expected: minimum_amount_searcher,
+                    found:    transaction_data.minimum_amount_searcher,
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rejects swaps of mints with Token-2022 extensions that the swap can not be executed with.
+    /// Transfer fees are supported, but the transferred amounts must exceed the fee withheld by the mint.
+    /// The user may receive as little as the minimum searcher amount if the swap is partially filled.
+    async fn check_swap_token_extensions(
+        &self,
+        opp: &OpportunitySvm,
+        transaction_data: &entities::BidTransactionDataSwap,
+    ) -> Result<(), RestError> {
+        let transfers = [
+            (
+                transaction_data.accounts.mint_searcher,
+                transaction_data.get_committed_amount_searcher(),
+            ),
+            (
+                transaction_data.accounts.mint_user,
+                transaction_data.data.amount_user,
+            ),
+        ];
+        for (mint, amount) in transfers {
+            let token_mint = self
+                .opportunity_service
+                .get_token_mint(GetTokenMintInput {
+                    chain_id: opp.chain_id.clone(),
+                    mint,
+                })
+                .await?;
+            token_mint.check_supported_extensions()?;
+            let transfer_fee = token_mint.extensions.calculate_transfer_fee(amount);
+            if transfer_fee > 0 && transfer_fee >= amount {
+                return Err(RestError::InvalidSwapInstruction(
+                    SwapInstructionError::AmountBelowTransferFee {
+                        mint,
+                        transfer_fee,
+                        found: amount,
+                    },
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -1022,7 +1172,8 @@ impl Service {
                     .ok_or(RestError::SwapOpportunityNotFound)?;
                 self.validate_swap_transaction_instructions(
                     bid_chain_data_create_svm.get_transaction(),
-                )?;
+                )
+                .await?;
                 let quote_tokens = get_swap_quote_tokens(&opp);
                 let opportunity_swap_data = get_opportunity_swap_data(&opp);
                 self.check_svm_swap_bid_fields(bid_data, opportunity_swap_data, &quote_tokens)
@@ -1031,6 +1182,8 @@ impl Service {
                 let transaction_data = self
                     .get_bid_transaction_data_swap(bid_data.transaction.clone())
                     .await?;
+                self.check_swap_token_extensions(&opp, &transaction_data)
+                    .await?;
 
                 let entities::SwapAccounts {
                     user_wallet,
@@ -1058,10 +1211,16 @@ impl Service {
                 )
                 .await?;
 
+                // Bids that can be partially filled are ranked by the amounts they commit to,
+                // so a winner can not use its searcher balance to provide less than it was ranked by
                 let bid_amount = match quote_tokens.clone() {
                     // bid is in the unspecified token
-                    QuoteTokens::UserTokenSpecified { .. } => transaction_data.data.amount_searcher,
-                    QuoteTokens::SearcherTokenSpecified { .. } => transaction_data.data.amount_user,
+                    QuoteTokens::UserTokenSpecified { .. } => {
+                        transaction_data.get_committed_amount_searcher()
+                    }
+                    QuoteTokens::SearcherTokenSpecified { .. } => {
+                        transaction_data.get_committed_amount_user()
+                    }
                 };
                 let (fee_token, fee_token_program) = match transaction_data.data.fee_token {
                     ::express_relay::FeeToken::Searcher => (mint_searcher, token_program_searcher),
@@ -1197,35 +1356,46 @@ impl Service {
         &self,
         bid: &entities::BidCreate,
         swap_instruction_index: usize,
-    ) -> Result<(), RestError> {
+    ) -> Result<entities::BidSimulation, RestError> {
         let tx = bid.chain_data.get_transaction();
-        let simulation = self
-            .config
-            .chain_config
-            .client
-            .simulate_transaction(tx)
+        let client = &self.config.chain_config.client;
+        let simulation = client
+            .simulate_transaction_with_config(
+                tx,
+                RpcSimulateTransactionConfig {
+                    commitment: Some(client.commitment()),
+                    inner_instructions: true,
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )
             .await;
         match simulation {
             Ok(simulation) => {
+                let bid_simulation = entities::BidSimulation {
+                    logs:                   simulation.value.logs.unwrap_or_default(),
+                    compute_units_consumed: simulation.value.units_consumed.unwrap_or_default(),
+                };
                 if let Some(transaction_error) = simulation.value.err {
-                    if let TransactionError::InstructionError(index, error) = transaction_error {
-                        let is_insufficient_funds_error = usize::from(index)
+                    if let TransactionError::InstructionError(index, error) = &transaction_error {
+                        let is_insufficient_funds_error = usize::from(*index)
                             == swap_instruction_index
-                            && error
+                            && *error
                                 == SolanaInstructionError::Custom(
                                     ErrorCode::InsufficientUserFunds.into(),
                                 );
                         if is_insufficient_funds_error {
-                            return Ok(());
+                            return Ok(bid_simulation);
                         }
                     }
 
-                    let msgs = simulation.value.logs.unwrap_or_default();
-                    Err(RestError::SimulationError {
-                        reason: msgs.join("\n"),
-                    })
+                    Err(get_simulation_error(
+                        transaction_error,
+                        bid_simulation.logs,
+                        simulation.value.units_consumed,
+                        get_rpc_simulation_inner_instructions(simulation.value.inner_instructions),
+                    ))
                 } else {
-                    Ok(())
+                    Ok(bid_simulation)
                 }
             }
 
@@ -1237,7 +1407,10 @@ impl Service {
     }
 
     #[tracing::instrument(skip_all, err(level = tracing::Level::TRACE))]
-    pub async fn simulate_bid(&self, bid: &entities::BidCreate) -> Result<(), RestError> {
+    pub async fn simulate_bid(
+        &self,
+        bid: &entities::BidCreate,
+    ) -> Result<entities::BidSimulation, RestError> {
         const RETRY_LIMIT: usize = 5;
         const RETRY_DELAY: Duration = Duration::from_millis(100);
         let mut retry_count = 0;
@@ -1283,14 +1456,34 @@ impl Service {
                         retry_count += 1;
                         continue;
                     }
-                    let msgs = err.meta.logs;
-                    Err(RestError::SimulationError {
-                        reason: msgs.join("\n"),
-                    })
+                    let inner_instructions = err
+                        .meta
+                        .inner_instructions
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(index, instructions)| {
+                            instructions
+                                .iter()
+                                .map(move |inner| SimulationInnerInstruction {
+                                    index:            index as u8,
+                                    program_id_index: inner.instruction.program_id_index,
+                                    stack_height:     inner.stack_height,
+                                })
+                        })
+                        .collect();
+                    Err(get_simulation_error(
+                        err.err,
+                        err.meta.logs,
+                        Some(err.meta.compute_units_consumed),
+                        inner_instructions,
+                    ))
                 }
                 // Not important to check if bid slot is less than simulation slot if simulation is successful
                 // since we want to fix incorrect verifications due to stale slot
-                Ok(_) => Ok(()),
+                Ok(info) => Ok(entities::BidSimulation {
+                    logs:                   info.meta.logs,
+                    compute_units_consumed: info.meta.compute_units_consumed,
+                }),
             };
         }
     }
@@ -1348,6 +1541,18 @@ impl Verification for Service {
             tracing::Span::current()
                 .record("opportunity_id", chain_data.opportunity_id.to_string());
         }
+        let penalties = match &bid.profile {
+            Some(profile) => {
+                self.get_reputation_penalties(GetReputationPenaltiesInput {
+                    profile_id: profile.id,
+                })
+                .await
+            }
+            None => entities::ReputationPenalties::default(),
+        };
+        if let Some(banned_until) = penalties.banned_until {
+            return Err(RestError::SearcherBanned { banned_until });
+        }
         let transaction = bid.chain_data.get_transaction().clone();
         Svm::check_tx_size(&transaction)?;
         self.check_compute_budget(&transaction).await?;
@@ -1366,6 +1571,7 @@ impl Verification for Service {
             router:                       bid_data.router,
             bid_payment_instruction_type: bid_payment_instruction_type.clone(),
             transaction:                  transaction.clone(),
+            last_valid_block_height:      None,
         };
         let permission_key = bid_chain_data.get_permission_key();
         tracing::Span::current().record("permission_key", bid_data.permission_account.to_string());
@@ -1384,7 +1590,12 @@ impl Verification for Service {
         }
         self.verify_signatures(&bid, &bid_chain_data, &bid_data.submit_type)
             .await?;
-        match bid_payment_instruction_type {
+        self.check_relayer_spend(CheckRelayerSpendInput {
+            profile_id: bid.profile.as_ref().map(|profile| profile.id),
+            chain_data: bid_chain_data.clone(),
+        })
+        .await?;
+        let simulation = match bid_payment_instruction_type {
             BidPaymentInstructionType::Swap => {
                 let is_indicative_quote =
                     bid_data
@@ -1392,13 +1603,20 @@ impl Verification for Service {
                         .is_some_and(|user_wallet_address| {
                             is_indicative_price_taker(&user_wallet_address)
                         });
-                if !is_indicative_quote {
-                    self.simulate_swap_bid(&bid, bid_data.express_relay_instruction_index)
-                        .await?
+                if is_indicative_quote {
+                    if penalties.require_simulation {
+                        return Err(RestError::SimulationRequired);
+                    }
+                    None
+                } else {
+                    Some(
+                        self.simulate_swap_bid(&bid, bid_data.express_relay_instruction_index)
+                            .await?,
+                    )
                 }
             }
-            BidPaymentInstructionType::SubmitBid => self.simulate_bid(&bid).await?,
-        }
+            BidPaymentInstructionType::SubmitBid => Some(self.simulate_bid(&bid).await?),
+        };
 
         // Check if the bid is not duplicate
         let pending_bids = self
@@ -1408,7 +1626,7 @@ impl Verification for Service {
             return Err(RestError::DuplicateBid);
         }
 
-        Ok((bid_chain_data, bid_data.amount))
+        Ok((bid_chain_data, bid_data.amount, simulation))
     }
 }
 
@@ -1418,7 +1636,11 @@ mod tests {
     use {
         super::{
             get_current_time_rounded_with_offset,
+            get_rpc_simulation_inner_instructions,
+            get_simulation_error,
             VerificationResult,
+            SIMULATION_INNER_INSTRUCTIONS_MAX_COUNT,
+            SIMULATION_LOGS_MAX_SIZE,
         },
         crate::{
             api::{
@@ -1433,6 +1655,7 @@ mod tests {
                     BidChainDataSwapCreateSvm,
                     BidCreate,
                     BidPaymentInstructionType,
+                    SearcherReputation,
                 },
                 repository::{
                     MockAnalyticsDatabase,
@@ -1440,18 +1663,29 @@ mod tests {
                     Repository,
                 },
                 service::{
+                    check_bid_rate_limits::BidRateLimiters,
                     verification::{
                         Verification,
                         BID_MAXIMUM_LIFE_TIME_SVM,
                         BID_MINIMUM_LIFE_TIME_SVM_OTHER,
                     },
+                    verify_bid::DryRunBidInput,
                     Service,
                 },
             },
+            config::{
+                AccountConstraint,
+                AllowedInstruction,
+                AllowedProgram,
+                BidRateLimitsConfig,
+                InstructionPolicy,
+                RateLimitConfig,
+            },
             kernel::{
                 entities::ChainId,
                 traced_sender_svm::tests::MockRpcClient,
             },
+            models,
             opportunity::{
                 entities::{
                     FeeToken,
@@ -1462,6 +1696,9 @@ mod tests {
                     TokenAccountInitializationConfig,
                     TokenAccountInitializationConfigs,
                     TokenAmountSvm,
+                    TokenMint,
+                    TokenMintExtensions,
+                    TransferFee,
                 },
                 service::{
                     get_quote::{
@@ -1474,7 +1711,10 @@ mod tests {
         },
         borsh::BorshDeserialize,
         express_relay::state::FEE_BPS_TO_PPM,
-        express_relay_api_types::opportunity as opportunity_api,
+        express_relay_api_types::{
+            opportunity as opportunity_api,
+            SimulationInnerInstruction,
+        },
         express_relay_client::svm::{
             self,
             GetSubmitBidInstructionParams,
@@ -1497,7 +1737,15 @@ mod tests {
             signature::Keypair,
             signer::Signer,
             system_instruction,
-            transaction::Transaction,
+            transaction::{
+                Transaction,
+                TransactionError,
+            },
+        },
+        solana_transaction_status::{
+            UiCompiledInstruction,
+            UiInnerInstructions,
+            UiInstruction,
         },
         spl_associated_token_account::{
             get_associated_token_address,
@@ -1512,6 +1760,7 @@ mod tests {
         time::{
             Duration,
             OffsetDateTime,
+            PrimitiveDateTime,
         },
         uuid::Uuid,
     };
@@ -1536,6 +1785,7 @@ mod tests {
                     BID_MINIMUM_LIFE_TIME_SVM_OTHER,
                 ),
                 cancellable: true,
+                partial_fill_tolerance_ppm: None,
             }
         }
     }
@@ -1549,9 +1799,13 @@ mod tests {
         pub with_user_payer:             OpportunitySvm,
         pub with_memo:                   OpportunitySvm,
         pub with_minimum_lifetime:       OpportunitySvm,
+        pub with_partial_fill:           OpportunitySvm,
     }
 
-    fn get_opportunity_service(chain_id: ChainId) -> (MockService, TestOpportunities) {
+    fn get_opportunity_service(
+        chain_id: ChainId,
+        token_mint_extensions: TokenMintExtensions,
+    ) -> (MockService, TestOpportunities) {
         let mut opportunity_service = MockService::default();
         let now = OffsetDateTime::now_utc();
         let router = Pubkey::new_unique();
@@ -1843,6 +2097,35 @@ mod tests {
             profile_id: None,
         };
 
+        let opp_with_partial_fill = OpportunitySvm {
+            id: Uuid::new_v4(),
+            permission_key: OpportunitySvm::get_permission_key(
+                BidPaymentInstructionType::Swap,
+                router,
+                permission_account_user_token_specified,
+            ),
+            chain_id: chain_id.clone(),
+            sell_tokens: vec![TokenAmountSvm {
+                token:  searcher_token_address,
+                amount: 0,
+            }],
+            buy_tokens: vec![TokenAmountSvm {
+                token: user_token_address,
+                amount,
+            }],
+            creation_time: now,
+            refresh_time: now,
+            router,
+            permission_account: permission_account_user_token_specified,
+            program: OpportunitySvmProgram::Swap(OpportunitySvmProgramSwap {
+                partial_fill_tolerance_ppm: Some(10_000),
+                ..OpportunitySvmProgramSwap::default_test_with_user_wallet_address(
+                    user_wallet_address,
+                )
+            }),
+            profile_id: None,
+        };
+
         let opp_with_minimum_lifetime = OpportunitySvm {
             id: Uuid::new_v4(),
             permission_key: OpportunitySvm::get_permission_key(
@@ -1884,6 +2167,7 @@ mod tests {
             opp_with_user_payer.clone(),
             opp_with_memo.clone(),
             opp_with_minimum_lifetime.clone(),
+            opp_with_partial_fill.clone(),
         ];
         let opps_cloned = opps.clone();
 
@@ -1904,6 +2188,17 @@ mod tests {
                     .cloned()
             });
 
+        opportunity_service
+            .expect_get_token_mint()
+            .returning(move |input| {
+                Ok(TokenMint {
+                    mint:          input.mint,
+                    decimals:      6,
+                    token_program: spl_token::id(),
+                    extensions:    token_mint_extensions.clone(),
+                })
+            });
+
         opportunity_service
             .expect_get_express_relay_metadata()
             .returning(move |_| {
@@ -1933,11 +2228,19 @@ mod tests {
                 with_user_payer:             opp_with_user_payer,
                 with_memo:                   opp_with_memo,
                 with_minimum_lifetime:       opp_with_minimum_lifetime,
+                with_partial_fill:           opp_with_partial_fill,
             },
         )
     }
 
     fn get_service(mock_simulation: bool) -> (super::Service, TestOpportunities) {
+        get_service_with_token_mint_extensions(mock_simulation, TokenMintExtensions::default())
+    }
+
+    fn get_service_with_token_mint_extensions(
+        mock_simulation: bool,
+        token_mint_extensions: TokenMintExtensions,
+    ) -> (super::Service, TestOpportunities) {
         let chain_id = "solana".to_string();
         let mut rpc_client = MockRpcClient::default();
         if mock_simulation {
@@ -1959,7 +2262,8 @@ mod tests {
         }
 
         let broadcaster_client = MockRpcClient::default();
-        let (opportunity_service, opportunities) = get_opportunity_service(chain_id.clone());
+        let (opportunity_service, opportunities) =
+            get_opportunity_service(chain_id.clone(), token_mint_extensions);
         let db = MockDatabase::default();
         let service = super::Service::new_with_mocks_svm(
             chain_id.clone(),
@@ -2071,6 +2375,7 @@ mod tests {
                 permission_account:           swap_params.permission_account,
                 router:                       swap_params.router_account,
                 bid_payment_instruction_type: BidPaymentInstructionType::Swap,
+                last_valid_block_height:      None,
             }
         );
         assert_eq!(result.1, bid_amount);
@@ -2118,6 +2423,68 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_verify_bid_searcher_banned() {
+        let (service, opportunities) = get_service(true);
+
+        let profile = models::Profile {
+            id:         Uuid::new_v4(),
+            name:       "searcher".to_string(),
+            email:      models::EmailAddress::try_from("searcher@example.com".to_string()).unwrap(),
+            role:       models::ProfileRole::Searcher,
+            created_at: PrimitiveDateTime::MIN,
+            updated_at: PrimitiveDateTime::MIN,
+        };
+        let banned_until = OffsetDateTime::now_utc() + Duration::minutes(10);
+        service
+            .repo
+            .in_memory_store
+            .searcher_reputations
+            .write()
+            .await
+            .insert(
+                profile.id,
+                SearcherReputation {
+                    profile_id:   profile.id,
+                    auctions_won: 10,
+                    bids_landed:  0,
+                    bids_failed:  10,
+                    bids_expired: 0,
+                    banned_until: Some(banned_until),
+                },
+            );
+
+        let opportunity = opportunities.user_token_specified.clone();
+        let searcher = Keypair::new();
+        let instruction = svm::Svm::get_swap_instruction(GetSwapInstructionParams {
+            searcher:             searcher.pubkey(),
+            opportunity_params:   get_opportunity_params(opportunity.clone()),
+            bid_amount:           1,
+            deadline:             (OffsetDateTime::now_utc() + Duration::seconds(30))
+                .unix_timestamp(),
+            fee_receiver_relayer: Pubkey::new_unique(),
+            relayer_signer:       service.config.chain_config.express_relay.relayer.pubkey(),
+        })
+        .unwrap();
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&searcher.pubkey()));
+        transaction.partial_sign(&[searcher], Hash::default());
+
+        let bid_create = BidCreate {
+            chain_id:        service.config.chain_id.clone(),
+            initiation_time: OffsetDateTime::now_utc(),
+            profile:         Some(profile),
+            chain_data:      BidChainDataCreateSvm::Swap(BidChainDataSwapCreateSvm {
+                opportunity_id: opportunity.id,
+                transaction:    transaction.into(),
+            }),
+        };
+
+        let result = service
+            .verify_bid(super::VerifyBidInput { bid_create })
+            .await
+            .unwrap_err();
+        assert_eq!(result, RestError::SearcherBanned { banned_until });
+    }
 
     #[tokio::test]
     async fn test_verify_bid_indicative_price_taker_skip_simulation() {
@@ -2160,6 +2527,7 @@ mod tests {
                 permission_account:           swap_params.permission_account,
                 router:                       swap_params.router_account,
                 bid_payment_instruction_type: BidPaymentInstructionType::Swap,
+                last_valid_block_height:      None,
             }
         );
         assert_eq!(result.1, bid_amount);
@@ -2479,6 +2847,87 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_verify_bid_with_instruction_policy() {
+        let (mut service, opportunities) = get_service(true);
+        let (program_id, allowed_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut policy = InstructionPolicy::default();
+        policy.allowed_programs.push(AllowedProgram {
+            program_id,
+            instructions: Some(vec![AllowedInstruction {
+                discriminator: vec![7, 7],
+                accounts:      vec![AccountConstraint {
+                    position: 0,
+                    one_of:   Some(vec![allowed_account]),
+                    readonly: true,
+                }],
+            }]),
+        });
+        Arc::get_mut(&mut service.0)
+            .expect("Service should not be shared")
+            .config
+            .chain_config
+            .swap_instruction_policy = policy;
+
+        let other_account = Pubkey::new_unique();
+        let cases = vec![
+            (
+                Instruction::new_with_bytes(program_id, &[7, 8], vec![]),
+                Some(InstructionError::UnsupportedInstruction(program_id)),
+            ),
+            (
+                Instruction::new_with_bytes(
+                    program_id,
+                    &[7, 7, 1],
+                    vec![AccountMeta::new_readonly(other_account, false)],
+                ),
+                Some(InstructionError::AccountNotAllowed {
+                    position: 0,
+                    account:  other_account,
+                }),
+            ),
+            (
+                Instruction::new_with_bytes(
+                    program_id,
+                    &[7, 7, 1],
+                    vec![AccountMeta::new(allowed_account, false)],
+                ),
+                Some(InstructionError::WritableAccountNotAllowed {
+                    position: 0,
+                    account:  allowed_account,
+                }),
+            ),
+            (
+                Instruction::new_with_bytes(
+                    program_id,
+                    &[7, 7, 1],
+                    vec![AccountMeta::new_readonly(allowed_account, false)],
+                ),
+                None,
+            ),
+        ];
+        for (instruction, expected_error) in cases.into_iter() {
+            let result = get_verify_bid_result(
+                service.clone(),
+                Keypair::new(),
+                vec![instruction],
+                opportunities.user_token_specified.clone(),
+            )
+            .await;
+            match expected_error {
+                Some(expected_error) => assert_eq!(
+                    result.unwrap_err(),
+                    RestError::InvalidInstruction(Some(0), expected_error)
+                ),
+                // The instruction is allowed, so the bid fails on the later checks
+                None => assert!(!matches!(
+                    result.unwrap_err(),
+                    RestError::InvalidInstruction(Some(0), _)
+                )),
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_verify_bid_when_multiple_express_relay_instructions() {
         let (service, opportunities) = get_service(true);
@@ -2571,6 +3020,62 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_verify_bid_when_invalid_minimum_amount_searcher() {
+        let (service, opportunities) = get_service(true);
+        let searcher = Keypair::new();
+        // The opportunity allows a partial fill of 1%, the bid is built for a partial fill of 10%
+        let opportunity = opportunities.with_partial_fill.clone();
+        let mut program = match opportunity.program.clone() {
+            OpportunitySvmProgram::Swap(program) => program,
+            _ => panic!("Expected swap program"),
+        };
+        program.partial_fill_tolerance_ppm = Some(100_000);
+        let mut opportunity_with_higher_tolerance = opportunity.clone();
+        opportunity_with_higher_tolerance.program = OpportunitySvmProgram::Swap(program);
+        let swap_instruction = svm::Svm::get_swap_instruction(GetSwapInstructionParams {
+            searcher:             searcher.pubkey(),
+            opportunity_params:   get_opportunity_params(opportunity_with_higher_tolerance),
+            bid_amount:           1_000,
+            deadline:             (OffsetDateTime::now_utc() + Duration::seconds(30))
+                .unix_timestamp(),
+            fee_receiver_relayer: Pubkey::new_unique(),
+            relayer_signer:       service.config.chain_config.express_relay.relayer.pubkey(),
+        })
+        .unwrap();
+        let result =
+            get_verify_bid_result(service, searcher, vec![swap_instruction], opportunity).await;
+        assert_eq!(
+            result.unwrap_err(),
+            RestError::InvalidSwapInstruction(SwapInstructionError::MinimumAmountSearcher {
+                expected: 990,
+                found:    900,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_bid_with_partial_fill_is_ranked_by_minimum_amount_searcher() {
+        let (service, opportunities) = get_service(true);
+        let searcher = Keypair::new();
+        let opportunity = opportunities.with_partial_fill.clone();
+        let swap_instruction = svm::Svm::get_swap_instruction(GetSwapInstructionParams {
+            searcher:             searcher.pubkey(),
+            opportunity_params:   get_opportunity_params(opportunity.clone()),
+            bid_amount:           1_000,
+            deadline:             (OffsetDateTime::now_utc() + Duration::seconds(30))
+                .unix_timestamp(),
+            fee_receiver_relayer: Pubkey::new_unique(),
+            relayer_signer:       service.config.chain_config.express_relay.relayer.pubkey(),
+        })
+        .unwrap();
+        let result = get_verify_bid_result(service, searcher, vec![swap_instruction], opportunity)
+            .await
+            .unwrap();
+        // The bid may only provide 99% of the searcher amount, so it is ranked by that amount
+        assert_eq!(result.1, 990);
+    }
+
     #[tokio::test]
     async fn test_verify_bid_when_invalid_mint_searcher() {
         let (service, opportunities) = get_service(true);
@@ -3238,6 +3743,7 @@ mod tests {
                 permission_account:           swap_params.permission_account,
                 router:                       swap_params.router_account,
                 bid_payment_instruction_type: BidPaymentInstructionType::Swap,
+                last_valid_block_height:      None,
             }
         );
         assert_eq!(result.1, bid_amount);
@@ -3317,6 +3823,7 @@ mod tests {
                 permission_account:           swap_params.permission_account,
                 router:                       swap_params.router_account,
                 bid_payment_instruction_type: BidPaymentInstructionType::Swap,
+                last_valid_block_height:      None,
             }
         );
         assert_eq!(result.1, bid_amount);
@@ -3553,6 +4060,7 @@ mod tests {
                 permission_account:           swap_params.permission_account,
                 router:                       swap_params.router_account,
                 bid_payment_instruction_type: BidPaymentInstructionType::Swap,
+                last_valid_block_height:      None,
             }
         );
         assert_eq!(result.1, bid_amount);
@@ -3812,12 +4320,98 @@ mod tests {
         let result = service
             .verify_bid(super::VerifyBidInput { bid_create })
             .await;
-        assert_eq!(
-            result.unwrap_err(),
-            RestError::SimulationError {
-                reason: "".to_string(),
+        match result.unwrap_err() {
+            RestError::SimulationError { reason, details } => {
+                assert_eq!(reason, "");
+                assert!(details.logs.is_empty());
+                assert!(!details.truncated);
             }
-        )
+            error => panic!("Expected simulation error, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_get_simulation_error_caps_details() {
+        let log = "a".repeat(1024);
+        let logs: Vec<String> = (0..2 * SIMULATION_LOGS_MAX_SIZE / log.len())
+            .map(|i| format!("{}{}", i, log))
+            .collect();
+        let inner_instructions = (0..SIMULATION_INNER_INSTRUCTIONS_MAX_COUNT + 1)
+            .map(|_| SimulationInnerInstruction {
+                index:            0,
+                program_id_index: 1,
+                stack_height:     2,
+            })
+            .collect();
+        let error = get_simulation_error(
+            TransactionError::AccountNotFound,
+            logs.clone(),
+            Some(100),
+            inner_instructions,
+        );
+        match error {
+            RestError::SimulationError { reason, details } => {
+                assert_eq!(reason, details.logs.join("\n"));
+                assert!(reason.len() <= SIMULATION_LOGS_MAX_SIZE);
+                assert!(details.truncated);
+                assert!(
+                    details.logs.iter().map(|log| log.len()).sum::<usize>()
+                        <= SIMULATION_LOGS_MAX_SIZE
+                );
+                // The last logs are kept since they contain the failure
+                assert_eq!(details.logs.last(), logs.last());
+                assert_eq!(
+                    details.inner_instructions.len(),
+                    SIMULATION_INNER_INSTRUCTIONS_MAX_COUNT
+                );
+                assert_eq!(details.compute_units_consumed, Some(100));
+                assert_eq!(details.error, "AccountNotFound");
+            }
+            error => panic!("Expected simulation error, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_get_rpc_simulation_inner_instructions() {
+        let compiled = |program_id_index: u8, stack_height: Option<u32>| {
+            UiInstruction::Compiled(UiCompiledInstruction {
+                program_id_index,
+                accounts: vec![],
+                data: String::new(),
+                stack_height,
+            })
+        };
+        let inner_instructions = get_rpc_simulation_inner_instructions(Some(vec![
+            UiInnerInstructions {
+                index:        0,
+                instructions: vec![compiled(3, Some(2)), compiled(4, Some(3))],
+            },
+            UiInnerInstructions {
+                index:        2,
+                instructions: vec![compiled(5, None)],
+            },
+        ]));
+        assert_eq!(
+            inner_instructions,
+            vec![
+                SimulationInnerInstruction {
+                    index:            0,
+                    program_id_index: 3,
+                    stack_height:     2,
+                },
+                SimulationInnerInstruction {
+                    index:            0,
+                    program_id_index: 4,
+                    stack_height:     3,
+                },
+                SimulationInnerInstruction {
+                    index:            2,
+                    program_id_index: 5,
+                    stack_height:     0,
+                },
+            ]
+        );
+        assert_eq!(get_rpc_simulation_inner_instructions(None), vec![]);
     }
 
     #[tokio::test]
@@ -3997,6 +4591,7 @@ mod tests {
                 permission_account:           swap_params.permission_account,
                 router:                       swap_params.router_account,
                 bid_payment_instruction_type: BidPaymentInstructionType::Swap,
+                last_valid_block_height:      None,
             }
         );
         assert_eq!(result.1, bid_amount);
@@ -4138,6 +4733,7 @@ mod tests {
                 permission_account:           swap_params.permission_account,
                 router:                       swap_params.router_account,
                 bid_payment_instruction_type: BidPaymentInstructionType::Swap,
+                last_valid_block_height:      None,
             }
         );
         assert_eq!(result.1, bid_amount);
@@ -4985,4 +5581,185 @@ mod tests {
         .await
         .unwrap();
     }
+
+    /// Returns a swap bid on the opportunity from a searcher profile which passes the verification.
+    fn get_profile_bid_create(
+        service: &super::Service,
+        opportunity: OpportunitySvm,
+        profile: Option<models::Profile>,
+    ) -> BidCreate {
+        let searcher = Keypair::new();
+        let instruction = svm::Svm::get_swap_instruction(GetSwapInstructionParams {
+            searcher:             searcher.pubkey(),
+            opportunity_params:   get_opportunity_params(opportunity.clone()),
+            bid_amount:           1,
+            deadline:             (OffsetDateTime::now_utc() + Duration::seconds(30))
+                .unix_timestamp(),
+            fee_receiver_relayer: Pubkey::new_unique(),
+            relayer_signer:       service.config.chain_config.express_relay.relayer.pubkey(),
+        })
+        .unwrap();
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&searcher.pubkey()));
+        transaction.partial_sign(&[searcher], Hash::default());
+        BidCreate {
+            chain_id: service.config.chain_id.clone(),
+            initiation_time: OffsetDateTime::now_utc(),
+            profile,
+            chain_data: BidChainDataCreateSvm::Swap(BidChainDataSwapCreateSvm {
+                opportunity_id: opportunity.id,
+                transaction:    transaction.into(),
+            }),
+        }
+    }
+
+    fn get_profile() -> models::Profile {
+        models::Profile {
+            id:         Uuid::new_v4(),
+            name:       "searcher".to_string(),
+            email:      models::EmailAddress::try_from("searcher@example.com".to_string()).unwrap(),
+            role:       models::ProfileRole::Searcher,
+            created_at: PrimitiveDateTime::MIN,
+            updated_at: PrimitiveDateTime::MIN,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_bid() {
+        let (service, opportunities) = get_service(true);
+        let bid_create = get_profile_bid_create(
+            &service,
+            opportunities.user_token_specified.clone(),
+            Some(get_profile()),
+        );
+
+        let (_, amount, simulation) = service
+            .dry_run_bid(DryRunBidInput { bid_create })
+            .await
+            .unwrap();
+        assert_eq!(amount, 1);
+        assert!(simulation.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_bid_without_profile() {
+        let (service, opportunities) = get_service(true);
+        let bid_create =
+            get_profile_bid_create(&service, opportunities.user_token_specified.clone(), None);
+
+        let result = service
+            .dry_run_bid(DryRunBidInput { bid_create })
+            .await
+            .unwrap_err();
+        assert_eq!(result, RestError::Unauthorized);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_bid_rate_limited() {
+        let (mut service, opportunities) = get_service(true);
+        Arc::get_mut(&mut service.0)
+            .expect("Only one reference should exist at setup")
+            .config
+            .chain_config
+            .bid_rate_limiters = BidRateLimiters::new(&BidRateLimitsConfig {
+            profile:        Some(RateLimitConfig {
+                capacity:          1,
+                refill_per_second: 0.001,
+            }),
+            permission_key: None,
+        });
+        let profile = get_profile();
+
+        service
+            .dry_run_bid(DryRunBidInput {
+                bid_create: get_profile_bid_create(
+                    &service,
+                    opportunities.user_token_specified.clone(),
+                    Some(profile.clone()),
+                ),
+            })
+            .await
+            .expect("The first verification should not be limited");
+        let result = service
+            .dry_run_bid(DryRunBidInput {
+                bid_create: get_profile_bid_create(
+                    &service,
+                    opportunities.user_token_specified.clone(),
+                    Some(profile),
+                ),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(result, RestError::RateLimited { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_verify_bid_when_confidential_transfer_mint() {
+        let (service, opportunities) = get_service_with_token_mint_extensions(
+            true,
+            TokenMintExtensions {
+                confidential_transfers: true,
+                ..Default::default()
+            },
+        );
+        let searcher = Keypair::new();
+        let opportunity = opportunities.user_token_specified.clone();
+        let swap_instruction = svm::Svm::get_swap_instruction(GetSwapInstructionParams {
+            searcher:             searcher.pubkey(),
+            opportunity_params:   get_opportunity_params(opportunity.clone()),
+            bid_amount:           1_000,
+            deadline:             (OffsetDateTime::now_utc() + Duration::seconds(30))
+                .unix_timestamp(),
+            fee_receiver_relayer: Pubkey::new_unique(),
+            relayer_signer:       service.config.chain_config.express_relay.relayer.pubkey(),
+        })
+        .unwrap();
+        let mint = opportunity.sell_tokens[0].token;
+        let result =
+            get_verify_bid_result(service, searcher, vec![swap_instruction], opportunity).await;
+        assert_eq!(
+            result.unwrap_err(),
+            RestError::UnsupportedTokenExtension {
+                mint,
+                extension: "confidential_transfer",
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_bid_when_amount_below_transfer_fee() {
+        let (service, opportunities) = get_service_with_token_mint_extensions(
+            true,
+            TokenMintExtensions {
+                transfer_fees: vec![TransferFee {
+                    epoch:       0,
+                    maximum_fee: 10,
+                    fee_bps:     10_000,
+                }],
+                ..Default::default()
+            },
+        );
+        let searcher = Keypair::new();
+        let opportunity = opportunities.user_token_specified.clone();
+        let swap_instruction = svm::Svm::get_swap_instruction(GetSwapInstructionParams {
+            searcher:             searcher.pubkey(),
+            opportunity_params:   get_opportunity_params(opportunity.clone()),
+            bid_amount:           10,
+            deadline:             (OffsetDateTime::now_utc() + Duration::seconds(30))
+                .unix_timestamp(),
+            fee_receiver_relayer: Pubkey::new_unique(),
+            relayer_signer:       service.config.chain_config.express_relay.relayer.pubkey(),
+        })
+        .unwrap();
+        let mint = opportunity.sell_tokens[0].token;
+        let result =
+            get_verify_bid_result(service, searcher, vec![swap_instruction], opportunity).await;
+        assert_eq!(
+            result.unwrap_err(),
+            RestError::InvalidSwapInstruction(SwapInstructionError::AmountBelowTransferFee {
+                mint,
+                transfer_fee: 10,
+                found: 10,
+            })
+        );
+    }
 }
@@ -0,0 +1,47 @@
+use {
+    super::{
+        check_bid_rate_limits::CheckBidRateLimitsInput,
+        verification::{
+            Verification,
+            VerificationResult,
+            VerifyBidInput,
+        },
+        Service,
+    },
+    crate::{
+        api::RestError,
+        auction::entities,
+    },
+};
+
+pub struct DryRunBidInput {
+    pub bid_create: entities::BidCreate,
+}
+
+impl Service {
+    /// Runs the whole verification of the bid without storing it or adding it to an auction.
+    ///
+    /// Only bids of a profile can be verified, and the verifications count towards the bid rate limits.
+    #[tracing::instrument(
+        skip_all,
+        fields(profile_name, permission_key, opportunity_id),
+        err(level = tracing::Level::TRACE)
+    )]
+    pub async fn dry_run_bid(
+        &self,
+        input: DryRunBidInput,
+    ) -> Result<VerificationResult, RestError> {
+        let Some(profile) = &input.bid_create.profile else {
+            return Err(RestError::Unauthorized);
+        };
+        tracing::Span::current().record("profile_name", &profile.name);
+        self.check_bid_rate_limits(CheckBidRateLimitsInput {
+            bid_create: input.bid_create.clone(),
+        })
+        .await?;
+        self.verify_bid(VerifyBidInput {
+            bid_create: input.bid_create,
+        })
+        .await
+    }
+}
@@ -19,16 +19,30 @@ use {
         Result,
     },
     express_relay_api_types::SvmChainUpdate,
-    futures::future::join_all,
+    futures::{
+        future::join_all,
+        stream::BoxStream,
+    },
+    solana_account_decoder::{
+        UiAccount,
+        UiAccountEncoding,
+    },
     solana_client::{
+        nonblocking::pubsub_client::PubsubClient,
         rpc_config::{
+            RpcAccountInfoConfig,
+            RpcProgramAccountsConfig,
             RpcTransactionLogsConfig,
             RpcTransactionLogsFilter,
         },
-        rpc_response::RpcLogsResponse,
+        rpc_response::{
+            Response,
+            RpcLogsResponse,
+        },
     },
     solana_sdk::{
         commitment_config::CommitmentConfig,
+        pubkey::Pubkey,
         signature::Signature,
     },
     std::{
@@ -37,10 +51,24 @@ use {
         time::Duration,
     },
     time::OffsetDateTime,
-    tokio_stream::StreamExt,
+    tokio_stream::{
+        StreamExt,
+        StreamMap,
+    },
 };
 
 const GET_LATEST_BLOCKHASH_INTERVAL_SVM: Duration = Duration::from_secs(5);
+const SIGNATURE_STATUS_POLL_INTERVAL_SVM: Duration = Duration::from_millis(200);
+const MAX_ACCOUNTS_PER_REQUEST_SVM: usize = 100;
+
+fn get_subscription_account_config() -> RpcAccountInfoConfig {
+    RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::processed()),
+        ..RpcAccountInfoConfig::default()
+    }
+}
+
 impl Service {
     pub async fn run_submission_loop(&self) -> Result<()> {
         tracing::info!(
@@ -50,7 +78,7 @@ impl Service {
         let mut exit_check_interval = tokio::time::interval(EXIT_CHECK_INTERVAL);
 
         let ws_client = self.get_ws_client().await?;
-        let mut stream = Service::get_trigger_stream(&ws_client).await?;
+        let mut stream = self.get_trigger_stream(&ws_client).await?;
 
         while !SHOULD_EXIT.load(Ordering::Acquire) {
             tokio::select! {
@@ -203,6 +231,121 @@ impl Service {
         Ok(())
     }
 
+    /// Subscribes to the accounts and fetches their current state, as the subscriptions only notify about later changes.
+    /// The account streams yield `None` once when their subscription ends.
+    async fn subscribe_to_accounts<'a>(
+        &self,
+        ws_client: &'a PubsubClient,
+        keys_with_owners: Vec<(Pubkey, Pubkey)>,
+        account_streams: &mut StreamMap<Pubkey, BoxStream<'a, Option<Response<UiAccount>>>>,
+    ) -> Result<()> {
+        let cache = self.config.chain_config.simulator.subscribed_accounts();
+        for (key, owner) in keys_with_owners.iter() {
+            // The express relay accounts are updated through the program subscription
+            if *owner == cache.express_relay_program_id() || account_streams.contains_key(key) {
+                continue;
+            }
+            let (stream, _) = ws_client
+                .account_subscribe(key, Some(get_subscription_account_config()))
+                .await?;
+            account_streams.insert(
+                *key,
+                Box::pin(stream.map(Some).chain(tokio_stream::once(None))),
+            );
+        }
+        let keys: Vec<Pubkey> = keys_with_owners.into_iter().map(|(key, _)| key).collect();
+        for chunk in keys.chunks(MAX_ACCOUNTS_PER_REQUEST_SVM) {
+            let response = self
+                .config
+                .chain_config
+                .client
+                .get_multiple_accounts_with_commitment(chunk, CommitmentConfig::processed())
+                .await?;
+            for (key, account) in chunk.iter().zip(response.value) {
+                cache.update(*key, account, response.context.slot).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps the hot accounts of the simulator up to date with account and program subscriptions.
+    pub async fn run_account_subscription_loop(&self) -> Result<()> {
+        tracing::info!(
+            chain_id = self.config.chain_id,
+            "Starting account subscription..."
+        );
+        let cache = self.config.chain_config.simulator.subscribed_accounts();
+        let mut new_keys_receiver = cache.lock_new_keys_receiver().await;
+        let ws_client = self.get_ws_client().await?;
+        let (mut program_stream, _) = ws_client
+            .program_subscribe(
+                &cache.express_relay_program_id(),
+                Some(RpcProgramAccountsConfig {
+                    account_config: get_subscription_account_config(),
+                    ..RpcProgramAccountsConfig::default()
+                }),
+            )
+            .await?;
+        let mut account_streams = StreamMap::new();
+
+        let result: Result<()> = async {
+            // Resubscribe to the accounts tracked before a restart of the loop
+            let tracked_keys = cache.get_tracked_keys().await;
+            self.subscribe_to_accounts(&ws_client, tracked_keys, &mut account_streams)
+                .await?;
+            let mut exit_check_interval = tokio::time::interval(EXIT_CHECK_INTERVAL);
+            while !SHOULD_EXIT.load(Ordering::Acquire) {
+                tokio::select! {
+                    Some(key_with_owner) = new_keys_receiver.recv() => {
+                        let mut keys = vec![key_with_owner];
+                        while let Ok(key_with_owner) = new_keys_receiver.try_recv() {
+                            keys.push(key_with_owner);
+                        }
+                        self.subscribe_to_accounts(&ws_client, keys, &mut account_streams).await?;
+                    }
+                    update = program_stream.next() => {
+                        let update = update.ok_or(anyhow!("Program subscription ended for chain: {}", self.config.chain_id))?;
+                        cache.update_from_program_notification(update).await;
+                    }
+                    Some((key, update)) = account_streams.next() => match update {
+                        Some(update) => cache.update_from_notification(key, update).await,
+                        None => {
+                            tracing::error!(chain_id = self.config.chain_id, key = %key, "Account subscription ended, resubscribing");
+                            account_streams.remove(&key);
+                            cache.resubscribe(&key).await;
+                        }
+                    },
+                    _ = exit_check_interval.tick() => {}
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        // The cached accounts are not kept up to date without the subscriptions
+        cache.clear().await;
+        tracing::info!("Shutting down account subscription...");
+        result
+    }
+
+    /// Polls the statuses of the signatures requested by all the auctions of the chain in batches.
+    pub async fn run_signature_status_poller_loop(&self) -> Result<()> {
+        tracing::info!(
+            chain_id = self.config.chain_id,
+            "Starting signature status poller..."
+        );
+        let poller = &self.config.chain_config.signature_status_poller;
+        let mut poll_interval = tokio::time::interval(SIGNATURE_STATUS_POLL_INTERVAL_SVM);
+        while !SHOULD_EXIT.load(Ordering::Acquire) {
+            poll_interval.tick().await;
+            poller.poll().await;
+        }
+        // Answer the requests registered before the shutdown
+        poller.poll().await;
+        tracing::info!("Shutting down signature status poller...");
+        Ok(())
+    }
+
     pub async fn update_metrics(&self) {
         self.repo.update_metrics().await;
     }
@@ -220,6 +363,9 @@ impl Service {
 
             match responses {
                 (Ok(block_hash_result), Ok(fee)) => {
+                    self.repo
+                        .add_recent_blockhash(block_hash_result.0, block_hash_result.1)
+                        .await;
                     // TODO we should not know about the api layer here
                     if let Err(e) =
                         self.event_sender
@@ -38,6 +38,8 @@ pub enum Options {
     Migrate(MigrateOptions),
     /// Run clickhouse migrations and exit.
     MigrateClickhouse(ClickhouseConfig),
+    /// Replay the winner selection of an auction on a snapshot of the chain state and exit.
+    Replay(ReplayOptions),
 }
 
 #[derive(Args, Clone, Debug)]
@@ -48,6 +50,33 @@ pub struct MigrateOptions {
     pub database_url: String,
 }
 
+#[derive(Args, Clone, Debug)]
+pub struct ReplayOptions {
+    /// database url to load the auction and its bids from.
+    #[arg(long = "database-url")]
+    #[arg(env = "DATABASE_URL")]
+    pub database_url: String,
+
+    /// The analytics database to load the bids that are no longer in the database from.
+    #[command(flatten)]
+    pub clickhouse_config: ClickhouseConfig,
+
+    #[command(flatten)]
+    pub config: ConfigOptions,
+
+    /// Id of the auction to replay.
+    #[arg(long = "auction-id")]
+    pub auction_id: Uuid,
+
+    /// Path to the json snapshot of the accounts touched by the auction.
+    #[arg(long = "snapshot")]
+    pub snapshot: String,
+
+    /// Path to write the json report to. The report is logged if not set.
+    #[arg(long = "output")]
+    pub output: Option<String>,
+}
+
 #[derive(Args, Clone, Debug)]
 pub struct SubwalletOptions {
     #[command(flatten)]
@@ -136,6 +165,14 @@ impl ConfigMap {
         // TODO: the default serde deserialization doesn't enforce unique keys
         let yaml_content = fs::read_to_string(path)?;
         let config: ConfigMap = serde_yaml::from_str(&yaml_content)?;
+        for (chain_id, chain_config) in config.chains.iter() {
+            match chain_config {
+                Config::Svm(chain_config) => chain_config
+                    .bid_rate_limits
+                    .validate()
+                    .map_err(|e| anyhow::anyhow!("Invalid config for chain {}: {}", chain_id, e))?,
+            }
+        }
         Ok(config)
     }
 }
@@ -187,6 +224,34 @@ pub struct ConfigSvm {
     /// Auction time for the chain (how long to wait before choosing winning bids)
     #[serde(default = "ConfigSvm::default_auction_time", with = "humantime_serde")]
     pub auction_time:                        Duration,
+    /// Strategy used to choose the winning bids of an auction.
+    #[serde(default)]
+    pub winner_selection_mode:               WinnerSelectionMode,
+    /// If set, the winner bids are submitted as an atomic bundle to a block engine.
+    #[serde(default)]
+    pub bundle_submission:                   Option<BundleSubmissionConfig>,
+    /// Strategy used to choose the `rpc_tx_submission_urls` endpoints to send each transaction to.
+    #[serde(default)]
+    pub tx_submission_strategy:              TransactionSubmissionStrategy,
+    /// What triggers the submission and conclusion of the auctions.
+    #[serde(default)]
+    pub auction_trigger_mode:                AuctionTriggerMode,
+    /// Rule used to rank the bids of an auction and price its winners.
+    #[serde(default)]
+    pub auction_pricing_rule:                AuctionPricingRule,
+    /// Instructions allowed in the transactions of swap bids.
+    /// If set, it replaces the default policy, so the default programs need to be listed as well.
+    #[serde(default)]
+    pub swap_instruction_policy:             InstructionPolicy,
+    /// Rate limits for the bids submitted to the chain.
+    #[serde(default)]
+    pub bid_rate_limits:                     BidRateLimitsConfig,
+    /// Penalties for the searchers whose winning bids fail on-chain or expire unsigned.
+    #[serde(default)]
+    pub searcher_reputation:                 SearcherReputationConfig,
+    /// Ceilings for the lamports the relayer pays for the fees and rent of the bid transactions.
+    #[serde(default)]
+    pub relayer_spend_limits:                RelayerSpendLimitsConfig,
 }
 
 impl ConfigSvm {
@@ -199,6 +264,289 @@ impl ConfigSvm {
     }
 }
 
+/// Strategy for choosing the winner bids among the bids of an auction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WinnerSelectionMode {
+    /// Replay the bids in descending amount order and keep the ones that succeed.
+    #[default]
+    Sequential,
+    /// Use the account write-lock sets and the simulation results of the bids
+    /// to choose the set of bids with the highest total payment.
+    BundleAware,
+}
+
+/// Source of the triggers for submitting and concluding the auctions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionTriggerMode {
+    /// Trigger on a fixed interval.
+    #[default]
+    Interval,
+    /// Trigger on every new slot using the `slotSubscribe` websocket subscription.
+    /// Falls back to the interval if the subscription drops.
+    Slot,
+}
+
+/// Price the winners of an auction are charged
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PricingRule {
+    /// Each winner pays its own bid.
+    #[default]
+    FirstPrice,
+    /// The winners pay the highest losing bid, or the reserve price if there is no losing bid.
+    /// The bid amount signed by the searcher is paid on-chain, and once the bid is won
+    /// the relayer refunds the searcher the difference to the clearing price.
+    SecondPrice,
+}
+
+/// Rule for ranking the bids of an auction and pricing its winners
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AuctionPricingRule {
+    #[serde(default)]
+    pub price:                 PricingRule,
+    /// Minimum bid amount per permission account. Bids below the reserve price are not eligible to win.
+    #[serde(default)]
+    #[serde_as(as = "HashMap<DisplayFromStr, _>")]
+    pub reserve_prices:        HashMap<Pubkey, u64>,
+    /// A bid only outranks another bid on amount if it is higher by at least this amount.
+    /// Bids within the increment of the best remaining bid are tied and ranked by priority fee and then arrival time.
+    #[serde(default)]
+    pub minimum_bid_increment: u64,
+}
+
+/// Token bucket rate limit
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RateLimitConfig {
+    /// Maximum number of requests in a burst.
+    pub capacity:          u32,
+    /// Number of requests regained per second.
+    pub refill_per_second: f64,
+}
+
+impl RateLimitConfig {
+    fn validate(&self) -> Result<()> {
+        if self.capacity == 0 {
+            anyhow::bail!("Rate limit capacity must be greater than 0");
+        }
+        if !self.refill_per_second.is_finite() || self.refill_per_second <= 0.0 {
+            anyhow::bail!(
+                "Rate limit refill_per_second must be a finite number greater than 0, got {}",
+                self.refill_per_second
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Rate limits applied to the bids before their verification. Unset limits are disabled.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BidRateLimitsConfig {
+    /// Limit per searcher profile. Bids without a profile are not limited by it.
+    #[serde(default)]
+    pub profile:        Option<RateLimitConfig>,
+    /// Limit per searcher profile and permission key. Bids without a profile share one limit per permission key.
+    #[serde(default)]
+    pub permission_key: Option<RateLimitConfig>,
+}
+
+impl BidRateLimitsConfig {
+    fn validate(&self) -> Result<()> {
+        if let Some(profile) = &self.profile {
+            profile
+                .validate()
+                .map_err(|e| anyhow::anyhow!("bid_rate_limits.profile: {}", e))?;
+        }
+        if let Some(permission_key) = &self.permission_key {
+            permission_key
+                .validate()
+                .map_err(|e| anyhow::anyhow!("bid_rate_limits.permission_key: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Ceilings for the lamports paid by the relayer for the bid transactions. Unset ceilings are disabled.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RelayerSpendLimitsConfig {
+    /// Maximum lamports the relayer pays for a single bid.
+    #[serde(default)]
+    pub per_bid:           Option<u64>,
+    /// Maximum lamports the relayer pays for the bids of a searcher profile in a UTC day.
+    /// Bids without a profile can not spend relayer lamports when it is set.
+    #[serde(default)]
+    pub per_profile_daily: Option<u64>,
+}
+
+/// Penalties for searchers with a bad reputation. All penalties are disabled by default.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearcherReputationConfig {
+    /// Number of won auctions before the penalties can apply to a searcher.
+    #[serde(default = "SearcherReputationConfig::default_minimum_auctions_won")]
+    pub minimum_auctions_won: u64,
+    /// Share of the won auctions that failed on-chain or expired unsigned above which the penalties apply.
+    /// It is a number between 0 and 1.
+    #[serde(default = "SearcherReputationConfig::default_maximum_failure_rate")]
+    pub maximum_failure_rate: f64,
+    /// Rank the bids of penalized searchers after the other bids with the same amount.
+    #[serde(default)]
+    pub deprioritize_on_ties: bool,
+    /// Reject the bids of a searcher for this duration once it gets penalized.
+    #[serde(default, with = "humantime_serde")]
+    pub ban_duration:         Option<Duration>,
+    /// Require the bids of penalized searchers to be simulated.
+    /// Bids on indicative quotes can not be simulated, so they are rejected.
+    #[serde(default)]
+    pub require_simulation:   bool,
+}
+
+impl SearcherReputationConfig {
+    pub fn default_minimum_auctions_won() -> u64 {
+        20
+    }
+
+    pub fn default_maximum_failure_rate() -> f64 {
+        0.2
+    }
+}
+
+impl Default for SearcherReputationConfig {
+    fn default() -> Self {
+        Self {
+            minimum_auctions_won: Self::default_minimum_auctions_won(),
+            maximum_failure_rate: Self::default_maximum_failure_rate(),
+            deprioritize_on_ties: false,
+            ban_duration:         None,
+            require_simulation:   false,
+        }
+    }
+}
+
+/// Policy for the instructions that may appear in a bid transaction.
+/// Instructions of the express relay program are always allowed.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct InstructionPolicy {
+    pub allowed_programs: Vec<AllowedProgram>,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AllowedProgram {
+    #[serde_as(as = "DisplayFromStr")]
+    pub program_id:   Pubkey,
+    /// Allowed instructions of the program. If not set, all the instructions of the program are allowed.
+    #[serde(default)]
+    pub instructions: Option<Vec<AllowedInstruction>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AllowedInstruction {
+    /// Prefix of the instruction data that identifies the instruction,
+    /// e.g. the 4 byte little endian index for system program instructions or the 8 byte anchor discriminator.
+    pub discriminator: Vec<u8>,
+    #[serde(default)]
+    pub accounts:      Vec<AccountConstraint>,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AccountConstraint {
+    /// Position of the account in the instruction accounts.
+    pub position: usize,
+    /// If set, the account must be one of these accounts.
+    #[serde(default)]
+    #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
+    pub one_of:   Option<Vec<Pubkey>>,
+    /// If true, the account must not be writable.
+    #[serde(default)]
+    pub readonly: bool,
+}
+
+impl AllowedInstruction {
+    fn new(discriminator: &[u8]) -> Self {
+        Self {
+            discriminator: discriminator.to_vec(),
+            accounts:      vec![],
+        }
+    }
+}
+
+impl InstructionPolicy {
+    pub fn get_allowed_program(&self, program_id: &Pubkey) -> Option<&AllowedProgram> {
+        self.allowed_programs
+            .iter()
+            .find(|program| program.program_id == *program_id)
+    }
+}
+
+impl Default for InstructionPolicy {
+    /// System transfers, wrapping and unwrapping of SOL, creation of associated token accounts,
+    /// memos and compute budget instructions.
+    fn default() -> Self {
+        Self {
+            allowed_programs: vec![
+                AllowedProgram {
+                    program_id:   solana_sdk::system_program::id(),
+                    // Transfer
+                    instructions: Some(vec![AllowedInstruction::new(&[2, 0, 0, 0])]),
+                },
+                AllowedProgram {
+                    program_id:   spl_token::id(),
+                    // CloseAccount and SyncNative
+                    instructions: Some(vec![
+                        AllowedInstruction::new(&[9]),
+                        AllowedInstruction::new(&[17]),
+                    ]),
+                },
+                AllowedProgram {
+                    program_id:   spl_associated_token_account::id(),
+                    // Create and CreateIdempotent
+                    instructions: Some(vec![
+                        AllowedInstruction::new(&[0]),
+                        AllowedInstruction::new(&[1]),
+                    ]),
+                },
+                AllowedProgram {
+                    program_id:   spl_memo_client::ID,
+                    instructions: None,
+                },
+                AllowedProgram {
+                    program_id:   solana_sdk::compute_budget::id(),
+                    instructions: None,
+                },
+            ],
+        }
+    }
+}
+
+/// Strategy for choosing the tx submission endpoints to send a transaction to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionSubmissionStrategy {
+    /// Send the transaction to all the endpoints.
+    #[default]
+    BroadcastAll,
+    /// Send the transaction to the endpoints with the lowest observed landing time.
+    RaceFastest,
+    /// Send the transaction to an endpoint chosen at random, weighted by the observed landing rate of the endpoints.
+    WeightedByLandingRate,
+}
+
+/// Config for submitting the winner bids of an auction as one atomic bundle
+#[serde_as]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BundleSubmissionConfig {
+    /// Url of the block engine that accepts the `sendBundle` requests.
+    pub block_engine_url: String,
+    /// Accounts that can receive the bundle tip. One of them is chosen for each bundle.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub tip_accounts:     Vec<Pubkey>,
+    /// Amount of lamports paid by the relayer as the tip of each bundle.
+    pub tip_lamports:     u64,
+}
+
 /// Optional whitelist of token mints to allow for getting quotes for
 #[serde_as]
 #[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
@@ -0,0 +1,278 @@
+//! Client for block engines that accept atomic transaction bundles (e.g. Jito block engines).
+//! Bundles are sent through the `sendBundle` JSON-RPC method and either land together in order or not at all.
+
+use {
+    base64::{
+        prelude::BASE64_STANDARD,
+        Engine,
+    },
+    serde::Deserialize,
+    solana_sdk::transaction::VersionedTransaction,
+    std::{
+        fmt::{
+            Display,
+            Formatter,
+        },
+        time::Duration,
+    },
+};
+
+/// Maximum number of transactions accepted by block engines in a single bundle
+pub const MAX_BUNDLE_SIZE: usize = 5;
+
+const BUNDLES_PATH: &str = "api/v1/bundles";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleError {
+    /// The block engine rejected the bundle
+    Rejected { code: i64, message: String },
+    /// The bundle could not be sent to the block engine or the response was invalid
+    Request(String),
+}
+
+impl Display for BundleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BundleError::Rejected { code, message } => {
+                write!(f, "Bundle rejected by block engine ({}): {}", code, message)
+            }
+            BundleError::Request(message) => {
+                write!(f, "Failed to send bundle to block engine: {}", message)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code:    i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<String>,
+    error:  Option<JsonRpcError>,
+}
+
+pub struct BlockEngineClient {
+    client: reqwest::Client,
+    url:    String,
+}
+
+impl BlockEngineClient {
+    pub fn new(url: &str, timeout_secs: u64) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(timeout_secs))
+                .build()
+                .expect("Failed to build block engine http client"),
+            url:    format!("{}/{}", url.trim_end_matches('/'), BUNDLES_PATH),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends the transactions as one atomic bundle.
+    /// Returns the bundle id if the block engine accepted the bundle.
+    #[tracing::instrument(skip_all, fields(bundle_id), err(level = tracing::Level::TRACE))]
+    pub async fn send_bundle(
+        &self,
+        transactions: &[VersionedTransaction],
+    ) -> Result<String, BundleError> {
+        if transactions.is_empty() || transactions.len() > MAX_BUNDLE_SIZE {
+            return Err(BundleError::Request(format!(
+                "Bundle must contain between 1 and {} transactions, found {}",
+                MAX_BUNDLE_SIZE,
+                transactions.len()
+            )));
+        }
+        let encoded_transactions = transactions
+            .iter()
+            .map(|tx| bincode::serialize(tx).map(|bytes| BASE64_STANDARD.encode(bytes)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| BundleError::Request(format!("Failed to serialize transaction: {}", e)))?;
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [encoded_transactions, { "encoding": "base64" }],
+        });
+
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|e| BundleError::Request(e.to_string()))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| BundleError::Request(e.to_string()))?;
+        let response: JsonRpcResponse = serde_json::from_str(&text).map_err(|e| {
+            BundleError::Request(format!(
+                "Invalid block engine response with status {}: {} ({})",
+                status, text, e
+            ))
+        })?;
+
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(BundleError::Rejected {
+                code:    error.code,
+                message: error.message,
+            }),
+            (Some(bundle_id), None) => {
+                tracing::Span::current().record("bundle_id", &bundle_id);
+                Ok(bundle_id)
+            }
+            (None, None) => Err(BundleError::Request(format!(
+                "Block engine response with status {} has no result",
+                status
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::*,
+        axum::{
+            routing::post,
+            Json,
+            Router,
+        },
+        solana_sdk::{
+            hash::Hash,
+            signature::Keypair,
+            signer::Signer,
+            system_instruction,
+            transaction::Transaction,
+        },
+        std::sync::Arc,
+        tokio::sync::Mutex,
+    };
+
+    /// A local stand-in for a block engine endpoint.
+    /// Records the received bundles and accepts or rejects them.
+    pub struct BlockEngineStandIn {
+        pub url:     String,
+        pub bundles: Arc<Mutex<Vec<Vec<VersionedTransaction>>>>,
+    }
+
+    impl BlockEngineStandIn {
+        pub async fn start(accept: bool) -> Self {
+            let bundles: Arc<Mutex<Vec<Vec<VersionedTransaction>>>> = Default::default();
+            let app = Router::new().route(
+                &format!("/{}", BUNDLES_PATH),
+                post({
+                    let bundles = bundles.clone();
+                    move |Json(request): Json<serde_json::Value>| {
+                        let bundles = bundles.clone();
+                        async move {
+                            let transactions = request["params"][0]
+                                .as_array()
+                                .cloned()
+                                .unwrap_or_default()
+                                .iter()
+                                .map(|tx| {
+                                    let bytes = BASE64_STANDARD
+                                        .decode(tx.as_str().unwrap_or_default())
+                                        .expect("Failed to decode transaction");
+                                    bincode::deserialize::<VersionedTransaction>(&bytes)
+                                        .expect("Failed to deserialize transaction")
+                                })
+                                .collect::<Vec<_>>();
+                            bundles.lock().await.push(transactions);
+                            if accept {
+                                Json(serde_json::json!({
+                                    "jsonrpc": "2.0",
+                                    "id": request["id"],
+                                    "result": "bundle-id",
+                                }))
+                            } else {
+                                Json(serde_json::json!({
+                                    "jsonrpc": "2.0",
+                                    "id": request["id"],
+                                    "error": { "code": -32602, "message": "bundle rejected" },
+                                }))
+                            }
+                        }
+                    }
+                }),
+            );
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+                .await
+                .expect("Failed to bind block engine stand-in");
+            let url = format!(
+                "http://{}",
+                listener
+                    .local_addr()
+                    .expect("Failed to get block engine stand-in address")
+            );
+            tokio::spawn(async move { axum::serve(listener, app).await });
+            Self { url, bundles }
+        }
+    }
+
+    fn get_transaction() -> VersionedTransaction {
+        let payer = Keypair::new();
+        let instruction =
+            system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1);
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        )
+        .into()
+    }
+
+    #[tokio::test]
+    async fn test_send_bundle_accepted() {
+        let stand_in = BlockEngineStandIn::start(true).await;
+        let client = BlockEngineClient::new(&stand_in.url, 2);
+        let transactions = vec![get_transaction(), get_transaction()];
+
+        let result = client.send_bundle(&transactions).await;
+
+        assert_eq!(result, Ok("bundle-id".to_string()));
+        assert_eq!(*stand_in.bundles.lock().await, vec![transactions]);
+    }
+
+    #[tokio::test]
+    async fn test_send_bundle_rejected() {
+        let stand_in = BlockEngineStandIn::start(false).await;
+        let client = BlockEngineClient::new(&stand_in.url, 2);
+
+        let result = client.send_bundle(&[get_transaction()]).await;
+
+        assert_eq!(
+            result,
+            Err(BundleError::Rejected {
+                code:    -32602,
+                message: "bundle rejected".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_bundle_too_large() {
+        let stand_in = BlockEngineStandIn::start(true).await;
+        let client = BlockEngineClient::new(&stand_in.url, 2);
+        let transactions = (0..MAX_BUNDLE_SIZE + 1)
+            .map(|_| get_transaction())
+            .collect::<Vec<_>>();
+
+        assert!(matches!(
+            client.send_bundle(&transactions).await,
+            Err(BundleError::Request(_))
+        ));
+        assert!(stand_in.bundles.lock().await.is_empty());
+    }
+}
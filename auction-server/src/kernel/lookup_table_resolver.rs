@@ -0,0 +1,418 @@
+use {
+    crate::{
+        kernel::entities::ChainId,
+        per_metrics::{
+            LOOKUP_TABLE_RESOLVER_SIZE,
+            LOOKUP_TABLE_RESOLVER_TOTAL,
+        },
+    },
+    axum_prometheus::metrics,
+    futures::future::join_all,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{
+        account::Account,
+        address_lookup_table::state::AddressLookupTable,
+        clock::Slot,
+        commitment_config::CommitmentConfig,
+        pubkey::Pubkey,
+    },
+    std::{
+        collections::HashMap,
+        fmt::{
+            Display,
+            Formatter,
+        },
+        time::{
+            Duration,
+            Instant,
+        },
+    },
+    tokio::sync::RwLock,
+};
+
+const MAX_RPC_ACCOUNT_LIMIT: usize = 100;
+/// Cached tables are refetched after this duration to find out if they were deactivated
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+const METRIC_LABEL_HIT: &str = "hit";
+const METRIC_LABEL_MISS: &str = "miss";
+const METRIC_LABEL_STALE: &str = "stale";
+const METRIC_LABEL_ERROR: &str = "error";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LookupTableError {
+    /// The lookup table account could not be fetched from the RPC
+    Rpc(String),
+    NotFound(Pubkey),
+    InvalidData(Pubkey, String),
+    Deactivated(Pubkey),
+    IndexOutOfBounds {
+        table: Pubkey,
+        index: u8,
+    },
+}
+
+impl Display for LookupTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LookupTableError::Rpc(error) => {
+                write!(f, "Failed to fetch lookup table account: {}", error)
+            }
+            LookupTableError::NotFound(table) => {
+                write!(f, "Lookup table account {} not found", table)
+            }
+            LookupTableError::InvalidData(table, error) => write!(
+                f,
+                "Failed deserializing lookup table account {} data: {}",
+                table, error
+            ),
+            LookupTableError::Deactivated(table) => {
+                write!(f, "Lookup table {} is deactivated", table)
+            }
+            LookupTableError::IndexOutOfBounds { table, index } => {
+                write!(
+                    f,
+                    "Account index {} not found in lookup table {}",
+                    index, table
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedLookupTable {
+    addresses:          Vec<Pubkey>,
+    /// Slot of the RPC response the table was fetched with
+    slot:               Slot,
+    last_extended_slot: Slot,
+    fetched_at:         Instant,
+}
+
+impl CachedLookupTable {
+    /// Returns None if the cached addresses can not be trusted for the given indexes.
+    ///
+    /// Lookup tables are append only, so the cached addresses stay valid until the table is deactivated.
+    /// As the deactivation is only visible on the table account, tables older than `CACHE_TTL` are refetched.
+    /// A table can be extended several times in the same slot, so a table fetched in the slot it was last
+    /// extended in may miss addresses. The same applies to indexes past the cached addresses.
+    fn lookup(&self, indexes: &[u8]) -> Option<Vec<Pubkey>> {
+        if self.fetched_at.elapsed() > CACHE_TTL || self.last_extended_slot >= self.slot {
+            return None;
+        }
+        indexes
+            .iter()
+            .map(|index| self.addresses.get(*index as usize).copied())
+            .collect()
+    }
+}
+
+/// Resolves the addresses of address lookup tables for the verification and the simulation of transactions.
+///
+/// Resolved tables are cached until they are found to be extended, expire or are invalidated.
+/// Deactivated tables are rejected since transactions using them will fail once the deactivation cools down.
+pub struct LookupTableResolver {
+    chain_id: ChainId,
+    client:   RpcClient,
+    tables:   RwLock<HashMap<Pubkey, CachedLookupTable>>,
+}
+
+impl LookupTableResolver {
+    pub fn new(chain_id: ChainId, client: RpcClient) -> Self {
+        Self {
+            chain_id,
+            client,
+            tables: Default::default(),
+        }
+    }
+
+    fn record(&self, result: &str, count: usize) {
+        if count == 0 {
+            return;
+        }
+        metrics::counter!(
+            LOOKUP_TABLE_RESOLVER_TOTAL,
+            &[
+                ("chain_id", self.chain_id.clone()),
+                ("result", result.to_string()),
+            ]
+        )
+        .increment(count as u64);
+    }
+
+    /// Drops the cached addresses of the tables, e.g. after a transaction using them failed to load its accounts.
+    pub async fn invalidate(&self, tables_to_invalidate: &[Pubkey]) {
+        let mut tables = self.tables.write().await;
+        for table in tables_to_invalidate {
+            tables.remove(table);
+        }
+        metrics::gauge!(
+            LOOKUP_TABLE_RESOLVER_SIZE,
+            &[("chain_id", self.chain_id.clone())]
+        )
+        .set(tables.len() as f64);
+    }
+
+    /// Returns the addresses of the table at the given indexes.
+    pub async fn resolve(
+        &self,
+        table: &Pubkey,
+        indexes: &[u8],
+    ) -> Result<Vec<Pubkey>, LookupTableError> {
+        self.resolve_many(&[(*table, indexes.to_vec())])
+            .await
+            .remove(0)
+    }
+
+    /// Returns the addresses of each table at the given indexes, in the same order as the lookups.
+    /// Tables missing from the cache or with stale cached addresses are fetched in a single batch.
+    pub async fn resolve_many(
+        &self,
+        lookups: &[(Pubkey, Vec<u8>)],
+    ) -> Vec<Result<Vec<Pubkey>, LookupTableError>> {
+        let mut results: Vec<Option<Result<Vec<Pubkey>, LookupTableError>>> =
+            vec![None; lookups.len()];
+        let mut to_fetch = vec![];
+        {
+            let tables = self.tables.read().await;
+            let (mut hits, mut misses, mut stale) = (0, 0, 0);
+            for (position, (table, indexes)) in lookups.iter().enumerate() {
+                match tables.get(table) {
+                    Some(cached) => match cached.lookup(indexes) {
+                        Some(addresses) => {
+                            hits += 1;
+                            results[position] = Some(Ok(addresses));
+                        }
+                        None => {
+                            stale += 1;
+                            to_fetch.push(position);
+                        }
+                    },
+                    None => {
+                        misses += 1;
+                        to_fetch.push(position);
+                    }
+                }
+            }
+            self.record(METRIC_LABEL_HIT, hits);
+            self.record(METRIC_LABEL_MISS, misses);
+            self.record(METRIC_LABEL_STALE, stale);
+        }
+
+        if !to_fetch.is_empty() {
+            let keys: Vec<Pubkey> = to_fetch
+                .iter()
+                .map(|position| lookups[*position].0)
+                .collect();
+            let fetched = self.fetch_tables(&keys).await;
+            let mut tables = self.tables.write().await;
+            for (position, fetched) in to_fetch.into_iter().zip(fetched) {
+                let (table, indexes) = &lookups[position];
+                let result = fetched.and_then(|(account, slot)| {
+                    let lookup_table = AddressLookupTable::deserialize(&account.data)
+                        .map_err(|e| LookupTableError::InvalidData(*table, e.to_string()))?;
+                    if lookup_table.meta.deactivation_slot != Slot::MAX {
+                        return Err(LookupTableError::Deactivated(*table));
+                    }
+                    Ok(CachedLookupTable {
+                        addresses: lookup_table.addresses.to_vec(),
+                        slot,
+                        last_extended_slot: lookup_table.meta.last_extended_slot,
+                        fetched_at: Instant::now(),
+                    })
+                });
+                results[position] = Some(match result {
+                    Ok(cached) => {
+                        let addresses = indexes
+                            .iter()
+                            .map(|index| {
+                                cached.addresses.get(*index as usize).copied().ok_or(
+                                    LookupTableError::IndexOutOfBounds {
+                                        table: *table,
+                                        index: *index,
+                                    },
+                                )
+                            })
+                            .collect();
+                        tables.insert(*table, cached);
+                        addresses
+                    }
+                    Err(e) => {
+                        tables.remove(table);
+                        Err(e)
+                    }
+                });
+            }
+            metrics::gauge!(
+                LOOKUP_TABLE_RESOLVER_SIZE,
+                &[("chain_id", self.chain_id.clone())]
+            )
+            .set(tables.len() as f64);
+        }
+
+        let results: Vec<_> = results
+            .into_iter()
+            .map(|result| result.expect("All lookups should be resolved"))
+            .collect();
+        self.record(
+            METRIC_LABEL_ERROR,
+            results.iter().filter(|result| result.is_err()).count(),
+        );
+        results
+    }
+
+    /// Fetches the table accounts with the slot of the RPC response they were fetched with.
+    async fn fetch_tables(
+        &self,
+        keys: &[Pubkey],
+    ) -> Vec<Result<(Account, Slot), LookupTableError>> {
+        let chunk_results = join_all(keys.chunks(MAX_RPC_ACCOUNT_LIMIT).map(|chunk| {
+            self.client
+                .get_multiple_accounts_with_commitment(chunk, CommitmentConfig::processed())
+        }))
+        .await;
+        keys.chunks(MAX_RPC_ACCOUNT_LIMIT)
+            .zip(chunk_results)
+            .flat_map(|(chunk, chunk_result)| match chunk_result {
+                Ok(response) => chunk
+                    .iter()
+                    .zip(response.value)
+                    .map(|(key, account)| {
+                        account
+                            .map(|account| (account, response.context.slot))
+                            .ok_or(LookupTableError::NotFound(*key))
+                    })
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    tracing::error!(error = e.to_string(), "Failed to get lookup table accounts");
+                    vec![Err(LookupTableError::Rpc(e.to_string())); chunk.len()]
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::kernel::rpc_client_svm_tester::{
+            CannedRequestMatcher,
+            RpcClientSvmTester,
+        },
+        solana_client::rpc_request::RpcRequest,
+        solana_sdk::{
+            address_lookup_table::{
+                self,
+                state::LookupTableMeta,
+            },
+            rent::Rent,
+        },
+        std::borrow::Cow,
+    };
+
+    fn cached_table(
+        addresses: Vec<Pubkey>,
+        slot: Slot,
+        last_extended_slot: Slot,
+    ) -> CachedLookupTable {
+        CachedLookupTable {
+            addresses,
+            slot,
+            last_extended_slot,
+            fetched_at: Instant::now(),
+        }
+    }
+
+    fn lookup_table_account(addresses: Vec<Pubkey>, deactivation_slot: Slot) -> Account {
+        let data = AddressLookupTable {
+            meta:      LookupTableMeta {
+                deactivation_slot,
+                ..LookupTableMeta::default()
+            },
+            addresses: Cow::Owned(addresses),
+        }
+        .serialize_for_tests()
+        .expect("Failed to serialize lookup table");
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: address_lookup_table::program::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    async fn can_next_lookup_table(
+        rpc_client: &RpcClientSvmTester,
+        addresses: Vec<Pubkey>,
+        deactivation_slot: Slot,
+    ) {
+        rpc_client
+            .can_next_multi_accounts(
+                CannedRequestMatcher::AllByRequest(RpcRequest::GetMultipleAccounts),
+                vec![lookup_table_account(addresses, deactivation_slot)],
+            )
+            .await;
+    }
+
+    #[test]
+    fn test_cached_lookup_table_lookup() {
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let cached = cached_table(addresses.clone(), 10, 5);
+        assert_eq!(
+            cached.lookup(&[1, 0]),
+            Some(vec![addresses[1], addresses[0]])
+        );
+        // The table may have been extended since it was fetched
+        assert_eq!(cached.lookup(&[2]), None);
+
+        // The table may have been extended again in the slot it was fetched in
+        let cached = cached_table(addresses.clone(), 10, 10);
+        assert_eq!(cached.lookup(&[0]), None);
+
+        // The table may have been deactivated since it was fetched
+        let mut cached = cached_table(addresses, 10, 5);
+        cached.fetched_at -= CACHE_TTL + Duration::from_secs(1);
+        assert_eq!(cached.lookup(&[0]), None);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_deactivated_table() {
+        let rpc_client = RpcClientSvmTester::new();
+        let resolver =
+            LookupTableResolver::new("solana".to_string(), rpc_client.make_test_client());
+        let table = Pubkey::new_unique();
+
+        can_next_lookup_table(&rpc_client, vec![Pubkey::new_unique()], 5).await;
+        assert_eq!(
+            resolver.resolve(&table, &[0]).await,
+            Err(LookupTableError::Deactivated(table))
+        );
+        assert!(resolver.tables.read().await.is_empty());
+        rpc_client.check_all_uncanned().await;
+    }
+
+    #[tokio::test]
+    async fn test_resolve_table_deactivated_after_invalidation() {
+        let rpc_client = RpcClientSvmTester::new();
+        let resolver =
+            LookupTableResolver::new("solana".to_string(), rpc_client.make_test_client());
+        let table = Pubkey::new_unique();
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        can_next_lookup_table(&rpc_client, addresses.clone(), Slot::MAX).await;
+        assert_eq!(resolver.resolve(&table, &[1]).await, Ok(vec![addresses[1]]));
+        // The cached addresses are used without fetching the table again
+        assert_eq!(resolver.resolve(&table, &[0]).await, Ok(vec![addresses[0]]));
+        rpc_client.check_all_uncanned().await;
+
+        resolver.invalidate(&[table]).await;
+        can_next_lookup_table(&rpc_client, addresses, 5).await;
+        assert_eq!(
+            resolver.resolve(&table, &[0]).await,
+            Err(LookupTableError::Deactivated(table))
+        );
+        rpc_client.check_all_uncanned().await;
+    }
+}
@@ -1,7 +1,10 @@
 pub mod analytics_db;
+pub mod block_engine;
 pub mod db;
 pub mod entities;
+pub mod lookup_table_resolver;
 pub mod pyth_lazer;
+pub mod rate_limiter;
 pub mod workers;
 
 #[cfg(test)]
@@ -0,0 +1,168 @@
+use {
+    crate::config::RateLimitConfig,
+    std::{
+        collections::HashMap,
+        hash::Hash,
+        sync::{
+            Mutex,
+            MutexGuard,
+        },
+        time::{
+            Duration,
+            Instant,
+        },
+    },
+};
+
+/// Number of buckets above which the full buckets are dropped, since they behave as new ones.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens:      f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter with one bucket per key.
+///
+/// Each bucket holds up to `capacity` tokens and is refilled at `refill_per_second` tokens per second.
+/// Every request takes one token and requests finding an empty bucket are limited.
+pub struct RateLimiter<K> {
+    capacity:          f64,
+    refill_per_second: f64,
+    buckets:           Mutex<HashMap<K, TokenBucket>>,
+}
+
+impl<K: Hash + Eq + Clone> RateLimiter<K> {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            capacity:          config.capacity as f64,
+            refill_per_second: config.refill_per_second,
+            buckets:           Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn refill(&self, bucket: &mut TokenBucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * self.refill_per_second).min(self.capacity);
+        bucket.last_refill = now;
+    }
+
+    /// Locks the buckets, so that several limiters can be checked before a token is taken from any of them.
+    /// Limiters locked together must always be locked in the same order.
+    pub fn lock(&self) -> RateLimiterGuard<'_, K> {
+        self.lock_at(Instant::now())
+    }
+
+    fn lock_at(&self, now: Instant) -> RateLimiterGuard<'_, K> {
+        let mut buckets = self.buckets.lock().expect("Rate limiter lock poisoned");
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| {
+                self.refill(bucket, now);
+                bucket.tokens < self.capacity
+            });
+        }
+        RateLimiterGuard {
+            limiter: self,
+            buckets,
+            now,
+        }
+    }
+}
+
+/// The locked buckets of a rate limiter
+pub struct RateLimiterGuard<'a, K> {
+    limiter: &'a RateLimiter<K>,
+    buckets: MutexGuard<'a, HashMap<K, TokenBucket>>,
+    now:     Instant,
+}
+
+impl<K: Hash + Eq + Clone> RateLimiterGuard<'_, K> {
+    fn get_bucket(&mut self, key: &K) -> &mut TokenBucket {
+        let (limiter, now) = (self.limiter, self.now);
+        let bucket = self.buckets.entry(key.clone()).or_insert(TokenBucket {
+            tokens:      limiter.capacity,
+            last_refill: now,
+        });
+        limiter.refill(bucket, now);
+        bucket
+    }
+
+    /// Returns the time to wait before a token is available if the bucket of the key is empty.
+    /// No token is taken.
+    pub fn check(&mut self, key: &K) -> Result<(), Duration> {
+        let refill_per_second = self.limiter.refill_per_second;
+        let bucket = self.get_bucket(key);
+        if bucket.tokens >= 1.0 {
+            return Ok(());
+        }
+        Err(
+            Duration::try_from_secs_f64((1.0 - bucket.tokens) / refill_per_second)
+                .unwrap_or(Duration::MAX),
+        )
+    }
+
+    /// Takes a token from the bucket of the key. The bucket should be checked first.
+    pub fn take(&mut self, key: &K) {
+        let bucket = self.get_bucket(key);
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl<K: Hash + Eq + Clone> RateLimiter<K> {
+        fn check_at(&self, key: &K, now: Instant) -> Result<(), Duration> {
+            let mut guard = self.lock_at(now);
+            guard.check(key)?;
+            guard.take(key);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter_check() {
+        let limiter = RateLimiter::new(&RateLimitConfig {
+            capacity:          2,
+            refill_per_second: 4.0,
+        });
+        let now = Instant::now();
+        assert_eq!(limiter.check_at(&1, now), Ok(()));
+        assert_eq!(limiter.check_at(&1, now), Ok(()));
+        assert_eq!(limiter.check_at(&1, now), Err(Duration::from_millis(250)));
+        // Other keys have their own buckets
+        assert_eq!(limiter.check_at(&2, now), Ok(()));
+
+        let later = now + Duration::from_millis(250);
+        assert_eq!(limiter.check_at(&1, later), Ok(()));
+        assert!(limiter.check_at(&1, later).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter_check_does_not_take_tokens() {
+        let limiter = RateLimiter::new(&RateLimitConfig {
+            capacity:          1,
+            refill_per_second: 1.0,
+        });
+        let now = Instant::now();
+        let mut guard = limiter.lock_at(now);
+        assert_eq!(guard.check(&1), Ok(()));
+        assert_eq!(guard.check(&1), Ok(()));
+        guard.take(&1);
+        assert_eq!(guard.check(&1), Err(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_rate_limiter_check_with_slow_refill() {
+        let limiter = RateLimiter::new(&RateLimitConfig {
+            capacity:          1,
+            refill_per_second: f64::MIN_POSITIVE,
+        });
+        let now = Instant::now();
+        assert_eq!(limiter.check_at(&1, now), Ok(()));
+        assert_eq!(limiter.check_at(&1, now), Err(Duration::MAX));
+    }
+}
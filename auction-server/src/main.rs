@@ -19,6 +19,7 @@ use {
     server::{
         run_migrations,
         run_migrations_clichouse,
+        run_replay,
         start_server,
     },
     std::{
@@ -123,5 +124,6 @@ async fn main() -> Result<()> {
         config::Options::Run(opts) => start_server(opts).await,
         config::Options::Migrate(opts) => run_migrations(opts).await,
         config::Options::MigrateClickhouse(opts) => run_migrations_clichouse(opts).await,
+        config::Options::Replay(opts) => run_replay(opts).await,
     }
 }
@@ -1,6 +1,7 @@
 use {
     super::{
         entities::{
+            Quote as QuoteEntity,
             QuoteCreate as QuoteCreateEntity,
             QuoteTokens,
             TokenAmountSvm,
@@ -8,12 +9,14 @@ use {
         repository::OPPORTUNITY_PAGE_SIZE_CAP,
         service::{
             add_opportunity::AddOpportunityInput,
+            get_indicative_quote::GetIndicativeQuoteInput,
             get_opportunities::GetOpportunitiesInput,
             get_quote::{
                 is_indicative_price_taker,
                 GetQuoteInput,
             },
             remove_opportunities::RemoveOpportunitiesInput,
+            subscribe_quote::SubscribeQuoteInput,
         },
     },
     crate::{
@@ -22,11 +25,13 @@ use {
             RestError,
             WrappedRouter,
         },
+        kernel::entities::ChainId,
         models,
         state::StoreNew,
     },
     axum::{
         extract::{
+            Path,
             Query,
             State,
         },
@@ -36,7 +41,9 @@ use {
     },
     express_relay_api_types::{
         opportunity::{
+            GetIndicativeQuoteQueryParams,
             GetOpportunitiesQueryParams,
+            IndicativeQuote,
             Opportunity,
             OpportunityCreate,
             OpportunityDelete,
@@ -51,6 +58,7 @@ use {
         ErrorBodyResponse,
     },
     std::sync::Arc,
+    tokio::sync::mpsc,
 };
 
 fn get_program(auth: &Auth) -> Result<ProgramSvm, RestError> {
@@ -155,6 +163,21 @@ pub async fn post_quote(
     State(store): State<Arc<StoreNew>>,
     Json(params): Json<QuoteCreate>,
 ) -> Result<Json<Quote>, RestError> {
+    let quote_create = validate_quote_create(auth, params)?;
+    // Ensure the get_quote process completes even if the client cancels the request
+    let handle = tokio::spawn(async move {
+        store
+            .opportunity_service_svm
+            .get_quote(GetQuoteInput { quote_create })
+            .await
+    });
+    let quote = handle
+        .await
+        .map_err(|_| RestError::TemporarilyUnavailable)??;
+    Ok(Json(quote.into()))
+}
+
+fn validate_quote_create(auth: Auth, params: QuoteCreate) -> Result<QuoteCreateEntity, RestError> {
     if let Some(address) = params.get_user_wallet_address() {
         if is_indicative_price_taker(&address) {
             return Err(RestError::BadParameters(
@@ -176,20 +199,59 @@ pub async fn post_quote(
         Auth::Authorized(_, profile) => Some(profile),
         _ => None,
     };
-    let quote_create = get_quote_create_entity(params, profile);
-    // Ensure the get_quote process completes even if the client cancels the request
-    let handle = tokio::spawn(async move {
-        store
-            .opportunity_service_svm
-            .get_quote(GetQuoteInput { quote_create })
-            .await
-    });
-    let quote = handle
-        .await
-        .map_err(|_| RestError::TemporarilyUnavailable)??;
+    Ok(get_quote_create_entity(params, profile))
+}
+
+/// Get an indicative quote for a swap without running an auction.
+///
+/// The output amount is estimated from the winning bids of the recent auctions of the token pair
+/// and the market prices of the tokens. No searcher is asked to bid, so the estimate is returned immediately
+/// but it is not guaranteed to be available in a quote.
+#[utoipa::path(get, path = "/v1/{chain_id}/quotes/indicative",
+    params(
+        ("chain_id"=String, Path, description = "The chain id to get the indicative quote for", example = "solana"),
+        GetIndicativeQuoteQueryParams,
+    ),
+    responses(
+        (status = 200, description = "The estimated quote", body = IndicativeQuote),
+        (status = 400, response = ErrorBodyResponse),
+        (status = 404, description = "No estimate available right now", body = ErrorBodyResponse),
+    ),
+    tag = "quote",
+)]
+pub async fn get_indicative_quote(
+    State(store): State<Arc<StoreNew>>,
+    Path(chain_id): Path<ChainId>,
+    Query(params): Query<GetIndicativeQuoteQueryParams>,
+) -> Result<Json<IndicativeQuote>, RestError> {
+    let quote = store
+        .opportunity_service_svm
+        .get_indicative_quote(GetIndicativeQuoteInput {
+            chain_id,
+            user_token: TokenAmountSvm {
+                token:  params.input_token_mint,
+                amount: params.input_token_amount,
+            },
+            mint_searcher: params.output_token_mint,
+            referral_fee_ppm: params.referral_fee_ppm,
+        })
+        .await?;
     Ok(Json(quote.into()))
 }
 
+/// Subscribe to a stream of improving quotes for the quote request.
+pub async fn subscribe_quote(
+    auth: Auth,
+    store: Arc<StoreNew>,
+    params: QuoteCreate,
+) -> Result<mpsc::Receiver<QuoteEntity>, RestError> {
+    let quote_create = validate_quote_create(auth, params)?;
+    store
+        .opportunity_service_svm
+        .subscribe_quote(SubscribeQuoteInput { quote_create })
+        .await
+}
+
 /// Delete all opportunities for specified data.
 #[utoipa::path(delete, path = "/v1/opportunities", request_body = OpportunityDelete,
 security(
@@ -230,6 +292,7 @@ pub fn get_routes(store: Arc<StoreNew>) -> Router<Arc<StoreNew>> {
     WrappedRouter::new(store)
         .route(Route::PostOpportunity, post_opportunity)
         .route(Route::PostQuote, post_quote)
+        .route(Route::GetIndicativeQuote, get_indicative_quote)
         .route(Route::GetOpportunities, get_opportunities)
         .route(Route::DeleteOpportunities, delete_opportunities)
         .router
@@ -274,6 +337,8 @@ pub fn get_quote_create_entity(
                 memo: params.memo,
                 cancellable: params.cancellable,
                 minimum_lifetime: params.minimum_lifetime,
+                number_of_quotes: params.number_of_quotes,
+                partial_fill_tolerance_ppm: params.partial_fill_tolerance_ppm,
                 profile_id: profile.map(|p| p.id),
             }
         }
@@ -153,6 +153,7 @@ pub struct OpportunitySvmProgramSwap {
     pub cancellable:                          bool,
     pub minimum_lifetime:                     Option<u32>,
     pub minimum_deadline:                     OffsetDateTime,
+    pub partial_fill_tolerance_ppm:           Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -246,6 +247,7 @@ impl OpportunitySvm {
                         memo:                                 program.memo,
                         cancellable:                          program.cancellable,
                         minimum_lifetime:                     program.minimum_lifetime,
+                        partial_fill_tolerance_ppm:           program.partial_fill_tolerance_ppm,
                     },
                 )
             }
@@ -355,6 +357,15 @@ impl OpportunitySvmProgramSwap {
                 .saturating_sub(self.get_user_sol_amount_paid_for_token_account_initializations()),
         )
     }
+
+    /// Returns the lowest searcher amount the swap can be partially filled with, given the partial fill tolerance of the user.
+    /// A partial fill tolerance of at least [`FEE_SPLIT_PRECISION_PPM`] allows the whole amount to be left unfilled.
+    pub fn get_minimum_amount_searcher(&self, amount_searcher: u64) -> u64 {
+        let partial_fill_tolerance_ppm = self.partial_fill_tolerance_ppm.unwrap_or(0);
+        let unfilled_amount = u128::from(amount_searcher) * u128::from(partial_fill_tolerance_ppm) // this multiplication is safe because both values are u64
+            / u128::from(FEE_SPLIT_PRECISION_PPM);
+        amount_searcher.saturating_sub(u64::try_from(unfilled_amount).unwrap_or(u64::MAX))
+    }
 }
 
 impl From<TokenAccountInitializationConfig> for api::TokenAccountInitializationConfig {
@@ -461,6 +472,7 @@ impl From<OpportunitySvm> for api::OpportunitySvm {
                     memo: program.memo,
                     cancellable: program.cancellable,
                     minimum_deadline: program.minimum_deadline.unix_timestamp(),
+                    partial_fill_tolerance_ppm: program.partial_fill_tolerance_ppm,
                     profile_id: val.profile_id,
                 }
             }
@@ -528,6 +540,7 @@ impl TryFrom<repository::Opportunity<repository::OpportunityMetadataSvm>> for Op
                             .map(|lifetime| Duration::from_secs(lifetime as u64))
                             .unwrap_or(BID_MINIMUM_LIFE_TIME_SVM_OTHER),
                     ),
+                    partial_fill_tolerance_ppm:           program.partial_fill_tolerance_ppm,
                 })
             }
         };
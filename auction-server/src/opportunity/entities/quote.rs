@@ -20,15 +20,18 @@ use {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Quote {
-    pub transaction:     Option<VersionedTransaction>,
+    pub transaction:             Option<VersionedTransaction>,
     // The expiration time of the quote (in seconds since the Unix epoch)
-    pub expiration_time: Option<i64>,
-    pub searcher_token:  TokenAmountSvm,
-    pub user_token:      TokenAmountSvm,
-    pub referrer_fee:    TokenAmountSvm,
-    pub platform_fee:    TokenAmountSvm,
-    pub chain_id:        ChainId,
-    pub reference_id:    BidId,
+    pub expiration_time:         Option<i64>,
+    pub searcher_token:          TokenAmountSvm,
+    pub user_token:              TokenAmountSvm,
+    pub referrer_fee:            TokenAmountSvm,
+    pub platform_fee:            TokenAmountSvm,
+    pub chain_id:                ChainId,
+    pub reference_id:            BidId,
+    pub fallback_quotes:         Vec<Quote>,
+    // The minimum searcher token amount the user receives if the quote is partially filled
+    pub minimum_searcher_amount: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -39,14 +42,16 @@ pub struct ReferralFeeInfo {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct QuoteCreate {
-    pub user_wallet_address: Option<Pubkey>,
-    pub tokens:              QuoteTokens,
-    pub referral_fee_info:   Option<ReferralFeeInfo>,
-    pub chain_id:            ChainId,
-    pub memo:                Option<String>,
-    pub cancellable:         bool,
-    pub minimum_lifetime:    Option<u32>,
-    pub profile_id:          Option<models::ProfileId>,
+    pub user_wallet_address:        Option<Pubkey>,
+    pub tokens:                     QuoteTokens,
+    pub referral_fee_info:          Option<ReferralFeeInfo>,
+    pub chain_id:                   ChainId,
+    pub memo:                       Option<String>,
+    pub cancellable:                bool,
+    pub minimum_lifetime:           Option<u32>,
+    pub number_of_quotes:           Option<u32>,
+    pub partial_fill_tolerance_ppm: Option<u64>,
+    pub profile_id:                 Option<models::ProfileId>,
 }
 
 
@@ -101,17 +106,62 @@ impl From<api::ReferralFeeInfo> for ReferralFeeInfo {
     }
 }
 
+impl From<Quote> for api::QuoteV1Svm {
+    fn from(quote: Quote) -> Self {
+        api::QuoteV1Svm {
+            transaction:           quote.transaction,
+            expiration_time:       quote.expiration_time,
+            input_token:           quote.user_token.into(),
+            output_token:          quote.searcher_token.into(),
+            referrer_fee:          quote.referrer_fee.into(),
+            platform_fee:          quote.platform_fee.into(),
+            chain_id:              quote.chain_id,
+            reference_id:          quote.reference_id,
+            fallback_quotes:       quote.fallback_quotes.into_iter().map(Into::into).collect(),
+            minimum_output_amount: quote.minimum_searcher_amount,
+        }
+    }
+}
+
 impl From<Quote> for api::Quote {
     fn from(quote: Quote) -> Self {
-        api::Quote::Svm(api::QuoteSvm::V1(api::QuoteV1Svm {
-            transaction:     quote.transaction,
-            expiration_time: quote.expiration_time,
-            input_token:     quote.user_token.into(),
-            output_token:    quote.searcher_token.into(),
-            referrer_fee:    quote.referrer_fee.into(),
-            platform_fee:    quote.platform_fee.into(),
-            chain_id:        quote.chain_id,
-            reference_id:    quote.reference_id,
-        }))
+        api::Quote::Svm(api::QuoteSvm::V1(quote.into()))
+    }
+}
+
+/// Output to input amount rates before the swap fees of recent winning swap bids for a token pair and trade size
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentSwapRates {
+    /// Order of magnitude of the input amounts, see `get_size_bucket`
+    pub size_bucket:    u8,
+    pub samples:        u64,
+    pub lower_quartile: f64,
+    pub median:         f64,
+    pub upper_quartile: f64,
+}
+
+impl RecentSwapRates {
+    /// Returns the trade size bucket of an input amount, i.e. its order of magnitude.
+    pub fn get_size_bucket(amount: u64) -> u8 {
+        amount.checked_ilog10().unwrap_or(0) as u8
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicativeQuote {
+    pub user_token:     TokenAmountSvm,
+    pub searcher_token: TokenAmountSvm,
+    pub confidence:     f64,
+    pub chain_id:       ChainId,
+}
+
+impl From<IndicativeQuote> for api::IndicativeQuote {
+    fn from(quote: IndicativeQuote) -> Self {
+        api::IndicativeQuote {
+            input_token:  quote.user_token.into(),
+            output_token: quote.searcher_token.into(),
+            confidence:   quote.confidence,
+            chain_id:     quote.chain_id,
+        }
     }
 }
@@ -1,4 +1,5 @@
 use {
+    crate::api::RestError,
     express_relay_api_types::opportunity as api,
     serde::{
         Deserialize,
@@ -37,10 +38,206 @@ impl From<api::TokenAmountSvm> for TokenAmountSvm {
     }
 }
 
+/// A transfer fee of a Token-2022 mint, effective from the given epoch on.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransferFee {
+    pub epoch:       u64,
+    pub maximum_fee: u64,
+    pub fee_bps:     u16,
+}
+
+impl TransferFee {
+    const MAX_FEE_BPS: u64 = 10_000;
+
+    /// The fee withheld when transferring the amount, rounded up like the token program does.
+    pub fn calculate_fee(&self, amount: u64) -> u64 {
+        if self.fee_bps == 0 || amount == 0 {
+            return 0;
+        }
+        let fee = (amount as u128 * self.fee_bps as u128).div_ceil(Self::MAX_FEE_BPS as u128);
+        fee.min(self.maximum_fee as u128) as u64
+    }
+
+    /// The amount to transfer so that at least `post_fee_amount` is received.
+    pub fn calculate_pre_fee_amount(&self, post_fee_amount: u64) -> Option<u64> {
+        if self.fee_bps == 0 || post_fee_amount == 0 {
+            return Some(post_fee_amount);
+        }
+        let fee_bps = self.fee_bps as u128;
+        let max_fee_bps = Self::MAX_FEE_BPS as u128;
+        if fee_bps >= max_fee_bps {
+            return post_fee_amount.checked_add(self.maximum_fee);
+        }
+        let pre_fee_amount =
+            (post_fee_amount as u128 * max_fee_bps).div_ceil(max_fee_bps - fee_bps);
+        if pre_fee_amount - post_fee_amount as u128 >= self.maximum_fee as u128 {
+            post_fee_amount.checked_add(self.maximum_fee)
+        } else {
+            u64::try_from(pre_fee_amount).ok()
+        }
+    }
+}
+
+/// The Token-2022 extensions of a mint that affect swaps.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenMintExtensions {
+    /// The older and the newer transfer fees of the mint.
+    pub transfer_fees:                Vec<TransferFee>,
+    /// The program invoked on every transfer, if any.
+    pub transfer_hook_program:        Option<Pubkey>,
+    pub non_transferable:             bool,
+    /// New token accounts of the mint are frozen by default.
+    pub default_account_state_frozen: bool,
+    /// Confidential balances can not be swapped and the mint may require all transfers to be confidential.
+    pub confidential_transfers:       bool,
+}
+
+impl TokenMintExtensions {
+    /// Returns the name of the first extension that swaps can not be executed with.
+    pub fn get_unsupported_extension(&self) -> Option<&'static str> {
+        if self.non_transferable {
+            Some("non_transferable")
+        } else if self.transfer_hook_program.is_some() {
+            Some("transfer_hook")
+        } else if self.default_account_state_frozen {
+            Some("default_account_state_frozen")
+        } else if self.confidential_transfers {
+            Some("confidential_transfer")
+        } else {
+            None
+        }
+    }
+
+    /// The highest fee withheld when transferring the amount.
+    /// The cached mint does not track the current epoch, so both fees are considered.
+    pub fn calculate_transfer_fee(&self, amount: u64) -> u64 {
+        self.transfer_fees
+            .iter()
+            .map(|fee| fee.calculate_fee(amount))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The amount to transfer so that at least `post_fee_amount` is received under both transfer fees.
+    pub fn calculate_pre_transfer_fee_amount(&self, post_fee_amount: u64) -> Option<u64> {
+        self.transfer_fees
+            .iter()
+            .try_fold(post_fee_amount, |amount, fee| {
+                fee.calculate_pre_fee_amount(post_fee_amount)
+                    .map(|pre_fee_amount| amount.max(pre_fee_amount))
+            })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenMint {
-    #[allow(dead_code)]
     pub mint:          Pubkey,
     pub decimals:      u8,
     pub token_program: Pubkey,
+    pub extensions:    TokenMintExtensions,
+}
+
+impl TokenMint {
+    pub fn check_supported_extensions(&self) -> Result<(), RestError> {
+        match self.extensions.get_unsupported_extension() {
+            Some(extension) => Err(RestError::UnsupportedTokenExtension {
+                mint: self.mint,
+                extension,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_fee() {
+        let fee = TransferFee {
+            epoch:       0,
+            maximum_fee: 50,
+            fee_bps:     100,
+        };
+        assert_eq!(fee.calculate_fee(0), 0);
+        assert_eq!(fee.calculate_fee(1), 1);
+        assert_eq!(fee.calculate_fee(1_000), 10);
+        assert_eq!(fee.calculate_fee(1_000_000), 50);
+
+        for amount in [1, 99, 1_000, 4_950, 4_951, 1_000_000] {
+            let pre_fee_amount = fee.calculate_pre_fee_amount(amount).unwrap();
+            assert!(pre_fee_amount - fee.calculate_fee(pre_fee_amount) >= amount);
+        }
+        assert_eq!(fee.calculate_pre_fee_amount(1_000_000), Some(1_000_050));
+    }
+
+    #[test]
+    fn test_token_mint_extensions_use_highest_transfer_fee() {
+        let extensions = TokenMintExtensions {
+            transfer_fees: vec![
+                TransferFee {
+                    epoch:       0,
+                    maximum_fee: u64::MAX,
+                    fee_bps:     100,
+                },
+                TransferFee {
+                    epoch:       10,
+                    maximum_fee: u64::MAX,
+                    fee_bps:     200,
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(extensions.calculate_transfer_fee(1_000), 20);
+        assert_eq!(
+            extensions.calculate_pre_transfer_fee_amount(980),
+            Some(1_000)
+        );
+        assert_eq!(extensions.get_unsupported_extension(), None);
+        assert_eq!(
+            TokenMintExtensions::default().calculate_pre_transfer_fee_amount(980),
+            Some(980)
+        );
+    }
+
+    #[test]
+    fn test_token_mint_extensions_unsupported_extension() {
+        assert_eq!(
+            TokenMintExtensions::default().get_unsupported_extension(),
+            None
+        );
+        for (extensions, expected) in [
+            (
+                TokenMintExtensions {
+                    non_transferable: true,
+                    ..Default::default()
+                },
+                "non_transferable",
+            ),
+            (
+                TokenMintExtensions {
+                    transfer_hook_program: Some(Pubkey::new_unique()),
+                    ..Default::default()
+                },
+                "transfer_hook",
+            ),
+            (
+                TokenMintExtensions {
+                    default_account_state_frozen: true,
+                    ..Default::default()
+                },
+                "default_account_state_frozen",
+            ),
+            (
+                TokenMintExtensions {
+                    confidential_transfers: true,
+                    ..Default::default()
+                },
+                "confidential_transfer",
+            ),
+        ] {
+            assert_eq!(extensions.get_unsupported_extension(), Some(expected));
+        }
+    }
 }
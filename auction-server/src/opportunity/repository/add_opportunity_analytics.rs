@@ -95,6 +95,7 @@ impl Repository {
                 cancellable,
                 minimum_lifetime,
                 minimum_deadline: _,
+                partial_fill_tolerance_ppm: _,
             }) => OpportunityAnalytics::Swap(OpportunityAnalyticsSwap {
                 id: opportunity.id,
                 creation_time: opportunity.creation_time,
@@ -0,0 +1,32 @@
+use {
+    super::Repository,
+    crate::{
+        kernel::entities::ChainId,
+        opportunity::entities,
+    },
+    solana_sdk::pubkey::Pubkey,
+};
+
+impl Repository {
+    /// Returns the recent swap rates of the token pair in the trade size bucket closest to the given one.
+    pub async fn get_in_memory_recent_swap_rates(
+        &self,
+        chain_id: &ChainId,
+        mint_user: &Pubkey,
+        mint_searcher: &Pubkey,
+        size_bucket: u8,
+    ) -> Option<entities::RecentSwapRates> {
+        self.in_memory_store
+            .recent_swap_rates
+            .read()
+            .await
+            .get(chain_id)
+            .and_then(|rates| rates.get(&(*mint_user, *mint_searcher)))
+            .and_then(|rates| {
+                rates
+                    .iter()
+                    .min_by_key(|rates| rates.size_bucket.abs_diff(size_bucket))
+            })
+            .cloned()
+    }
+}
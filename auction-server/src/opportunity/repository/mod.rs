@@ -2,7 +2,10 @@
 use mockall::mock;
 use {
     super::entities,
-    crate::kernel::analytics_db::ClickhouseInserter,
+    crate::kernel::{
+        analytics_db::ClickhouseInserter,
+        entities::ChainId,
+    },
     axum_prometheus::metrics,
     express_relay::state::ExpressRelayMetadata,
     solana_sdk::pubkey::Pubkey,
@@ -19,10 +22,12 @@ mod get_express_relay_metadata;
 mod get_in_memory_opportunities;
 mod get_in_memory_opportunities_by_key;
 mod get_in_memory_opportunity_by_id;
+mod get_in_memory_recent_swap_rates;
 mod get_opportunities;
 mod get_token_mint;
 mod models;
 mod refresh_in_memory_opportunity;
+mod refresh_in_memory_recent_swap_rates;
 mod remove_opportunities;
 mod remove_opportunity;
 
@@ -53,6 +58,8 @@ pub struct InMemoryStoreSvm {
     pub core_fields:            InMemoryStoreCoreFields,
     pub token_mint_cache:       RwLock<HashMap<Pubkey, entities::TokenMint>>,
     pub express_relay_metadata: RwLock<Option<ExpressRelayMetadata>>,
+    pub recent_swap_rates:
+        RwLock<HashMap<ChainId, HashMap<(Pubkey, Pubkey), Vec<entities::RecentSwapRates>>>>,
 }
 
 impl InMemoryStoreSvm {
@@ -61,6 +68,7 @@ impl InMemoryStoreSvm {
             core_fields:            InMemoryStoreCoreFields::new(),
             token_mint_cache:       RwLock::new(HashMap::new()),
             express_relay_metadata: RwLock::new(None),
+            recent_swap_rates:      RwLock::new(HashMap::new()),
         }
     }
 }
@@ -75,6 +83,7 @@ impl Deref for InMemoryStoreSvm {
 }
 
 pub struct AnalyticsDatabaseInserter {
+    client:                    clickhouse::Client,
     inserter_opportunity_limo: ClickhouseInserter<OpportunityAnalyticsLimo>,
     inserter_opportunity_swap: ClickhouseInserter<OpportunityAnalyticsSwap>,
 }
@@ -84,8 +93,9 @@ impl AnalyticsDatabaseInserter {
         let inserter_opportunity_limo =
             ClickhouseInserter::new(client.clone(), "opportunity_limo".to_string());
         let inserter_opportunity_swap =
-            ClickhouseInserter::new(client, "opportunity_swap".to_string());
+            ClickhouseInserter::new(client.clone(), "opportunity_swap".to_string());
         Self {
+            client,
             inserter_opportunity_limo,
             inserter_opportunity_swap,
         }
@@ -49,7 +49,10 @@ use {
         },
         QueryBuilder,
     },
-    std::fmt::Debug,
+    std::{
+        fmt::Debug,
+        time::Duration,
+    },
     time::{
         OffsetDateTime,
         PrimitiveDateTime,
@@ -103,6 +106,8 @@ pub struct OpportunityMetadataSvmProgramSwap {
     pub cancellable:                          bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum_lifetime:                     Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_fill_tolerance_ppm:           Option<u64>,
 }
 
 fn default_cancellable() -> bool {
@@ -170,6 +175,11 @@ pub struct Opportunity<T: OpportunityMetadata> {
 pub trait AnalyticsDatabase: Send + Sync + 'static {
     async fn add_opportunity(&self, opportunity: OpportunityAnalytics)
         -> Result<(), anyhow::Error>;
+    async fn get_recent_swap_rates(
+        &self,
+        chain_id: ChainId,
+        window: Duration,
+    ) -> Result<Vec<SwapRatesAnalytics>, anyhow::Error>;
 }
 
 #[cfg_attr(test, automock)]
@@ -266,6 +276,27 @@ pub struct OpportunityAnalyticsSwap {
     pub profile_id: Option<Uuid>,
 }
 
+/// Rates of the winning swap bids of a token pair and trade size, aggregated from the `bid_swap` table
+#[derive(Row, Serialize, Deserialize, Debug)]
+pub struct SwapRatesAnalytics {
+    pub user_token_mint:     String,
+    pub searcher_token_mint: String,
+    /// Order of magnitude of the user token amounts
+    pub size_bucket:         u8,
+    pub samples:             u64,
+    /// Lower quartile, median and upper quartile of the searcher to user token amount rates before the swap fees
+    pub rates:               Vec<f64>,
+}
+
+// Bid statuses are stored as json strings in the analytics database
+const WINNING_BID_STATUSES: [&str; 5] = [
+    "\"awaiting_signature\"",
+    "\"sent_to_user_for_submission\"",
+    "\"submitted\"",
+    "\"won\"",
+    "\"expired\"",
+];
+
 #[async_trait]
 impl AnalyticsDatabase for AnalyticsDatabaseInserter {
     #[instrument(
@@ -295,6 +326,52 @@ impl AnalyticsDatabase for AnalyticsDatabaseInserter {
                 .map_err(|e| anyhow::anyhow!("Failed to send swap opportunity analytics {:?}", e)),
         }
     }
+
+    #[instrument(
+        target = "metrics",
+        name = "db_analytics_get_recent_swap_rates",
+        fields(
+            category = "db_analytics_queries",
+            result = "success",
+            name = "get_recent_swap_rates",
+            tracing_enabled
+        ),
+        skip_all
+    )]
+    async fn get_recent_swap_rates(
+        &self,
+        chain_id: ChainId,
+        window: Duration,
+    ) -> anyhow::Result<Vec<SwapRatesAnalytics>> {
+        self.client
+            .query(
+                "SELECT user_token_mint, searcher_token_mint,
+                    toUInt8(floor(log10(user_token_amount))) AS size_bucket, count() AS samples,
+                    quantiles(0.25, 0.5, 0.75)(
+                        searcher_token_amount / if(
+                            fee_token = 'user',
+                            user_token_amount * (1000000 - referral_fee_ppm - platform_fee_ppm) / 1000000,
+                            user_token_amount
+                        )
+                    ) AS rates
+                FROM bid_swap FINAL
+                WHERE chain_id = ?
+                    AND creation_time >= now64(6) - toIntervalSecond(?)
+                    AND has(?, status)
+                    AND user_token_amount > 0
+                    AND referral_fee_ppm + platform_fee_ppm < 1000000
+                GROUP BY user_token_mint, searcher_token_mint, size_bucket",
+            )
+            .bind(chain_id)
+            .bind(window.as_secs())
+            .bind(WINNING_BID_STATUSES.to_vec())
+            .fetch_all::<SwapRatesAnalytics>()
+            .await
+            .map_err(|e| {
+                tracing::Span::current().record("result", "error");
+                anyhow::anyhow!("Failed to get recent swap rates {:?}", e)
+            })
+    }
 }
 
 #[async_trait]
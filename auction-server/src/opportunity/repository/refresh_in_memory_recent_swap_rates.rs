@@ -0,0 +1,54 @@
+use {
+    super::Repository,
+    crate::{
+        kernel::entities::ChainId,
+        opportunity::entities,
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::HashMap,
+        str::FromStr,
+        time::Duration,
+    },
+};
+
+impl Repository {
+    /// Replaces the in memory swap rates of the chain with the rates of the winning swap bids in the recent window.
+    /// The rates of each token pair are kept per trade size bucket.
+    pub async fn refresh_in_memory_recent_swap_rates(
+        &self,
+        chain_id: ChainId,
+        window: Duration,
+    ) -> anyhow::Result<()> {
+        let mut rates: HashMap<(Pubkey, Pubkey), Vec<entities::RecentSwapRates>> = HashMap::new();
+        for row in self
+            .db_analytics
+            .get_recent_swap_rates(chain_id.clone(), window)
+            .await?
+        {
+            let (Ok(mint_user), Ok(mint_searcher)) = (
+                Pubkey::from_str(&row.user_token_mint),
+                Pubkey::from_str(&row.searcher_token_mint),
+            ) else {
+                continue;
+            };
+            if let [lower_quartile, median, upper_quartile] = row.rates.as_slice() {
+                rates.entry((mint_user, mint_searcher)).or_default().push(
+                    entities::RecentSwapRates {
+                        size_bucket:    row.size_bucket,
+                        samples:        row.samples,
+                        lower_quartile: *lower_quartile,
+                        median:         *median,
+                        upper_quartile: *upper_quartile,
+                    },
+                );
+            }
+        }
+        self.in_memory_store
+            .recent_swap_rates
+            .write()
+            .await
+            .insert(chain_id, rates);
+        Ok(())
+    }
+}
@@ -14,6 +14,7 @@ use {
             service::add_opportunity_analytics::AddOpportunityAnalyticsInput,
         },
     },
+    express_relay::state::FEE_SPLIT_PRECISION_PPM,
 };
 
 pub struct AddOpportunityInput {
@@ -50,6 +51,14 @@ impl Service {
     ) -> Result<OpportunitySvm, RestError> {
         // Make sure the chain id is valid
         self.get_config(&input.opportunity.chain_id.clone())?;
+        if let entities::OpportunitySvmProgram::Swap(program) = &input.opportunity.program {
+            if program.partial_fill_tolerance_ppm.unwrap_or(0) >= FEE_SPLIT_PRECISION_PPM {
+                return Err(RestError::BadParameters(format!(
+                    "Partial fill tolerance ppm must be lower than {}",
+                    FEE_SPLIT_PRECISION_PPM
+                )));
+            }
+        }
 
         let opportunity_create = input.opportunity;
         let action = self.assess_action(&opportunity_create).await;
@@ -110,7 +119,10 @@ impl Service {
 mod tests {
     use {
         crate::{
-            api::ws,
+            api::{
+                ws,
+                RestError,
+            },
             kernel::{
                 entities::PermissionKeySvm,
                 rpc_client_svm_tester::RpcClientSvmTester,
@@ -121,6 +133,7 @@ mod tests {
                     OpportunityKey,
                     OpportunitySvmProgram,
                     OpportunitySvmProgramLimo,
+                    OpportunitySvmProgramSwap,
                     TokenAmountSvm,
                 },
                 repository::MockDatabase,
@@ -130,6 +143,7 @@ mod tests {
                 },
             },
         },
+        express_relay::state::FEE_SPLIT_PRECISION_PPM,
         solana_sdk::pubkey::Pubkey,
     };
 
@@ -237,4 +251,45 @@ mod tests {
         );
         assert!(ws_receiver.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_add_opportunity_with_invalid_partial_fill_tolerance() {
+        let chain_id = "solana".to_string();
+        let rpc_client = RpcClientSvmTester::new();
+        let (service, _) =
+            Service::new_with_mocks_svm(chain_id.clone(), MockDatabase::default(), &rpc_client);
+
+        let mut program =
+            OpportunitySvmProgramSwap::default_test_with_user_wallet_address(Pubkey::new_unique());
+        program.partial_fill_tolerance_ppm = Some(FEE_SPLIT_PRECISION_PPM);
+        let opportunity_create = OpportunityCreateSvm {
+            permission_key: PermissionKeySvm::try_from(&[1; 65][..]).expect("permission key"),
+            chain_id,
+            sell_tokens: vec![TokenAmountSvm {
+                token:  Pubkey::new_unique(),
+                amount: 2,
+            }],
+            buy_tokens: vec![TokenAmountSvm {
+                token:  Pubkey::new_unique(),
+                amount: 1,
+            }],
+            router: Pubkey::new_unique(),
+            permission_account: Pubkey::new_unique(),
+            program: OpportunitySvmProgram::Swap(program),
+            profile_id: None,
+        };
+
+        let result = service
+            .add_opportunity(AddOpportunityInput {
+                opportunity: opportunity_create,
+            })
+            .await;
+        assert_eq!(
+            result,
+            Err(RestError::BadParameters(format!(
+                "Partial fill tolerance ppm must be lower than {}",
+                FEE_SPLIT_PRECISION_PPM
+            )))
+        );
+    }
 }
@@ -0,0 +1,561 @@
+use {
+    super::{
+        get_express_relay_metadata::GetExpressRelayMetadataInput,
+        get_quote::get_fee_token,
+        get_token_mint::GetTokenMintInput,
+        ConfigSvm,
+        Service,
+    },
+    crate::{
+        api::RestError,
+        kernel::entities::ChainId,
+        opportunity::entities,
+        state::Price,
+    },
+    express_relay::state::{
+        FEE_BPS_TO_PPM,
+        FEE_SPLIT_PRECISION_PPM,
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        cmp::max,
+        time::Duration,
+    },
+};
+
+/// The window of recent auctions used to estimate the indicative quotes
+pub const RECENT_SWAP_RATES_WINDOW: Duration = Duration::from_secs(60 * 60);
+/// The number of recent auctions of a token pair needed to fully trust its rates
+const FULL_CONFIDENCE_SAMPLES: u64 = 20;
+/// The confidence of an estimate only based on market prices, as they ignore liquidity
+const MARKET_PRICE_ONLY_CONFIDENCE: f64 = 0.3;
+/// The confidence factor applied per order of magnitude between the trade size and the recent auctions used
+const SIZE_BUCKET_DISTANCE_CONFIDENCE: f64 = 0.5;
+
+pub struct GetIndicativeQuoteInput {
+    pub chain_id:         ChainId,
+    pub user_token:       entities::TokenAmountSvm,
+    pub mint_searcher:    Pubkey,
+    pub referral_fee_ppm: u64,
+}
+
+/// Returns the value of the smallest denomination of a token in the quote currency of the price.
+fn get_unit_value(price: &Price, decimals: u8) -> f64 {
+    price.price as f64 * 10f64.powi(price.exponent - decimals as i32)
+}
+
+/// Returns the searcher token amount per user token amount implied by the market prices of the tokens.
+fn get_market_rate(
+    price_user: &Price,
+    decimals_user: u8,
+    price_searcher: &Price,
+    decimals_searcher: u8,
+) -> Option<f64> {
+    let unit_value_searcher = get_unit_value(price_searcher, decimals_searcher);
+    if unit_value_searcher <= 0. {
+        return None;
+    }
+    Some(get_unit_value(price_user, decimals_user) / unit_value_searcher)
+}
+
+/// Returns the rate and the confidence of the estimate from the recent auctions and the market rate.
+///
+/// The confidence of the recent auctions grows with the number of auctions and shrinks with the spread of their rates
+/// and with the distance between their trade size and the requested one.
+/// When the market rate is available, the confidence also shrinks with the deviation of the auctions from the market.
+fn estimate_rate(
+    recent_swap_rates: Option<&entities::RecentSwapRates>,
+    size_bucket: u8,
+    market_rate: Option<f64>,
+) -> Option<(f64, f64)> {
+    let recent_swap_rates = recent_swap_rates.filter(|rates| rates.median > 0.);
+    let market_rate = market_rate.filter(|rate| rate.is_finite() && *rate > 0.);
+    match (recent_swap_rates, market_rate) {
+        (Some(rates), market_rate) => {
+            let samples_factor = (rates.samples as f64 / FULL_CONFIDENCE_SAMPLES as f64).min(1.);
+            let spread = (rates.upper_quartile - rates.lower_quartile) / rates.median;
+            let deviation = market_rate.map_or(0., |rate| (rates.median - rate).abs() / rate);
+            let size_factor = SIZE_BUCKET_DISTANCE_CONFIDENCE
+                .powi(rates.size_bucket.abs_diff(size_bucket).into());
+            let confidence = samples_factor
+                * size_factor
+                * (1. - spread.clamp(0., 1.))
+                * (1. - deviation.clamp(0., 1.));
+            Some((rates.median, confidence))
+        }
+        (None, Some(rate)) => Some((rate, MARKET_PRICE_ONLY_CONFIDENCE)),
+        (None, None) => None,
+    }
+}
+
+impl Service {
+    async fn get_market_rate(
+        &self,
+        chain_id: &ChainId,
+        mint_user: Pubkey,
+        mint_searcher: Pubkey,
+    ) -> Result<Option<f64>, RestError> {
+        let (price_user, price_searcher) = {
+            let prices = self.store.prices.read().await;
+            match (prices.get(&mint_user), prices.get(&mint_searcher)) {
+                (Some(price_user), Some(price_searcher)) => {
+                    (price_user.clone(), price_searcher.clone())
+                }
+                _ => return Ok(None),
+            }
+        };
+        let token_mint_user = self
+            .get_token_mint(GetTokenMintInput {
+                chain_id: chain_id.clone(),
+                mint:     mint_user,
+            })
+            .await?;
+        let token_mint_searcher = self
+            .get_token_mint(GetTokenMintInput {
+                chain_id: chain_id.clone(),
+                mint:     mint_searcher,
+            })
+            .await?;
+        Ok(get_market_rate(
+            &price_user,
+            token_mint_user.decimals,
+            &price_searcher,
+            token_mint_searcher.decimals,
+        ))
+    }
+
+    /// Returns the searcher token amount the user receives for the user token amount at the given rate.
+    ///
+    /// Like in the quotes, the swap fees are deducted from the fee token of the pair
+    /// and the transfer fee of the searcher token is withheld from the amount the user receives.
+    async fn get_searcher_amount_after_fees(
+        &self,
+        input: &GetIndicativeQuoteInput,
+        config: &ConfigSvm,
+        rate: f64,
+    ) -> Result<u64, RestError> {
+        let mint_user = input.user_token.token;
+        let mint_searcher = input.mint_searcher;
+        let metadata = self
+            .get_express_relay_metadata(GetExpressRelayMetadataInput {
+                chain_id: input.chain_id.clone(),
+            })
+            .await?;
+        let platform_fee_ppm = max(
+            config
+                .get_platform_fee_ppm(&mint_user, &mint_searcher)
+                .unwrap_or(0),
+            metadata.swap_platform_fee_bps * FEE_BPS_TO_PPM,
+        );
+        if input.referral_fee_ppm.saturating_add(platform_fee_ppm) >= FEE_SPLIT_PRECISION_PPM {
+            return Err(RestError::BadParameters(format!(
+                "Referral fee ppm + platform fee ppm must be less than {}",
+                FEE_SPLIT_PRECISION_PPM
+            )));
+        }
+
+        let deduct_fees = |amount: u64| {
+            metadata
+                .compute_swap_fees(input.referral_fee_ppm, platform_fee_ppm, amount)
+                .map(|swap_fees| swap_fees.remaining_amount)
+                .map_err(|e| {
+                    tracing::error!("Failed to compute swap fees: {:?}", e);
+                    RestError::TemporarilyUnavailable
+                })
+        };
+        let swap = |amount: u64| (amount as f64 * rate).floor() as u64;
+        let searcher_amount =
+            match get_fee_token(mint_user, mint_searcher, &config.ordered_fee_tokens) {
+                entities::FeeToken::SearcherToken => deduct_fees(swap(input.user_token.amount))?,
+                entities::FeeToken::UserToken => swap(deduct_fees(input.user_token.amount)?),
+            };
+
+        let token_mint_searcher = self
+            .get_token_mint(GetTokenMintInput {
+                chain_id: input.chain_id.clone(),
+                mint:     mint_searcher,
+            })
+            .await?;
+        Ok(searcher_amount
+            - token_mint_searcher
+                .extensions
+                .calculate_transfer_fee(searcher_amount))
+    }
+
+    /// Estimates the searcher token amount for the user token amount without running an auction.
+    ///
+    /// The estimate is based on the winning bids of the recent auctions of the token pair with a similar trade size
+    /// and the market prices from Pyth Lazer. The swap fees are deducted from the estimated amount.
+    #[tracing::instrument(skip_all, err(level = tracing::Level::TRACE))]
+    pub async fn get_indicative_quote(
+        &self,
+        input: GetIndicativeQuoteInput,
+    ) -> Result<entities::IndicativeQuote, RestError> {
+        let config = self.get_config(&input.chain_id)?;
+        if input.user_token.amount == 0 {
+            return Err(RestError::BadParameters(
+                "Input token amount must be greater than 0".to_string(),
+            ));
+        }
+        if input.user_token.token == input.mint_searcher {
+            return Err(RestError::BadParameters(
+                "Input and output token mints must be different".to_string(),
+            ));
+        }
+        if !config
+            .token_whitelist
+            .is_token_mint_allowed(&input.user_token.token)
+        {
+            return Err(RestError::TokenMintNotAllowed(
+                "Input".to_string(),
+                input.user_token.token.to_string(),
+            ));
+        }
+        if !config
+            .token_whitelist
+            .is_token_mint_allowed(&input.mint_searcher)
+        {
+            return Err(RestError::TokenMintNotAllowed(
+                "Output".to_string(),
+                input.mint_searcher.to_string(),
+            ));
+        }
+
+        let size_bucket = entities::RecentSwapRates::get_size_bucket(input.user_token.amount);
+        let recent_swap_rates = self
+            .repo
+            .get_in_memory_recent_swap_rates(
+                &input.chain_id,
+                &input.user_token.token,
+                &input.mint_searcher,
+                size_bucket,
+            )
+            .await;
+        let market_rate = self
+            .get_market_rate(&input.chain_id, input.user_token.token, input.mint_searcher)
+            .await?;
+        let (rate, confidence) =
+            estimate_rate(recent_swap_rates.as_ref(), size_bucket, market_rate)
+                .ok_or(RestError::QuoteNotFound)?;
+        let searcher_amount = self
+            .get_searcher_amount_after_fees(&input, config, rate)
+            .await?;
+
+        Ok(entities::IndicativeQuote {
+            searcher_token: entities::TokenAmountSvm {
+                token:  input.mint_searcher,
+                amount: searcher_amount,
+            },
+            user_token: input.user_token,
+            confidence,
+            chain_id: input.chain_id,
+        })
+    }
+
+    /// Refreshes the rates of the recent auctions used to estimate the indicative quotes for all chains.
+    pub async fn refresh_recent_swap_rates(&self) {
+        for chain_id in self.config.keys() {
+            if let Err(e) = self
+                .repo
+                .refresh_in_memory_recent_swap_rates(chain_id.clone(), RECENT_SWAP_RATES_WINDOW)
+                .await
+            {
+                tracing::error!(chain_id = %chain_id, error = ?e, "Failed to refresh recent swap rates");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            config::TokenWhitelistConfig,
+            kernel::{
+                rpc_client_svm_tester::RpcClientSvmTester,
+                test_utils::DEFAULT_CHAIN_ID,
+            },
+            opportunity::{
+                repository::MockDatabase,
+                service::TokenWhitelist,
+            },
+        },
+        express_relay::state::ExpressRelayMetadata,
+        std::{
+            collections::HashMap,
+            sync::Arc,
+        },
+    };
+
+    const SIZE_BUCKET: u8 = 6;
+
+    fn recent_swap_rates(
+        samples: u64,
+        lower_quartile: f64,
+        upper_quartile: f64,
+    ) -> entities::RecentSwapRates {
+        entities::RecentSwapRates {
+            size_bucket: SIZE_BUCKET,
+            samples,
+            lower_quartile,
+            median: 2.,
+            upper_quartile,
+        }
+    }
+
+    struct TestSetup {
+        service:       Service,
+        mint_user:     Pubkey,
+        mint_searcher: Pubkey,
+    }
+
+    /// Sets up a service with a platform fee of 0.1% where only the user token is whitelisted if the whitelist is enabled.
+    async fn setup(fee_token: entities::FeeToken, enable_token_whitelist: bool) -> TestSetup {
+        let chain_id = DEFAULT_CHAIN_ID.to_string();
+        let (mint_user, mint_searcher) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut service, _) = Service::new_with_mocks_svm(
+            chain_id.clone(),
+            MockDatabase::default(),
+            &RpcClientSvmTester::new(),
+        );
+        let inner = Arc::get_mut(&mut service.0).expect("Only one reference should exist at setup");
+        let config = inner.config.get_mut(&chain_id).unwrap();
+        config.ordered_fee_tokens = match fee_token {
+            entities::FeeToken::SearcherToken => vec![mint_searcher],
+            entities::FeeToken::UserToken => vec![mint_user],
+        };
+        config.token_whitelist = TokenWhitelist::from(TokenWhitelistConfig {
+            enabled:         enable_token_whitelist,
+            whitelist_mints: vec![mint_user],
+        });
+
+        service
+            .repo
+            .cache_express_relay_metadata(ExpressRelayMetadata {
+                swap_platform_fee_bps: 10,
+                ..Default::default()
+            })
+            .await;
+        for mint in [mint_user, mint_searcher] {
+            service
+                .repo
+                .cache_token_mint(
+                    mint,
+                    entities::TokenMint {
+                        mint,
+                        decimals: 6,
+                        token_program: spl_token::id(),
+                        extensions: Default::default(),
+                    },
+                )
+                .await;
+        }
+        TestSetup {
+            service,
+            mint_user,
+            mint_searcher,
+        }
+    }
+
+    fn get_input(mint_user: Pubkey, mint_searcher: Pubkey) -> GetIndicativeQuoteInput {
+        GetIndicativeQuoteInput {
+            chain_id: DEFAULT_CHAIN_ID.to_string(),
+            user_token: entities::TokenAmountSvm {
+                token:  mint_user,
+                amount: 1_000_000,
+            },
+            mint_searcher,
+            referral_fee_ppm: 4_000,
+        }
+    }
+
+    async fn add_recent_swap_rates(
+        service: &Service,
+        mint_user: Pubkey,
+        mint_searcher: Pubkey,
+        rates: entities::RecentSwapRates,
+    ) {
+        service
+            .repo
+            .in_memory_store
+            .recent_swap_rates
+            .write()
+            .await
+            .insert(
+                DEFAULT_CHAIN_ID.to_string(),
+                HashMap::from([((mint_user, mint_searcher), vec![rates])]),
+            );
+    }
+
+    #[test]
+    fn test_get_market_rate() {
+        // 1 token with 6 decimals is worth 1 usd and 1 token with 9 decimals is worth 200 usd
+        let price_user = Price {
+            exponent: -8,
+            price:    100_000_000,
+        };
+        let price_searcher = Price {
+            exponent: -8,
+            price:    20_000_000_000,
+        };
+        let rate = get_market_rate(&price_user, 6, &price_searcher, 9).unwrap();
+        assert!((rate - 5.).abs() < 1e-9);
+
+        let zero_price = Price {
+            exponent: -8,
+            price:    0,
+        };
+        assert_eq!(get_market_rate(&price_user, 6, &zero_price, 9), None);
+    }
+
+    #[test]
+    fn test_estimate_rate() {
+        assert_eq!(estimate_rate(None, SIZE_BUCKET, None), None);
+        assert_eq!(
+            estimate_rate(None, SIZE_BUCKET, Some(1.5)),
+            Some((1.5, MARKET_PRICE_ONLY_CONFIDENCE))
+        );
+
+        // Tight rates from enough auctions of the same size are fully trusted
+        let rates = recent_swap_rates(FULL_CONFIDENCE_SAMPLES, 2., 2.);
+        assert_eq!(
+            estimate_rate(Some(&rates), SIZE_BUCKET, None),
+            Some((2., 1.))
+        );
+        assert_eq!(
+            estimate_rate(Some(&rates), SIZE_BUCKET, Some(2.)),
+            Some((2., 1.))
+        );
+
+        // Few auctions, spread rates, other trade sizes and deviation from the market reduce the confidence
+        let rates = recent_swap_rates(FULL_CONFIDENCE_SAMPLES / 2, 2., 2.);
+        assert_eq!(
+            estimate_rate(Some(&rates), SIZE_BUCKET, None),
+            Some((2., 0.5))
+        );
+        let rates = recent_swap_rates(FULL_CONFIDENCE_SAMPLES, 1.5, 2.5);
+        assert_eq!(
+            estimate_rate(Some(&rates), SIZE_BUCKET, None),
+            Some((2., 0.5))
+        );
+        let rates = recent_swap_rates(FULL_CONFIDENCE_SAMPLES, 2., 2.);
+        assert_eq!(
+            estimate_rate(Some(&rates), SIZE_BUCKET + 1, None),
+            Some((2., 0.5))
+        );
+        assert_eq!(
+            estimate_rate(Some(&rates), SIZE_BUCKET - 2, None),
+            Some((2., 0.25))
+        );
+        assert_eq!(
+            estimate_rate(Some(&rates), SIZE_BUCKET, Some(4.)),
+            Some((2., 0.5))
+        );
+    }
+
+    #[test]
+    fn test_get_size_bucket() {
+        assert_eq!(entities::RecentSwapRates::get_size_bucket(0), 0);
+        assert_eq!(entities::RecentSwapRates::get_size_bucket(9), 0);
+        assert_eq!(entities::RecentSwapRates::get_size_bucket(10), 1);
+        assert_eq!(entities::RecentSwapRates::get_size_bucket(1_000_000), 6);
+        assert_eq!(entities::RecentSwapRates::get_size_bucket(u64::MAX), 19);
+    }
+
+    #[tokio::test]
+    async fn test_get_indicative_quote_token_not_whitelisted() {
+        let TestSetup {
+            service,
+            mint_user,
+            mint_searcher,
+        } = setup(entities::FeeToken::UserToken, true).await;
+
+        let result = service
+            .get_indicative_quote(get_input(mint_user, mint_searcher))
+            .await;
+        assert_eq!(
+            result,
+            Err(RestError::TokenMintNotAllowed(
+                "Output".to_string(),
+                mint_searcher.to_string(),
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_indicative_quote_not_found() {
+        let TestSetup {
+            service,
+            mint_user,
+            mint_searcher,
+        } = setup(entities::FeeToken::UserToken, false).await;
+
+        let result = service
+            .get_indicative_quote(get_input(mint_user, mint_searcher))
+            .await;
+        assert_eq!(result, Err(RestError::QuoteNotFound));
+    }
+
+    #[tokio::test]
+    async fn test_get_indicative_quote_from_market_prices() {
+        let TestSetup {
+            service,
+            mint_user,
+            mint_searcher,
+        } = setup(entities::FeeToken::UserToken, false).await;
+        service.store.prices.write().await.extend([
+            (
+                mint_user,
+                Price {
+                    exponent: -8,
+                    price:    200_000_000,
+                },
+            ),
+            (
+                mint_searcher,
+                Price {
+                    exponent: -8,
+                    price:    100_000_000,
+                },
+            ),
+        ]);
+
+        let quote = service
+            .get_indicative_quote(get_input(mint_user, mint_searcher))
+            .await
+            .unwrap();
+        // The referral and platform fees of 0.4% and 0.1% are deducted from the user token amount
+        assert_eq!(quote.searcher_token.amount, 1_990_000);
+        assert_eq!(quote.confidence, MARKET_PRICE_ONLY_CONFIDENCE);
+    }
+
+    #[tokio::test]
+    async fn test_get_indicative_quote_from_recent_swap_rates() {
+        // The fees are deducted from the user token before the swap or from the searcher token after the swap
+        for fee_token in [
+            entities::FeeToken::UserToken,
+            entities::FeeToken::SearcherToken,
+        ] {
+            let TestSetup {
+                service,
+                mint_user,
+                mint_searcher,
+            } = setup(fee_token, false).await;
+            add_recent_swap_rates(
+                &service,
+                mint_user,
+                mint_searcher,
+                recent_swap_rates(FULL_CONFIDENCE_SAMPLES, 2., 2.),
+            )
+            .await;
+
+            let quote = service
+                .get_indicative_quote(get_input(mint_user, mint_searcher))
+                .await
+                .unwrap();
+            assert_eq!(quote.searcher_token.amount, 1_990_000);
+            assert_eq!(quote.confidence, 1.);
+        }
+    }
+}
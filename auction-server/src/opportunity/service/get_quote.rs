@@ -3,6 +3,7 @@ use {
         get_quote_request_account_balances::QuoteRequestAccountBalancesInput,
         get_token_mint::GetTokenMintInput,
         remove_opportunity::RemoveOpportunityInput,
+        AuctionService,
         Service,
     },
     crate::{
@@ -11,14 +12,18 @@ use {
             self,
             entities::{
                 Auction,
+                AuctionId,
                 BidPaymentInstructionType,
                 BidStatusAuction,
                 BidStatusSvm,
+                BidTransactionDataSwap,
             },
             service::{
                 add_auction::AddAuctionInput,
                 auction_manager::AuctionManager,
+                auction_pricing_rule::get_bid_tie_break_key,
                 get_pending_bids::GetLiveBidsInput,
+                quote_fallback_auctions::AddQuoteFallbackAuctionsInput,
                 update_bid_status::UpdateBidStatusInput,
                 verification::{
                     get_current_time_rounded_with_offset,
@@ -27,7 +32,7 @@ use {
                 },
             },
         },
-        kernel::entities::PermissionKeySvm,
+        models::ProfileId,
         opportunity::{
             entities::{
                 self,
@@ -54,7 +59,11 @@ use {
     spl_associated_token_account::get_associated_token_address_with_program_id,
     spl_token::native_mint,
     std::{
-        cmp::max,
+        cmp::{
+            max,
+            Reverse,
+        },
+        collections::HashSet,
         time::Duration,
     },
     time::OffsetDateTime,
@@ -77,6 +86,10 @@ use {
 // Sdk shows a smaller amount (after fees) to the searcher for their pricing engine
 // while keeping the original amount (before fees) in the bid
 // --------------------------------------------------------------------------------------------
+// Token-2022 transfer fees of the searcher token are withheld from the amount the user receives,
+// so a specified searcher amount is increased to cover them, and get_quote returns the searcher
+// amount after them.
+// --------------------------------------------------------------------------------------------
 
 /// Prefix for indicative price taker keys
 /// We use the first 24 bytes of "Price11111111111111111111111111111111111112"
@@ -102,6 +115,9 @@ pub fn is_indicative_price_taker(wallet_address: &Pubkey) -> bool {
     wallet_bytes[0..24] == INDICATIVE_PRICE_TAKER_BASE.as_array()[0..24]
 }
 
+/// Maximum number of quotes returned for a single quote request
+pub const MAXIMUM_NUMBER_OF_QUOTES: u32 = 5;
+
 pub struct GetQuoteInput {
     pub quote_create: entities::QuoteCreate,
 }
@@ -160,7 +176,7 @@ pub fn get_quote_virtual_permission_account(
 /// Determines if the fee token should be the user token or the searcher token.
 /// If the user token is explicitly tiered higher than the searcher token, the fee token will be the user token, and vice versa.
 /// If neither token tier has been specified, the fee token is set to the user token to simplify the logic for searchers.
-fn get_fee_token(
+pub(super) fn get_fee_token(
     user_mint: Pubkey,
     searcher_mint: Pubkey,
     ordered_fee_tokens: &[Pubkey],
@@ -180,9 +196,24 @@ fn get_fee_token(
     }
 }
 
+/// The searcher token amount to request so that the user receives the specified amount after the transfer fee of the mint.
+fn get_pre_transfer_fee_amount(
+    token_mint: &entities::TokenMint,
+    amount: u64,
+) -> Result<u64, RestError> {
+    token_mint
+        .extensions
+        .calculate_pre_transfer_fee_amount(amount)
+        .ok_or_else(|| {
+            RestError::BadParameters(
+                "Specified token amount is too large to cover the token transfer fee".to_string(),
+            )
+        })
+}
+
 impl Service {
     #[tracing::instrument(skip_all, err(level = tracing::Level::TRACE))]
-    async fn get_opportunity_create_for_quote(
+    pub(super) async fn get_opportunity_create_for_quote(
         &self,
         quote_create: entities::QuoteCreate,
     ) -> Result<entities::OpportunityCreateSvm, RestError> {
@@ -225,6 +256,32 @@ impl Service {
             metadata.swap_platform_fee_bps * FEE_BPS_TO_PPM,
         );
 
+        let token_mint_searcher = self
+            .get_token_mint(GetTokenMintInput {
+                chain_id: quote_create.chain_id.clone(),
+                mint:     mint_searcher,
+            })
+            .await
+            .map_err(|err| {
+                tracing::error!("Failed to get searcher token program: {:?}", err);
+                RestError::BadParameters("Searcher token program not found".to_string())
+            })?;
+        let token_mint_user = self
+            .get_token_mint(GetTokenMintInput {
+                chain_id: quote_create.chain_id.clone(),
+                mint:     mint_user,
+            })
+            .await
+            .map_err(|err| {
+                tracing::error!("Failed to get user token program: {:?}", err);
+                RestError::BadParameters("User token program not found".to_string())
+            })?;
+
+        token_mint_searcher.check_supported_extensions()?;
+        token_mint_user.check_supported_extensions()?;
+        let token_program_searcher = token_mint_searcher.token_program;
+        let token_program_user = token_mint_user.token_program;
+
         let fee_token = get_fee_token(mint_user, mint_searcher, &config.ordered_fee_tokens);
         let (searcher_amount, user_amount) = match (quote_create.tokens.clone(), fee_token.clone())
         {
@@ -232,6 +289,8 @@ impl Service {
                 entities::QuoteTokens::SearcherTokenSpecified { searcher_token, .. },
                 entities::FeeToken::SearcherToken,
             ) => {
+                let searcher_amount =
+                    get_pre_transfer_fee_amount(&token_mint_searcher, searcher_token.amount)?;
                 // This is not exactly accurate and may overestimate the amount needed
                 // because of floor / ceil rounding errors.
                 let referral_fee_ppm = referral_fee_info.referral_fee_ppm;
@@ -243,14 +302,17 @@ impl Service {
                 }
                 let denominator: u64 =
                     FEE_SPLIT_PRECISION_PPM - referral_fee_ppm - platform_fee_ppm;
-                let numerator = searcher_token.amount * FEE_SPLIT_PRECISION_PPM;
+                let numerator = searcher_amount * FEE_SPLIT_PRECISION_PPM;
                 let amount_including_fees = numerator.div_ceil(denominator);
                 (amount_including_fees, 0u64)
             }
             (
                 entities::QuoteTokens::SearcherTokenSpecified { searcher_token, .. },
                 entities::FeeToken::UserToken,
-            ) => (searcher_token.amount, 0u64),
+            ) => (
+                get_pre_transfer_fee_amount(&token_mint_searcher, searcher_token.amount)?,
+                0u64,
+            ),
             (entities::QuoteTokens::UserTokenSpecified { user_token, .. }, _) => {
                 (0, user_token.amount)
             }
@@ -262,29 +324,6 @@ impl Service {
             ));
         }
 
-        let token_program_searcher = self
-            .get_token_mint(GetTokenMintInput {
-                chain_id: quote_create.chain_id.clone(),
-                mint:     mint_searcher,
-            })
-            .await
-            .map_err(|err| {
-                tracing::error!("Failed to get searcher token program: {:?}", err);
-                RestError::BadParameters("Searcher token program not found".to_string())
-            })?
-            .token_program;
-        let token_program_user = self
-            .get_token_mint(GetTokenMintInput {
-                chain_id: quote_create.chain_id.clone(),
-                mint:     mint_user,
-            })
-            .await
-            .map_err(|err| {
-                tracing::error!("Failed to get user token program: {:?}", err);
-                RestError::BadParameters("User token program not found".to_string())
-            })?
-            .token_program;
-
         let router_token_account = match fee_token {
             entities::FeeToken::SearcherToken => get_associated_token_address_with_program_id(
                 &referral_fee_info.router,
@@ -402,6 +441,7 @@ impl Service {
                         .map(|lifetime| Duration::from_secs(lifetime as u64))
                         .unwrap_or(BID_MINIMUM_LIFE_TIME_SVM_OTHER),
                 ),
+                partial_fill_tolerance_ppm: quote_create.partial_fill_tolerance_ppm,
             });
 
         Ok(entities::OpportunityCreateSvm {
@@ -426,7 +466,7 @@ impl Service {
         })
     }
 
-    async fn remove_quote_opportunity(&self, opportunity: entities::OpportunitySvm) {
+    pub(super) async fn remove_quote_opportunity(&self, opportunity: entities::OpportunitySvm) {
         // TODO
         // Maybe we should add state for opportunity.
         // Right now logic for removing halted/expired bids, checks if opportunity exists.
@@ -446,25 +486,17 @@ impl Service {
         }
     }
 
-    #[tracing::instrument(
-        skip_all,
-        err(level = tracing::Level::TRACE),
-        fields(
-            opportunity_id,
-            auction_id,
-            searcher_token,
-            user_token,
-            bid_ids,
-            winner_bid
-        )
-    )]
-    pub async fn get_quote(&self, input: GetQuoteInput) -> Result<entities::Quote, RestError> {
+    /// Checks the parameters of a quote request that are independent of the quote tokens.
+    pub(super) fn validate_quote_create(
+        &self,
+        quote_create: &entities::QuoteCreate,
+    ) -> Result<entities::ReferralFeeInfo, RestError> {
         let referral_fee_info = self.unwrap_referral_fee_info(
-            input.quote_create.referral_fee_info.clone(),
-            &input.quote_create.chain_id,
+            quote_create.referral_fee_info.clone(),
+            &quote_create.chain_id,
         )?;
 
-        if Duration::from_secs(input.quote_create.minimum_lifetime.unwrap_or(0) as u64)
+        if Duration::from_secs(quote_create.minimum_lifetime.unwrap_or(0) as u64)
             > BID_MAXIMUM_LIFE_TIME_SVM
         {
             return Err(RestError::BadParameters(format!(
@@ -481,6 +513,244 @@ impl Service {
             )));
         }
 
+        let number_of_quotes = quote_create.number_of_quotes.unwrap_or(1);
+        if number_of_quotes == 0 || number_of_quotes > MAXIMUM_NUMBER_OF_QUOTES {
+            return Err(RestError::BadParameters(format!(
+                "Number of quotes must be between 1 and {}",
+                MAXIMUM_NUMBER_OF_QUOTES
+            )));
+        }
+        // The bids of the fallback quotes are held until one of the quotes is submitted back
+        if number_of_quotes > 1 && !quote_create.cancellable {
+            return Err(RestError::BadParameters(
+                "Multiple quotes are only supported for cancellable quotes".to_string(),
+            ));
+        }
+        if quote_create.partial_fill_tolerance_ppm.unwrap_or(0) >= FEE_SPLIT_PRECISION_PPM {
+            return Err(RestError::BadParameters(format!(
+                "Partial fill tolerance ppm must be lower than {}",
+                FEE_SPLIT_PRECISION_PPM
+            )));
+        }
+        Ok(referral_fee_info)
+    }
+
+    /// Builds the quote for the winner bid of a quote opportunity.
+    /// The transaction of the winner bid is only included if the user has enough balance to execute it.
+    pub(super) async fn get_quote_for_winner_bid(
+        &self,
+        quote_create: &entities::QuoteCreate,
+        referral_fee_ppm: u64,
+        opportunity: &entities::OpportunitySvm,
+        winner_bid: &auction::entities::Bid,
+        transaction_data: BidTransactionDataSwap,
+        reference_id: AuctionId,
+    ) -> Result<entities::Quote, RestError> {
+        let swap_data = transaction_data.data;
+        let searcher_token = opportunity.sell_tokens[0].clone();
+        let user_token = opportunity.buy_tokens[0].clone();
+        let deadline = swap_data.deadline;
+        let metadata = self
+            .get_express_relay_metadata(GetExpressRelayMetadataInput {
+                chain_id: quote_create.chain_id.clone(),
+            })
+            .await?;
+
+        let fee_token = match swap_data.fee_token {
+            FeeToken::Searcher => searcher_token.token,
+            FeeToken::User => user_token.token,
+        };
+        let compute_fees = |amount: u64| {
+            metadata
+                .compute_swap_fees_with_default_platform_fee(referral_fee_ppm, amount)
+                .map_err(|e| {
+                    tracing::error!("Failed to compute swap fees: {:?}", e);
+                    RestError::TemporarilyUnavailable
+                })
+        };
+        let (mut searcher_amount, user_amount, fees) = match swap_data.fee_token {
+            FeeToken::Searcher => {
+                let swap_fees = compute_fees(swap_data.amount_searcher)?;
+                (
+                    swap_fees.remaining_amount,
+                    swap_data.amount_user,
+                    swap_fees.fees,
+                )
+            }
+            FeeToken::User => (
+                swap_data.amount_searcher,
+                swap_data.amount_user,
+                compute_fees(swap_data.amount_user)?.fees,
+            ),
+        };
+
+        // The user receives the searcher token amount minus the transfer fee withheld by the mint
+        let token_mint_searcher = self
+            .get_token_mint(GetTokenMintInput {
+                chain_id: quote_create.chain_id.clone(),
+                mint:     searcher_token.token,
+            })
+            .await?;
+        searcher_amount -= token_mint_searcher
+            .extensions
+            .calculate_transfer_fee(searcher_amount);
+
+        // The user receives at least the minimum searcher amount if the swap is partially filled
+        let minimum_searcher_amount =
+            if transaction_data.minimum_amount_searcher < swap_data.amount_searcher {
+                let minimum_amount = match swap_data.fee_token {
+                    FeeToken::Searcher => {
+                        compute_fees(transaction_data.minimum_amount_searcher)?.remaining_amount
+                    }
+                    FeeToken::User => transaction_data.minimum_amount_searcher,
+                };
+                Some(
+                    minimum_amount
+                        - token_mint_searcher
+                            .extensions
+                            .calculate_transfer_fee(minimum_amount),
+                )
+            } else {
+                None
+            };
+
+        let opportunity_svm_program_swap = match &opportunity.program {
+            OpportunitySvmProgram::Swap(swap) => swap,
+            _ => return Err(RestError::TemporarilyUnavailable), // This should be unreachable
+        };
+
+        let user_mint_user_balance_after_token_account_initializations =
+            if user_token.token == native_mint::ID {
+                opportunity_svm_program_swap
+                    .user_mint_user_balance
+                    .saturating_sub(
+                        opportunity_svm_program_swap
+                            .get_user_sol_amount_paid_for_token_account_initializations(),
+                    )
+            } else {
+                opportunity_svm_program_swap.user_mint_user_balance
+            };
+        let (transaction, expiration_time) =
+            if user_mint_user_balance_after_token_account_initializations >= swap_data.amount_user {
+                (
+                    Some(winner_bid.chain_data.transaction.clone()),
+                    Some(deadline),
+                )
+            } else {
+                (None, None)
+            };
+
+
+        Ok(entities::Quote {
+            transaction,
+            expiration_time,
+
+            searcher_token: TokenAmountSvm {
+                token:  searcher_token.token,
+                amount: searcher_amount,
+            },
+            user_token: TokenAmountSvm {
+                token:  user_token.token,
+                amount: user_amount,
+            },
+            referrer_fee: TokenAmountSvm {
+                token:  fee_token,
+                amount: fees.router_fee,
+            },
+            platform_fee: TokenAmountSvm {
+                token:  fee_token,
+                amount: fees.express_relay_fee + fees.relayer_fee,
+            },
+            chain_id: quote_create.chain_id.clone(),
+            reference_id,
+            fallback_quotes: vec![],
+            minimum_searcher_amount,
+        })
+    }
+
+    /// Returns the pending bids for the opportunity of a quote.
+    pub(super) async fn get_pending_quote_bids(
+        auction_service: &AuctionService,
+        opportunity: &entities::OpportunitySvm,
+    ) -> Vec<auction::entities::Bid> {
+        auction_service
+            .get_pending_bids(GetLiveBidsInput {
+                permission_key: opportunity.permission_key.clone(),
+            })
+            .await
+    }
+
+    /// Holds the bid in its own auction until it is submitted back by the user, and builds its quote.
+    /// Returns None if the bid is already concluded by another auction.
+    pub(super) async fn get_quote_for_held_bid(
+        &self,
+        quote_create: &entities::QuoteCreate,
+        referral_fee_ppm: u64,
+        opportunity: &entities::OpportunitySvm,
+        bid: auction::entities::Bid,
+        bid_collection_time: OffsetDateTime,
+    ) -> Result<Option<entities::Quote>, RestError> {
+        let config = self.get_config(&quote_create.chain_id)?;
+        let auction_service = config.auction_service_container.get_service();
+
+        let transaction_data = auction_service
+            .get_bid_transaction_data_swap(bid.chain_data.transaction.clone())
+            .await
+            .map_err(|e| {
+                tracing::error!(error = ?e, "Failed to extract swap transaction data");
+                RestError::TemporarilyUnavailable
+            })?;
+
+        let signature = bid.chain_data.transaction.signatures[0];
+        let mut auction = Auction::try_new(vec![bid.clone()], bid_collection_time)
+            .expect("Failed to create auction for bids");
+        // Add tx_hash to auction to make sure conclude_auction works correctly
+        auction.tx_hash = Some(signature);
+        let auction = auction_service
+            .add_auction(AddAuctionInput { auction })
+            .await?;
+        if !auction_service
+            .update_bid_status(UpdateBidStatusInput {
+                new_status: BidStatusSvm::AwaitingSignature {
+                    auction: BidStatusAuction {
+                        tx_hash: signature,
+                        id:      auction.id,
+                    },
+                },
+                bid:        bid.clone(),
+            })
+            .await?
+        {
+            return Ok(None);
+        }
+
+        self.get_quote_for_winner_bid(
+            quote_create,
+            referral_fee_ppm,
+            opportunity,
+            &bid,
+            transaction_data,
+            auction.id,
+        )
+        .await
+        .map(Some)
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        err(level = tracing::Level::TRACE),
+        fields(
+            opportunity_id,
+            auction_id,
+            searcher_token,
+            user_token,
+            bid_ids,
+            winner_bid
+        )
+    )]
+    pub async fn get_quote(&self, input: GetQuoteInput) -> Result<entities::Quote, RestError> {
+        let referral_fee_info = self.validate_quote_create(&input.quote_create)?;
+
         let config = self.get_config(&input.quote_create.chain_id)?;
         let auction_service = config.auction_service_container.get_service();
 
@@ -503,20 +773,8 @@ impl Service {
         // Wait to make sure searchers had enough time to submit bids
         sleep(config.auction_time).await;
 
-        // NOTE: This part will be removed after refactoring the permission key type
-        let slice: [u8; 65] = opportunity
-            .permission_key
-            .to_vec()
-            .try_into()
-            .expect("Failed to convert permission key to slice");
-        let permission_key_svm = PermissionKeySvm(slice);
-
         let bid_collection_time = OffsetDateTime::now_utc();
-        let mut bids = auction_service
-            .get_pending_bids(GetLiveBidsInput {
-                permission_key: permission_key_svm.clone(),
-            })
-            .await;
+        let mut bids = Self::get_pending_quote_bids(&auction_service, &opportunity).await;
         tracing::Span::current().record(
             "bid_ids",
             tracing::field::display(crate::auction::entities::BidContainerTracing(&bids)),
@@ -544,16 +802,15 @@ impl Service {
         }
 
         // Find winner bid:
-        match input.quote_create.tokens {
-            entities::QuoteTokens::UserTokenSpecified { .. } => {
-                // highest bid = best (most searcher token returned)
-                bids.sort_by(|a, b| b.amount.cmp(&a.amount));
-            }
-            entities::QuoteTokens::SearcherTokenSpecified { .. } => {
-                // lowest bid = best (least user token consumed)
-                bids.sort_by(|a, b| a.amount.cmp(&b.amount));
-            }
-        }
+        let deprioritized_profiles = auction_service.get_deprioritized_profiles(&bids).await;
+        sort_quote_bids(
+            &input.quote_create.tokens,
+            &mut bids,
+            &deprioritized_profiles,
+        );
+        // The next best bids are held for the fallback quotes instead of losing the auction
+        let number_of_quotes = input.quote_create.number_of_quotes.unwrap_or(1) as usize;
+        let fallback_bids: Vec<_> = bids.drain(1..number_of_quotes.min(bids.len())).collect();
         let winner_bid = bids.first().expect("failed to get first bid");
         tracing::Span::current().record("winner_bid_id", winner_bid.id.to_string());
 
@@ -564,8 +821,6 @@ impl Service {
                 tracing::error!(error = ?e, "Failed to extract swap transaction data");
                 RestError::TemporarilyUnavailable
             })?;
-        let swap_data = transaction_data.data;
-        let deadline = swap_data.deadline;
 
         // Bids are not empty
         let mut auction = Auction::try_new(bids.clone(), bid_collection_time)
@@ -641,107 +896,89 @@ impl Service {
             return Err(RestError::DuplicateOpportunity);
         }
 
-        let metadata = self
-            .get_express_relay_metadata(GetExpressRelayMetadataInput {
-                chain_id: input.quote_create.chain_id.clone(),
-            })
+        let mut quote = self
+            .get_quote_for_winner_bid(
+                &input.quote_create,
+                referral_fee_info.referral_fee_ppm,
+                &opportunity,
+                winner_bid,
+                transaction_data,
+                auction.id,
+            )
             .await?;
+        if quote.transaction.is_some() && !input.quote_create.cancellable {
+            quote.transaction = Some(
+                auction_service
+                    .sign_bid_and_submit_auction(winner_bid.clone(), auction.clone())
+                    .await?,
+            );
+        }
 
-        let fee_token = match swap_data.fee_token {
-            FeeToken::Searcher => searcher_token.token,
-            FeeToken::User => user_token.token,
-        };
-        let compute_fees = |amount: u64| {
-            metadata
-                .compute_swap_fees_with_default_platform_fee(
+        for bid in fallback_bids {
+            match self
+                .get_quote_for_held_bid(
+                    &input.quote_create,
                     referral_fee_info.referral_fee_ppm,
-                    amount,
+                    &opportunity,
+                    bid.clone(),
+                    bid_collection_time,
                 )
-                .map_err(|e| {
-                    tracing::error!("Failed to compute swap fees: {:?}", e);
-                    RestError::TemporarilyUnavailable
+                .await
+            {
+                Ok(Some(fallback_quote)) => quote.fallback_quotes.push(fallback_quote),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!(error = ?e, bid_id = ?bid.id, "Failed to get fallback quote");
+                }
+            }
+        }
+        if !quote.fallback_quotes.is_empty() {
+            auction_service
+                .add_quote_fallback_auctions(AddQuoteFallbackAuctionsInput {
+                    primary_auction_id:   quote.reference_id,
+                    fallback_auction_ids: quote
+                        .fallback_quotes
+                        .iter()
+                        .map(|fallback_quote| fallback_quote.reference_id)
+                        .collect(),
                 })
-        };
-        let (searcher_amount, user_amount, fees) = match swap_data.fee_token {
-            FeeToken::Searcher => {
-                let swap_fees = compute_fees(swap_data.amount_searcher)?;
+                .await;
+        }
+        Ok(quote)
+    }
+}
+
+/// Sorts the bids of a quote opportunity from the best to the worst for the user.
+/// Equal bids are tie-broken by searcher reputation, priority fee and then arrival time.
+pub fn sort_quote_bids(
+    tokens: &entities::QuoteTokens,
+    bids: &mut [auction::entities::Bid],
+    deprioritized_profiles: &HashSet<ProfileId>,
+) {
+    match tokens {
+        entities::QuoteTokens::UserTokenSpecified { .. } => {
+            // highest bid = best (most searcher token returned)
+            bids.sort_by_cached_key(|bid| {
                 (
-                    swap_fees.remaining_amount,
-                    swap_data.amount_user,
-                    swap_fees.fees,
+                    Reverse(bid.amount),
+                    get_bid_tie_break_key(bid, deprioritized_profiles),
                 )
-            }
-            FeeToken::User => (
-                swap_data.amount_searcher,
-                swap_data.amount_user,
-                compute_fees(swap_data.amount_user)?.fees,
-            ),
-        };
-
-        let opportunity_svm_program_swap = match &opportunity.program {
-            OpportunitySvmProgram::Swap(swap) => swap,
-            _ => return Err(RestError::TemporarilyUnavailable), // This should be unreachable
-        };
-
-        let user_mint_user_balance_after_token_account_initializations =
-            if user_token.token == native_mint::ID {
-                opportunity_svm_program_swap
-                    .user_mint_user_balance
-                    .saturating_sub(
-                        opportunity_svm_program_swap
-                            .get_user_sol_amount_paid_for_token_account_initializations(),
-                    )
-            } else {
-                opportunity_svm_program_swap.user_mint_user_balance
-            };
-        let (transaction, expiration_time) =
-            if user_mint_user_balance_after_token_account_initializations >= swap_data.amount_user {
+            });
+        }
+        entities::QuoteTokens::SearcherTokenSpecified { .. } => {
+            // lowest bid = best (least user token consumed)
+            bids.sort_by_cached_key(|bid| {
                 (
-                    Some(winner_bid.chain_data.transaction.clone()),
-                    Some(deadline),
+                    bid.amount,
+                    get_bid_tie_break_key(bid, deprioritized_profiles),
                 )
-            } else {
-                (None, None)
-            };
-
-        let transaction = match (&transaction, input.quote_create.cancellable) {
-            (Some(_), false) => Some(
-                auction_service
-                    .sign_bid_and_submit_auction(winner_bid.clone(), auction.clone())
-                    .await?,
-            ),
-            _ => transaction,
-        };
-
-
-        Ok(entities::Quote {
-            transaction,
-            expiration_time,
-
-            searcher_token: TokenAmountSvm {
-                token:  searcher_token.token,
-                amount: searcher_amount,
-            },
-            user_token: TokenAmountSvm {
-                token:  user_token.token,
-                amount: user_amount,
-            },
-            referrer_fee: TokenAmountSvm {
-                token:  fee_token,
-                amount: fees.router_fee,
-            },
-            platform_fee: TokenAmountSvm {
-                token:  fee_token,
-                amount: fees.express_relay_fee + fees.relayer_fee,
-            },
-            chain_id: input.quote_create.chain_id,
-            reference_id: auction.id,
-        })
+            });
+        }
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     #![allow(clippy::needless_update)]
 
     use {
@@ -762,6 +999,7 @@ mod tests {
             },
             config::TokenWhitelistConfig,
             kernel::{
+                entities::PermissionKeySvm,
                 rpc_client_svm_tester::{
                     RpcClientSvmTester,
                     TokenAccountWithLamports,
@@ -791,6 +1029,7 @@ mod tests {
             AccountState,
         },
         std::{
+            collections::HashSet,
             sync::Arc,
             time::Instant,
         },
@@ -801,13 +1040,13 @@ mod tests {
     const DEFAULT_AUCTION_ID: Uuid = Uuid::from_u128(4242);
 
     #[derive(Clone, Default)]
-    struct BidParams {
-        id:        Option<Uuid>,
-        amount:    Option<u64>,
-        signature: Option<Vec<Signature>>,
+    pub struct BidParams {
+        pub id:        Option<Uuid>,
+        pub amount:    Option<u64>,
+        pub signature: Option<Vec<Signature>>,
     }
 
-    fn make_test_bid(params: BidParams) -> auction::entities::Bid {
+    pub fn make_test_bid(params: BidParams) -> auction::entities::Bid {
         let BidParams {
             id,
             signature,
@@ -833,15 +1072,16 @@ mod tests {
                 bid_payment_instruction_type: BidPaymentInstructionType::SubmitBid,
                 router:                       Default::default(),
                 permission_account:           Default::default(),
+                last_valid_block_height:      None,
             },
         }
     }
 
     #[derive(Clone, Default)]
-    struct AuctionServiceSequenceParams {
-        bids:            Option<Vec<BidParams>>,
-        swap_args:       Option<SwapV2Args>,
-        skip_bid_update: bool,
+    pub struct AuctionServiceSequenceParams {
+        pub bids:            Option<Vec<BidParams>>,
+        pub swap_args:       Option<SwapV2Args>,
+        pub skip_bid_update: bool,
     }
 
     fn setup_mock_auction_service(
@@ -861,6 +1101,10 @@ mod tests {
                 express_relay::id()
             });
 
+        auction_service
+            .expect_get_deprioritized_profiles()
+            .returning(|_| HashSet::new());
+
         let bids_pending = bids.clone();
         auction_service
             .expect_get_pending_bids()
@@ -896,6 +1140,7 @@ mod tests {
             .return_once(move |_| {
                 Ok(BidTransactionDataSwap {
                     data:                            swap_args,
+                    minimum_amount_searcher:         swap_args.amount_searcher,
                     express_relay_instruction_index: 0,
                     accounts:                        swap_accounts,
                 })
@@ -911,6 +1156,7 @@ mod tests {
                 bid_collection_time: OffsetDateTime::from_unix_timestamp(1200).unwrap(),
                 submission_time:     None,
                 tx_hash:             None,
+                pricing:             None,
                 bids:                bids
                     .clone()
                     .unwrap_or(vec![BidParams::default()])
@@ -939,25 +1185,25 @@ mod tests {
     }
 
     #[derive(Clone, Default)]
-    struct QuoteSequenceParams {
-        auction_service_sequence: AuctionServiceSequenceParams,
-        metadata:                 Option<ExpressRelayMetadata>,
-        enable_token_whitelist:   bool,
+    pub struct QuoteSequenceParams {
+        pub auction_service_sequence: AuctionServiceSequenceParams,
+        pub metadata:                 Option<ExpressRelayMetadata>,
+        pub enable_token_whitelist:   bool,
     }
 
-    struct QuoteSequence {
-        service:         Service,
-        auction_service: StatefulMockAuctionService,
-        rpc_client:      RpcClientSvmTester,
+    pub struct QuoteSequence {
+        pub service:         Service,
+        pub auction_service: StatefulMockAuctionService,
+        pub rpc_client:      RpcClientSvmTester,
 
-        token_program_user:     Pubkey,
-        token_program_searcher: Pubkey,
+        pub token_program_user:     Pubkey,
+        pub token_program_searcher: Pubkey,
 
-        allowed_token_mint_1: Pubkey,
-        allowed_token_mint_2: Pubkey,
+        pub allowed_token_mint_1: Pubkey,
+        pub allowed_token_mint_2: Pubkey,
     }
 
-    async fn setup_basic_sequence(params: QuoteSequenceParams) -> QuoteSequence {
+    pub async fn setup_basic_sequence(params: QuoteSequenceParams) -> QuoteSequence {
         let QuoteSequenceParams {
             auction_service_sequence: auction_service_params,
             metadata,
@@ -1006,7 +1252,7 @@ mod tests {
         }
     }
 
-    fn inject_auction_service(
+    pub fn inject_auction_service(
         service: &Service,
         auction_service_in_call: StatefulMockAuctionService,
     ) {
@@ -1093,6 +1339,7 @@ mod tests {
                     mint:          searcher_token,
                     decimals:      6,
                     token_program: token_program_searcher,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1104,6 +1351,7 @@ mod tests {
                     mint:          user_token,
                     decimals:      6,
                     token_program: token_program_user,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1111,20 +1359,22 @@ mod tests {
         let quote = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: None,
-                    tokens:              QuoteTokens::UserTokenSpecified {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
                         user_token: TokenAmountSvm {
                             token:  user_token,
                             amount: 2,
                         },
                         searcher_token,
                     },
-                    referral_fee_info:   None,
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await
@@ -1174,23 +1424,25 @@ mod tests {
         let result = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: None,
-                    tokens:              QuoteTokens::UserTokenSpecified {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
                         user_token:     TokenAmountSvm {
                             token:  Pubkey::new_unique(),
                             amount: 2,
                         },
                         searcher_token: Pubkey::new_unique(),
                     },
-                    referral_fee_info:   Some(ReferralFeeInfo {
+                    referral_fee_info:          Some(ReferralFeeInfo {
                         router:           Pubkey::new_unique(),
                         referral_fee_ppm: 1_000_001,
                     }),
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await;
@@ -1203,6 +1455,87 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_quote_multiple_quotes_not_cancellable() {
+        let QuoteSequence {
+            service,
+            auction_service,
+            ..
+        } = setup_basic_sequence(QuoteSequenceParams::default()).await;
+        inject_auction_service(&service, auction_service);
+
+        let result = service
+            .get_quote(GetQuoteInput {
+                quote_create: QuoteCreate {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
+                        user_token:     TokenAmountSvm {
+                            token:  Pubkey::new_unique(),
+                            amount: 2,
+                        },
+                        searcher_token: Pubkey::new_unique(),
+                    },
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                false,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           Some(3),
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
+                },
+            })
+            .await;
+
+        assert_eq!(
+            result,
+            Err(RestError::BadParameters(
+                "Multiple quotes are only supported for cancellable quotes".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_quote_invalid_partial_fill_tolerance() {
+        let QuoteSequence {
+            service,
+            auction_service,
+            ..
+        } = setup_basic_sequence(QuoteSequenceParams::default()).await;
+        inject_auction_service(&service, auction_service);
+
+        let result = service
+            .get_quote(GetQuoteInput {
+                quote_create: QuoteCreate {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
+                        user_token:     TokenAmountSvm {
+                            token:  Pubkey::new_unique(),
+                            amount: 2,
+                        },
+                        searcher_token: Pubkey::new_unique(),
+                    },
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: Some(FEE_SPLIT_PRECISION_PPM),
+                    profile_id:                 None,
+                },
+            })
+            .await;
+
+        assert_eq!(
+            result,
+            Err(RestError::BadParameters(format!(
+                "Partial fill tolerance ppm must be lower than {}",
+                FEE_SPLIT_PRECISION_PPM
+            )))
+        );
+    }
+
     #[tokio::test]
     async fn test_get_quote_indicative_no_bids() {
         let QuoteSequence {
@@ -1231,6 +1564,7 @@ mod tests {
                     mint:          searcher_token,
                     decimals:      6,
                     token_program: token_program_searcher,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1242,6 +1576,7 @@ mod tests {
                     mint:          user_token,
                     decimals:      6,
                     token_program: token_program_user,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1249,20 +1584,22 @@ mod tests {
         let result = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: None,
-                    tokens:              QuoteTokens::UserTokenSpecified {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
                         user_token: TokenAmountSvm {
                             token:  user_token,
                             amount: 2,
                         },
                         searcher_token,
                     },
-                    referral_fee_info:   None,
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await;
@@ -1347,6 +1684,7 @@ mod tests {
                     mint:          searcher_token,
                     decimals:      6,
                     token_program: token_program_searcher,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1358,6 +1696,7 @@ mod tests {
                     mint:          user_token,
                     decimals:      6,
                     token_program: token_program_user,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1365,20 +1704,22 @@ mod tests {
         service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: None,
-                    tokens:              QuoteTokens::SearcherTokenSpecified {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::SearcherTokenSpecified {
                         searcher_token: TokenAmountSvm {
                             token:  searcher_token,
                             amount: 2,
                         },
                         user_token,
                     },
-                    referral_fee_info:   None,
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await
@@ -1429,6 +1770,7 @@ mod tests {
                     mint:          searcher_token,
                     decimals:      6,
                     token_program: token_program_searcher,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1440,6 +1782,7 @@ mod tests {
                     mint:          user_token,
                     decimals:      6,
                     token_program: token_program_user,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1447,20 +1790,22 @@ mod tests {
         let quote = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: None,
-                    tokens:              QuoteTokens::UserTokenSpecified {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
                         user_token: TokenAmountSvm {
                             token:  user_token,
                             amount: 2000,
                         },
                         searcher_token,
                     },
-                    referral_fee_info:   None,
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await
@@ -1518,6 +1863,7 @@ mod tests {
                     mint:          searcher_token,
                     decimals:      6,
                     token_program: token_program_searcher,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1529,6 +1875,7 @@ mod tests {
                     mint:          user_token,
                     decimals:      6,
                     token_program: token_program_user,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1552,20 +1899,22 @@ mod tests {
         let quote = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: Some(user_wallet),
-                    tokens:              QuoteTokens::SearcherTokenSpecified {
+                    user_wallet_address:        Some(user_wallet),
+                    tokens:                     QuoteTokens::SearcherTokenSpecified {
                         searcher_token: TokenAmountSvm {
                             token:  searcher_token,
                             amount: 2000,
                         },
                         user_token,
                     },
-                    referral_fee_info:   None,
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await
@@ -1617,6 +1966,7 @@ mod tests {
                     mint:          searcher_token,
                     decimals:      6,
                     token_program: token_program_searcher,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1628,6 +1978,7 @@ mod tests {
                     mint:          user_token,
                     decimals:      6,
                     token_program: token_program_user,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1651,20 +2002,22 @@ mod tests {
         let quote = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: Some(user_wallet),
-                    tokens:              QuoteTokens::SearcherTokenSpecified {
+                    user_wallet_address:        Some(user_wallet),
+                    tokens:                     QuoteTokens::SearcherTokenSpecified {
                         searcher_token: TokenAmountSvm {
                             token:  searcher_token,
                             amount: 2000,
                         },
                         user_token,
                     },
-                    referral_fee_info:   None,
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await
@@ -1693,23 +2046,25 @@ mod tests {
         let result = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: None,
-                    tokens:              QuoteTokens::UserTokenSpecified {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
                         user_token:     TokenAmountSvm {
                             token:  invalid_mint,
                             amount: 2,
                         },
                         searcher_token: allowed_token_mint_1,
                     },
-                    referral_fee_info:   Some(ReferralFeeInfo {
+                    referral_fee_info:          Some(ReferralFeeInfo {
                         router:           Pubkey::new_unique(),
                         referral_fee_ppm: 20_000,
                     }),
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await;
@@ -1725,23 +2080,25 @@ mod tests {
         let result = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: None,
-                    tokens:              QuoteTokens::UserTokenSpecified {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
                         user_token:     TokenAmountSvm {
                             token:  allowed_token_mint_1, // invalid token
                             amount: 2,
                         },
                         searcher_token: invalid_mint,
                     },
-                    referral_fee_info:   Some(ReferralFeeInfo {
+                    referral_fee_info:          Some(ReferralFeeInfo {
                         router:           Pubkey::new_unique(),
                         referral_fee_ppm: 20_000,
                     }),
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await;
@@ -1783,6 +2140,7 @@ mod tests {
                     mint:          searcher_token,
                     decimals:      6,
                     token_program: token_program_searcher,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1794,6 +2152,7 @@ mod tests {
                     mint:          user_token,
                     decimals:      6,
                     token_program: token_program_user,
+                    extensions:    Default::default(),
                 },
             )
             .await;
@@ -1810,20 +2169,22 @@ mod tests {
         let _ = service
             .get_quote(GetQuoteInput {
                 quote_create: QuoteCreate {
-                    user_wallet_address: None,
-                    tokens:              QuoteTokens::UserTokenSpecified {
+                    user_wallet_address:        None,
+                    tokens:                     QuoteTokens::UserTokenSpecified {
                         user_token: TokenAmountSvm {
                             token:  user_token,
                             amount: 2,
                         },
                         searcher_token,
                     },
-                    referral_fee_info:   None,
-                    chain_id:            DEFAULT_CHAIN_ID.to_string(),
-                    memo:                None,
-                    cancellable:         true,
-                    minimum_lifetime:    None,
-                    profile_id:          None,
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
                 },
             })
             .await
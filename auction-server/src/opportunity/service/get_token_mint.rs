@@ -6,13 +6,24 @@ use {
         opportunity::entities,
     },
     solana_sdk::{
+        program_error::ProgramError,
         program_pack::Pack,
         pubkey::Pubkey,
     },
     spl_token::state::Mint,
     spl_token_2022::{
-        extension::StateWithExtensionsOwned,
-        state::Mint as Mint2022,
+        extension::{
+            default_account_state::DefaultAccountState,
+            transfer_fee::TransferFeeConfig,
+            transfer_hook::TransferHook,
+            BaseStateWithExtensions,
+            ExtensionType,
+            StateWithExtensionsOwned,
+        },
+        state::{
+            AccountState,
+            Mint as Mint2022,
+        },
     },
 };
 
@@ -21,8 +32,40 @@ pub struct GetTokenMintInput {
     pub mint:     Pubkey,
 }
 
+fn get_mint_extensions(
+    mint: &StateWithExtensionsOwned<Mint2022>,
+) -> Result<entities::TokenMintExtensions, ProgramError> {
+    let extension_types = mint.get_extension_types()?;
+    let transfer_fees = match mint.get_extension::<TransferFeeConfig>() {
+        Ok(config) => [&config.older_transfer_fee, &config.newer_transfer_fee]
+            .into_iter()
+            .map(|fee| entities::TransferFee {
+                epoch:       u64::from(fee.epoch),
+                maximum_fee: u64::from(fee.maximum_fee),
+                fee_bps:     u16::from(fee.transfer_fee_basis_points),
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+    let transfer_hook_program = match mint.get_extension::<TransferHook>() {
+        Ok(transfer_hook) => Option::<Pubkey>::from(transfer_hook.program_id),
+        Err(_) => None,
+    };
+    let default_account_state_frozen = match mint.get_extension::<DefaultAccountState>() {
+        Ok(default_account_state) => default_account_state.state == AccountState::Frozen as u8,
+        Err(_) => false,
+    };
+    Ok(entities::TokenMintExtensions {
+        transfer_fees,
+        transfer_hook_program,
+        non_transferable: extension_types.contains(&ExtensionType::NonTransferable),
+        default_account_state_frozen,
+        confidential_transfers: extension_types.contains(&ExtensionType::ConfidentialTransferMint),
+    })
+}
+
 impl Service {
-    /// Find the token mint data for a given mint.
+    /// Find the token mint data for a given mint, including the Token-2022 extensions relevant to swaps.
     /// Pulls from the cache if already present, otherwise queries the RPC and saves in the cache.
     pub async fn get_token_mint(
         &self,
@@ -48,34 +91,42 @@ impl Service {
                         ))
                     })?;
                 let owner = account.owner;
-                let decimals = if owner == spl_token::id() {
-                    Mint::unpack(&account.data)
-                        .map_err(|err| {
-                            tracing::error!(
-                                mint = ?input.mint,
-                                error = ?err,
-                                "Failed to unpack mint account",
-                            );
-                            RestError::TemporarilyUnavailable
-                        })?
-                        .decimals
+                let (decimals, extensions) = if owner == spl_token::id() {
+                    let mint = Mint::unpack(&account.data).map_err(|err| {
+                        tracing::error!(
+                            mint = ?input.mint,
+                            error = ?err,
+                            "Failed to unpack mint account",
+                        );
+                        RestError::TemporarilyUnavailable
+                    })?;
+                    (mint.decimals, entities::TokenMintExtensions::default())
                 } else {
-                    StateWithExtensionsOwned::<Mint2022>::unpack(account.data)
-                        .map_err(|err| {
+                    let mint = StateWithExtensionsOwned::<Mint2022>::unpack(account.data).map_err(
+                        |err| {
                             tracing::error!(
                                 mint = ?input.mint,
                                 error = ?err,
                                 "Failed to unpack mint account token 2022",
                             );
                             RestError::TemporarilyUnavailable
-                        })?
-                        .base
-                        .decimals
+                        },
+                    )?;
+                    let extensions = get_mint_extensions(&mint).map_err(|err| {
+                        tracing::error!(
+                            mint = ?input.mint,
+                            error = ?err,
+                            "Failed to parse mint account token 2022 extensions",
+                        );
+                        RestError::TemporarilyUnavailable
+                    })?;
+                    (mint.base.decimals, extensions)
                 };
                 let token_mint = entities::TokenMint {
                     mint: input.mint,
                     decimals,
                     token_program: owner,
+                    extensions,
                 };
                 self.repo
                     .cache_token_mint(input.mint, token_mint.clone())
@@ -100,3 +151,104 @@ impl Service {
         Ok(token_mint)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        spl_token_2022::extension::{
+            confidential_transfer::ConfidentialTransferMint,
+            non_transferable::NonTransferable,
+            StateWithExtensionsMut,
+        },
+    };
+
+    /// Packs a Token-2022 mint with the given extensions, initialized by `init_extensions`.
+    fn get_mint_account_data(
+        extension_types: &[ExtensionType],
+        init_extensions: impl FnOnce(&mut StateWithExtensionsMut<Mint2022>),
+    ) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<Mint2022>(extension_types).unwrap();
+        let mut data = vec![0; len];
+        let mut state =
+            StateWithExtensionsMut::<Mint2022>::unpack_uninitialized(&mut data).unwrap();
+        init_extensions(&mut state);
+        state.base = Mint2022 {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn test_get_mint_extensions_without_extensions() {
+        let data = get_mint_account_data(&[], |_| {});
+        let mint = StateWithExtensionsOwned::<Mint2022>::unpack(data).unwrap();
+
+        assert_eq!(
+            get_mint_extensions(&mint).unwrap(),
+            entities::TokenMintExtensions::default()
+        );
+    }
+
+    #[test]
+    fn test_get_mint_extensions() {
+        let transfer_hook_program = Pubkey::new_unique();
+        let data = get_mint_account_data(
+            &[
+                ExtensionType::TransferFeeConfig,
+                ExtensionType::TransferHook,
+                ExtensionType::DefaultAccountState,
+                ExtensionType::NonTransferable,
+                ExtensionType::ConfidentialTransferMint,
+            ],
+            |state| {
+                let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+                config.older_transfer_fee.epoch = 1u64.into();
+                config.older_transfer_fee.maximum_fee = 100u64.into();
+                config.older_transfer_fee.transfer_fee_basis_points = 10u16.into();
+                config.newer_transfer_fee.epoch = 5u64.into();
+                config.newer_transfer_fee.maximum_fee = 200u64.into();
+                config.newer_transfer_fee.transfer_fee_basis_points = 20u16.into();
+                state
+                    .init_extension::<TransferHook>(true)
+                    .unwrap()
+                    .program_id = Some(transfer_hook_program).try_into().unwrap();
+                state
+                    .init_extension::<DefaultAccountState>(true)
+                    .unwrap()
+                    .state = AccountState::Frozen as u8;
+                state.init_extension::<NonTransferable>(true).unwrap();
+                state
+                    .init_extension::<ConfidentialTransferMint>(true)
+                    .unwrap();
+            },
+        );
+        let mint = StateWithExtensionsOwned::<Mint2022>::unpack(data).unwrap();
+
+        assert_eq!(
+            get_mint_extensions(&mint).unwrap(),
+            entities::TokenMintExtensions {
+                transfer_fees:                vec![
+                    entities::TransferFee {
+                        epoch:       1,
+                        maximum_fee: 100,
+                        fee_bps:     10,
+                    },
+                    entities::TransferFee {
+                        epoch:       5,
+                        maximum_fee: 200,
+                        fee_bps:     20,
+                    },
+                ],
+                transfer_hook_program:        Some(transfer_hook_program),
+                non_transferable:             true,
+                default_account_state_frozen: true,
+                confidential_transfers:       true,
+            }
+        );
+    }
+}
@@ -56,6 +56,7 @@ use {
 pub mod add_opportunity;
 pub mod get_config;
 pub mod get_express_relay_metadata;
+pub mod get_indicative_quote;
 pub mod get_live_opportunities;
 pub mod get_opportunities;
 pub mod get_quote;
@@ -63,6 +64,7 @@ pub mod get_token_mint;
 pub mod remove_invalid_or_expired_opportunities;
 pub mod remove_opportunities;
 pub mod remove_opportunity;
+pub mod subscribe_quote;
 
 mod add_opportunity_analytics;
 mod get_quote_request_account_balances;
@@ -533,6 +535,8 @@ mock! {
             input: get_opportunities::GetOpportunitiesInput,
         ) -> Result<Vec<OpportunitySvm>, crate::api::RestError>;
         pub async fn get_quote(&self, input: get_quote::GetQuoteInput) -> Result<crate::opportunity::entities::Quote, crate::api::RestError>;
+        pub async fn get_indicative_quote(&self, input: get_indicative_quote::GetIndicativeQuoteInput) -> Result<crate::opportunity::entities::IndicativeQuote, crate::api::RestError>;
+        pub async fn refresh_recent_swap_rates(&self);
         pub async fn get_express_relay_metadata(&self, input: get_express_relay_metadata::GetExpressRelayMetadataInput) -> Result<express_relay::state::ExpressRelayMetadata, crate::api::RestError>;
         pub async fn get_token_mint(
             &self,
@@ -0,0 +1,532 @@
+use {
+    super::{
+        get_quote::sort_quote_bids,
+        AuctionService,
+        Service,
+    },
+    crate::{
+        api::RestError,
+        auction::{
+            entities::{
+                AuctionId,
+                BidId,
+                BidStatusSvm,
+            },
+            service::{
+                get_auction_by_id::GetAuctionByIdInput,
+                get_bid::GetBidInput,
+                quote_fallback_auctions::ResolveReplacedQuoteInput,
+            },
+        },
+        opportunity::{
+            entities,
+            service::add_opportunity::AddOpportunityInput,
+        },
+        per_metrics::QUOTE_SUBSCRIPTION_UPDATES_TOTAL,
+    },
+    axum_prometheus::metrics,
+    std::time::Duration,
+    time::OffsetDateTime,
+    tokio::{
+        sync::mpsc,
+        time::{
+            interval_at,
+            sleep,
+            Instant,
+        },
+    },
+};
+
+/// Maximum duration of a quote subscription. The rolling auction is stopped after this duration
+/// and the client needs to subscribe again to receive new quotes.
+pub const QUOTE_SUBSCRIPTION_MAXIMUM_DURATION: Duration = Duration::from_secs(60);
+const QUOTE_SUBSCRIPTION_CHANNEL_SIZE: usize = 16;
+
+pub struct SubscribeQuoteInput {
+    pub quote_create: entities::QuoteCreate,
+}
+
+/// The quote of a rolling quote auction which is held for the user.
+struct HeldQuote {
+    auction_id: AuctionId,
+    bid_id:     BidId,
+}
+
+/// Whether a bid amount gives the user a better quote than the best amount quoted so far.
+fn is_improving_bid_amount(tokens: &entities::QuoteTokens, amount: u64, best: Option<u64>) -> bool {
+    match (tokens, best) {
+        (_, None) => true,
+        // higher bid = better (more searcher token returned)
+        (entities::QuoteTokens::UserTokenSpecified { .. }, Some(best)) => amount > best,
+        // lower bid = better (less user token consumed)
+        (entities::QuoteTokens::SearcherTokenSpecified { .. }, Some(best)) => amount < best,
+    }
+}
+
+impl Service {
+    /// Returns the latest status of the bid of a held quote.
+    async fn get_held_quote_status(
+        auction_service: &AuctionService,
+        held_quote: &HeldQuote,
+    ) -> Option<BidStatusSvm> {
+        let bid = auction_service
+            .get_auction_by_id(GetAuctionByIdInput {
+                auction_id: held_quote.auction_id,
+            })
+            .and_then(|auction| {
+                auction
+                    .bids
+                    .into_iter()
+                    .find(|bid| bid.id == held_quote.bid_id)
+            });
+        match bid {
+            Some(bid) => Some(bid.status),
+            // The auction is already concluded
+            None => auction_service
+                .get_bid(GetBidInput {
+                    bid_id: held_quote.bid_id,
+                })
+                .await
+                .ok()
+                .map(|bid| bid.status),
+        }
+    }
+
+    /// Starts a rolling auction for the quote request and returns the stream of quotes.
+    ///
+    /// Every auction round, the best pending bid is turned into a quote and pushed to the stream
+    /// if it improves on the last pushed quote. The replaced quote is resolved as lost.
+    /// The auction keeps running until the receiver is dropped, a quote is submitted
+    /// or [`QUOTE_SUBSCRIPTION_MAXIMUM_DURATION`] has passed.
+    /// Firm quotes are accepted by signing and submitting them back with their reference id.
+    #[tracing::instrument(skip_all, err(level = tracing::Level::TRACE), fields(opportunity_id))]
+    pub async fn subscribe_quote(
+        &self,
+        input: SubscribeQuoteInput,
+    ) -> Result<mpsc::Receiver<entities::Quote>, RestError> {
+        // Several quotes are sent to the user, so they can only be executed after being submitted back
+        if !input.quote_create.cancellable {
+            return Err(RestError::BadParameters(
+                "Quote subscriptions are only supported for cancellable quotes".to_string(),
+            ));
+        }
+        if input.quote_create.number_of_quotes.unwrap_or(1) > 1 {
+            return Err(RestError::BadParameters(
+                "Quote subscriptions only support a single quote per update".to_string(),
+            ));
+        }
+        let referral_fee_info = self.validate_quote_create(&input.quote_create)?;
+
+        tracing::info!(quote_create = ?input.quote_create, "Received request to subscribe to quotes");
+
+        let opportunity_create = self
+            .get_opportunity_create_for_quote(input.quote_create.clone())
+            .await?;
+        let opportunity = self
+            .add_opportunity(AddOpportunityInput {
+                opportunity: opportunity_create,
+            })
+            .await?;
+        tracing::Span::current().record("opportunity_id", opportunity.id.to_string());
+
+        let (sender, receiver) = mpsc::channel(QUOTE_SUBSCRIPTION_CHANNEL_SIZE);
+        self.task_tracker.spawn({
+            let service = self.clone();
+            async move {
+                if let Err(e) = service
+                    .run_rolling_quote_auction(
+                        &input.quote_create,
+                        referral_fee_info.referral_fee_ppm,
+                        &opportunity,
+                        sender,
+                    )
+                    .await
+                {
+                    tracing::error!(error = ?e, opportunity_id = ?opportunity.id, "Rolling quote auction failed");
+                }
+                // Remove opportunity to prevent further bids
+                // The handle auction loop will take care of the bids that were not quoted
+                service.remove_quote_opportunity(opportunity).await;
+            }
+        });
+        Ok(receiver)
+    }
+
+    async fn run_rolling_quote_auction(
+        &self,
+        quote_create: &entities::QuoteCreate,
+        referral_fee_ppm: u64,
+        opportunity: &entities::OpportunitySvm,
+        sender: mpsc::Sender<entities::Quote>,
+    ) -> Result<(), RestError> {
+        let config = self.get_config(&quote_create.chain_id)?;
+        let auction_service = config.auction_service_container.get_service();
+
+        let subscription_end = sleep(QUOTE_SUBSCRIPTION_MAXIMUM_DURATION);
+        tokio::pin!(subscription_end);
+        // Wait to make sure searchers had enough time to submit bids before the first round
+        let mut auction_rounds =
+            interval_at(Instant::now() + config.auction_time, config.auction_time);
+        let mut best_amount = None;
+        let mut held_quote: Option<HeldQuote> = None;
+        loop {
+            tokio::select! {
+                _ = sender.closed() => return Ok(()),
+                _ = &mut subscription_end => return Ok(()),
+                _ = auction_rounds.tick() => {}
+            }
+
+            if let Some(quote) = &held_quote {
+                match Self::get_held_quote_status(&auction_service, quote).await {
+                    Some(BidStatusSvm::AwaitingSignature { .. }) => {}
+                    // The user accepted the quote, no need for better quotes
+                    Some(
+                        BidStatusSvm::Submitted { .. }
+                        | BidStatusSvm::Won { .. }
+                        | BidStatusSvm::Failed { .. },
+                    ) => return Ok(()),
+                    // The quote is expired or cancelled, any bid can be quoted again
+                    _ => {
+                        best_amount = None;
+                        held_quote = None;
+                    }
+                }
+            }
+
+            let bid_collection_time = OffsetDateTime::now_utc();
+            let mut bids = Self::get_pending_quote_bids(&auction_service, opportunity).await;
+            bids.retain(|bid| {
+                is_improving_bid_amount(&quote_create.tokens, bid.amount, best_amount)
+            });
+            if bids.is_empty() {
+                continue;
+            }
+            let deprioritized_profiles = auction_service.get_deprioritized_profiles(&bids).await;
+            sort_quote_bids(&quote_create.tokens, &mut bids, &deprioritized_profiles);
+            let winner_bid = bids.first().expect("failed to get first bid").clone();
+
+            // Only the winner bid is held for the user, the other bids can still win the next rounds
+            let quote = match self
+                .get_quote_for_held_bid(
+                    quote_create,
+                    referral_fee_ppm,
+                    opportunity,
+                    winner_bid.clone(),
+                    bid_collection_time,
+                )
+                .await
+            {
+                Ok(Some(quote)) => quote,
+                // The bid is already concluded by another auction
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::error!(error = ?e, bid_id = ?winner_bid.id, "Failed to get quote for the winner bid");
+                    continue;
+                }
+            };
+            if let Some(replaced_quote) = held_quote.replace(HeldQuote {
+                auction_id: quote.reference_id,
+                bid_id:     winner_bid.id,
+            }) {
+                match auction_service
+                    .resolve_replaced_quote(ResolveReplacedQuoteInput {
+                        auction_id: replaced_quote.auction_id,
+                    })
+                    .await
+                {
+                    Ok(()) => {}
+                    // The replaced quote was submitted in the meantime, so the new quote is not needed anymore
+                    Err(RestError::QuoteIsFinalized) => {
+                        if let Err(e) = auction_service
+                            .resolve_replaced_quote(ResolveReplacedQuoteInput {
+                                auction_id: quote.reference_id,
+                            })
+                            .await
+                        {
+                            tracing::error!(error = ?e, auction_id = ?quote.reference_id, "Failed to resolve the unused quote");
+                        }
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        tracing::error!(error = ?e, auction_id = ?replaced_quote.auction_id, "Failed to resolve the replaced quote");
+                    }
+                }
+            }
+            best_amount = Some(winner_bid.amount);
+            metrics::counter!(
+                QUOTE_SUBSCRIPTION_UPDATES_TOTAL,
+                &[
+                    ("chain_id", quote_create.chain_id.to_string()),
+                    ("firm", quote.transaction.is_some().to_string()),
+                ]
+            )
+            .increment(1);
+            if sender.send(quote).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction::{
+                entities::{
+                    BidStatusAuction,
+                    BidTransactionDataSwap,
+                    SwapAccounts,
+                },
+                service::StatefulMockAuctionService,
+            },
+            kernel::test_utils::DEFAULT_CHAIN_ID,
+            opportunity::service::get_quote::tests::{
+                inject_auction_service,
+                make_test_bid,
+                setup_basic_sequence,
+                BidParams,
+                QuoteSequence,
+                QuoteSequenceParams,
+            },
+        },
+        express_relay::{
+            FeeToken,
+            SwapV2Args,
+        },
+        solana_sdk::pubkey::Pubkey,
+        std::{
+            collections::HashSet,
+            sync::{
+                atomic::{
+                    AtomicUsize,
+                    Ordering,
+                },
+                Arc,
+                Mutex,
+            },
+        },
+        uuid::Uuid,
+    };
+
+    #[test]
+    fn test_is_improving_bid_amount() {
+        let user_token_specified = entities::QuoteTokens::UserTokenSpecified {
+            user_token:     entities::TokenAmountSvm {
+                token:  Pubkey::new_unique(),
+                amount: 100,
+            },
+            searcher_token: Pubkey::new_unique(),
+        };
+        assert!(is_improving_bid_amount(&user_token_specified, 10, None));
+        assert!(is_improving_bid_amount(&user_token_specified, 11, Some(10)));
+        assert!(!is_improving_bid_amount(
+            &user_token_specified,
+            10,
+            Some(10)
+        ));
+        assert!(!is_improving_bid_amount(&user_token_specified, 9, Some(10)));
+
+        let searcher_token_specified = entities::QuoteTokens::SearcherTokenSpecified {
+            user_token:     Pubkey::new_unique(),
+            searcher_token: entities::TokenAmountSvm {
+                token:  Pubkey::new_unique(),
+                amount: 100,
+            },
+        };
+        assert!(is_improving_bid_amount(&searcher_token_specified, 10, None));
+        assert!(is_improving_bid_amount(
+            &searcher_token_specified,
+            9,
+            Some(10)
+        ));
+        assert!(!is_improving_bid_amount(
+            &searcher_token_specified,
+            10,
+            Some(10)
+        ));
+        assert!(!is_improving_bid_amount(
+            &searcher_token_specified,
+            11,
+            Some(10)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rolling_quote_auction() {
+        let QuoteSequence {
+            service,
+            token_program_user,
+            token_program_searcher,
+            allowed_token_mint_1: user_token,
+            allowed_token_mint_2: searcher_token,
+            ..
+        } = setup_basic_sequence(QuoteSequenceParams::default()).await;
+        for (mint, token_program) in [
+            (user_token, token_program_user),
+            (searcher_token, token_program_searcher),
+        ] {
+            service
+                .repo
+                .cache_token_mint(
+                    mint,
+                    entities::TokenMint {
+                        mint,
+                        decimals: 6,
+                        token_program,
+                        extensions: Default::default(),
+                    },
+                )
+                .await;
+        }
+
+        let first_bid_id = Uuid::from_u128(1);
+        let second_bid_id = Uuid::from_u128(2);
+        let mut auction_service = StatefulMockAuctionService::default();
+        auction_service
+            .expect_get_express_relay_program_id()
+            .returning(express_relay::id);
+        auction_service
+            .expect_get_deprioritized_profiles()
+            .returning(|_| HashSet::new());
+        // A better bid arrives in the second round
+        let rounds = AtomicUsize::new(0);
+        auction_service
+            .expect_get_pending_bids()
+            .returning(move |_| match rounds.fetch_add(1, Ordering::SeqCst) {
+                0 => vec![make_test_bid(BidParams {
+                    id: Some(first_bid_id),
+                    amount: Some(100),
+                    ..Default::default()
+                })],
+                1 => vec![make_test_bid(BidParams {
+                    id: Some(second_bid_id),
+                    amount: Some(110),
+                    ..Default::default()
+                })],
+                _ => vec![],
+            });
+        auction_service
+            .expect_get_bid_transaction_data_swap()
+            .returning(move |_| {
+                Ok(BidTransactionDataSwap {
+                    data:                            SwapV2Args {
+                        deadline:              10,
+                        amount_searcher:       100,
+                        amount_user:           1,
+                        referral_fee_ppm:      0,
+                        fee_token:             FeeToken::User,
+                        swap_platform_fee_ppm: 0,
+                    },
+                    minimum_amount_searcher:         100,
+                    express_relay_instruction_index: 0,
+                    accounts:                        SwapAccounts {
+                        searcher: Pubkey::new_unique(),
+                        user_wallet: Pubkey::new_unique(),
+                        mint_searcher: searcher_token,
+                        mint_user: user_token,
+                        router_token_account: Pubkey::new_unique(),
+                        token_program_searcher,
+                        token_program_user,
+                    },
+                })
+            });
+        let auctions = Arc::new(Mutex::new(vec![]));
+        auction_service.expect_add_auction().returning({
+            let auctions = auctions.clone();
+            move |input| {
+                auctions.lock().unwrap().push(input.auction.clone());
+                Ok(input.auction)
+            }
+        });
+        auction_service
+            .expect_update_bid_status()
+            .returning(|_| Ok(true));
+
+        // The first quote is still held for the user when the second quote replaces it
+        auction_service.expect_get_auction_by_id().returning({
+            let auctions = auctions.clone();
+            move |input| {
+                let mut auction = auctions
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|auction| {
+                        auction.id == input.auction_id && auction.bids[0].id == first_bid_id
+                    })
+                    .cloned()?;
+                auction.bids[0].status = BidStatusSvm::AwaitingSignature {
+                    auction: BidStatusAuction {
+                        id:      auction.id,
+                        tx_hash: auction.bids[0].chain_data.transaction.signatures[0],
+                    },
+                };
+                Some(auction)
+            }
+        });
+        auction_service
+            .expect_resolve_replaced_quote()
+            .times(1)
+            .withf({
+                let auctions = auctions.clone();
+                move |input| input.auction_id == auctions.lock().unwrap()[0].id
+            })
+            .returning(|_| Ok(()));
+        // The second quote is submitted and concluded
+        auction_service
+            .expect_get_bid()
+            .times(1)
+            .withf(move |input| input.bid_id == second_bid_id)
+            .returning(move |input| {
+                let mut bid = make_test_bid(BidParams {
+                    id: Some(input.bid_id),
+                    ..Default::default()
+                });
+                bid.status = BidStatusSvm::Won {
+                    auction: BidStatusAuction {
+                        id:      Uuid::new_v4(),
+                        tx_hash: bid.chain_data.transaction.signatures[0],
+                    },
+                };
+                Ok(bid)
+            });
+        inject_auction_service(&service, auction_service);
+
+        let mut receiver = service
+            .subscribe_quote(SubscribeQuoteInput {
+                quote_create: entities::QuoteCreate {
+                    user_wallet_address:        None,
+                    tokens:                     entities::QuoteTokens::UserTokenSpecified {
+                        user_token: entities::TokenAmountSvm {
+                            token:  user_token,
+                            amount: 1,
+                        },
+                        searcher_token,
+                    },
+                    referral_fee_info:          None,
+                    chain_id:                   DEFAULT_CHAIN_ID.to_string(),
+                    memo:                       None,
+                    cancellable:                true,
+                    minimum_lifetime:           None,
+                    number_of_quotes:           None,
+                    partial_fill_tolerance_ppm: None,
+                    profile_id:                 None,
+                },
+            })
+            .await
+            .expect("Failed to subscribe to quotes");
+
+        let first_quote = receiver.recv().await.expect("Missing first quote");
+        let second_quote = receiver.recv().await.expect("Missing second quote");
+        {
+            let auctions = auctions.lock().unwrap();
+            assert_eq!(first_quote.reference_id, auctions[0].id);
+            assert_eq!(second_quote.reference_id, auctions[1].id);
+        }
+        // The rolling auction stops once the second quote is submitted
+        assert!(receiver.recv().await.is_none());
+        service.task_tracker.close();
+        service.task_tracker.wait().await;
+    }
+}
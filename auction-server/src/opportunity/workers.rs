@@ -33,3 +33,21 @@ pub async fn run_verification_loop(service: Arc<Service>) -> anyhow::Result<()>
     tracing::info!("Shutting down opportunity verifier...");
     Ok(())
 }
+
+pub async fn run_recent_swap_rates_loop(service: Arc<Service>) -> anyhow::Result<()> {
+    tracing::info!("Starting recent swap rates refresher...");
+    let mut exit_check_interval = tokio::time::interval(EXIT_CHECK_INTERVAL);
+
+    let mut refresh_interval = tokio::time::interval(Duration::from_secs(30));
+    while !SHOULD_EXIT.load(Ordering::Acquire) {
+        tokio::select! {
+            _ = refresh_interval.tick() => {
+                service.refresh_recent_swap_rates().await;
+            }
+            _ = exit_check_interval.tick() => {
+            }
+        }
+    }
+    tracing::info!("Shutting down recent swap rates refresher...");
+    Ok(())
+}
@@ -45,6 +45,8 @@ pub const TRANSACTION_LANDING_TIME_SVM_METRIC: &str = "transaction_landing_time_
 pub const TRANSACTION_LANDING_TIME_SVM_BUCKETS: &[f64; 16] = &[
     0.1, 0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 2.0, 2.25, 2.5, 3.75, 5.0, 10.0, 20.0, 40.0,
 ];
+pub const TRANSACTION_LANDING_TIME_SVM_ENDPOINT_METRIC: &str =
+    "transaction_landing_time_seconds_svm_endpoint";
 
 pub const SUBMIT_QUOTE_DEADLINE_BUFFER_METRIC: &str = "submit_quote_deadline_buffer";
 pub const SUBMIT_QUOTE_DEADLINE_BUFFER_BUCKETS: &[f64; 20] = &[
@@ -55,6 +57,19 @@ pub const SUBMIT_QUOTE_DEADLINE_TOTAL: &str = "submit_quote_deadline_total";
 
 pub const QUOTE_VALIDATION_TOTAL: &str = "quote_validation_total";
 
+pub const BUNDLE_SUBMISSION_TOTAL: &str = "bundle_submission_total";
+
+pub const SIMULATOR_ACCOUNT_CACHE_TOTAL: &str = "simulator_account_cache_total";
+
+pub const LOOKUP_TABLE_RESOLVER_TOTAL: &str = "lookup_table_resolver_total";
+pub const LOOKUP_TABLE_RESOLVER_SIZE: &str = "lookup_table_resolver_size";
+
+pub const BID_RATE_LIMITED_TOTAL: &str = "bid_rate_limited_total";
+pub const SEARCHER_BANNED_TOTAL: &str = "searcher_banned_total";
+pub const RELAYER_SPEND_LAMPORTS_TOTAL: &str = "relayer_spend_lamports_total";
+pub const SECOND_PRICE_REFUND_LAMPORTS_TOTAL: &str = "second_price_refund_lamports_total";
+pub const QUOTE_SUBSCRIPTION_UPDATES_TOTAL: &str = "quote_subscription_updates_total";
+
 #[derive(Debug, Clone)]
 pub struct MetricsLayerData {
     category:   String,
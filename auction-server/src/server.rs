@@ -9,7 +9,14 @@ use {
         auction::service::{
             self as auction_service,
             create_analytics_db_inserter as auction_create_analytics_db_inserter,
+            replay::{
+                replay_auction,
+                AccountsSnapshotFile,
+            },
+            signature_status_poller::SignatureStatusPoller,
             simulator::Simulator,
+            subscribed_account_cache::SubscribedAccountCache,
+            transaction_sender::TransactionBroadcaster,
             SubmitBidInstructionAccountPositions,
             SwapInstructionAccountPositions,
         },
@@ -19,9 +26,12 @@ use {
             Config,
             ConfigMap,
             MigrateOptions,
+            ReplayOptions,
             RunOptions,
         },
         kernel::{
+            block_engine::BlockEngineClient,
+            lookup_table_resolver::LookupTableResolver,
             traced_sender_svm::TracedSenderSvm,
             workers::{
                 run_delete_pg_db_history,
@@ -32,7 +42,10 @@ use {
         opportunity::{
             service as opportunity_service,
             service::create_analytics_db_inserter as opportunity_create_analytics_db_inserter,
-            workers::run_verification_loop,
+            workers::{
+                run_recent_swap_rates_loop,
+                run_verification_loop,
+            },
         },
         per_metrics,
         state::{
@@ -206,6 +219,13 @@ pub fn setup_metrics_recorder() -> Result<PrometheusHandle> {
             per_metrics::TRANSACTION_LANDING_TIME_SVM_BUCKETS,
         )
         .unwrap()
+        .set_buckets_for_metric(
+            axum_prometheus::metrics_exporter_prometheus::Matcher::Full(
+                per_metrics::TRANSACTION_LANDING_TIME_SVM_ENDPOINT_METRIC.to_string(),
+            ),
+            per_metrics::TRANSACTION_LANDING_TIME_SVM_BUCKETS,
+        )
+        .unwrap()
         .set_buckets_for_metric(
             axum_prometheus::metrics_exporter_prometheus::Matcher::Full(
                 per_metrics::SUBMIT_QUOTE_DEADLINE_BUFFER_METRIC.to_string(),
@@ -364,6 +384,45 @@ pub async fn run_migrations(migrate_options: MigrateOptions) -> Result<()> {
     Ok(())
 }
 
+pub async fn run_replay(replay_options: ReplayOptions) -> Result<()> {
+    let config_map = ConfigMap::load(&replay_options.config.config).map_err(|err| {
+        anyhow!(
+            "Failed to load config from file({path}): {:?}",
+            err,
+            path = replay_options.config.config
+        )
+    })?;
+    let snapshot = AccountsSnapshotFile::load(&replay_options.snapshot).map_err(|err| {
+        anyhow!(
+            "Failed to load snapshot from file({path}): {:?}",
+            err,
+            path = replay_options.snapshot
+        )
+    })?;
+    let pool = create_pg_pool(&replay_options.database_url, 1, 1).await?;
+    let analytics_db = auction_create_analytics_db_inserter(get_analytics_client(
+        replay_options.clickhouse_config,
+    ));
+    let report = replay_auction(
+        &pool,
+        &analytics_db,
+        &config_map,
+        replay_options.auction_id,
+        snapshot,
+    )
+    .await?;
+    let report = serde_json::to_string_pretty(&report)?;
+    match replay_options.output {
+        Some(path) => {
+            std::fs::write(&path, report)
+                .map_err(|err| anyhow!("Failed to write report to file({path}): {:?}", err))?;
+            tracing::info!(path = path, "Replay report written");
+        }
+        None => tracing::info!(report = report, "Replay report"),
+    }
+    Ok(())
+}
+
 macro_rules! read_svm_position_env {
     ($name:expr) => {{
         // Access the environment variable at compile-time
@@ -393,6 +452,7 @@ pub fn get_submit_bid_instruction_account_positions() -> SubmitBidInstructionAcc
     SubmitBidInstructionAccountPositions {
         permission_account: read_svm_position_env!("SUBMIT_BID_PERMISSION_ACCOUNT_POSITION"),
         router_account:     read_svm_position_env!("SUBMIT_BID_ROUTER_ACCOUNT_POSITION"),
+        searcher_account:   read_svm_position_env!("SUBMIT_BID_SEARCHER_ACCOUNT_POSITION"),
     }
 }
 
@@ -480,6 +540,28 @@ pub async fn start_server(run_options: RunOptions) -> Result<()> {
             if tx_broadcaster_clients.is_empty() {
                 panic!("No tx broadcaster client provided for chain {}", chain_id);
             }
+            let bundle_submission = chain_store.config.bundle_submission.as_ref().map(|config| {
+                if config.tip_accounts.is_empty() {
+                    panic!("No bundle tip account provided for chain {}", chain_id);
+                }
+                auction_service::BundleSubmissionSvm {
+                    block_engine: BlockEngineClient::new(
+                        &config.block_engine_url,
+                        chain_store.config.rpc_timeout,
+                    ),
+                    tip_accounts: config.tip_accounts.clone(),
+                    tip_lamports: config.tip_lamports,
+                }
+            });
+            let lookup_table_resolver = Arc::new(LookupTableResolver::new(
+                chain_id.clone(),
+                TracedSenderSvm::new_client(
+                    chain_id.clone(),
+                    chain_store.config.rpc_read_url.as_str(),
+                    chain_store.config.rpc_timeout,
+                    RpcClientConfig::with_commitment(CommitmentConfig::processed()),
+                ),
+            ));
             (
                 chain_id.clone(),
                 auction_service::ServiceEnum::Svm(auction_service::Service::new(
@@ -495,12 +577,28 @@ pub async fn start_server(run_options: RunOptions) -> Result<()> {
                                 chain_store.config.rpc_timeout,
                                 RpcClientConfig::with_commitment(CommitmentConfig::processed()),
                             ),
-                            simulator: Simulator::new(TracedSenderSvm::new_client(
-                                chain_id.clone(),
-                                chain_store.config.rpc_read_url.as_str(),
-                                chain_store.config.rpc_timeout,
-                                RpcClientConfig::with_commitment(CommitmentConfig::processed()),
-                            )),
+                            simulator: Simulator::new(
+                                TracedSenderSvm::new_client(
+                                    chain_id.clone(),
+                                    chain_store.config.rpc_read_url.as_str(),
+                                    chain_store.config.rpc_timeout,
+                                    RpcClientConfig::with_commitment(CommitmentConfig::processed()),
+                                ),
+                                SubscribedAccountCache::new(
+                                    chain_id.clone(),
+                                    chain_store.config.express_relay_program_id,
+                                ),
+                                lookup_table_resolver.clone(),
+                            ),
+                            lookup_table_resolver,
+                            signature_status_poller: SignatureStatusPoller::new(
+                                TracedSenderSvm::new_client(
+                                    chain_id.clone(),
+                                    chain_store.config.rpc_read_url.as_str(),
+                                    chain_store.config.rpc_timeout,
+                                    RpcClientConfig::with_commitment(CommitmentConfig::processed()),
+                                ),
+                            ),
                             express_relay: auction_service::ExpressRelaySvm {
                                 program_id:                               chain_store
                                     .config
@@ -518,11 +616,29 @@ pub async fn start_server(run_options: RunOptions) -> Result<()> {
                                     get_swap_instruction_account_positions(),
                             },
                             ws_address: chain_store.config.ws_addr.clone(),
-                            tx_broadcaster_clients,
+                            tx_broadcaster: TransactionBroadcaster::new(
+                                chain_id.clone(),
+                                tx_broadcaster_clients,
+                                chain_store.config.tx_submission_strategy,
+                            ),
                             log_sender: chain_store.log_sender.clone(),
                             prioritization_fee_percentile: chain_store
                                 .config
                                 .prioritization_fee_percentile,
+                            winner_selection_mode: chain_store.config.winner_selection_mode,
+                            bundle_submission,
+                            auction_trigger_mode: chain_store.config.auction_trigger_mode,
+                            auction_pricing_rule: chain_store.config.auction_pricing_rule.clone(),
+                            swap_instruction_policy: chain_store
+                                .config
+                                .swap_instruction_policy
+                                .clone(),
+                            bid_rate_limiters:
+                                auction_service::check_bid_rate_limits::BidRateLimiters::new(
+                                    &chain_store.config.bid_rate_limits,
+                                ),
+                            searcher_reputation: chain_store.config.searcher_reputation.clone(),
+                            relayer_spend_limits: chain_store.config.relayer_spend_limits.clone(),
                             // _dummy_log_receiver: chain_store._dummy_log_receiver.clone(),
                         },
                     },
@@ -543,6 +659,34 @@ pub async fn start_server(run_options: RunOptions) -> Result<()> {
                 config
                     .auction_service_container
                     .inject_service(service.clone());
+                if let Err(e) = service.restore_pending_transactions().await {
+                    tracing::error!(
+                        error = ?e,
+                        chain_id = chain_id,
+                        "Failed to restore pending transactions"
+                    );
+                }
+                if let Err(e) = service.load_searcher_reputations().await {
+                    tracing::error!(
+                        error = ?e,
+                        chain_id = chain_id,
+                        "Failed to load searcher reputations"
+                    );
+                }
+                if let Err(e) = service.load_relayer_spends().await {
+                    tracing::error!(
+                        error = ?e,
+                        chain_id = chain_id,
+                        "Failed to load relayer spends"
+                    );
+                }
+                if let Err(e) = service.load_quote_fallback_auctions().await {
+                    tracing::error!(
+                        error = ?e,
+                        chain_id = chain_id,
+                        "Failed to load quote fallback auctions"
+                    );
+                }
             }
         }
     }
@@ -604,6 +748,36 @@ pub async fn start_server(run_options: RunOptions) -> Result<()> {
             });
             join_all(auction_conclusion_loops).await;
         },
+        async {
+            let signature_status_poller_loops =
+                auction_services.iter().map(|(chain_id, service)| {
+                    let auction_service::ServiceEnum::Svm(service) = service;
+                    fault_tolerant_handler(
+                        format!(
+                            "signature status poller loop for chain {}",
+                            chain_id.clone()
+                        ),
+                        || {
+                            let service = service.clone();
+                            async move { service.run_signature_status_poller_loop().await }
+                        },
+                    )
+                });
+            join_all(signature_status_poller_loops).await;
+        },
+        async {
+            let account_subscription_loops = auction_services.iter().map(|(chain_id, service)| {
+                let auction_service::ServiceEnum::Svm(service) = service;
+                fault_tolerant_handler(
+                    format!("account subscription loop for chain {}", chain_id.clone()),
+                    || {
+                        let service = service.clone();
+                        async move { service.run_account_subscription_loop().await }
+                    },
+                )
+            });
+            join_all(account_subscription_loops).await;
+        },
         async {
             let metric_loops = auction_services.iter().map(|(chain_id, service)| {
                 let auction_service::ServiceEnum::Svm(service) = service;
@@ -633,6 +807,9 @@ pub async fn start_server(run_options: RunOptions) -> Result<()> {
         fault_tolerant_handler("svm verification loop".to_string(), || {
             run_verification_loop(store_new.opportunity_service_svm.clone())
         }),
+        fault_tolerant_handler("recent swap rates loop".to_string(), || {
+            run_recent_swap_rates_loop(store_new.opportunity_service_svm.clone())
+        }),
         metric_collector("opportunity store".to_string(), || {
             let service = store_new.opportunity_service_svm.clone();
             async move { service.update_metrics().await }
@@ -22,4 +22,8 @@ pub enum ErrorCode {
     InvalidReferralFee,
     #[msg("Insufficient user funds")]
     InsufficientUserFunds,
+    #[msg("Minimum searcher amount larger than searcher amount")]
+    InvalidMinimumAmountSearcher,
+    #[msg("Searcher amount below the minimum amount")]
+    PartialFillBelowMinimum,
 }
@@ -266,6 +266,14 @@ pub mod express_relay {
     pub fn swap_v2(ctx: Context<Swap>, data: SwapV2Args) -> Result<()> {
         swap_internal(ctx, data)
     }
+
+    /// Swaps with partial fills at the fixed price of `amount_searcher` to `amount_user`.
+    /// If the searcher can not provide `amount_searcher`, the swap is partially filled
+    /// with the searcher balance as long as it is at least `minimum_amount_searcher`.
+    pub fn swap_v3(ctx: Context<Swap>, data: SwapV3Args) -> Result<()> {
+        let data = ctx.accounts.compute_partial_fill(&data)?;
+        swap_internal(ctx, data)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Eq, PartialEq, Clone, Copy, Debug)]
@@ -522,6 +530,37 @@ pub struct SwapV2Args {
     pub swap_platform_fee_ppm: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Eq, PartialEq, Clone, Copy, Debug)]
+pub struct SwapV3Args {
+    /// deadline as a unix timestamp in seconds
+    pub deadline:                i64,
+    pub amount_searcher:         u64,
+    /// Minimum amount the searcher should provide, the user amount is scaled down proportionally when
+    /// the swap is partially filled
+    pub minimum_amount_searcher: u64,
+    pub amount_user:             u64,
+    /// The referral fee is specified in parts per million
+    pub referral_fee_ppm:        u64,
+    /// Token in which the fees will be paid
+    pub fee_token:               FeeToken,
+    /// The platform fee is specified in parts per million
+    pub swap_platform_fee_ppm:   u64,
+}
+
+impl SwapV3Args {
+    /// Returns the swap arguments for the case where the swap is completely filled
+    pub fn convert_to_v2(&self) -> SwapV2Args {
+        SwapV2Args {
+            deadline:              self.deadline,
+            amount_searcher:       self.amount_searcher,
+            amount_user:           self.amount_user,
+            referral_fee_ppm:      self.referral_fee_ppm,
+            fee_token:             self.fee_token,
+            swap_platform_fee_ppm: self.swap_platform_fee_ppm,
+        }
+    }
+}
+
 #[derive(Accounts)]
 pub struct Swap<'info> {
     /// Searcher is the party that fulfills the quote request
@@ -6,6 +6,7 @@ use {
         FeeToken,
         SubmitBidArgs,
         SwapV2Args,
+        SwapV3Args,
         SEED_CONFIG_ROUTER,
         SEED_METADATA,
     },
@@ -110,11 +111,81 @@ pub fn create_swap_instruction(
     token_program_user: Pubkey,
     swap_args: SwapV2Args,
     relayer_signer: Pubkey,
+) -> Instruction {
+    create_swap_instruction_with_data(
+        express_relay_pid,
+        searcher,
+        user,
+        searcher_ta_mint_searcher,
+        searcher_ta_mint_user,
+        router_fee_receiver_ta,
+        fee_receiver_relayer,
+        mint_searcher,
+        mint_user,
+        token_program_searcher,
+        token_program_user,
+        swap_args.fee_token,
+        instruction::SwapV2 { data: swap_args }.data(),
+        relayer_signer,
+    )
+}
+
+/// Creates a `SwapV3` instruction, which can be partially filled down to `minimum_amount_searcher`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_swap_v3_instruction(
+    express_relay_pid: Pubkey,
+    searcher: Pubkey,
+    user: Pubkey,
+    searcher_ta_mint_searcher: Option<Pubkey>,
+    searcher_ta_mint_user: Option<Pubkey>,
+    router_fee_receiver_ta: Pubkey,
+    fee_receiver_relayer: Pubkey,
+    mint_searcher: Pubkey,
+    mint_user: Pubkey,
+    token_program_searcher: Pubkey,
+    token_program_user: Pubkey,
+    swap_args: SwapV3Args,
+    relayer_signer: Pubkey,
+) -> Instruction {
+    create_swap_instruction_with_data(
+        express_relay_pid,
+        searcher,
+        user,
+        searcher_ta_mint_searcher,
+        searcher_ta_mint_user,
+        router_fee_receiver_ta,
+        fee_receiver_relayer,
+        mint_searcher,
+        mint_user,
+        token_program_searcher,
+        token_program_user,
+        swap_args.fee_token,
+        instruction::SwapV3 { data: swap_args }.data(),
+        relayer_signer,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_swap_instruction_with_data(
+    express_relay_pid: Pubkey,
+    searcher: Pubkey,
+    user: Pubkey,
+    searcher_ta_mint_searcher: Option<Pubkey>,
+    searcher_ta_mint_user: Option<Pubkey>,
+    router_fee_receiver_ta: Pubkey,
+    fee_receiver_relayer: Pubkey,
+    mint_searcher: Pubkey,
+    mint_user: Pubkey,
+    token_program_searcher: Pubkey,
+    token_program_user: Pubkey,
+    fee_token: FeeToken,
+    data: Vec<u8>,
+    relayer_signer: Pubkey,
 ) -> Instruction {
     let express_relay_metadata =
         Pubkey::find_program_address(&[SEED_METADATA], &express_relay_pid).0;
 
-    let (mint_fee, token_program_fee) = match swap_args.fee_token {
+    let (mint_fee, token_program_fee) = match fee_token {
         FeeToken::Searcher => (mint_searcher, token_program_searcher),
         FeeToken::User => (mint_user, token_program_user),
     };
@@ -167,12 +238,11 @@ pub fn create_swap_instruction(
         relayer_signer,
     }
     .to_account_metas(None);
-    let data_submit_bid = instruction::SwapV2 { data: swap_args }.data();
 
     Instruction {
         program_id: express_relay_pid,
-        accounts:   accounts_swap,
-        data:       data_submit_bid,
+        accounts: accounts_swap,
+        data,
     }
 }
 
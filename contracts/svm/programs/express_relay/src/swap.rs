@@ -11,6 +11,7 @@ use {
         Swap,
         SwapArgs,
         SwapV2Args,
+        SwapV3Args,
         FEE_SPLIT_PRECISION,
     },
     anchor_lang::{
@@ -158,6 +159,40 @@ impl<'info> Swap<'info> {
         Ok(())
     }
 
+    /// Fills the swap with as much as the searcher can provide, as long as it is not less than the minimum searcher amount.
+    /// The user amount is scaled down proportionally and rounded down in favor of the user.
+    pub fn compute_partial_fill(&self, args: &SwapV3Args) -> Result<SwapV2Args> {
+        require_gte!(
+            args.amount_searcher,
+            args.minimum_amount_searcher,
+            ErrorCode::InvalidMinimumAmountSearcher
+        );
+        let amount_searcher = args
+            .amount_searcher
+            .min(self.searcher_ta_mint_searcher.amount);
+        require_gte!(
+            amount_searcher,
+            args.minimum_amount_searcher,
+            ErrorCode::PartialFillBelowMinimum
+        );
+
+        let amount_user = if amount_searcher == args.amount_searcher {
+            args.amount_user
+        } else {
+            let amount_user = u128::from(args.amount_user)
+                .checked_mul(u128::from(amount_searcher))
+                .ok_or(ProgramError::ArithmeticOverflow)?
+                / u128::from(args.amount_searcher);
+            u64::try_from(amount_user).map_err(|_| ProgramError::ArithmeticOverflow)?
+        };
+
+        Ok(SwapV2Args {
+            amount_searcher,
+            amount_user,
+            ..args.convert_to_v2()
+        })
+    }
+
     pub fn check_enough_balances(&self, args: &SwapV2Args) -> Result<()> {
         require_gte!(
             self.searcher_ta_mint_searcher.amount,
@@ -16,10 +16,12 @@ use {
         instruction::{
             Swap,
             SwapV2,
+            SwapV3,
         },
         FeeToken,
         SwapArgs,
         SwapV2Args,
+        SwapV3Args,
     },
     solana_sdk::{
         instruction::Instruction,
@@ -63,6 +65,16 @@ impl AnyVersionSwapArgs for SwapV2Args {
     }
 }
 
+impl AnyVersionSwapArgs for SwapV3Args {
+    fn get_fee_token(&self) -> FeeToken {
+        self.fee_token
+    }
+
+    fn into_swap_instruction_data(self) -> Vec<u8> {
+        SwapV3 { data: self }.data()
+    }
+}
+
 pub struct SwapParams<Args: AnyVersionSwapArgs = SwapArgs> {
     pub searcher:               Pubkey,
     pub user:                   Pubkey,
@@ -12,6 +12,7 @@ use {
         FeeToken,
         SwapArgs,
         SwapV2Args,
+        SwapV3Args,
     },
     litesvm::LiteSVM,
     solana_sdk::{
@@ -1865,3 +1866,132 @@ fn test_swap_v2_mint_searcher_ppm_fee() {
         }
     );
 }
+
+#[test]
+fn test_swap_v3_partial_fill() {
+    let SwapSetupResult {
+        mut svm,
+        user,
+        searcher,
+        token_searcher,
+        token_user,
+        router_ta_mint_searcher,
+        router_ta_mint_user,
+        relayer_signer,
+        ..
+    } = setup_swap(Default::default());
+
+    let express_relay_metadata = get_express_relay_metadata(&mut svm);
+
+    // user token fee
+    let swap_args = SwapV3Args {
+        deadline:                svm.get_sysvar::<Clock>().unix_timestamp,
+        amount_searcher:         token_searcher.get_amount_with_decimals(12.), // <--- more than searcher has
+        minimum_amount_searcher: token_searcher.get_amount_with_decimals(8.),
+        amount_user:             token_user.get_amount_with_decimals(3.),
+        referral_fee_ppm:        0,
+        fee_token:               FeeToken::User,
+        swap_platform_fee_ppm:   0,
+    };
+    let instructions = build_swap_instructions(SwapParams {
+        searcher: searcher.pubkey(),
+        user: user.pubkey(),
+        router_fee_receiver_ta: router_ta_mint_user,
+        fee_receiver_relayer: express_relay_metadata.fee_receiver_relayer,
+        token_searcher: token_searcher.clone(),
+        token_user: token_user.clone(),
+        swap_args,
+        overrides: Default::default(),
+        relayer_signer: relayer_signer.pubkey(),
+    });
+    submit_transaction(
+        &mut svm,
+        &instructions,
+        &searcher,
+        &[&searcher, &user, &relayer_signer],
+    )
+    .unwrap();
+
+    // the searcher provides its whole balance and the user amount is scaled down proportionally
+    assert_all_token_balances!(
+        &mut svm,
+        token_searcher,
+        {
+            associated: {
+                searcher.pubkey() => 0.0,
+                user.pubkey() => 10.0,
+                get_express_relay_metadata_key() => 0.0,
+                express_relay_metadata.fee_receiver_relayer => 0.0,
+            },
+            raw: {
+                router_ta_mint_searcher => 0.0,
+            }
+        }
+    );
+
+    assert_all_token_balances!(
+        &mut svm,
+        token_user,
+        {
+            associated: {
+                searcher.pubkey() => 2.5,
+                user.pubkey() => 7.5,
+                get_express_relay_metadata_key() => 0.0,
+                express_relay_metadata.fee_receiver_relayer => 0.0,
+            },
+            raw: {
+                router_ta_mint_user => 0.0,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_swap_v3_partial_fill_below_minimum() {
+    let SwapSetupResult {
+        mut svm,
+        user,
+        searcher,
+        token_searcher,
+        token_user,
+        router_ta_mint_user,
+        relayer_signer,
+        ..
+    } = setup_swap(Default::default());
+
+    let express_relay_metadata = get_express_relay_metadata(&mut svm);
+
+    // user token fee
+    let swap_args = SwapV3Args {
+        deadline:                svm.get_sysvar::<Clock>().unix_timestamp,
+        amount_searcher:         token_searcher.get_amount_with_decimals(12.),
+        minimum_amount_searcher: token_searcher.get_amount_with_decimals(11.), // <--- more than searcher has
+        amount_user:             token_user.get_amount_with_decimals(3.),
+        referral_fee_ppm:        0,
+        fee_token:               FeeToken::User,
+        swap_platform_fee_ppm:   0,
+    };
+    let instructions = build_swap_instructions(SwapParams {
+        searcher: searcher.pubkey(),
+        user: user.pubkey(),
+        router_fee_receiver_ta: router_ta_mint_user,
+        fee_receiver_relayer: express_relay_metadata.fee_receiver_relayer,
+        token_searcher: token_searcher.clone(),
+        token_user: token_user.clone(),
+        swap_args,
+        overrides: Default::default(),
+        relayer_signer: relayer_signer.pubkey(),
+    });
+    let result = submit_transaction(
+        &mut svm,
+        &instructions,
+        &searcher,
+        &[&searcher, &user, &relayer_signer],
+    )
+    .unwrap_err();
+    assert_custom_error(
+        result.err,
+        4,
+        InstructionError::Custom(ErrorCode::PartialFillBelowMinimum.into()),
+    );
+}
@@ -305,7 +305,7 @@ export class Client {
         if (response.status === "success") {
           resolve(response.result);
         } else {
-          reject(ClientError.newWebsocketError(response.result));
+          reject(ClientError.newWebsocketError(response.result.error));
         }
       };
       if (this.websocket === undefined) {
@@ -847,7 +847,7 @@ export interface components {
           status: "success";
         }
       | {
-          result: string;
+          result: components["schemas"]["ErrorBodyResponse"];
           /** @enum {string} */
           status: "error";
         };
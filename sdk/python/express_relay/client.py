@@ -244,8 +244,11 @@ class ExpressRelayClient:
             The result field of the message.
         """
         if msg.get("status") and msg.get("status") != "success":
+            error = msg.get("result")
+            if isinstance(error, dict):
+                error = error.get("error")
             raise ExpressRelayClientException(
-                f"Error in websocket response with message id {msg.get('id')}: {msg.get('result')}"
+                f"Error in websocket response with message id {msg.get('id')}: {error}"
             )
         return msg["result"]
 
@@ -92,6 +92,24 @@ class InsufficientUserFunds(ProgramError):
     msg = "Insufficient user funds"
 
 
+class InvalidMinimumAmountSearcher(ProgramError):
+    def __init__(self) -> None:
+        super().__init__(6010, "Minimum searcher amount larger than searcher amount")
+
+    code = 6010
+    name = "InvalidMinimumAmountSearcher"
+    msg = "Minimum searcher amount larger than searcher amount"
+
+
+class PartialFillBelowMinimum(ProgramError):
+    def __init__(self) -> None:
+        super().__init__(6011, "Searcher amount below the minimum amount")
+
+    code = 6011
+    name = "PartialFillBelowMinimum"
+    msg = "Searcher amount below the minimum amount"
+
+
 CustomError = typing.Union[
     FeeSplitLargerThanPrecision,
     FeesHigherThanBid,
@@ -103,6 +121,8 @@ CustomError = typing.Union[
     InsufficientRent,
     InvalidReferralFee,
     InsufficientUserFunds,
+    InvalidMinimumAmountSearcher,
+    PartialFillBelowMinimum,
 ]
 CUSTOM_ERROR_MAP: dict[int, CustomError] = {
     6000: FeeSplitLargerThanPrecision(),
@@ -115,6 +135,8 @@ CUSTOM_ERROR_MAP: dict[int, CustomError] = {
     6007: InsufficientRent(),
     6008: InvalidReferralFee(),
     6009: InsufficientUserFunds(),
+    6010: InvalidMinimumAmountSearcher(),
+    6011: PartialFillBelowMinimum(),
 }
 
 
@@ -19,3 +19,4 @@ from .withdraw_fees import withdraw_fees, WithdrawFeesAccounts
 from .swap_internal import swap_internal, SwapInternalArgs, SwapInternalAccounts
 from .swap import swap, SwapArgs, SwapAccounts
 from .swap_v2 import swap_v2, SwapV2Args, SwapV2Accounts
+from .swap_v3 import swap_v3, SwapV3Args, SwapV3Accounts
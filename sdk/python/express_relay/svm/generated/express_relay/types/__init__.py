@@ -18,3 +18,5 @@ from . import swap_args
 from .swap_args import SwapArgs, SwapArgsJSON
 from . import swap_v2_args
 from .swap_v2_args import SwapV2Args, SwapV2ArgsJSON
+from . import swap_v3_args
+from .swap_v3_args import SwapV3Args, SwapV3ArgsJSON
@@ -332,6 +332,10 @@ impl SimpleSearcher {
                         }
                     }
                 }
+                // Quote updates are only sent to quote subscribers
+                ServerUpdateResponse::QuoteUpdate { .. }
+                | ServerUpdateResponse::QuoteSubscriptionEnded { .. }
+                | ServerUpdateResponse::QuoteStatusUpdate { .. } => {}
             }
         }
 
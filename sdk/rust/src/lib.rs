@@ -386,7 +386,7 @@ impl WsClient {
         let result = self.send(message).await?;
         match result {
             ServerResultMessage::Success(_) => Ok(()),
-            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error)),
+            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error.error)),
         }
     }
 
@@ -413,7 +413,7 @@ impl WsClient {
         let result = self.send(message).await?;
         match result {
             ServerResultMessage::Success(_) => Ok(()),
-            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error)),
+            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error.error)),
         }
     }
 
@@ -441,10 +441,44 @@ impl WsClient {
                 let response = response.ok_or(ClientError::InvalidResponse(
                     "Invalid server response: Expected BidResult but got None.".to_string(),
                 ))?;
-                let api_types::ws::APIResponse::BidResult(response) = response;
-                Ok(response)
+                match response {
+                    api_types::ws::APIResponse::BidResult(response) => Ok(response),
+                    _ => Err(ClientError::InvalidResponse(
+                        "Invalid server response: Expected BidResult.".to_string(),
+                    )),
+                }
             }
-            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error)),
+            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error.error)),
+        }
+    }
+
+    /// Verifies a bid without submitting it to the auction.
+    ///
+    /// # Arguments
+    ///
+    /// * `bid` - The bid to verify.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<api_types::bid::BidVerificationResult, ClientError>` - The result of the bid verification.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WebSocket request fails or the server rejects the bid.
+    pub async fn verify_bid(
+        &self,
+        bid: api_types::bid::BidCreate,
+    ) -> Result<api_types::bid::BidVerificationResult, ClientError> {
+        let message = api_types::ws::ClientMessage::VerifyBid { bid };
+        let result = self.send(message).await?;
+        match result {
+            ServerResultMessage::Success(response) => match response {
+                Some(api_types::ws::APIResponse::BidVerificationResult(response)) => Ok(response),
+                _ => Err(ClientError::InvalidResponse(
+                    "Invalid server response: Expected BidVerificationResult.".to_string(),
+                )),
+            },
+            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error.error)),
         }
     }
 
@@ -469,7 +503,7 @@ impl WsClient {
         let result = self.send(message).await?;
         match result {
             ServerResultMessage::Success(_) => Ok(()),
-            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error)),
+            ServerResultMessage::Err(error) => Err(ClientError::WsRequestFailed(error.error)),
         }
     }
 }
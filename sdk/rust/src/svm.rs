@@ -4,6 +4,7 @@ use {
         sdk::helpers::{
             create_submit_bid_instruction,
             create_swap_instruction,
+            create_swap_v3_instruction,
             deserialize_metadata,
         },
         state::{
@@ -13,6 +14,7 @@ use {
         },
         FeeToken,
         SwapV2Args,
+        SwapV3Args,
     },
     express_relay_api_types::opportunity::{
         FeeToken as ApiFeeToken,
@@ -131,12 +133,13 @@ pub struct GetSwapInstructionParams {
 }
 
 struct OpportunitySwapData<'a> {
-    user:             &'a Pubkey,
-    tokens:           &'a QuoteTokensWithTokenPrograms,
-    fee_token:        &'a ApiFeeToken,
-    router_account:   &'a Pubkey,
-    referral_fee_ppm: &'a u64,
-    platform_fee_ppm: &'a u64,
+    user:                       &'a Pubkey,
+    tokens:                     &'a QuoteTokensWithTokenPrograms,
+    fee_token:                  &'a ApiFeeToken,
+    router_account:             &'a Pubkey,
+    referral_fee_ppm:           &'a u64,
+    platform_fee_ppm:           &'a u64,
+    partial_fill_tolerance_ppm: &'a Option<u64>,
 }
 pub struct GetSwapCreateAccountsIdempotentInstructionsParams {
     pub searcher:               Pubkey,
@@ -314,6 +317,7 @@ impl Svm {
                 referral_fee_ppm,
                 router_account,
                 platform_fee_ppm,
+                partial_fill_tolerance_ppm,
                 ..
             } => Ok(OpportunitySwapData {
                 user: user_wallet_address,
@@ -322,6 +326,7 @@ impl Svm {
                 router_account,
                 referral_fee_ppm,
                 platform_fee_ppm,
+                partial_fill_tolerance_ppm,
             }),
             _ => Err(ClientError::SvmError(
                 "Invalid opportunity program".to_string(),
@@ -426,7 +431,40 @@ impl Svm {
             fee_token,
         };
 
-        Ok(create_swap_instruction(
+        // Swaps with a partial fill tolerance can be partially filled down to the minimum searcher amount
+        let Some(partial_fill_tolerance_ppm) = *swap_data.partial_fill_tolerance_ppm else {
+            return Ok(create_swap_instruction(
+                Self::get_express_relay_pid(chain_id),
+                params.searcher,
+                *swap_data.user,
+                None,
+                None,
+                router_fee_receiver_ta,
+                params.fee_receiver_relayer,
+                mint_searcher,
+                mint_user,
+                token_program_searcher,
+                token_program_user,
+                swap_args,
+                params.relayer_signer,
+            ));
+        };
+        let unfilled_amount = u64::try_from(
+            u128::from(amount_searcher) * u128::from(partial_fill_tolerance_ppm)
+                / u128::from(FEE_SPLIT_PRECISION_PPM),
+        )
+        .map_err(|_| ClientError::SvmError("Invalid partial fill tolerance".to_string()))?;
+        let swap_args = SwapV3Args {
+            deadline:                swap_args.deadline,
+            amount_searcher:         swap_args.amount_searcher,
+            minimum_amount_searcher: amount_searcher.saturating_sub(unfilled_amount),
+            amount_user:             swap_args.amount_user,
+            referral_fee_ppm:        swap_args.referral_fee_ppm,
+            fee_token:               swap_args.fee_token,
+            swap_platform_fee_ppm:   swap_args.swap_platform_fee_ppm,
+        };
+
+        Ok(create_swap_v3_instruction(
             Self::get_express_relay_pid(chain_id),
             params.searcher,
             *swap_data.user,